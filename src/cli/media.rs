@@ -0,0 +1,30 @@
+//! Media maintenance commands
+
+use crate::web::db;
+use serde::Serialize;
+use std::env;
+use std::io;
+
+#[derive(Serialize)]
+struct RequeueResult {
+    command: &'static str,
+    status: &'static str,
+    enqueued: u64,
+}
+
+/// Queue derivative regeneration for all live media, e.g. after a quality change.
+///
+/// Media that already has a queued derivative job is skipped; the running
+/// server picks the jobs up.
+pub async fn requeue_derivatives() -> Result<(), Box<dyn std::error::Error>> {
+    let database_url = env::var("DATABASE_URL")
+        .map_err(|_| io::Error::other("Missing required environment variable: DATABASE_URL"))?;
+    let pool = db::create_pool(&database_url).await?;
+    let result = RequeueResult {
+        command: "media-requeue-derivatives",
+        status: "pass",
+        enqueued: db::requeue_media_derivatives(&pool).await?,
+    };
+    println!("{}", serde_json::to_string(&result)?);
+    Ok(())
+}
@@ -2,4 +2,6 @@
 
 pub mod compose;
 pub mod docs;
+pub mod media;
 pub mod quality;
+pub mod tokens;
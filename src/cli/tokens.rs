@@ -0,0 +1,73 @@
+//! Service token commands
+
+use crate::web::db::{self, ApiScope, WriteActor, MAX_TOKEN_LIFETIME_DAYS};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::env;
+use std::io;
+
+#[derive(Serialize)]
+struct IssuedToken {
+    command: &'static str,
+    status: &'static str,
+    space: String,
+    service_account_id: String,
+    scopes: Vec<&'static str>,
+    token: String,
+}
+
+/// Create a service account and mint its first bearer token.
+pub async fn issue(
+    space: &str,
+    name: &str,
+    scopes: &[String],
+    expires_days: Option<i64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let scopes = parse_scopes(scopes)?;
+    if expires_days.is_some_and(|days| !(1..=MAX_TOKEN_LIFETIME_DAYS).contains(&days)) {
+        return Err(io::Error::other(format!(
+            "--expires-days must be 1 to {MAX_TOKEN_LIFETIME_DAYS}"
+        ))
+        .into());
+    }
+    let database_url = env::var("DATABASE_URL")
+        .map_err(|_| io::Error::other("Missing required environment variable: DATABASE_URL"))?;
+    let pool = db::create_pool(&database_url).await?;
+    db::require_space(&pool, space).await?;
+    // Operator-issued tokens have no browser or service actor.
+    let actor = WriteActor::default();
+    let service_account_id = db::create_service_account(&pool, space, name, &actor).await?;
+    let expires_at = expires_days.map(|days| Utc::now() + Duration::days(days));
+    let token = db::issue_api_token(
+        &pool,
+        space,
+        service_account_id,
+        &scopes,
+        expires_at,
+        &actor,
+    )
+    .await?;
+    let result = IssuedToken {
+        command: "tokens-issue",
+        status: "pass",
+        space: space.to_string(),
+        service_account_id: service_account_id.to_string(),
+        scopes: scopes.iter().map(|scope| scope.as_str()).collect(),
+        token,
+    };
+    println!("{}", serde_json::to_string(&result)?);
+    Ok(())
+}
+
+fn parse_scopes(values: &[String]) -> Result<Vec<ApiScope>, io::Error> {
+    if values.is_empty() {
+        return Ok(ApiScope::ALL.to_vec());
+    }
+    values
+        .iter()
+        .map(|value| {
+            ApiScope::parse(value)
+                .ok_or_else(|| io::Error::other(format!("unknown scope: {value}")))
+        })
+        .collect()
+}
@@ -2,6 +2,7 @@
 
 use std::env;
 use std::net::IpAddr;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -35,8 +36,14 @@ pub struct Config {
     pub seaweedfs_s3_secret_key: String,
     pub seaweedfs_s3_path_style: bool,
     pub media_upload_max_bytes: usize,
+    pub upload_session_max_bytes: usize,
+    pub upload_session_dir: PathBuf,
     pub site_icon_upload_max_bytes: usize,
+    pub trash_retention_days: usize,
     pub setup_code: Option<String>,
+    /// Model server for semantic search; the local hash embedder when unset.
+    pub embedding_url: Option<String>,
+    pub embedding_model: String,
 }
 
 impl Config {
@@ -58,10 +65,21 @@ impl Config {
             seaweedfs_s3_secret_key: required_var("SEAWEEDFS_S3_SECRET_KEY")?,
             seaweedfs_s3_path_style: parse_bool("SEAWEEDFS_S3_PATH_STYLE", "true")?,
             media_upload_max_bytes: parse_usize("MEDIA_UPLOAD_MAX_BYTES", "536870912")?,
+            upload_session_max_bytes: parse_usize("UPLOAD_SESSION_MAX_BYTES", "8589934592")?,
+            upload_session_dir: env::var_os("UPLOAD_SESSION_DIR")
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| env::temp_dir().join("kjxlkj-uploads")),
             site_icon_upload_max_bytes: parse_usize("SITE_ICON_UPLOAD_MAX_BYTES", "2097152")?,
+            trash_retention_days: parse_usize("TRASH_RETENTION_DAYS", "30")?,
             setup_code: env::var("SETUP_CODE")
                 .ok()
                 .filter(|value| !value.is_empty()),
+            embedding_url: env::var("EMBEDDING_URL")
+                .ok()
+                .filter(|value| !value.is_empty()),
+            embedding_model: env::var("EMBEDDING_MODEL")
+                .unwrap_or_else(|_| "nomic-embed-text".to_string()),
         })
     }
 
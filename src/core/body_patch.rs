@@ -0,0 +1,138 @@
+//! Text edits applied to Markdown bodies by partial updates
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BodyPatchError {
+    #[error("find text must not be empty")]
+    EmptyFind,
+    #[error("heading '{0}' not found")]
+    HeadingNotFound(String),
+    #[error("heading '{heading}' matches {count} sections")]
+    AmbiguousHeading { heading: String, count: usize },
+    #[error("expected {expected} matches for find text, found {found}")]
+    MatchCount { expected: usize, found: usize },
+}
+
+/// Add `text` after the body, starting it on a new line.
+pub fn append(body: &str, text: &str) -> String {
+    if body.is_empty() || body.ends_with('\n') {
+        format!("{body}{text}")
+    } else {
+        format!("{body}\n{text}")
+    }
+}
+
+/// Add `text` before the body, ending it on its own line.
+pub fn prepend(body: &str, text: &str) -> String {
+    if text.is_empty() || text.ends_with('\n') {
+        format!("{text}{body}")
+    } else {
+        format!("{text}\n{body}")
+    }
+}
+
+/// Replace every occurrence of `find`, failing unless exactly `expected` match.
+pub fn find_replace(
+    body: &str,
+    find: &str,
+    replace: &str,
+    expected: usize,
+) -> Result<String, BodyPatchError> {
+    if find.is_empty() {
+        return Err(BodyPatchError::EmptyFind);
+    }
+    let found = body.matches(find).count();
+    if found != expected {
+        return Err(BodyPatchError::MatchCount { expected, found });
+    }
+    Ok(body.replace(find, replace))
+}
+
+/// Replace the content under one ATX heading up to the next heading of the same
+/// or a higher level; the heading line itself is kept.
+///
+/// `heading` matches the heading text; a leading `#` run also pins the level.
+pub fn replace_section(body: &str, heading: &str, text: &str) -> Result<String, BodyPatchError> {
+    let wanted = parse_heading(heading).unwrap_or((0, heading.trim()));
+    let lines: Vec<&str> = body.split_inclusive('\n').collect();
+    let headings = heading_lines(&lines);
+    let matches: Vec<(usize, usize)> = headings
+        .iter()
+        .copied()
+        .filter(|(index, level)| {
+            parse_heading(lines[*index]).is_some_and(|(_, title)| title == wanted.1)
+                && (wanted.0 == 0 || wanted.0 == *level)
+        })
+        .collect();
+    let (start, level) = match matches.as_slice() {
+        [] => return Err(BodyPatchError::HeadingNotFound(heading.trim().to_string())),
+        [only] => *only,
+        _ => {
+            return Err(BodyPatchError::AmbiguousHeading {
+                heading: heading.trim().to_string(),
+                count: matches.len(),
+            })
+        }
+    };
+    let end = headings
+        .iter()
+        .find(|(index, other)| *index > start && *other <= level)
+        .map_or(lines.len(), |(index, _)| *index);
+    let mut output: String = lines[..=start].concat();
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str(text);
+    if end < lines.len() {
+        if !text.is_empty() && !text.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&lines[end..].concat());
+    }
+    Ok(output)
+}
+
+/// Line indexes and levels of ATX headings outside fenced code blocks.
+fn heading_lines(lines: &[&str]) -> Vec<(usize, usize)> {
+    let mut fence: Option<char> = None;
+    let mut headings = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        if let Some(marker) = marker.filter(|c| trimmed.starts_with(&c.to_string().repeat(3))) {
+            fence = match fence {
+                Some(open) if open == marker => None,
+                None => Some(marker),
+                open => open,
+            };
+            continue;
+        }
+        if fence.is_none() {
+            if let Some((level, _)) = parse_heading(line) {
+                headings.push((index, level));
+            }
+        }
+    }
+    headings
+}
+
+/// Split an ATX heading line into its level and trimmed text.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let title = rest.trim();
+    let closed = title.trim_end_matches('#');
+    if closed.is_empty() || closed.ends_with([' ', '\t']) {
+        return Some((level, closed.trim_end()));
+    }
+    Some((level, title))
+}
@@ -0,0 +1,69 @@
+use super::body_patch::*;
+
+const BODY: &str =
+    "# Plan\n\nIntro\n\n## Tasks\n\n- one\n\n### Detail\n\nnested\n\n## Notes\n\nkeep\n";
+
+#[test]
+fn append_and_prepend_keep_line_breaks() {
+    assert_eq!(append("one", "two"), "one\ntwo");
+    assert_eq!(append("one\n", "two\n"), "one\ntwo\n");
+    assert_eq!(append("", "two"), "two");
+    assert_eq!(prepend("body\n", "> note"), "> note\nbody\n");
+    assert_eq!(prepend("body\n", "> note\n"), "> note\nbody\n");
+}
+
+#[test]
+fn find_replace_requires_the_expected_match_count() {
+    assert_eq!(
+        find_replace("a b a", "a", "c", 2).unwrap(),
+        "c b c".to_string()
+    );
+    assert_eq!(
+        find_replace("a b a", "a", "c", 1),
+        Err(BodyPatchError::MatchCount {
+            expected: 1,
+            found: 2
+        })
+    );
+    assert_eq!(
+        find_replace("a", "", "c", 1),
+        Err(BodyPatchError::EmptyFind)
+    );
+}
+
+#[test]
+fn replace_section_stops_at_same_or_higher_heading() {
+    let patched = replace_section(BODY, "Tasks", "\n- two\n\n").unwrap();
+    assert_eq!(
+        patched,
+        "# Plan\n\nIntro\n\n## Tasks\n\n- two\n\n## Notes\n\nkeep\n"
+    );
+    let last = replace_section(BODY, "## Notes", "\nreplaced").unwrap();
+    assert!(last.ends_with("## Notes\n\nreplaced"));
+}
+
+#[test]
+fn replace_section_rejects_missing_or_ambiguous_headings() {
+    assert_eq!(
+        replace_section(BODY, "### Tasks", "x"),
+        Err(BodyPatchError::HeadingNotFound("### Tasks".to_string()))
+    );
+    let twice = "## A\n\none\n\n## A\n\ntwo\n";
+    assert_eq!(
+        replace_section(twice, "A", "x"),
+        Err(BodyPatchError::AmbiguousHeading {
+            heading: "A".to_string(),
+            count: 2
+        })
+    );
+}
+
+#[test]
+fn replace_section_ignores_headings_inside_code_fences() {
+    let body = "## Code\n\n```md\n## Fake\n```\n\n## Next\n";
+    assert_eq!(
+        replace_section(body, "Code", "\nnew\n\n").unwrap(),
+        "## Code\n\nnew\n\n## Next\n"
+    );
+    assert!(replace_section(body, "Fake", "x").is_err());
+}
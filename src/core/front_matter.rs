@@ -0,0 +1,166 @@
+//! YAML and TOML front matter parsed into typed resource metadata
+
+use super::front_matter_formats::{toml_mapping, yaml_mapping};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+static KEY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9_][a-z0-9_.-]{0,63}$").unwrap());
+static TAG_SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+
+const MAX_FIELDS: usize = 64;
+const MAX_STRING_CHARS: usize = 1000;
+const MAX_ARRAY_ITEMS: usize = 100;
+const MAX_TAGS: usize = 32;
+const MAX_TAG_CHARS: usize = 64;
+
+/// Normalized front matter: `tags` plus scalar or list fields keyed in
+/// lowercase. Serializes to the stored `metadata` object.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FrontMatter {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+impl FrontMatter {
+    pub fn text(&self, key: &str) -> Option<&str> {
+        self.fields
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_else(|_| Value::Object(Map::new()))
+    }
+}
+
+/// Front matter at the very start of `body`; a leading block that does not
+/// parse into a mapping is ordinary Markdown and yields `None`.
+pub fn parse_front_matter(body: &str) -> Option<FrontMatter> {
+    split(body).map(|(front, _)| front)
+}
+
+/// `body` without its front matter block, or unchanged when there is none.
+pub fn strip_front_matter(body: &str) -> &str {
+    split(body).map_or(body, |(_, rest)| rest)
+}
+
+/// Lowercased tag with surrounding `#` and whitespace removed and inner
+/// whitespace folded to `-`.
+pub fn normalize_tag(raw: &str) -> Option<String> {
+    let trimmed = raw.trim().trim_start_matches('#').trim().to_lowercase();
+    let tag = TAG_SPACES.replace_all(&trimmed, "-").into_owned();
+    (!tag.is_empty() && tag.chars().count() <= MAX_TAG_CHARS).then_some(tag)
+}
+
+fn split(body: &str) -> Option<(FrontMatter, &str)> {
+    let text = body.strip_prefix('\u{feff}').unwrap_or(body);
+    let first_end = text.find('\n').unwrap_or(text.len());
+    let fence = text[..first_end].trim_end();
+    let closers: &[&str] = match fence {
+        "---" => &["---", "..."],
+        "+++" => &["+++"],
+        _ => return None,
+    };
+    let mut pos = (first_end + 1).min(text.len());
+    let start = pos;
+    while pos < text.len() {
+        let end = text[pos..].find('\n').map_or(text.len(), |at| pos + at);
+        if closers.contains(&text[pos..end].trim_end()) {
+            let raw = &text[start..pos];
+            let mapping = if fence == "---" {
+                yaml_mapping(raw)?
+            } else {
+                toml_mapping(raw)?
+            };
+            return Some((normalize(mapping), &text[(end + 1).min(text.len())..]));
+        }
+        pos = end + 1;
+    }
+    None
+}
+
+fn normalize(mapping: Map<String, Value>) -> FrontMatter {
+    let mut front = FrontMatter::default();
+    for (key, value) in mapping {
+        let key = key.trim().to_lowercase();
+        if key == "tags" {
+            front.tags = tags(value);
+            continue;
+        }
+        if front.fields.len() >= MAX_FIELDS || !KEY_REGEX.is_match(&key) {
+            continue;
+        }
+        if let Some(value) = field_value(value) {
+            front.fields.insert(key, value);
+        }
+    }
+    front
+}
+
+fn tags(value: Value) -> Vec<String> {
+    let raw: Vec<String> = match value {
+        Value::String(text) => text.split(',').map(str::to_string).collect(),
+        Value::Array(items) => items.iter().filter_map(scalar_text).collect(),
+        _ => Vec::new(),
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw.iter().filter_map(|tag| normalize_tag(tag)) {
+        if tags.len() < MAX_TAGS && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn field_value(value: Value) -> Option<Value> {
+    match value {
+        Value::Array(items) => Some(Value::Array(
+            items
+                .into_iter()
+                .filter_map(scalar)
+                .take(MAX_ARRAY_ITEMS)
+                .collect(),
+        )),
+        other => scalar(other),
+    }
+}
+
+fn scalar(value: Value) -> Option<Value> {
+    match value {
+        Value::String(text) => {
+            let text: String = text.trim().chars().take(MAX_STRING_CHARS).collect();
+            Some(Value::String(canonical_date(&text).unwrap_or(text)))
+        }
+        Value::Number(_) | Value::Bool(_) => Some(value),
+        _ => None,
+    }
+}
+
+/// Dates become `YYYY-MM-DD` and timestamps RFC 3339 so filters can match
+/// them textually.
+fn canonical_date(text: &str) -> Option<String> {
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Some(date.format("%Y-%m-%d").to_string());
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(datetime.to_rfc3339());
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|datetime| datetime.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
@@ -0,0 +1,58 @@
+//! YAML and TOML front matter blocks converted to JSON mappings
+
+use serde_json::{Map, Value};
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// Top-level mapping of a YAML block; `None` when it is not one mapping.
+/// Entries with non-string keys are dropped.
+pub(super) fn yaml_mapping(raw: &str) -> Option<Map<String, Value>> {
+    if raw.trim().is_empty() {
+        return Some(Map::new());
+    }
+    let mut documents = YamlLoader::load_from_str(raw).ok()?;
+    if documents.len() != 1 {
+        return None;
+    }
+    match documents.pop()? {
+        Yaml::Hash(hash) => Some(
+            hash.into_iter()
+                .filter_map(|(key, value)| Some((key.into_string()?, yaml_value(value))))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Nested mappings become `null`, as with TOML tables.
+fn yaml_value(value: Yaml) -> Value {
+    match value {
+        Yaml::String(text) => Value::String(text),
+        Yaml::Integer(number) => Value::from(number),
+        Yaml::Real(_) => value.as_f64().map_or(Value::Null, Value::from),
+        Yaml::Boolean(flag) => Value::Bool(flag),
+        Yaml::Array(items) => Value::Array(items.into_iter().map(yaml_value).collect()),
+        Yaml::Hash(_) | Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => Value::Null,
+    }
+}
+
+pub(super) fn toml_mapping(raw: &str) -> Option<Map<String, Value>> {
+    let table = raw.parse::<toml::Table>().ok()?;
+    Some(
+        table
+            .into_iter()
+            .map(|(key, value)| (key, toml_value(value)))
+            .collect(),
+    )
+}
+
+fn toml_value(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(text) => Value::String(text),
+        toml::Value::Integer(number) => Value::from(number),
+        toml::Value::Float(number) => Value::from(number),
+        toml::Value::Boolean(flag) => Value::Bool(flag),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_value).collect()),
+        toml::Value::Table(_) => Value::Null,
+    }
+}
@@ -0,0 +1,96 @@
+use super::front_matter::*;
+use super::{derive_summary, derive_title, render_markdown};
+use serde_json::json;
+
+const YAML_NOTE: &str = "---
+title: Trip Log
+Tags: [Travel, '#Japan', travel, Road Trip]
+published: 2024-05-01
+rating: 4
+draft: false
+authors: [ana, ben]
+nested: {skip: me}
+---
+# Heading in body
+
+First paragraph.
+";
+
+#[test]
+fn yaml_front_matter_normalizes_into_metadata() {
+    let front = parse_front_matter(YAML_NOTE).unwrap();
+    assert_eq!(
+        front.to_json(),
+        json!({
+            "tags": ["travel", "japan", "road-trip"],
+            "title": "Trip Log",
+            "published": "2024-05-01",
+            "rating": 4,
+            "draft": false,
+            "authors": ["ana", "ben"],
+        })
+    );
+    assert_eq!(
+        strip_front_matter(YAML_NOTE),
+        "# Heading in body\n\nFirst paragraph.\n"
+    );
+}
+
+#[test]
+fn toml_front_matter_converts_datetimes_and_comma_tags() {
+    let body = "+++\ntags = \"Rust, web\"\nupdated = 2024-05-01T10:00:00Z\n+++\nBody";
+    let front = parse_front_matter(body).unwrap();
+    assert_eq!(front.tags, vec!["rust", "web"]);
+    assert_eq!(
+        front.fields.get("updated"),
+        Some(&json!("2024-05-01T10:00:00+00:00"))
+    );
+    assert_eq!(strip_front_matter(body), "Body");
+}
+
+#[test]
+fn leading_rules_and_unclosed_blocks_are_plain_markdown() {
+    for body in [
+        "---\nJust a paragraph\n---\n",
+        "---\ntitle: open",
+        "Intro\n---\na: b\n---",
+    ] {
+        assert_eq!(parse_front_matter(body), None);
+        assert_eq!(strip_front_matter(body), body);
+    }
+}
+
+#[test]
+fn titles_and_summaries_prefer_front_matter_fields() {
+    assert_eq!(derive_title(YAML_NOTE), "Trip Log");
+    assert_eq!(derive_summary(YAML_NOTE), "First paragraph.");
+    let described = "---\ndescription: Short blurb\n---\nLong body text";
+    assert_eq!(derive_title(described), "Untitled note");
+    assert_eq!(derive_summary(described), "Short blurb");
+}
+
+#[test]
+fn rendered_html_omits_front_matter() {
+    let html = render_markdown(YAML_NOTE);
+    assert!(!html.contains("Trip Log"));
+    assert!(!html.contains("<hr"));
+    assert!(html.contains("<h1>Heading in body</h1>"));
+}
+
+#[test]
+fn tags_normalize_case_hashes_and_spaces() {
+    assert_eq!(
+        normalize_tag("  #Road  Trip "),
+        Some("road-trip".to_string())
+    );
+    assert_eq!(normalize_tag("#"), None);
+    assert_eq!(normalize_tag(&"x".repeat(65)), None);
+}
+
+#[test]
+fn yaml_floats_parse_and_non_mapping_blocks_are_plain_markdown() {
+    let front = parse_front_matter("---\nscore: 4.5\n1: numeric key\n---\nBody\n").unwrap();
+    assert_eq!(front.to_json(), json!({ "score": 4.5 }));
+    assert!(parse_front_matter("---\n- a\n- b\n---\nBody\n").is_none());
+    assert!(parse_front_matter("---\ntitle: [unclosed\n---\nBody\n").is_none());
+}
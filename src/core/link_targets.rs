@@ -0,0 +1,44 @@
+//! Local resource links collected from Markdown bodies
+
+use super::markdown_links::{link_target, LinkTarget};
+use super::markdown_wiki::wiki_links;
+use once_cell::sync::Lazy;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
+
+static HTML_URL_ATTR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\b(?:href|src)\s*=\s*"([^"]*)""#).unwrap());
+
+/// Distinct local resource links in document order, from Markdown links,
+/// images, raw HTML `href`/`src` attributes, and `[[ref]]` wiki links.
+pub fn local_link_targets(body: &str) -> Vec<LinkTarget> {
+    let mut targets = Vec::new();
+    for event in Parser::new_ext(body, Options::ENABLE_TABLES) {
+        match event {
+            Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. }) => {
+                push_target(&mut targets, link_target(&dest_url));
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                for capture in HTML_URL_ATTR.captures_iter(&html) {
+                    push_target(&mut targets, link_target(&capture[1]));
+                }
+            }
+            _ => {}
+        }
+    }
+    for link in wiki_links(body) {
+        let target = link.resource_ref().map(|reference| LinkTarget {
+            space: None,
+            reference,
+            is_file: false,
+        });
+        push_target(&mut targets, target);
+    }
+    targets
+}
+
+fn push_target(targets: &mut Vec<LinkTarget>, target: Option<LinkTarget>) {
+    if let Some(target) = target.filter(|target| !targets.contains(target)) {
+        targets.push(target);
+    }
+}
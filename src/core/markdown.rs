@@ -3,10 +3,11 @@
 use ammonia::Builder;
 use pulldown_cmark::{html, Options, Parser};
 
+use super::front_matter::strip_front_matter;
 use super::markdown_embed_blocks;
-use super::markdown_links::{
-    escape_attr, is_local_file_href, poster_href, replace_local_resource_cards, variant_href,
-};
+use super::markdown_links::replace_local_resource_cards;
+use super::markdown_media::decorate_local_media;
+use super::markdown_wiki;
 use super::MarkdownOptions;
 
 pub fn render_markdown(body: &str) -> String {
@@ -20,25 +21,35 @@ pub fn render_markdown_with_origin(body: &str, public_base_url: Option<&str>) ->
             public_base_url,
             google_maps_embed_api_key: None,
             external_embed_cache: None,
+            wiki_targets: None,
         },
     )
 }
 
 pub fn render_markdown_with_options(body: &str, options: MarkdownOptions<'_>) -> String {
-    let (body, embed_blocks) = markdown_embed_blocks::extract(body, options);
+    let body = strip_front_matter(body);
+    let (body, wiki_blocks) = match options.wiki_targets {
+        Some(targets) => markdown_wiki::extract(body, targets),
+        None => (body.to_string(), Vec::new()),
+    };
+    let (body, embed_blocks) = markdown_embed_blocks::extract(&body, options);
     let mut html_out = String::new();
     let options =
         Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
     html::push_html(&mut html_out, Parser::new_ext(&body, options));
     let mut builder = Builder::default();
-    builder.add_tags(["input", "video", "source"]);
+    builder.add_tags(["input", "video", "audio", "source"]);
     builder.add_tag_attributes("input", ["checked", "disabled", "type"]);
     builder.add_tag_attributes("video", ["controls", "src", "poster", "preload"]);
     builder.add_tag_attributes("video", ["muted", "loop", "autoplay", "playsinline"]);
+    builder.add_tag_attributes("audio", ["controls", "src", "preload", "muted", "loop"]);
     builder.add_tag_attributes("source", ["src", "type"]);
     markdown_embed_blocks::restore(
-        post_process_html(&builder.clean(&html_out).to_string()),
-        &embed_blocks,
+        markdown_embed_blocks::restore(
+            post_process_html(&builder.clean(&html_out).to_string()),
+            &embed_blocks,
+        ),
+        &wiki_blocks,
     )
 }
 
@@ -47,91 +58,5 @@ pub fn external_embed_urls(body: &str, public_base_url: Option<&str>) -> Vec<Str
 }
 
 fn post_process_html(html: &str) -> String {
-    replace_local_resource_cards(&decorate_local_images(&decorate_local_videos(html)))
-}
-
-fn decorate_local_videos(html: &str) -> String {
-    let mut rest = html;
-    let mut output = String::new();
-    let marker = "<video";
-    while let Some(start) = rest.find(marker) {
-        output.push_str(&rest[..start]);
-        let after_marker = &rest[start..];
-        let Some(end) = after_marker.find('>') else {
-            output.push_str(after_marker);
-            return output;
-        };
-        output.push_str(&decorate_video_tag(&after_marker[..=end]));
-        rest = &after_marker[end + 1..];
-    }
-    output.push_str(rest);
-    output
-}
-
-fn decorate_local_images(html: &str) -> String {
-    let mut rest = html;
-    let mut output = String::new();
-    let marker = "<img";
-    while let Some(start) = rest.find(marker) {
-        output.push_str(&rest[..start]);
-        let after_marker = &rest[start..];
-        let Some(end) = after_marker.find('>') else {
-            output.push_str(after_marker);
-            return output;
-        };
-        output.push_str(&decorate_image_tag(&after_marker[..=end]));
-        rest = &after_marker[end + 1..];
-    }
-    output.push_str(rest);
-    output
-}
-
-fn decorate_video_tag(tag: &str) -> String {
-    if tag.contains(" poster=") {
-        return tag.to_string();
-    }
-    let Some(src) = attribute_value(tag, "src") else {
-        return tag.to_string();
-    };
-    if !is_local_file_href(src) {
-        return tag.to_string();
-    }
-    format!(
-        "{} poster=\"{}\">",
-        &tag[..tag.len().saturating_sub(1)],
-        escape_attr(&poster_href(src))
-    )
-}
-
-fn decorate_image_tag(tag: &str) -> String {
-    let Some(src) = attribute_value(tag, "src") else {
-        return tag.to_string();
-    };
-    if !is_local_file_href(src) || src.contains("variant=") {
-        return tag.to_string();
-    }
-    replace_attribute(tag, "src", &variant_href(src, "display"))
-}
-
-fn attribute_value<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
-    let marker = format!(r#"{name}=""#);
-    let start = tag.find(&marker)? + marker.len();
-    let end = tag[start..].find('"')?;
-    Some(&tag[start..start + end])
-}
-
-fn replace_attribute(tag: &str, name: &str, value: &str) -> String {
-    let marker = format!(r#"{name}=""#);
-    let Some(start) = tag.find(&marker).map(|index| index + marker.len()) else {
-        return tag.to_string();
-    };
-    let Some(end) = tag[start..].find('"') else {
-        return tag.to_string();
-    };
-    format!(
-        "{}{}{}",
-        &tag[..start],
-        escape_attr(value),
-        &tag[start + end..]
-    )
+    replace_local_resource_cards(&decorate_local_media(html))
 }
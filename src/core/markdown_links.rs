@@ -104,6 +104,46 @@ fn resource_link_parts(href: &str) -> Option<(&str, bool)> {
     }
 }
 
+/// Space page segments that share the `/{space}/{ref}` shape but are not resources.
+const SPACE_PAGES: &[&str] = &["admin", "favorites", "live", "search", "settings"];
+
+/// Resource addressed by a local link, with its space when the path names one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkTarget {
+    pub space: Option<String>,
+    pub reference: String,
+    pub is_file: bool,
+}
+
+/// Resolve `/{ref}`, `/{ref}/file`, `/{space}/{ref}`, and `/{space}/{ref}/file` hrefs.
+pub fn link_target(href: &str) -> Option<LinkTarget> {
+    let href = href.split('#').next().unwrap_or("");
+    if let Some((reference, is_file)) = resource_link_parts(href) {
+        return Some(LinkTarget {
+            space: None,
+            reference: reference.to_string(),
+            is_file,
+        });
+    }
+    if !href.starts_with('/') || href.starts_with("//") {
+        return None;
+    }
+    let path = href.split('?').next().unwrap_or("").trim_start_matches('/');
+    let (space, reference, is_file) = match path.split('/').collect::<Vec<_>>().as_slice() {
+        [space, reference] => (*space, *reference, false),
+        [space, reference, "file"] => (*space, *reference, true),
+        _ => return None,
+    };
+    (valid_resource_reference(space)
+        && valid_resource_reference(reference)
+        && !SPACE_PAGES.contains(&reference))
+    .then(|| LinkTarget {
+        space: Some(space.to_string()),
+        reference: reference.to_string(),
+        is_file,
+    })
+}
+
 fn valid_resource_reference(value: &str) -> bool {
     looks_like_id(value) || normalize_alias(Some(value)).is_ok()
 }
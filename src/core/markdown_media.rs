@@ -0,0 +1,145 @@
+//! Local image and video decoration for rendered Markdown
+
+use super::markdown_links::{escape_attr, is_local_file_href, poster_href, variant_href};
+
+const HLS_TYPE: &str = "application/vnd.apple.mpegurl";
+/// Responsive widths for local images; each must be on the image transform ladder.
+const SRCSET_WIDTHS: [u32; 4] = [480, 960, 1400, 1920];
+const SRCSET_SIZES: &str = "(max-width: 900px) 100vw, 900px";
+
+/// Give local videos fallback sources and local images a display variant and srcset.
+pub(super) fn decorate_local_media(html: &str) -> String {
+    decorate_local_images(&decorate_local_videos(html))
+}
+
+fn decorate_local_videos(html: &str) -> String {
+    let mut rest = html;
+    let mut output = String::new();
+    let marker = "<video";
+    while let Some(start) = rest.find(marker) {
+        output.push_str(&rest[..start]);
+        let after_marker = &rest[start..];
+        let Some(end) = after_marker.find('>') else {
+            output.push_str(after_marker);
+            return output;
+        };
+        output.push_str(&decorate_video_tag(&after_marker[..=end]));
+        rest = &after_marker[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn decorate_local_images(html: &str) -> String {
+    let mut rest = html;
+    let mut output = String::new();
+    let marker = "<img";
+    while let Some(start) = rest.find(marker) {
+        output.push_str(&rest[..start]);
+        let after_marker = &rest[start..];
+        let Some(end) = after_marker.find('>') else {
+            output.push_str(after_marker);
+            return output;
+        };
+        output.push_str(&decorate_image_tag(&after_marker[..=end]));
+        rest = &after_marker[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Local videos gain a poster and `<source>` children that try the HLS
+/// playlist and transcoded renditions before the original upload; a source
+/// that is missing fails over to the next one.
+fn decorate_video_tag(tag: &str) -> String {
+    if tag.contains(" poster=") {
+        return tag.to_string();
+    }
+    let Some(src) = attribute_value(tag, "src") else {
+        return tag.to_string();
+    };
+    if !is_local_file_href(src) {
+        return tag.to_string();
+    }
+    let opening = remove_attribute(tag, "src");
+    let mut output = format!(
+        "{} poster=\"{}\">",
+        &opening[..opening.len().saturating_sub(1)],
+        escape_attr(&poster_href(src))
+    );
+    if !src.contains('?') {
+        push_source(&mut output, &format!("{src}/hls/master.m3u8"), HLS_TYPE);
+    }
+    push_source(&mut output, &variant_href(src, "webm"), "video/webm");
+    push_source(&mut output, &variant_href(src, "mp4"), "video/mp4");
+    output.push_str(&format!(r#"<source src="{}">"#, escape_attr(src)));
+    output
+}
+
+fn push_source(output: &mut String, href: &str, content_type: &str) {
+    output.push_str(&format!(
+        r#"<source src="{}" type="{content_type}">"#,
+        escape_attr(href)
+    ));
+}
+
+fn decorate_image_tag(tag: &str) -> String {
+    let Some(src) = attribute_value(tag, "src") else {
+        return tag.to_string();
+    };
+    if !is_local_file_href(src) || src.contains("variant=") {
+        return tag.to_string();
+    }
+    let tag = replace_attribute(tag, "src", &variant_href(src, "display"));
+    if tag.contains(" srcset=") {
+        return tag;
+    }
+    let srcset = SRCSET_WIDTHS
+        .iter()
+        .map(|width| {
+            let separator = if src.contains('?') { '&' } else { '?' };
+            format!("{src}{separator}w={width} {width}w")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"{} srcset="{}" sizes="{SRCSET_SIZES}">"#,
+        &tag[..tag.len().saturating_sub(1)],
+        escape_attr(&srcset)
+    )
+}
+
+fn attribute_value<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let marker = format!(r#"{name}=""#);
+    let start = tag.find(&marker)? + marker.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+fn remove_attribute(tag: &str, name: &str) -> String {
+    let marker = format!(r#" {name}=""#);
+    let Some(start) = tag.find(&marker) else {
+        return tag.to_string();
+    };
+    let value_start = start + marker.len();
+    match tag[value_start..].find('"') {
+        Some(end) => format!("{}{}", &tag[..start], &tag[value_start + end + 1..]),
+        None => tag.to_string(),
+    }
+}
+
+fn replace_attribute(tag: &str, name: &str, value: &str) -> String {
+    let marker = format!(r#"{name}=""#);
+    let Some(start) = tag.find(&marker).map(|index| index + marker.len()) else {
+        return tag.to_string();
+    };
+    let Some(end) = tag[start..].find('"') else {
+        return tag.to_string();
+    };
+    format!(
+        "{}{}{}",
+        &tag[..start],
+        escape_attr(value),
+        &tag[start + end..]
+    )
+}
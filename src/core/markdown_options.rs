@@ -1,3 +1,4 @@
+use super::markdown_wiki::WikiTarget;
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -15,4 +16,7 @@ pub struct MarkdownOptions<'a> {
     pub public_base_url: Option<&'a str>,
     pub google_maps_embed_api_key: Option<&'a str>,
     pub external_embed_cache: Option<&'a HashMap<String, EmbedMetadata>>,
+    /// Resolved `[[ref]]` targets keyed by the reference as written; `None`
+    /// leaves wiki syntax as plain text.
+    pub wiki_targets: Option<&'a HashMap<String, WikiTarget>>,
 }
@@ -1,3 +1,4 @@
+use super::{local_link_targets, LinkTarget};
 use super::{render_markdown, render_markdown_with_options, EmbedMetadata, MarkdownOptions};
 use std::collections::HashMap;
 
@@ -6,9 +7,24 @@ fn render_markdown_keeps_safe_media_embeds() {
     let html = render_markdown("![](/demo/file)\n\n<video controls src=\"/clip/file\"></video>");
     assert!(html.contains("<img"));
     assert!(html.contains("src=\"/demo/file?variant=display\""));
-    assert!(html.contains("<video"));
-    assert!(html.contains("src=\"/clip/file\""));
-    assert!(html.contains("poster=\"/clip/file?variant=poster\""));
+    assert!(html.contains("srcset=\"/demo/file?w=480 480w, /demo/file?w=960 960w, /demo/file?w=1400 1400w, /demo/file?w=1920 1920w\""));
+    assert!(html.contains(
+        "<video controls=\"\" poster=\"/clip/file?variant=poster\">\
+         <source src=\"/clip/file/hls/master.m3u8\" type=\"application/vnd.apple.mpegurl\">\
+         <source src=\"/clip/file?variant=webm\" type=\"video/webm\">\
+         <source src=\"/clip/file?variant=mp4\" type=\"video/mp4\">\
+         <source src=\"/clip/file\"></video>"
+    ));
+}
+
+#[test]
+fn render_markdown_keeps_local_audio_players() {
+    let html = render_markdown(
+        "<audio controls preload=\"metadata\" onplay=\"evil()\" src=\"/song/file\"></audio>",
+    );
+    assert!(html.contains("<audio controls"));
+    assert!(html.contains("src=\"/song/file\""));
+    assert!(!html.contains("onplay="));
 }
 
 #[test]
@@ -109,6 +125,7 @@ fn render_markdown_embeds_maps_only_with_key() {
             public_base_url: None,
             google_maps_embed_api_key: Some("maps-key"),
             external_embed_cache: None,
+            wiki_targets: None,
         },
     );
 
@@ -148,6 +165,7 @@ fn render_markdown_uses_cached_external_metadata_cards() {
             public_base_url: None,
             google_maps_embed_api_key: None,
             external_embed_cache: Some(&cache),
+            wiki_targets: None,
         },
     );
 
@@ -156,3 +174,25 @@ fn render_markdown_uses_cached_external_metadata_cards() {
     assert!(html.contains("Cached summary"));
     assert!(html.contains("https://example.com/card.jpg"));
 }
+
+#[test]
+fn local_link_targets_collect_distinct_local_references() {
+    let targets = local_link_targets(
+        "[a](/plan) [b](/alice/plan#top) ![](/alice/photo/file)\n\n\
+         <video src=\"/clip/file\"></video> [c](/plan) [d](/alice/search) [e](https://x.test/plan)",
+    );
+    let target = |space: Option<&str>, reference: &str, is_file| LinkTarget {
+        space: space.map(str::to_string),
+        reference: reference.to_string(),
+        is_file,
+    };
+    assert_eq!(
+        targets,
+        vec![
+            target(None, "plan", false),
+            target(Some("alice"), "plan", false),
+            target(Some("alice"), "photo", true),
+            target(None, "clip", true),
+        ]
+    );
+}
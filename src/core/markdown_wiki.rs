@@ -0,0 +1,184 @@
+//! `[[ref|label]]` wiki links and `![[ref]]` transclusions
+
+use super::markdown_embed_blocks::EmbedBlock;
+use super::markdown_links::{escape_attr, html_text};
+use super::{looks_like_id, normalize_alias};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+static WIKI_LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(!?)\[\[([^\[\]|\n]+)(?:\|([^\[\]\n]*))?\]\]").unwrap());
+
+/// One wiki reference as written in a body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WikiLink {
+    pub reference: String,
+    pub label: Option<String>,
+    pub transclude: bool,
+}
+
+impl WikiLink {
+    /// Resource id or normalized alias the reference names, if it can name one.
+    pub fn resource_ref(&self) -> Option<String> {
+        if looks_like_id(&self.reference) {
+            return Some(self.reference.clone());
+        }
+        normalize_alias(Some(&self.reference)).ok().flatten()
+    }
+}
+
+/// A wiki reference the caller resolved and may show to the reader.
+#[derive(Clone, Debug, Default)]
+pub struct WikiTarget {
+    pub href: String,
+    pub title: String,
+    /// Block HTML used when the link stands alone in its paragraph.
+    pub card_html: String,
+    /// Rendered body of the target for `![[ref]]`; `None` falls back to the card.
+    pub transclusion_html: Option<String>,
+}
+
+/// Wiki references in document order, skipping code fences and code spans.
+pub fn wiki_links(body: &str) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+    for_each_prose_line(body, |line| {
+        for segment in prose_segments(line) {
+            links.extend(WIKI_LINK.captures_iter(segment).map(|capture| {
+                WikiLink {
+                    reference: capture[2].trim().to_string(),
+                    label: capture
+                        .get(3)
+                        .map(|label| label.as_str().trim().to_string()),
+                    transclude: !capture[1].is_empty(),
+                }
+            }));
+        }
+        line.to_string()
+    });
+    links
+}
+
+/// Reduce wiki references outside code spans to their label, or to the
+/// reference itself when unlabeled, for plain-text uses such as summaries.
+pub fn strip_wiki_links(line: &str) -> String {
+    let mut output = String::new();
+    for (index, segment) in line.split('`').enumerate() {
+        if index > 0 {
+            output.push('`');
+        }
+        if index % 2 == 1 {
+            output.push_str(segment);
+            continue;
+        }
+        output.push_str(
+            &WIKI_LINK.replace_all(segment, |capture: &regex::Captures| {
+                capture
+                    .get(3)
+                    .map(|label| label.as_str().trim())
+                    .filter(|label| !label.is_empty())
+                    .unwrap_or(capture[2].trim())
+                    .to_string()
+            }),
+        );
+    }
+    output
+}
+
+/// Swap wiki references for tokens restored after sanitizing; unresolved
+/// references render as plain text so hidden and missing targets look alike.
+pub(super) fn extract(
+    body: &str,
+    targets: &HashMap<String, WikiTarget>,
+) -> (String, Vec<EmbedBlock>) {
+    let mut blocks = Vec::new();
+    let out = for_each_prose_line(body, |line| {
+        if !line.contains("[[") {
+            return line.to_string();
+        }
+        if let Some(capture) = WIKI_LINK
+            .captures(line.trim())
+            .filter(|capture| capture[0].len() == line.trim().len())
+        {
+            let target = targets.get(capture[2].trim());
+            let html = match target {
+                Some(target) if !capture[1].is_empty() => target
+                    .transclusion_html
+                    .clone()
+                    .unwrap_or_else(|| target.card_html.clone()),
+                Some(target) => target.card_html.clone(),
+                None => format!("<p>{}</p>", inline_html(&capture, None)),
+            };
+            return push_block(&mut blocks, html);
+        }
+        let mut output = String::new();
+        for (index, segment) in line.split('`').enumerate() {
+            if index > 0 {
+                output.push('`');
+            }
+            if index % 2 == 1 {
+                output.push_str(segment);
+                continue;
+            }
+            output.push_str(
+                &WIKI_LINK.replace_all(segment, |capture: &regex::Captures| {
+                    let html = inline_html(capture, targets.get(capture[2].trim()));
+                    push_block(&mut blocks, html)
+                }),
+            );
+        }
+        output
+    });
+    (out, blocks)
+}
+
+fn inline_html(capture: &regex::Captures, target: Option<&WikiTarget>) -> String {
+    let label = capture
+        .get(3)
+        .map(|label| label.as_str().trim())
+        .filter(|label| !label.is_empty());
+    match target {
+        Some(target) => format!(
+            r#"<a href="{}" class="wiki-link">{}</a>"#,
+            escape_attr(&target.href),
+            html_text(label.unwrap_or(&target.title)),
+        ),
+        None => format!(
+            r#"<span class="wiki-link wiki-link-missing">{}</span>"#,
+            html_text(label.unwrap_or(capture[2].trim())),
+        ),
+    }
+}
+
+fn push_block(blocks: &mut Vec<EmbedBlock>, html: String) -> String {
+    let token = format!("KJXLKJ_WIKI_TOKEN_{}_END", blocks.len());
+    blocks.push(EmbedBlock {
+        token: token.clone(),
+        html,
+    });
+    token
+}
+
+/// Rebuild the body, rewriting only lines outside fenced code blocks.
+fn for_each_prose_line<F: FnMut(&str) -> String>(body: &str, mut rewrite: F) -> String {
+    let mut out = String::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            out.push_str(line);
+        } else if in_fence {
+            out.push_str(line);
+        } else {
+            out.push_str(&rewrite(line));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Line segments outside inline code spans.
+fn prose_segments(line: &str) -> impl Iterator<Item = &str> {
+    line.split('`').step_by(2)
+}
@@ -0,0 +1,102 @@
+use super::{local_link_targets, strip_wiki_links, wiki_links, LinkTarget, WikiLink, WikiTarget};
+use super::{render_markdown_with_options, MarkdownOptions};
+use std::collections::HashMap;
+
+fn render(body: &str, targets: &HashMap<String, WikiTarget>) -> String {
+    render_markdown_with_options(
+        body,
+        MarkdownOptions {
+            wiki_targets: Some(targets),
+            ..MarkdownOptions::default()
+        },
+    )
+}
+
+fn target() -> HashMap<String, WikiTarget> {
+    HashMap::from([(
+        "plan".to_string(),
+        WikiTarget {
+            href: "/alice/plan".to_string(),
+            title: "The Plan".to_string(),
+            card_html: "<a class=\"local-url-card\" href=\"/alice/plan\">The Plan</a>".to_string(),
+            transclusion_html: Some("<section class=\"transclusion\">inner</section>".to_string()),
+        },
+    )])
+}
+
+#[test]
+fn wiki_links_skip_code_and_keep_labels() {
+    let links =
+        wiki_links("See [[plan|the plan]] and ![[Notes]]\n\n`[[code]]`\n\n```\n[[fenced]]\n```\n");
+    assert_eq!(
+        links,
+        vec![
+            WikiLink {
+                reference: "plan".to_string(),
+                label: Some("the plan".to_string()),
+                transclude: false,
+            },
+            WikiLink {
+                reference: "Notes".to_string(),
+                label: None,
+                transclude: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn inline_wiki_links_use_the_label_or_target_title() {
+    let html = render("See [[plan|the plan]] and [[plan]].", &target());
+    assert!(html.contains(r#"<a href="/alice/plan" class="wiki-link">the plan</a>"#));
+    assert!(html.contains(r#"<a href="/alice/plan" class="wiki-link">The Plan</a>"#));
+}
+
+#[test]
+fn standalone_wiki_links_render_cards_and_transclusions() {
+    let html = render("[[plan]]\n\n![[plan]]\n", &target());
+    assert!(html.contains("class=\"local-url-card\""));
+    assert!(html.contains("<section class=\"transclusion\">inner</section>"));
+    assert!(!html.contains("[[plan]]"));
+}
+
+#[test]
+fn unresolved_wiki_links_render_as_plain_text() {
+    let html = render("[[secret]] and see [[secret|hidden]]", &HashMap::new());
+    assert!(html.contains(r#"<span class="wiki-link wiki-link-missing">secret</span>"#));
+    assert!(html.contains(r#"<span class="wiki-link wiki-link-missing">hidden</span>"#));
+    assert!(!html.contains("<a "));
+    let escaped = render("[[<b>x</b>]]", &HashMap::new());
+    assert!(!escaped.contains("<b>"));
+}
+
+#[test]
+fn wiki_links_in_code_stay_literal() {
+    let html = render("`[[plan]]`\n\n```\n[[plan]]\n```\n", &target());
+    assert!(!html.contains("wiki-link"));
+    assert_eq!(html.matches("[[plan]]").count(), 2);
+}
+
+#[test]
+fn wiki_links_are_indexed_as_local_links() {
+    assert_eq!(
+        local_link_targets("[[Plan Notes]] and [[plan-notes|again]]"),
+        vec![LinkTarget {
+            space: None,
+            reference: "plan-notes".to_string(),
+            is_file: false,
+        }]
+    );
+}
+
+#[test]
+fn stripping_keeps_labels_or_references_as_plain_text() {
+    assert_eq!(
+        strip_wiki_links("See [[trip-plan|the plan]] and ![[photo]] or [[notes| ]]"),
+        "See the plan and photo or notes"
+    );
+    assert_eq!(
+        strip_wiki_links("Keep `[[code]]` as is"),
+        "Keep `[[code]]` as is"
+    );
+}
@@ -1,24 +1,51 @@
 //! Core domain models and validation
 
+pub mod body_patch;
+#[cfg(test)]
+mod body_patch_tests;
+pub mod front_matter;
+mod front_matter_formats;
+#[cfg(test)]
+mod front_matter_tests;
+mod link_targets;
 pub mod live_settings;
 mod markdown;
 mod markdown_embed_blocks;
 mod markdown_embeds;
 mod markdown_links;
+mod markdown_media;
 mod markdown_options;
 #[cfg(test)]
 mod markdown_tests;
+mod markdown_wiki;
+#[cfg(test)]
+mod markdown_wiki_tests;
 pub mod nostr;
 #[cfg(test)]
 mod nostr_tests;
+pub mod search_language;
+mod search_query;
+mod search_query_format;
+#[cfg(test)]
+mod search_query_tests;
+pub mod text_diff;
+#[cfg(test)]
+mod text_diff_tests;
 mod validation;
 #[cfg(test)]
 mod validation_tests;
 
+pub use front_matter::{normalize_tag, parse_front_matter, strip_front_matter, FrontMatter};
+pub use link_targets::local_link_targets;
 pub use markdown::{
     external_embed_urls, render_markdown, render_markdown_with_options, render_markdown_with_origin,
 };
+pub use markdown_links::LinkTarget;
 pub use markdown_options::{EmbedMetadata, MarkdownOptions};
+pub use markdown_wiki::{strip_wiki_links, wiki_links, WikiLink, WikiTarget};
+pub use search_query::{
+    parse_search_query, Comparison, FilterTerm, SearchFilter, SearchQuery, TimeBound, TimeValue,
+};
 pub use validation::{
     derive_summary, derive_title, derive_title_with_fallback, extract_title, generate_id,
     looks_like_id, normalize_alias, validate_id, AliasError, IdError,
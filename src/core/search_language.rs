@@ -0,0 +1,40 @@
+//! Full-text search language settings helpers
+
+/// Postgres text search configurations a space may choose; `simple` only
+/// lowercases words, the others add stemming and stop words.
+pub const SEARCH_LANGUAGES: &[&str] = &[
+    "simple",
+    "arabic",
+    "danish",
+    "dutch",
+    "english",
+    "finnish",
+    "french",
+    "german",
+    "greek",
+    "hindi",
+    "hungarian",
+    "indonesian",
+    "irish",
+    "italian",
+    "lithuanian",
+    "nepali",
+    "norwegian",
+    "portuguese",
+    "romanian",
+    "russian",
+    "serbian",
+    "spanish",
+    "swedish",
+    "tamil",
+    "turkish",
+];
+
+pub fn normalize_search_language(value: &str) -> Result<String, String> {
+    let value = value.trim().to_lowercase();
+    if SEARCH_LANGUAGES.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err("Search language must be simple or a supported language".to_string())
+    }
+}
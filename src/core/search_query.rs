@@ -0,0 +1,180 @@
+//! Search query syntax: free text plus `field:value` filters
+
+use super::normalize_tag;
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+
+/// A parsed search box query. `text` keeps every token that is not a
+/// recognized filter, in order, for full-text matching.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchQuery {
+    pub text: String,
+    pub filters: Vec<SearchFilter>,
+}
+
+/// One filter token; a leading `-` negates it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchFilter {
+    pub negated: bool,
+    #[serde(flatten)]
+    pub term: FilterTerm,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "field", content = "value", rename_all = "snake_case")]
+pub enum FilterTerm {
+    Title(String),
+    Alias(String),
+    Tag(String),
+    Kind(&'static str),
+    Family(&'static str),
+    Is(&'static str),
+    Has(&'static str),
+    Created(TimeBound),
+    Updated(TimeBound),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+/// A calendar day in UTC, or an age such as `7d` measured back from now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeValue {
+    Date(NaiveDate),
+    Age { amount: u32, unit: char },
+}
+
+/// `op` applies to dates as written; for ages `<7d` means younger than seven
+/// days, and a bare age means the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeBound {
+    pub op: Comparison,
+    pub value: TimeValue,
+}
+
+const KINDS: &[&str] = &["note", "media"];
+const FAMILIES: &[&str] = &["image", "video", "audio", "file"];
+const IS_FLAGS: &[&str] = &["public", "space", "private", "favorite"];
+const HAS_FLAGS: &[&str] = &["attachment", "tags", "links"];
+/// Age units with the largest amount each accepts, about 1000 years, so
+/// `NOW()` minus the age stays inside the Postgres timestamp range.
+const AGE_UNITS: &[(char, u32)] = &[
+    ('h', 8_766_000),
+    ('d', 365_250),
+    ('w', 52_179),
+    ('y', 1_000),
+];
+
+pub fn parse_search_query(input: &str) -> SearchQuery {
+    let mut text = Vec::new();
+    let mut filters = Vec::new();
+    for token in tokens(input) {
+        match parse_filter(&token) {
+            Some(filter) => filters.push(filter),
+            None => text.push(token),
+        }
+    }
+    SearchQuery {
+        text: text.join(" "),
+        filters,
+    }
+}
+
+/// Whitespace-separated tokens; double quotes keep spaces inside one token.
+fn tokens(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in input.chars() {
+        if ch == '"' {
+            quoted = !quoted;
+        }
+        if ch.is_whitespace() && !quoted {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(ch);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn parse_filter(token: &str) -> Option<SearchFilter> {
+    let (negated, body) = match token.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, token),
+    };
+    let (key, raw) = body.split_once(':')?;
+    let value = raw
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(raw)
+        .trim();
+    if value.is_empty() {
+        return None;
+    }
+    let lower = value.to_lowercase();
+    let term = match key.to_ascii_lowercase().as_str() {
+        "title" => FilterTerm::Title(value.to_string()),
+        "alias" => FilterTerm::Alias(value.to_string()),
+        "tag" => FilterTerm::Tag(normalize_tag(value)?),
+        "kind" => FilterTerm::Kind(known(KINDS, &lower)?),
+        "family" => FilterTerm::Family(known(FAMILIES, &lower)?),
+        "is" => FilterTerm::Is(known(IS_FLAGS, &lower)?),
+        "has" => FilterTerm::Has(known(HAS_FLAGS, &lower)?),
+        "created" => FilterTerm::Created(TimeBound::parse(&lower)?),
+        "updated" => FilterTerm::Updated(TimeBound::parse(&lower)?),
+        _ => return None,
+    };
+    Some(SearchFilter { negated, term })
+}
+
+fn known(values: &[&'static str], value: &str) -> Option<&'static str> {
+    values.iter().copied().find(|item| *item == value)
+}
+
+impl TimeBound {
+    fn parse(value: &str) -> Option<Self> {
+        let (op, rest) = [
+            (">=", Comparison::Ge),
+            ("<=", Comparison::Le),
+            (">", Comparison::Gt),
+            ("<", Comparison::Lt),
+            ("=", Comparison::Eq),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|rest| (Some(op), rest)))
+        .unwrap_or((None, value));
+        let date = NaiveDate::parse_from_str(rest, "%Y-%m-%d").ok();
+        if let Some(date) = date.filter(|date| (1..=9999).contains(&date.year())) {
+            let op = op.unwrap_or(Comparison::Eq);
+            return Some(Self {
+                op,
+                value: TimeValue::Date(date),
+            });
+        }
+        let (unit, max) = rest
+            .chars()
+            .last()
+            .and_then(|last| AGE_UNITS.iter().copied().find(|(unit, _)| *unit == last))?;
+        let amount = rest[..rest.len() - 1]
+            .parse::<u32>()
+            .ok()
+            .filter(|n| (1..=max).contains(n))?;
+        let op = op.unwrap_or(Comparison::Lt);
+        (op != Comparison::Eq).then_some(Self {
+            op,
+            value: TimeValue::Age { amount, unit },
+        })
+    }
+}
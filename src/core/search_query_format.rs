@@ -0,0 +1,68 @@
+//! Canonical text and JSON forms of parsed search queries
+
+use super::search_query::{
+    Comparison, FilterTerm, SearchFilter, SearchQuery, TimeBound, TimeValue,
+};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+impl Comparison {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Eq => "=",
+            Self::Ge => ">=",
+            Self::Gt => ">",
+        }
+    }
+}
+
+impl fmt::Display for TimeBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            TimeValue::Date(date) if self.op == Comparison::Eq => write!(f, "{date}"),
+            TimeValue::Date(date) => write!(f, "{}{date}", self.op.as_str()),
+            TimeValue::Age { amount, unit } => write!(f, "{}{amount}{unit}", self.op.as_str()),
+        }
+    }
+}
+
+impl Serialize for TimeBound {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for SearchFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (key, value) = match &self.term {
+            FilterTerm::Title(value) => ("title", value.clone()),
+            FilterTerm::Alias(value) => ("alias", value.clone()),
+            FilterTerm::Tag(value) => ("tag", value.clone()),
+            FilterTerm::Kind(value) => ("kind", value.to_string()),
+            FilterTerm::Family(value) => ("family", value.to_string()),
+            FilterTerm::Is(value) => ("is", value.to_string()),
+            FilterTerm::Has(value) => ("has", value.to_string()),
+            FilterTerm::Created(bound) => ("created", bound.to_string()),
+            FilterTerm::Updated(bound) => ("updated", bound.to_string()),
+        };
+        let sign = if self.negated { "-" } else { "" };
+        if value.contains(char::is_whitespace) {
+            write!(f, "{sign}{key}:\"{value}\"")
+        } else {
+            write!(f, "{sign}{key}:{value}")
+        }
+    }
+}
+
+/// Canonical query text: filters in order, then the free text.
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = self.filters.iter().map(ToString::to_string).collect();
+        if !self.text.is_empty() {
+            parts.push(self.text.clone());
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
@@ -0,0 +1,140 @@
+use super::search_query::*;
+use chrono::NaiveDate;
+use serde_json::json;
+
+fn filter(negated: bool, term: FilterTerm) -> SearchFilter {
+    SearchFilter { negated, term }
+}
+
+#[test]
+fn filters_split_from_free_text() {
+    let query = parse_search_query(
+        "title:foo alias:bar kind:media family:video is:private is:favorite \
+         created:>2026-01-01 updated:<7d has:attachment launch plan",
+    );
+    assert_eq!(query.text, "launch plan");
+    assert_eq!(
+        query.filters,
+        vec![
+            filter(false, FilterTerm::Title("foo".to_string())),
+            filter(false, FilterTerm::Alias("bar".to_string())),
+            filter(false, FilterTerm::Kind("media")),
+            filter(false, FilterTerm::Family("video")),
+            filter(false, FilterTerm::Is("private")),
+            filter(false, FilterTerm::Is("favorite")),
+            filter(
+                false,
+                FilterTerm::Created(TimeBound {
+                    op: Comparison::Gt,
+                    value: TimeValue::Date(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+                })
+            ),
+            filter(
+                false,
+                FilterTerm::Updated(TimeBound {
+                    op: Comparison::Lt,
+                    value: TimeValue::Age {
+                        amount: 7,
+                        unit: 'd'
+                    },
+                })
+            ),
+            filter(false, FilterTerm::Has("attachment")),
+        ]
+    );
+}
+
+#[test]
+fn quotes_negation_and_case_are_understood() {
+    let query =
+        parse_search_query(r#"-is:Private TITLE:"Trip Log" tag:#Road "exact phrase" -draft"#);
+    assert_eq!(query.text, r#""exact phrase" -draft"#);
+    assert_eq!(
+        query.filters,
+        vec![
+            filter(true, FilterTerm::Is("private")),
+            filter(false, FilterTerm::Title("Trip Log".to_string())),
+            filter(false, FilterTerm::Tag("road".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn unknown_or_invalid_filters_stay_free_text() {
+    for input in [
+        "https://example.com",
+        "kind:video",
+        "is:draft",
+        "created:yesterday",
+        "updated:=7d",
+        "updated:0d",
+        "created:<4294967295y",
+        "created:<1001y",
+        "updated:>8766001h",
+        "created:-5000-01-01",
+        "created:+12345-01-01",
+        "title:",
+    ] {
+        let query = parse_search_query(input);
+        assert!(query.filters.is_empty(), "{input}");
+        assert_eq!(query.text, input);
+    }
+}
+
+#[test]
+fn age_amounts_are_capped_near_a_thousand_years_per_unit() {
+    for input in [
+        "created:<1000y",
+        "created:<52179w",
+        "created:<365250d",
+        "updated:>8766000h",
+    ] {
+        assert_eq!(parse_search_query(input).filters.len(), 1, "{input}");
+    }
+}
+
+#[test]
+fn canonical_text_round_trips() {
+    let input = r#"plan  TITLE:"Trip Log" created:2026-01-01 -updated:>=2w has:tags"#;
+    let canonical = parse_search_query(input).to_string();
+    assert_eq!(
+        canonical,
+        r#"title:"Trip Log" created:2026-01-01 -updated:>=2w has:tags plan"#
+    );
+    assert_eq!(parse_search_query(&canonical), parse_search_query(input));
+}
+
+#[test]
+fn filters_serialize_as_field_value_pairs() {
+    let query = parse_search_query("-created:<=2026-02-03 kind:note x");
+    assert_eq!(
+        serde_json::to_value(&query).unwrap(),
+        json!({
+            "text": "x",
+            "filters": [
+                {"negated": true, "field": "created", "value": "<=2026-02-03"},
+                {"negated": false, "field": "kind", "value": "note"},
+            ],
+        })
+    );
+}
+
+#[test]
+fn the_last_representable_day_parses_as_a_date() {
+    let last = NaiveDate::from_ymd_opt(9999, 12, 31).unwrap();
+    for (input, op) in [
+        ("created:<=9999-12-31", Comparison::Le),
+        ("created:9999-12-31", Comparison::Eq),
+        ("updated:>9999-12-31", Comparison::Gt),
+    ] {
+        let bound = match &parse_search_query(input).filters[..] {
+            [SearchFilter {
+                term: FilterTerm::Created(bound) | FilterTerm::Updated(bound),
+                ..
+            }] => *bound,
+            other => panic!("{input}: {other:?}"),
+        };
+        assert_eq!(bound.op, op, "{input}");
+        assert_eq!(bound.value, TimeValue::Date(last), "{input}");
+    }
+}
@@ -0,0 +1,167 @@
+//! Line and word diffs between two Markdown bodies
+
+use serde::Serialize;
+use similar::udiff::UnifiedHunkHeader;
+use similar::{ChangeTag, DiffOp, TextDiff};
+use std::time::{Duration, Instant};
+
+/// Lines of unchanged context kept around each hunk.
+pub const DEFAULT_CONTEXT: usize = 3;
+/// Budget for one full diff, word emphasis included; past it `similar`
+/// falls back to a coarser but still correct diff.
+pub const DIFF_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DiffStats {
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineTag {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// One run of words inside a line; `changed` marks the words that differ.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WordSpan {
+    pub text: String,
+    pub changed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    pub tag: LineTag,
+    /// 1-based line numbers on each side.
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub words: Vec<WordSpan>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DiffHunk {
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// One side-by-side row; a missing side means the line exists only on the other.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SplitRow {
+    pub old: Option<DiffLine>,
+    pub new: Option<DiffLine>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BodyDiff {
+    pub stats: DiffStats,
+    pub unified: String,
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// Count added and removed lines without building hunks, giving up on the
+/// minimal diff after `timeout`.
+pub fn line_stats(old: &str, new: &str, timeout: Duration) -> DiffStats {
+    stats_of(&TextDiff::configure().timeout(timeout).diff_lines(old, new))
+}
+
+fn stats_of<'a>(diff: &TextDiff<'a, 'a, 'a, str>) -> DiffStats {
+    let mut stats = DiffStats::default();
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => stats.added += 1,
+            ChangeTag::Delete => stats.removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    stats
+}
+
+/// Unified text plus word-annotated hunks with `context` lines around changes.
+pub fn diff_bodies(old: &str, new: &str, labels: (&str, &str), context: usize) -> BodyDiff {
+    let deadline = Instant::now() + DIFF_TIMEOUT;
+    let diff = TextDiff::configure()
+        .deadline(deadline)
+        .diff_lines(old, new);
+    let unified = diff
+        .unified_diff()
+        .context_radius(context)
+        .header(labels.0, labels.1)
+        .to_string();
+    let hunks = diff
+        .grouped_ops(context)
+        .iter()
+        .map(|group| DiffHunk {
+            header: hunk_header(group),
+            lines: group
+                .iter()
+                .flat_map(|op| diff.iter_inline_changes_deadline(op, Some(deadline)))
+                .map(|change| DiffLine {
+                    tag: match change.tag() {
+                        ChangeTag::Equal => LineTag::Equal,
+                        ChangeTag::Insert => LineTag::Insert,
+                        ChangeTag::Delete => LineTag::Delete,
+                    },
+                    old_line: change.old_index().map(|index| index + 1),
+                    new_line: change.new_index().map(|index| index + 1),
+                    words: change
+                        .iter_strings_lossy()
+                        .map(|(changed, text)| WordSpan {
+                            text: text.trim_end_matches(['\r', '\n']).to_string(),
+                            changed,
+                        })
+                        .filter(|span| !span.text.is_empty())
+                        .collect(),
+                })
+                .collect(),
+        })
+        .collect();
+    BodyDiff {
+        stats: stats_of(&diff),
+        unified,
+        hunks,
+    }
+}
+
+/// Pair deletions with the insertions that replaced them for two-column display.
+pub fn split_rows(hunk: &DiffHunk) -> Vec<SplitRow> {
+    let mut rows = Vec::new();
+    let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+    for line in &hunk.lines {
+        match line.tag {
+            LineTag::Delete => deleted.push(line.clone()),
+            LineTag::Insert => inserted.push(line.clone()),
+            LineTag::Equal => {
+                flush_pairs(&mut rows, &mut deleted, &mut inserted);
+                rows.push(SplitRow {
+                    old: Some(line.clone()),
+                    new: Some(line.clone()),
+                });
+            }
+        }
+    }
+    flush_pairs(&mut rows, &mut deleted, &mut inserted);
+    rows
+}
+
+fn flush_pairs(
+    rows: &mut Vec<SplitRow>,
+    deleted: &mut Vec<DiffLine>,
+    inserted: &mut Vec<DiffLine>,
+) {
+    let count = deleted.len().max(inserted.len());
+    let mut deleted = deleted.drain(..);
+    let mut inserted = inserted.drain(..);
+    for _ in 0..count {
+        rows.push(SplitRow {
+            old: deleted.next(),
+            new: inserted.next(),
+        });
+    }
+}
+
+fn hunk_header(group: &[DiffOp]) -> String {
+    UnifiedHunkHeader::new(group).to_string()
+}
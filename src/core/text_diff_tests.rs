@@ -0,0 +1,88 @@
+use super::text_diff::*;
+
+#[test]
+fn line_stats_count_added_and_removed_lines() {
+    let stats = line_stats(
+        "# Title\n\nkeep\nold line\n",
+        "# Title\n\nkeep\nnew line\nextra\n",
+        DIFF_TIMEOUT,
+    );
+    assert_eq!(
+        stats,
+        DiffStats {
+            added: 2,
+            removed: 1
+        }
+    );
+    assert_eq!(
+        line_stats("same\n", "same\n", DIFF_TIMEOUT),
+        DiffStats::default()
+    );
+}
+
+#[test]
+fn line_stats_past_the_deadline_still_count_every_line() {
+    let old = (0..3000).map(|n| format!("a{n}\n")).collect::<String>();
+    let new = (0..3000).map(|n| format!("b{n}\n")).collect::<String>();
+    assert_eq!(
+        line_stats(&old, &new, std::time::Duration::ZERO),
+        DiffStats {
+            added: 3000,
+            removed: 3000
+        }
+    );
+}
+
+#[test]
+fn diff_bodies_builds_unified_text_and_word_spans() {
+    let diff = diff_bodies(
+        "# Title\nthe quick fox\n",
+        "# Title\nthe slow fox\n",
+        ("snapshot 1", "snapshot 2"),
+        DEFAULT_CONTEXT,
+    );
+    assert!(diff.unified.starts_with("--- snapshot 1\n+++ snapshot 2\n"));
+    assert!(diff.unified.contains("-the quick fox\n+the slow fox\n"));
+    assert_eq!(diff.hunks.len(), 1);
+    assert_eq!(diff.hunks[0].header, "@@ -1,2 +1,2 @@");
+    let inserted = diff.hunks[0]
+        .lines
+        .iter()
+        .find(|line| line.tag == LineTag::Insert)
+        .unwrap();
+    assert_eq!(inserted.new_line, Some(2));
+    assert!(inserted
+        .words
+        .iter()
+        .any(|span| span.changed && span.text == "slow"));
+    assert!(inserted
+        .words
+        .iter()
+        .any(|span| !span.changed && span.text.contains("fox")));
+}
+
+#[test]
+fn diff_bodies_limits_context_around_changes() {
+    let old = (1..=20).map(|n| format!("line {n}\n")).collect::<String>();
+    let new = old.replace("line 10\n", "line ten\n");
+    let diff = diff_bodies(&old, &new, ("a", "b"), 1);
+    assert_eq!(diff.hunks.len(), 1);
+    assert_eq!(diff.hunks[0].lines.len(), 4);
+    assert!(diff_bodies(&old, &old, ("a", "b"), 1).hunks.is_empty());
+}
+
+#[test]
+fn split_rows_pair_replacements_and_keep_unmatched_sides() {
+    let diff = diff_bodies("a\nb\nc\n", "a\nB\nc\nd\n", ("a", "b"), DEFAULT_CONTEXT);
+    let rows = split_rows(&diff.hunks[0]);
+    let sides = rows
+        .iter()
+        .map(|row| (row.old.is_some(), row.new.is_some()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sides,
+        vec![(true, true), (true, true), (true, true), (false, true)]
+    );
+    assert_eq!(rows[1].old.as_ref().unwrap().tag, LineTag::Delete);
+    assert_eq!(rows[1].new.as_ref().unwrap().tag, LineTag::Insert);
+}
@@ -1,5 +1,7 @@
 //! Validation logic for resource ids, aliases, and derived fields
 
+use super::front_matter::{parse_front_matter, strip_front_matter};
+use super::markdown_wiki::strip_wiki_links;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashSet;
@@ -16,6 +18,9 @@ static RESERVED_ALIASES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     [
         "admin",
         "assets",
+        "feed.atom",
+        "feed.json",
+        "feed.rss",
         "healthz",
         "login",
         "logout",
@@ -24,6 +29,8 @@ static RESERVED_ALIASES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
         "search",
         "setup",
         "sitemap.xml",
+        "tags",
+        "uploads",
     ]
     .into_iter()
     .collect()
@@ -102,7 +109,13 @@ pub fn looks_like_id(value: &str) -> bool {
 }
 
 pub fn extract_title(body: &str) -> Option<String> {
-    for line in body.lines() {
+    if let Some(title) = parse_front_matter(body)
+        .as_ref()
+        .and_then(|front| front.text("title"))
+    {
+        return Some(title.to_string());
+    }
+    for line in strip_front_matter(body).lines() {
         let trimmed = line.trim();
         if let Some(title) = trimmed.strip_prefix("# ") {
             return Some(title.to_string());
@@ -123,7 +136,14 @@ pub fn derive_title_with_fallback(body: &str, fallback: &str) -> String {
 }
 
 pub fn derive_summary(body: &str) -> String {
-    let mut lines = meaningful_lines(body);
+    let front = parse_front_matter(body);
+    if let Some(summary) = front
+        .as_ref()
+        .and_then(|front| front.text("summary").or_else(|| front.text("description")))
+    {
+        return shorten(&strip_wiki_links(summary), false);
+    }
+    let mut lines = meaningful_lines(strip_front_matter(body));
     let Some(first_line) = lines.next() else {
         return "No summary yet.".to_string();
     };
@@ -131,7 +151,9 @@ pub fn derive_summary(body: &str) -> String {
 }
 
 fn strip_summary_markers(line: &str) -> String {
-    SUMMARY_PREFIX_REGEX.replace(line, "").trim().to_string()
+    strip_wiki_links(&SUMMARY_PREFIX_REGEX.replace(line, ""))
+        .trim()
+        .to_string()
 }
 
 fn meaningful_lines<'a>(body: &'a str) -> impl Iterator<Item = String> + 'a {
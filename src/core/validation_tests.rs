@@ -44,6 +44,10 @@ fn aliases_normalize_and_validate() {
         normalize_alias(Some("resources")),
         Err(AliasError::Reserved)
     );
+    assert_eq!(
+        normalize_alias(Some("feed.atom")),
+        Err(AliasError::Reserved)
+    );
     assert_eq!(
         normalize_alias(Some("records")),
         Ok(Some("records".to_string()))
@@ -71,4 +75,13 @@ fn title_and_summary_derivation() {
     );
     assert!(derive_summary(&format!("# Hello\n\n{}", "A".repeat(180))).ends_with("..."));
     assert_eq!(derive_summary(""), "No summary yet.".to_string());
+    assert_eq!(
+        derive_summary("# Hello\n\n- Read [[trip-plan|the plan]] first"),
+        "Read the plan first"
+    );
+    assert_eq!(derive_summary("# Hello\n\n![[photo]]"), "photo");
+    assert_eq!(
+        derive_summary("---\nsummary: About [[trip-plan]]\n---\nBody"),
+        "About trip-plan"
+    );
 }
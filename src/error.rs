@@ -1,18 +1,25 @@
 //! Application error types
 
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::Serialize;
 use std::fmt;
 
 /// Application error type
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum AppError {
     Unauthorized(String),
+    Forbidden(String),
     InvalidRequest(String),
     PayloadTooLarge(String),
     NotFound(String),
+    /// State conflict; `current_snapshot` is set when a stale snapshot
+    /// base caused it.
+    Conflict {
+        message: String,
+        current_snapshot: Option<i32>,
+    },
     StorageError(String),
     DatabaseError(String),
 }
@@ -22,15 +29,19 @@ pub enum AppError {
 struct ErrorResponse {
     error: &'static str,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_snapshot: Option<i32>,
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::Unauthorized(msg) => write!(f, "unauthorized: {msg}"),
+            AppError::Forbidden(msg) => write!(f, "forbidden: {msg}"),
             AppError::InvalidRequest(msg) => write!(f, "invalid_request: {msg}"),
             AppError::PayloadTooLarge(msg) => write!(f, "payload_too_large: {msg}"),
             AppError::NotFound(msg) => write!(f, "not_found: {msg}"),
+            AppError::Conflict { message, .. } => write!(f, "conflict: {message}"),
             AppError::StorageError(msg) => write!(f, "storage_error: {msg}"),
             AppError::DatabaseError(msg) => write!(f, "database_error: {msg}"),
         }
@@ -41,8 +52,22 @@ impl std::error::Error for AppError {}
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        if let AppError::Conflict {
+            message,
+            current_snapshot: Some(current_snapshot),
+        } = self
+        {
+            let body = ErrorResponse {
+                error: "conflict",
+                message,
+                current_snapshot: Some(current_snapshot),
+            };
+            let etag = format!("\"{current_snapshot}\"");
+            return (StatusCode::CONFLICT, [(header::ETAG, etag)], Json(body)).into_response();
+        }
         let (status, error, message) = match self {
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "unauthorized", msg.clone()),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, "forbidden", msg.clone()),
             AppError::InvalidRequest(msg) => {
                 (StatusCode::BAD_REQUEST, "invalid_request", msg.clone())
             }
@@ -52,6 +77,7 @@ impl IntoResponse for AppError {
                 msg.clone(),
             ),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "not_found", msg.clone()),
+            AppError::Conflict { message, .. } => (StatusCode::CONFLICT, "conflict", message),
             AppError::StorageError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "storage_error",
@@ -64,7 +90,15 @@ impl IntoResponse for AppError {
             ),
         };
 
-        (status, Json(ErrorResponse { error, message })).into_response()
+        (
+            status,
+            Json(ErrorResponse {
+                error,
+                message,
+                current_snapshot: None,
+            }),
+        )
+            .into_response()
     }
 }
 
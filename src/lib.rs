@@ -5,6 +5,29 @@ pub mod config;
 pub mod core;
 pub mod error;
 pub mod media;
+mod media_audio;
+mod media_command;
+mod media_details;
+mod media_exif;
+mod media_exif_blocks;
+mod media_exif_strip;
+#[cfg(test)]
+mod media_exif_tests;
+mod media_exif_tiff;
+mod media_hls;
+mod media_probe;
+#[cfg(test)]
+mod media_probe_tests;
 mod media_svg;
+mod media_transcoder;
+mod media_transform;
+#[cfg(test)]
+mod media_transform_tests;
+mod media_variants;
+#[cfg(test)]
+mod media_variants_tests;
+mod media_video;
+#[cfg(test)]
+mod media_video_tests;
 pub mod storage;
 pub mod web;
@@ -30,6 +30,16 @@ enum Commands {
         #[command(subcommand)]
         action: ComposeAction,
     },
+    /// Service token commands
+    Tokens {
+        #[command(subcommand)]
+        action: TokensAction,
+    },
+    /// Media maintenance commands
+    Media {
+        #[command(subcommand)]
+        action: MediaAction,
+    },
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -55,6 +65,31 @@ enum ComposeAction {
     Verify,
 }
 
+#[derive(Subcommand)]
+enum TokensAction {
+    /// Create a service account and print a new bearer token once
+    Issue {
+        /// Personal-space slug that owns the service account
+        #[arg(long)]
+        space: String,
+        /// Service account display name
+        #[arg(long)]
+        name: String,
+        /// Token scope; repeat for several (defaults to all resource scopes)
+        #[arg(long = "scope")]
+        scopes: Vec<String>,
+        /// Optional lifetime in days, from 1 to 3650
+        #[arg(long)]
+        expires_days: Option<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MediaAction {
+    /// Queue derivative regeneration for every image, video, and audio resource
+    RequeueDerivatives,
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     init_crypto_provider();
@@ -74,6 +109,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Compose { action }) => match action {
             ComposeAction::Verify => cli::compose::verify()?,
         },
+        Some(Commands::Tokens { action }) => match action {
+            TokensAction::Issue {
+                space,
+                name,
+                scopes,
+                expires_days,
+            } => cli::tokens::issue(&space, &name, &scopes, expires_days).await?,
+        },
+        Some(Commands::Media { action }) => match action {
+            MediaAction::RequeueDerivatives => cli::media::requeue_derivatives().await?,
+        },
         None => {
             let config = Config::from_env()?;
             info!(
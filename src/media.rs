@@ -1,66 +1,31 @@
 //! Media derivative metadata and generation helpers
 
-use image::{imageops::FilterType, DynamicImage};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use crate::media_command::output_within;
+use image::metadata::Orientation;
+use image::{imageops::FilterType, DynamicImage, ImageDecoder, ImageReader};
+use std::io::Cursor;
 use std::path::Path;
+use std::time::Duration;
 use tokio::fs;
 use tokio::process::Command;
 
-const WEBP_CONTENT_TYPE: &str = "image/webp";
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct MediaVariants {
-    pub card: Option<MediaVariant>,
-    pub display: Option<MediaVariant>,
-    pub poster: Option<MediaVariant>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MediaVariant {
-    pub key: String,
-    pub content_type: String,
-    pub byte_size: i64,
-    pub width: i32,
-    pub height: i32,
-}
-
-pub struct GeneratedVariant {
-    pub name: &'static str,
-    pub variant: MediaVariant,
-    pub bytes: Vec<u8>,
-}
+pub use crate::media_audio::audio_waveform_from_path;
+pub use crate::media_details::{media_details_from_json, media_details_to_json, MediaDetails};
+pub use crate::media_exif::{read_capture_metadata, strip_location, CaptureMetadata};
+pub use crate::media_probe::{probe_audio_video, probe_image, MediaProbe};
+pub use crate::media_transcoder::VideoTranscoder;
+pub use crate::media_transform::{
+    transform_image, transform_prefix, ImageTransform, TransformParams, TRANSFORM_SIZES,
+};
+pub use crate::media_variants::{
+    is_variant_name, media_variants_from_json, media_variants_to_json, GeneratedVariant, HlsFile,
+    MediaRendition, MediaVariant, MediaVariants,
+};
 
-impl MediaVariants {
-    pub fn get(&self, name: &str) -> Option<&MediaVariant> {
-        match name {
-            "card" => self.card.as_ref(),
-            "display" => self.display.as_ref(),
-            "poster" => self.poster.as_ref(),
-            _ => None,
-        }
-    }
-
-    pub fn is_empty(&self) -> bool {
-        self.card.is_none() && self.display.is_none() && self.poster.is_none()
-    }
-}
-
-pub fn media_variants_from_json(value: Option<Value>) -> Option<MediaVariants> {
-    value
-        .and_then(|value| serde_json::from_value(value).ok())
-        .filter(|variants: &MediaVariants| !variants.is_empty())
-}
-
-pub fn media_variants_to_json(variants: &Option<MediaVariants>) -> Option<Value> {
-    variants
-        .as_ref()
-        .filter(|variants| !variants.is_empty())
-        .and_then(|variants| serde_json::to_value(variants).ok())
-}
+const WEBP_CONTENT_TYPE: &str = "image/webp";
 
 pub fn image_variants(id: &str, bytes: &[u8], quality: i64) -> Vec<GeneratedVariant> {
-    let Ok(image) = image::load_from_memory(bytes) else {
+    let Some(image) = load_oriented(bytes) else {
         return Vec::new();
     };
     encode_image_variants(id, &image, quality)
@@ -99,7 +64,7 @@ fn encode_image_variants(id: &str, image: &DynamicImage, quality: i64) -> Vec<Ge
 
 async fn decode_image_from_path(path: &Path) -> Option<DynamicImage> {
     if let Ok(bytes) = fs::read(path).await {
-        if let Ok(image) = image::load_from_memory(&bytes) {
+        if let Some(image) = load_oriented(&bytes) {
             return Some(image);
         }
         if let Some(image) = crate::media_svg::decode_svg(&bytes, path) {
@@ -109,32 +74,79 @@ async fn decode_image_from_path(path: &Path) -> Option<DynamicImage> {
     decode_frame_from_path(path).await
 }
 
-async fn decode_frame_from_path(path: &Path) -> Option<DynamicImage> {
-    let output = Command::new("ffmpeg")
-        .args([
-            "-hide_banner",
-            "-loglevel",
-            "error",
-            "-i",
-            path.to_str()?,
-            "-frames:v",
-            "1",
-            "-f",
-            "image2pipe",
-            "-vcodec",
-            "png",
-            "pipe:1",
-        ])
-        .output()
-        .await
+/// Largest source, in pixels, an on-demand transform will decode.
+pub const MAX_TRANSFORM_SOURCE_PIXELS: u64 = 40_000_000;
+
+/// Whether the dimensions declared in the header fit the transform limit,
+/// checked before anything is decoded; unreadable headers are left to fail
+/// at decode.
+pub(crate) fn transform_source_fits(bytes: &[u8]) -> bool {
+    ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()
+        .and_then(|reader| reader.into_dimensions().ok())
+        .or_else(|| crate::media_svg::svg_size(bytes))
+        .is_none_or(|(width, height)| {
+            u64::from(width) * u64::from(height) <= MAX_TRANSFORM_SOURCE_PIXELS
+        })
+}
+
+/// Decode stored image bytes upright, rasterizing SVG without a resource directory.
+pub(crate) fn decode_image_bytes(bytes: &[u8]) -> Option<DynamicImage> {
+    load_oriented(bytes).or_else(|| crate::media_svg::decode_svg(bytes, Path::new("/")))
+}
+
+/// Decode an image upright, applying its EXIF orientation; the tag is read
+/// directly when the decoder does not report one.
+fn load_oriented(bytes: &[u8]) -> Option<DynamicImage> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
         .ok()?;
+    let orientation = decoder
+        .orientation()
+        .ok()
+        .filter(|value| *value != Orientation::NoTransforms)
+        .or_else(|| {
+            read_capture_metadata(bytes)
+                .orientation
+                .and_then(Orientation::from_exif)
+        });
+    let mut image = DynamicImage::from_decoder(decoder).ok()?;
+    if let Some(orientation) = orientation {
+        image.apply_orientation(orientation);
+    }
+    Some(image)
+}
+
+/// Longest wait for FFmpeg to extract one frame.
+const FRAME_TIMEOUT: Duration = Duration::from_secs(60);
+
+async fn decode_frame_from_path(path: &Path) -> Option<DynamicImage> {
+    let mut command = Command::new("ffmpeg");
+    command.args([
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-i",
+        path.to_str()?,
+        "-frames:v",
+        "1",
+        "-f",
+        "image2pipe",
+        "-vcodec",
+        "png",
+        "pipe:1",
+    ]);
+    let output = output_within(&mut command, FRAME_TIMEOUT).await?;
     if !output.status.success() || output.stdout.is_empty() {
         return None;
     }
     image::load_from_memory(&output.stdout).ok()
 }
 
-fn encode_resized(
+pub(crate) fn encode_resized(
     id: &str,
     name: &'static str,
     image: &DynamicImage,
@@ -145,7 +157,7 @@ fn encode_resized(
     encode_webp(id, name, resized, quality)
 }
 
-fn encode_webp(
+pub(crate) fn encode_webp(
     id: &str,
     name: &'static str,
     image: DynamicImage,
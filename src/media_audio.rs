@@ -0,0 +1,59 @@
+//! Audio waveform helpers for media derivatives
+
+use crate::media::{encode_resized, encode_webp, GeneratedVariant};
+use crate::media_command::output_within;
+use image::DynamicImage;
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+
+const WAVEFORM_SIZE: &str = "1280x320";
+const WAVEFORM_COLOR: &str = "#9ec1ff";
+/// Drawing the waveform decodes the whole track.
+const WAVEFORM_TIMEOUT: Duration = Duration::from_secs(3 * 60);
+
+/// Waveform `card` and `poster` images; empty when ffmpeg cannot read the file.
+pub async fn audio_waveform_from_path(
+    id: &str,
+    path: &Path,
+    quality: i64,
+) -> Vec<GeneratedVariant> {
+    let Some(image) = render_waveform(path).await else {
+        return Vec::new();
+    };
+    [
+        encode_resized(id, "card", &image, 640, quality),
+        encode_webp(id, "poster", image, quality),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+async fn render_waveform(path: &Path) -> Option<DynamicImage> {
+    let filter = format!(
+        "aformat=channel_layouts=mono,showwavespic=s={WAVEFORM_SIZE}:colors={WAVEFORM_COLOR}"
+    );
+    let mut command = Command::new("ffmpeg");
+    command.args([
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-i",
+        path.to_str()?,
+        "-filter_complex",
+        &filter,
+        "-frames:v",
+        "1",
+        "-f",
+        "image2pipe",
+        "-vcodec",
+        "png",
+        "pipe:1",
+    ]);
+    let output = output_within(&mut command, WAVEFORM_TIMEOUT).await?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return None;
+    }
+    image::load_from_memory(&output.stdout).ok()
+}
@@ -0,0 +1,14 @@
+//! FFmpeg and FFprobe runs bounded by a time limit
+
+use std::process::Output;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// Run `command` to completion and collect its output, or kill it and give
+/// up once `limit` passes. Dropping the run, as an aborted job does, also
+/// kills the child.
+pub(crate) async fn output_within(command: &mut Command, limit: Duration) -> Option<Output> {
+    command.kill_on_drop(true);
+    timeout(limit, command.output()).await.ok()?.ok()
+}
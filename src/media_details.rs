@@ -0,0 +1,50 @@
+//! Codec, orientation, and capture facts stored as `media_details` JSON
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Codec, orientation, and capture facts kept beside the stored dimensions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_codec: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_codec: Option<String>,
+    /// Overall container bitrate in bits per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bit_rate: Option<i64>,
+    /// Clockwise display rotation in degrees from container metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<i32>,
+    /// EXIF orientation tag (`1`-`8`) of the original image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<u8>,
+    /// EXIF capture time as `YYYY-MM-DDTHH:MM:SS[+hh:mm]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captured_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_make: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_model: Option<String>,
+    /// The original carries EXIF GPS or XMP location data.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub has_location: bool,
+}
+
+impl MediaDetails {
+    pub(crate) fn into_option(self) -> Option<Self> {
+        (self != Self::default()).then_some(self)
+    }
+}
+
+pub fn media_details_from_json(value: Option<Value>) -> Option<MediaDetails> {
+    value
+        .and_then(|value| serde_json::from_value(value).ok())
+        .and_then(MediaDetails::into_option)
+}
+
+pub fn media_details_to_json(details: &Option<MediaDetails>) -> Option<Value> {
+    details
+        .as_ref()
+        .and_then(|details| serde_json::to_value(details).ok())
+}
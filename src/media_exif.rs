@@ -0,0 +1,123 @@
+//! EXIF and XMP capture metadata read from JPEG, PNG, and WebP originals
+
+use crate::media_exif_blocks::{metadata_blocks, BlockKind};
+use crate::media_exif_tiff::{Entry, Tiff};
+
+pub use crate::media_exif_strip::strip_location;
+
+const TAG_MAKE: u16 = 0x010F;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_EXIF_IFD: u16 = 0x8769;
+pub(crate) const TAG_GPS_IFD: u16 = 0x8825;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TAG_OFFSET_TIME_ORIGINAL: u16 = 0x9011;
+const XMP_GPS_MARKERS: [&str; 2] = ["GPSLatitude", "GPSLongitude"];
+
+/// Capture facts of one image; `has_location` covers both EXIF GPS and XMP.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaptureMetadata {
+    /// `YYYY-MM-DDTHH:MM:SS`, with the recorded UTC offset when present.
+    pub captured_at: Option<String>,
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub orientation: Option<u8>,
+    pub has_location: bool,
+}
+
+pub fn read_capture_metadata(bytes: &[u8]) -> CaptureMetadata {
+    let mut metadata = CaptureMetadata::default();
+    let Some((_, blocks)) = metadata_blocks(bytes) else {
+        return metadata;
+    };
+    let mut exif_seen = false;
+    for block in blocks {
+        let payload = &bytes[block.payload];
+        match block.kind {
+            BlockKind::Exif if !exif_seen => {
+                exif_seen = true;
+                read_tiff(payload, &mut metadata);
+            }
+            BlockKind::Exif => metadata.has_location |= tiff_has_location(payload),
+            BlockKind::Xmp => metadata.has_location |= xmp_has_location(payload),
+        }
+    }
+    metadata
+}
+
+pub(crate) fn xmp_has_location(payload: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(payload);
+    XMP_GPS_MARKERS.iter().any(|marker| text.contains(marker))
+}
+
+/// Later EXIF blocks only matter for whether they carry GPS.
+fn tiff_has_location(tiff: &[u8]) -> bool {
+    let Some(reader) = Tiff::new(tiff) else {
+        return false;
+    };
+    let ifd0 = reader.first_ifd().unwrap_or(0);
+    reader
+        .entries(ifd0)
+        .iter()
+        .any(|entry| entry.tag == TAG_GPS_IFD && gps_entries(&reader, entry) > 0)
+}
+
+fn gps_entries(reader: &Tiff<'_>, entry: &Entry) -> u16 {
+    reader
+        .long(entry)
+        .and_then(|at| reader.u16_at(at as usize))
+        .unwrap_or(0)
+}
+
+fn read_tiff(tiff: &[u8], metadata: &mut CaptureMetadata) {
+    let Some(reader) = Tiff::new(tiff) else {
+        return;
+    };
+    let Some(ifd0) = reader.first_ifd() else {
+        return;
+    };
+    let mut date = None;
+    let mut offset = None;
+    for entry in reader.entries(ifd0) {
+        match entry.tag {
+            TAG_MAKE => metadata.camera_make = reader.ascii(&entry),
+            TAG_MODEL => metadata.camera_model = reader.ascii(&entry),
+            TAG_ORIENTATION => {
+                metadata.orientation = reader.short(&entry).filter(|v| (1..=8).contains(v))
+            }
+            TAG_GPS_IFD => metadata.has_location |= gps_entries(&reader, &entry) > 0,
+            TAG_EXIF_IFD => {
+                let Some(exif) = reader.long(&entry) else {
+                    continue;
+                };
+                for entry in reader.entries(exif as usize) {
+                    match entry.tag {
+                        TAG_DATE_TIME_ORIGINAL => date = reader.ascii(&entry),
+                        TAG_OFFSET_TIME_ORIGINAL => offset = reader.ascii(&entry),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    metadata.captured_at = date.and_then(|date| capture_time(&date, offset.as_deref()));
+}
+
+/// `2024:05:01 14:03:22` plus `+02:00` becomes `2024-05-01T14:03:22+02:00`.
+fn capture_time(date: &str, offset: Option<&str>) -> Option<String> {
+    let parsed = chrono::NaiveDateTime::parse_from_str(date.trim(), "%Y:%m:%d %H:%M:%S").ok()?;
+    let mut text = parsed.format("%Y-%m-%dT%H:%M:%S").to_string();
+    if let Some(offset) = offset.map(str::trim).filter(|value| valid_offset(value)) {
+        text.push_str(offset);
+    }
+    Some(text)
+}
+
+fn valid_offset(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 6
+        && matches!(bytes[0], b'+' | b'-')
+        && bytes[3] == b':'
+        && [1, 2, 4, 5].iter().all(|at| bytes[*at].is_ascii_digit())
+}
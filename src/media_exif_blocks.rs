@@ -0,0 +1,162 @@
+//! Locating EXIF and XMP blocks inside JPEG, PNG, and WebP containers
+
+use std::ops::Range;
+
+const EXIF_PREFIX: &[u8] = b"Exif\0\0";
+const XMP_PREFIXES: [&[u8]; 2] = [
+    b"http://ns.adobe.com/xap/1.0/\0",
+    b"http://ns.adobe.com/xmp/extension/\0",
+];
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp\0";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Container {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BlockKind {
+    Exif,
+    Xmp,
+}
+
+/// One metadata segment or chunk: `payload` is the TIFF or XMP text, and
+/// `segment` spans the whole marker or chunk including its framing.
+#[derive(Clone, Debug)]
+pub(crate) struct Block {
+    pub kind: BlockKind,
+    pub payload: Range<usize>,
+    pub segment: Range<usize>,
+}
+
+/// Metadata blocks in file order; `None` for containers without a walker.
+pub(crate) fn metadata_blocks(bytes: &[u8]) -> Option<(Container, Vec<Block>)> {
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        Some((Container::Jpeg, jpeg_blocks(bytes)))
+    } else if bytes.starts_with(PNG_SIGNATURE) {
+        Some((Container::Png, png_blocks(bytes)))
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some((Container::Webp, webp_blocks(bytes)))
+    } else {
+        None
+    }
+}
+
+fn jpeg_blocks(bytes: &[u8]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut pos = 2;
+    while pos + 4 <= bytes.len() && bytes[pos] == 0xFF {
+        let marker = bytes[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        if matches!(marker, 0x01 | 0xD0..=0xD7) {
+            pos += 2;
+            continue;
+        }
+        if matches!(marker, 0xD9 | 0xDA) {
+            break;
+        }
+        let len = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+        let end = pos + 2 + len;
+        if len < 2 || end > bytes.len() {
+            break;
+        }
+        if marker == 0xE1 {
+            let data = pos + 4..end;
+            if let Some(kind) = app1_kind(&bytes[data.clone()]) {
+                let skip = match kind {
+                    BlockKind::Exif => EXIF_PREFIX.len(),
+                    BlockKind::Xmp => 0,
+                };
+                blocks.push(Block {
+                    kind,
+                    payload: data.start + skip..data.end,
+                    segment: pos..end,
+                });
+            }
+        }
+        pos = end;
+    }
+    blocks
+}
+
+fn app1_kind(data: &[u8]) -> Option<BlockKind> {
+    if data.starts_with(EXIF_PREFIX) {
+        Some(BlockKind::Exif)
+    } else if XMP_PREFIXES.iter().any(|prefix| data.starts_with(prefix)) {
+        Some(BlockKind::Xmp)
+    } else {
+        None
+    }
+}
+
+fn png_blocks(bytes: &[u8]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut pos = PNG_SIGNATURE.len();
+    while pos + 12 <= bytes.len() {
+        let len = u32::from_be_bytes(bytes[pos..pos + 4].try_into().unwrap_or_default()) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let Some(end) = (pos + 12)
+            .checked_add(len)
+            .filter(|end| *end <= bytes.len())
+        else {
+            break;
+        };
+        let data = pos + 8..end - 4;
+        let block = match kind {
+            b"eXIf" => Some(BlockKind::Exif),
+            b"iTXt" if bytes[data.clone()].starts_with(PNG_XMP_KEYWORD) => Some(BlockKind::Xmp),
+            _ => None,
+        };
+        if let Some(kind) = block {
+            blocks.push(Block {
+                kind,
+                payload: data,
+                segment: pos..end,
+            });
+        }
+        if kind == b"IEND" {
+            break;
+        }
+        pos = end;
+    }
+    blocks
+}
+
+fn webp_blocks(bytes: &[u8]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let len = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().unwrap_or_default());
+        let data_end = (pos + 8).checked_add(len as usize);
+        let Some(data_end) = data_end.filter(|end| *end <= bytes.len()) else {
+            break;
+        };
+        let end = (data_end + (len as usize & 1)).min(bytes.len());
+        let data = pos + 8..data_end;
+        let block = match &bytes[pos..pos + 4] {
+            b"EXIF" => Some(BlockKind::Exif),
+            b"XMP " => Some(BlockKind::Xmp),
+            _ => None,
+        };
+        if let Some(kind) = block {
+            let skip = if kind == BlockKind::Exif && bytes[data.clone()].starts_with(EXIF_PREFIX) {
+                EXIF_PREFIX.len()
+            } else {
+                0
+            };
+            blocks.push(Block {
+                kind,
+                payload: data.start + skip..data.end,
+                segment: pos..end,
+            });
+        }
+        pos = end;
+    }
+    blocks
+}
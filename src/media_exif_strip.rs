@@ -0,0 +1,88 @@
+//! Removing GPS location from EXIF and XMP without re-encoding pixels
+
+use crate::media_exif::{xmp_has_location, TAG_GPS_IFD};
+use crate::media_exif_blocks::{metadata_blocks, BlockKind, Container};
+use crate::media_exif_tiff::Tiff;
+use std::ops::Range;
+
+const VP8X_XMP_FLAG: u8 = 0x04;
+
+/// Copy of `bytes` with the EXIF GPS directory emptied and GPS-bearing XMP
+/// packets dropped; `None` for containers other than JPEG, PNG, and WebP.
+pub fn strip_location(bytes: &[u8]) -> Option<Vec<u8>> {
+    let (container, blocks) = metadata_blocks(bytes)?;
+    let mut out = bytes.to_vec();
+    let mut removed: Vec<Range<usize>> = Vec::new();
+    let mut xmp_kept = false;
+    for block in blocks {
+        match block.kind {
+            BlockKind::Exif => {
+                if blank_gps(&mut out[block.payload.clone()]) && container == Container::Png {
+                    rewrite_png_crc(&mut out, &block.segment);
+                }
+            }
+            BlockKind::Xmp if xmp_has_location(&bytes[block.payload.clone()]) => {
+                removed.push(block.segment);
+            }
+            BlockKind::Xmp => xmp_kept = true,
+        }
+    }
+    if removed.is_empty() {
+        return Some(out);
+    }
+    let mut kept = Vec::with_capacity(out.len());
+    let mut pos = 0;
+    for range in &removed {
+        kept.extend_from_slice(&out[pos..range.start]);
+        pos = range.end;
+    }
+    kept.extend_from_slice(&out[pos..]);
+    if container == Container::Webp {
+        let riff_size = (kept.len() - 8) as u32;
+        kept[4..8].copy_from_slice(&riff_size.to_le_bytes());
+        if !xmp_kept && kept.len() > 20 && &kept[12..16] == b"VP8X" {
+            kept[20] &= !VP8X_XMP_FLAG;
+        }
+    }
+    Some(kept)
+}
+
+/// Zero the GPS IFD entries and their out-of-line values in place, keeping
+/// every other offset in the TIFF valid.
+fn blank_gps(tiff: &mut [u8]) -> bool {
+    let ranges = {
+        let Some(reader) = Tiff::new(tiff) else {
+            return false;
+        };
+        let gps = reader.first_ifd().and_then(|ifd0| {
+            reader
+                .entries(ifd0)
+                .into_iter()
+                .find(|entry| entry.tag == TAG_GPS_IFD)
+                .and_then(|entry| reader.long(&entry))
+        });
+        let Some(gps) = gps.map(|at| at as usize) else {
+            return false;
+        };
+        let entries = reader.entries(gps);
+        if entries.is_empty() {
+            return false;
+        }
+        let mut ranges: Vec<Range<usize>> = entries
+            .iter()
+            .filter_map(|entry| reader.value_range(entry))
+            .filter(|range| range.len() > 4)
+            .collect();
+        ranges.push(gps..(gps + 6 + entries.len() * 12).min(tiff.len()));
+        ranges
+    };
+    for range in ranges {
+        tiff[range].fill(0);
+    }
+    true
+}
+
+fn rewrite_png_crc(bytes: &mut [u8], segment: &Range<usize>) {
+    let crc = crc32fast::hash(&bytes[segment.start + 4..segment.end - 4]);
+    bytes[segment.end - 4..segment.end].copy_from_slice(&crc.to_be_bytes());
+}
@@ -0,0 +1,144 @@
+use super::media::{image_variants, read_capture_metadata, strip_location, CaptureMetadata};
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+
+const LATITUDE: [u8; 8] = [52, 0, 0, 0, 1, 0, 0, 0];
+const XMP_WITH_GPS: &[u8] =
+    b"<x:xmpmeta><rdf:Description exif:GPSLatitude=\"52,31.2N\"/></x:xmpmeta>";
+
+fn entry(tiff: &mut Vec<u8>, tag: u16, kind: u16, count: u32, value: u32) {
+    tiff.extend_from_slice(&tag.to_le_bytes());
+    tiff.extend_from_slice(&kind.to_le_bytes());
+    tiff.extend_from_slice(&count.to_le_bytes());
+    tiff.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Little-endian TIFF: IFD0 at 8, Exif IFD at 100, GPS IFD at 160.
+fn phone_tiff() -> Vec<u8> {
+    let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+    tiff.extend_from_slice(&5u16.to_le_bytes());
+    entry(&mut tiff, 0x010F, 2, 7, 80);
+    entry(&mut tiff, 0x0110, 2, 8, 88);
+    entry(&mut tiff, 0x0112, 3, 1, 6);
+    entry(&mut tiff, 0x8769, 4, 1, 100);
+    entry(&mut tiff, 0x8825, 4, 1, 160);
+    tiff.resize(80, 0);
+    tiff.extend_from_slice(b"Google\0\0Pixel 8\0\0\0\0\0");
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    entry(&mut tiff, 0x9003, 2, 20, 132);
+    entry(&mut tiff, 0x9011, 2, 7, 152);
+    tiff.resize(132, 0);
+    tiff.extend_from_slice(b"2024:05:01 14:03:22\0+02:00\0\0");
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    entry(&mut tiff, 0x0001, 2, 2, u32::from_le_bytes(*b"N\0\0\0"));
+    entry(&mut tiff, 0x0002, 5, 3, 192);
+    tiff.resize(192, 0);
+    for _ in 0..3 {
+        tiff.extend_from_slice(&LATITUDE);
+    }
+    tiff
+}
+
+fn encoded(format: ImageFormat, width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    DynamicImage::new_rgb8(width, height)
+        .write_to(&mut Cursor::new(&mut bytes), format)
+        .unwrap();
+    bytes
+}
+
+fn phone_jpeg() -> Vec<u8> {
+    let jpeg = encoded(ImageFormat::Jpeg, 40, 20);
+    let mut app1 = b"Exif\0\0".to_vec();
+    app1.extend_from_slice(&phone_tiff());
+    let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    bytes.extend_from_slice(&(app1.len() as u16 + 2).to_be_bytes());
+    bytes.extend_from_slice(&app1);
+    bytes.extend_from_slice(&jpeg[2..]);
+    bytes
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn jpeg_exif_yields_capture_details() {
+    assert_eq!(
+        read_capture_metadata(&phone_jpeg()),
+        CaptureMetadata {
+            captured_at: Some("2024-05-01T14:03:22+02:00".to_string()),
+            camera_make: Some("Google".to_string()),
+            camera_model: Some("Pixel 8".to_string()),
+            orientation: Some(6),
+            has_location: true,
+        }
+    );
+}
+
+#[test]
+fn jpeg_strip_empties_gps_and_keeps_the_rest() {
+    let original = phone_jpeg();
+    let stripped = strip_location(&original).unwrap();
+    assert_eq!(stripped.len(), original.len());
+    assert!(!contains(&stripped, &LATITUDE));
+    let metadata = read_capture_metadata(&stripped);
+    assert!(!metadata.has_location);
+    assert_eq!(metadata.camera_model.as_deref(), Some("Pixel 8"));
+    assert!(image::load_from_memory(&stripped).is_ok());
+}
+
+#[test]
+fn png_strip_rewrites_exif_crc_and_drops_gps_xmp() {
+    let png = encoded(ImageFormat::Png, 4, 4);
+    let mut bytes = png[..33].to_vec();
+    for (kind, data) in [
+        (&b"eXIf"[..], phone_tiff()),
+        (
+            b"iTXt",
+            [&b"XML:com.adobe.xmp\0\0\0\0\0"[..], XMP_WITH_GPS].concat(),
+        ),
+    ] {
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let body = [kind, &data].concat();
+        bytes.extend_from_slice(&body);
+        bytes.extend_from_slice(&crc32fast::hash(&body).to_be_bytes());
+    }
+    bytes.extend_from_slice(&png[33..]);
+    assert!(read_capture_metadata(&bytes).has_location);
+    let stripped = strip_location(&bytes).unwrap();
+    assert!(!contains(&stripped, b"GPSLatitude"));
+    assert!(!read_capture_metadata(&stripped).has_location);
+    assert!(image::load_from_memory(&stripped).is_ok());
+}
+
+#[test]
+fn webp_strip_drops_xmp_chunk_and_fixes_riff_size() {
+    let webp = encoded(ImageFormat::WebP, 4, 4);
+    let mut bytes = webp.clone();
+    bytes.extend_from_slice(b"XMP ");
+    bytes.extend_from_slice(&(XMP_WITH_GPS.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(XMP_WITH_GPS);
+    if XMP_WITH_GPS.len() % 2 == 1 {
+        bytes.push(0);
+    }
+    let riff_size = (bytes.len() - 8) as u32;
+    bytes[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    assert!(read_capture_metadata(&bytes).has_location);
+    assert_eq!(strip_location(&bytes), Some(webp));
+}
+
+#[test]
+fn unsupported_containers_are_not_stripped() {
+    assert_eq!(strip_location(b"GIF89a"), None);
+    assert_eq!(read_capture_metadata(b"GIF89a"), CaptureMetadata::default());
+}
+
+#[test]
+fn image_variants_follow_exif_orientation() {
+    let variants = image_variants("01HZX0000000000000000000AB", &phone_jpeg(), 80);
+    let card = &variants[0].variant;
+    assert_eq!(card.height, card.width * 2);
+}
@@ -0,0 +1,109 @@
+//! Bounds-checked TIFF IFD reading shared by EXIF parsing and stripping
+
+use std::ops::Range;
+
+/// One 12-byte IFD entry; `value_at` is where its value or value offset sits.
+pub(crate) struct Entry {
+    pub tag: u16,
+    pub kind: u16,
+    pub count: u32,
+    pub value_at: usize,
+}
+
+/// Bounds-checked reader over a TIFF header and its IFDs.
+pub(crate) struct Tiff<'a> {
+    pub bytes: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        let little_endian = match bytes.get(..4)? {
+            [b'I', b'I', 42, 0] => true,
+            [b'M', b'M', 0, 42] => false,
+            _ => return None,
+        };
+        Some(Self {
+            bytes,
+            little_endian,
+        })
+    }
+
+    pub fn u16_at(&self, at: usize) -> Option<u16> {
+        let raw: [u8; 2] = self.bytes.get(at..at + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(raw)
+        } else {
+            u16::from_be_bytes(raw)
+        })
+    }
+
+    pub fn u32_at(&self, at: usize) -> Option<u32> {
+        let raw: [u8; 4] = self.bytes.get(at..at + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(raw)
+        } else {
+            u32::from_be_bytes(raw)
+        })
+    }
+
+    pub fn first_ifd(&self) -> Option<usize> {
+        self.u32_at(4).map(|at| at as usize)
+    }
+
+    pub fn entries(&self, ifd: usize) -> Vec<Entry> {
+        let count = self.u16_at(ifd).unwrap_or(0) as usize;
+        (0..count)
+            .map(|index| ifd + 2 + index * 12)
+            .map_while(|at| {
+                Some(Entry {
+                    tag: self.u16_at(at)?,
+                    kind: self.u16_at(at + 2)?,
+                    count: self.u32_at(at + 4)?,
+                    value_at: at + 8,
+                })
+            })
+            .collect()
+    }
+
+    /// Byte range of an entry's value, following the offset when it is
+    /// longer than four bytes.
+    pub fn value_range(&self, entry: &Entry) -> Option<Range<usize>> {
+        let unit = match entry.kind {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => return None,
+        };
+        let len = (entry.count as usize).checked_mul(unit)?;
+        let start = if len <= 4 {
+            entry.value_at
+        } else {
+            self.u32_at(entry.value_at)? as usize
+        };
+        let end = start.checked_add(len)?;
+        (end <= self.bytes.len()).then_some(start..end)
+    }
+
+    pub fn ascii(&self, entry: &Entry) -> Option<String> {
+        let raw = &self.bytes[self.value_range(entry).filter(|_| entry.kind == 2)?];
+        let text = String::from_utf8_lossy(raw.split(|b| *b == 0).next()?);
+        let text = text.trim();
+        (!text.is_empty() && text.len() <= 128).then(|| text.to_string())
+    }
+
+    pub fn short(&self, entry: &Entry) -> Option<u8> {
+        if entry.kind != 3 {
+            return None;
+        }
+        self.u16_at(entry.value_at)
+            .and_then(|v| u8::try_from(v).ok())
+    }
+
+    pub fn long(&self, entry: &Entry) -> Option<u32> {
+        matches!(entry.kind, 4 | 13)
+            .then(|| self.u32_at(entry.value_at))
+            .flatten()
+    }
+}
@@ -0,0 +1,100 @@
+//! HLS packaging of MP4 renditions
+
+use crate::media_probe::probe_audio_video;
+use crate::media_variants::{HlsFile, MediaRendition};
+use crate::media_video::{ffmpeg, StagedObject, VideoTranscode};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::time::Instant;
+
+const HLS_SEGMENT_SECONDS: &str = "6";
+pub(crate) const PLAYLIST_CONTENT_TYPE: &str = "application/vnd.apple.mpegurl";
+
+/// One HLS variant stream listed in the master playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HlsStream {
+    pub playlist: String,
+    pub bandwidth: i64,
+    pub width: i32,
+    pub height: i32,
+}
+
+pub fn hls_master_playlist(streams: &[HlsStream]) -> String {
+    let mut playlist = String::from("#EXTM3U\n");
+    for stream in streams {
+        playlist.push_str(&format!(
+            "#EXT-X-STREAM-INF:BANDWIDTH={},RESOLUTION={}x{}\n{}\n",
+            stream.bandwidth.max(1),
+            stream.width,
+            stream.height,
+            stream.playlist,
+        ));
+    }
+    playlist
+}
+
+/// Remux one MP4 rendition into a single-file MPEG-TS HLS stream.
+pub(crate) async fn package_hls(
+    id: &str,
+    mp4: &Path,
+    rendition: &MediaRendition,
+    dir: &Path,
+    deadline: Instant,
+    output: &mut VideoTranscode,
+) -> Option<HlsStream> {
+    let (playlist, segment_name) = (
+        format!("{}p.m3u8", rendition.height),
+        format!("{}p.ts", rendition.height),
+    );
+    let (segments, playlist_path) = (dir.join(&segment_name), dir.join(&playlist));
+    #[rustfmt::skip]
+    let args = [
+        "-i", mp4.to_str()?, "-c", "copy", "-f", "hls", "-hls_time", HLS_SEGMENT_SECONDS,
+        "-hls_playlist_type", "vod", "-hls_flags", "single_file",
+        "-hls_segment_filename", segments.to_str()?, playlist_path.to_str()?,
+    ];
+    if !ffmpeg(&args, deadline).await {
+        return None;
+    }
+    let seconds = probe_audio_video(mp4).await.duration_ms? as f64 / 1000.0;
+    let segment_bytes = fs::metadata(&segments).await.ok()?.len() as f64;
+    push_hls_file(
+        id,
+        &playlist,
+        PLAYLIST_CONTENT_TYPE,
+        dir.join(&playlist),
+        output,
+    )
+    .await;
+    push_hls_file(id, &segment_name, "video/mp2t", segments, output).await;
+    Some(HlsStream {
+        playlist,
+        bandwidth: (segment_bytes * 8.0 / seconds.max(0.001)).round() as i64,
+        width: rendition.width,
+        height: rendition.height,
+    })
+}
+
+pub(crate) async fn push_hls_file(
+    id: &str,
+    name: &str,
+    content_type: &str,
+    path: PathBuf,
+    output: &mut VideoTranscode,
+) {
+    let key = format!("media/{id}/hls/{name}");
+    let byte_size = fs::metadata(&path)
+        .await
+        .map_or(0, |meta| meta.len() as i64);
+    output.hls.push(HlsFile {
+        name: name.to_string(),
+        key: key.clone(),
+        content_type: content_type.to_string(),
+        byte_size,
+    });
+    output.staged.push(StagedObject {
+        key,
+        content_type: content_type.to_string(),
+        path,
+    });
+}
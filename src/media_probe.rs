@@ -0,0 +1,175 @@
+//! Dimension, duration, and codec probing for uploaded media
+
+use crate::media_command::output_within;
+use crate::media_details::MediaDetails;
+use crate::media_exif::{read_capture_metadata, CaptureMetadata};
+use image::{ImageDecoder, ImageReader};
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+use tokio::fs;
+use tokio::process::Command;
+
+/// Probe result; `width` and `height` are display dimensions after rotation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaProbe {
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub details: Option<MediaDetails>,
+}
+
+/// Read image dimensions and EXIF capture details from the file, falling
+/// back to SVG rasterization and then to FFprobe.
+pub async fn probe_image(path: &Path) -> MediaProbe {
+    let bytes = fs::read(path).await.ok();
+    let capture = bytes
+        .as_deref()
+        .map(read_capture_metadata)
+        .unwrap_or_default();
+    if let Some(probe) = image_header(path, capture) {
+        return probe;
+    }
+    if let Some(bytes) = bytes {
+        if let Some(image) = crate::media_svg::decode_svg(&bytes, path) {
+            return MediaProbe {
+                width: Some(image.width() as i32),
+                height: Some(image.height() as i32),
+                ..MediaProbe::default()
+            };
+        }
+    }
+    probe_audio_video(path).await
+}
+
+/// Longest wait for FFprobe to read a container.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Run FFprobe on a video or audio file.
+pub async fn probe_audio_video(path: &Path) -> MediaProbe {
+    let Some(path) = path.to_str() else {
+        return MediaProbe::default();
+    };
+    let mut command = Command::new("ffprobe");
+    command
+        .args(["-v", "error", "-print_format", "json"])
+        .args(["-show_format", "-show_streams", path]);
+    match output_within(&mut command, PROBE_TIMEOUT).await {
+        Some(output) if output.status.success() => {
+            parse_ffprobe(&String::from_utf8_lossy(&output.stdout)).unwrap_or_default()
+        }
+        _ => MediaProbe::default(),
+    }
+}
+
+fn image_header(path: &Path, capture: CaptureMetadata) -> Option<MediaProbe> {
+    let mut decoder = ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    let (width, height) = decoder.dimensions();
+    let orientation = decoder
+        .orientation()
+        .ok()
+        .map(|value| value.to_exif())
+        .filter(|value| *value != 1)
+        .or(capture.orientation);
+    let quarter_turn = matches!(orientation, Some(5..=8));
+    Some(display_probe(
+        (width as i32, height as i32),
+        quarter_turn,
+        None,
+        MediaDetails {
+            orientation: orientation.filter(|value| *value != 1),
+            captured_at: capture.captured_at,
+            camera_make: capture.camera_make,
+            camera_model: capture.camera_model,
+            has_location: capture.has_location,
+            ..MediaDetails::default()
+        },
+    ))
+}
+
+/// Turn FFprobe `-print_format json` output into display dimensions and details.
+pub fn parse_ffprobe(output: &str) -> Option<MediaProbe> {
+    let json: Value = serde_json::from_str(output).ok()?;
+    let streams = json["streams"].as_array().cloned().unwrap_or_default();
+    let stream = |kind: &str| {
+        streams
+            .iter()
+            .find(|stream| stream["codec_type"] == kind)
+            .cloned()
+    };
+    let video = stream("video").filter(|video| video["disposition"]["attached_pic"] != 1);
+    let audio = stream("audio");
+    let rotation = video.as_ref().and_then(rotation_degrees);
+    let seconds = number(&json["format"]["duration"])
+        .or_else(|| video.as_ref().and_then(|video| number(&video["duration"])))
+        .or_else(|| audio.as_ref().and_then(|audio| number(&audio["duration"])));
+    let size = video.as_ref().and_then(|video| {
+        Some((
+            video["width"].as_i64()? as i32,
+            video["height"].as_i64()? as i32,
+        ))
+    });
+    let details = MediaDetails {
+        video_codec: video.as_ref().and_then(codec_name),
+        audio_codec: audio.as_ref().and_then(codec_name),
+        bit_rate: number(&json["format"]["bit_rate"]).map(|rate| rate as i64),
+        rotation: rotation.filter(|degrees| *degrees != 0),
+        ..MediaDetails::default()
+    };
+    let duration_ms = seconds
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(|seconds| (seconds * 1000.0).round() as i64);
+    let quarter_turn = matches!(rotation, Some(90 | 270));
+    Some(match size {
+        Some(size) => display_probe(size, quarter_turn, duration_ms, details),
+        None => MediaProbe {
+            duration_ms,
+            details: details.into_option(),
+            ..MediaProbe::default()
+        },
+    })
+}
+
+fn display_probe(
+    (width, height): (i32, i32),
+    quarter_turn: bool,
+    duration_ms: Option<i64>,
+    details: MediaDetails,
+) -> MediaProbe {
+    let (width, height) = if quarter_turn {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    MediaProbe {
+        width: Some(width).filter(|value| *value > 0),
+        height: Some(height).filter(|value| *value > 0),
+        duration_ms,
+        details: details.into_option(),
+    }
+}
+
+/// Clockwise rotation from a display matrix (counter-clockwise) or a legacy `rotate` tag.
+fn rotation_degrees(video: &Value) -> Option<i32> {
+    let matrix = video["side_data_list"]
+        .as_array()
+        .and_then(|list| list.iter().find_map(|item| item["rotation"].as_f64()))
+        .map(|degrees| -degrees);
+    let degrees = matrix.or_else(|| number(&video["tags"]["rotate"]))?;
+    Some((degrees.round() as i32).rem_euclid(360))
+}
+
+fn codec_name(stream: &Value) -> Option<String> {
+    stream["codec_name"].as_str().map(str::to_string)
+}
+
+fn number(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|text| text.trim().parse().ok()))
+}
@@ -0,0 +1,71 @@
+use super::media_details::*;
+use super::media_probe::*;
+use image::{DynamicImage, ImageFormat};
+
+const ROTATED_PHONE_VIDEO: &str = r#"{
+  "streams": [
+    {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080,
+     "side_data_list": [{"side_data_type": "Display Matrix", "rotation": -90}]},
+    {"codec_type": "audio", "codec_name": "aac", "duration": "12.5"}
+  ],
+  "format": {"duration": "12.480000", "bit_rate": "8012345"}
+}"#;
+
+#[test]
+fn ffprobe_video_reports_display_dimensions_and_details() {
+    let probe = parse_ffprobe(ROTATED_PHONE_VIDEO).unwrap();
+    assert_eq!((probe.width, probe.height), (Some(1080), Some(1920)));
+    assert_eq!(probe.duration_ms, Some(12_480));
+    assert_eq!(
+        probe.details,
+        Some(MediaDetails {
+            video_codec: Some("h264".to_string()),
+            audio_codec: Some("aac".to_string()),
+            bit_rate: Some(8_012_345),
+            rotation: Some(90),
+            ..MediaDetails::default()
+        })
+    );
+}
+
+#[test]
+fn ffprobe_audio_skips_cover_art_and_legacy_rotate_tags_turn_clockwise() {
+    let audio = r#"{"streams": [
+        {"codec_type": "audio", "codec_name": "mp3"},
+        {"codec_type": "video", "codec_name": "mjpeg", "width": 600, "height": 600,
+         "disposition": {"attached_pic": 1}}
+      ], "format": {"duration": "61.0"}}"#;
+    let probe = parse_ffprobe(audio).unwrap();
+    assert_eq!((probe.width, probe.height), (None, None));
+    assert_eq!(probe.duration_ms, Some(61_000));
+    assert_eq!(probe.details.unwrap().video_codec, None);
+
+    let legacy = r#"{"streams": [{"codec_type": "video", "width": 640, "height": 480,
+        "tags": {"rotate": "270"}}], "format": {}}"#;
+    let probe = parse_ffprobe(legacy).unwrap();
+    assert_eq!((probe.width, probe.height), (Some(480), Some(640)));
+    assert_eq!(probe.details.unwrap().rotation, Some(270));
+    assert_eq!(parse_ffprobe("not json"), None);
+}
+
+#[tokio::test]
+async fn image_probe_reads_header_dimensions() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wide.png");
+    DynamicImage::new_rgb8(40, 10)
+        .save_with_format(&path, ImageFormat::Png)
+        .unwrap();
+    let probe = probe_image(&path).await;
+    assert_eq!((probe.width, probe.height), (Some(40), Some(10)));
+    assert_eq!(probe.details, None);
+}
+
+#[test]
+fn media_details_json_drops_empty_values() {
+    assert_eq!(media_details_to_json(&None), None);
+    let empty = serde_json::json!({});
+    assert_eq!(media_details_from_json(Some(empty)), None);
+    let stored = serde_json::json!({"video_codec": "vp9", "bit_rate": 1000});
+    let details = media_details_from_json(Some(stored.clone())).unwrap();
+    assert_eq!(media_details_to_json(&Some(details)), Some(stored));
+}
@@ -4,6 +4,13 @@ use image::{DynamicImage, RgbaImage};
 use resvg::{render, tiny_skia, usvg};
 use std::path::Path;
 
+/// Declared pixel size of an SVG document, without rasterizing it.
+pub fn svg_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default()).ok()?;
+    let size = tree.size().to_int_size();
+    Some((size.width(), size.height()))
+}
+
 pub fn decode_svg(bytes: &[u8], path: &Path) -> Option<DynamicImage> {
     let mut options = usvg::Options {
         resources_dir: path.parent().map(|dir| dir.to_path_buf()),
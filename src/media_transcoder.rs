@@ -0,0 +1,97 @@
+//! Step-by-step video transcoding into renditions and an HLS package
+
+use crate::media_hls::{
+    hls_master_playlist, package_hls, push_hls_file, HlsStream, PLAYLIST_CONTENT_TYPE,
+};
+use crate::media_probe::probe_audio_video;
+use crate::media_video::{
+    encode, rendition_heights, transcode_allowed, Container, StagedObject, VideoTranscode, MP4,
+    TRANSCODE_BUDGET, WEBM,
+};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::time::Instant;
+
+/// Transcodes a source into MP4 (H.264/AAC) at each ladder height, packaged
+/// as HLS, then into WebM (VP9/Opus), one rendition per step so callers can
+/// store each as soon as it exists. Steps FFmpeg cannot complete within the
+/// shared budget are skipped, and sources over the caps produce nothing.
+pub struct VideoTranscoder {
+    id: String,
+    source: PathBuf,
+    dir: PathBuf,
+    deadline: Instant,
+    steps: Vec<(i32, &'static Container)>,
+    streams: Vec<HlsStream>,
+}
+
+impl VideoTranscoder {
+    pub async fn new(id: &str, source: &Path, dir: &Path) -> Self {
+        let deadline = Instant::now() + TRANSCODE_BUDGET;
+        let probe = probe_audio_video(source).await;
+        let heights = if transcode_allowed(&probe) {
+            rendition_heights(probe.height)
+        } else {
+            Vec::new()
+        };
+        let mut steps: Vec<_> = [&MP4, &WEBM]
+            .into_iter()
+            .flat_map(|container| heights.iter().map(move |height| (*height, container)))
+            .collect();
+        steps.reverse();
+        Self {
+            id: id.to_string(),
+            source: source.to_path_buf(),
+            dir: dir.to_path_buf(),
+            deadline,
+            steps,
+            streams: Vec::new(),
+        }
+    }
+
+    /// Produce the next rendition, or `None` once the ladder is done.
+    pub async fn next_rendition(&mut self) -> Option<VideoTranscode> {
+        while let Some((height, container)) = self.steps.pop() {
+            let (id, dir) = (self.id.as_str(), self.dir.as_path());
+            let file = dir.join(format!("{height}p.{}", container.name));
+            let Some(rendition) =
+                encode(id, &self.source, &file, height, container, self.deadline).await
+            else {
+                continue;
+            };
+            let mut step = VideoTranscode::default();
+            if container.name == MP4.name {
+                let stream =
+                    package_hls(id, &file, &rendition, dir, self.deadline, &mut step).await;
+                if let Some(stream) = stream {
+                    self.streams.push(stream);
+                    write_master_playlist(id, dir, &self.streams, &mut step).await;
+                }
+            }
+            step.staged.push(StagedObject {
+                key: rendition.key.clone(),
+                content_type: container.content_type.to_string(),
+                path: file,
+            });
+            step.renditions.push(rendition);
+            return Some(step);
+        }
+        None
+    }
+}
+
+/// Rewrite the master playlist to list every stream packaged so far.
+async fn write_master_playlist(
+    id: &str,
+    dir: &Path,
+    streams: &[HlsStream],
+    output: &mut VideoTranscode,
+) {
+    let master = dir.join("master.m3u8");
+    if fs::write(&master, hls_master_playlist(streams))
+        .await
+        .is_ok()
+    {
+        push_hls_file(id, "master.m3u8", PLAYLIST_CONTENT_TYPE, master, output).await;
+    }
+}
@@ -0,0 +1,191 @@
+//! On-demand image transforms behind `/{ref}/file?w=&h=&fit=&format=&q=`
+
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::{imageops::FilterType, DynamicImage};
+
+/// Edge lengths a transform may ask for, keeping the cache bounded.
+pub const TRANSFORM_SIZES: [u32; 11] = [160, 320, 480, 640, 800, 960, 1200, 1400, 1600, 1920, 2400];
+/// Qualities a transform may ask for with `q`; the space default is used otherwise.
+pub const TRANSFORM_QUALITIES: [u8; 3] = [50, 75, 90];
+/// AVIF encoder speed from `1` (slowest) to `10`; cache misses wait on the encode.
+const AVIF_SPEED: u8 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformFit {
+    /// Fit inside the box, keeping the aspect ratio.
+    Contain,
+    /// Fill the box exactly, cropping the overflow around the center.
+    Cover,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformFormat {
+    Webp,
+    Avif,
+    Jpeg,
+}
+
+/// One validated transform; never upscales past the original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageTransform {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fit: TransformFit,
+    pub format: TransformFormat,
+    pub quality: u8,
+}
+
+/// Raw transform query values as the file route receives them.
+#[derive(Debug, Default)]
+pub struct TransformParams<'a> {
+    pub width: Option<&'a str>,
+    pub height: Option<&'a str>,
+    pub fit: Option<&'a str>,
+    pub format: Option<&'a str>,
+    pub quality: Option<&'a str>,
+}
+
+impl TransformFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Webp => "image/webp",
+            Self::Avif => "image/avif",
+            Self::Jpeg => "image/jpeg",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Jpeg => "jpg",
+        }
+    }
+}
+
+impl TransformParams<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.width.is_none()
+            && self.height.is_none()
+            && self.fit.is_none()
+            && self.format.is_none()
+            && self.quality.is_none()
+    }
+}
+
+impl ImageTransform {
+    /// Validate query values; `default_quality` applies when `q` is absent.
+    pub fn parse(params: &TransformParams<'_>, default_quality: i64) -> Result<Self, String> {
+        let width = params.width.map(ladder_size).transpose()?;
+        let height = params.height.map(ladder_size).transpose()?;
+        let fit = match params.fit.unwrap_or("contain") {
+            "contain" => TransformFit::Contain,
+            "cover" => TransformFit::Cover,
+            _ => return Err("fit must be contain or cover".to_string()),
+        };
+        let format = match params.format.unwrap_or("webp") {
+            "webp" => TransformFormat::Webp,
+            "avif" => TransformFormat::Avif,
+            "jpeg" | "jpg" => TransformFormat::Jpeg,
+            _ => return Err("format must be webp, avif, or jpeg".to_string()),
+        };
+        let quality = match params.quality {
+            Some(value) => value
+                .parse::<u8>()
+                .ok()
+                .filter(|q| TRANSFORM_QUALITIES.contains(q))
+                .ok_or_else(|| "q must be 50, 75, or 90".to_string())?,
+            None => default_quality.clamp(1, 100) as u8,
+        };
+        if fit == TransformFit::Cover && (width.is_none() || height.is_none()) {
+            return Err("fit=cover needs both w and h".to_string());
+        }
+        Ok(Self {
+            width,
+            height,
+            fit,
+            format,
+            quality,
+        })
+    }
+
+    /// Deterministic storage key, kept beside the original it derives from.
+    pub fn cache_key(&self, file_key: &str) -> String {
+        let fit = match self.fit {
+            TransformFit::Contain => "contain",
+            TransformFit::Cover => "cover",
+        };
+        format!(
+            "{}w{}-h{}-{fit}-q{}.{}",
+            transform_prefix(file_key),
+            self.width.unwrap_or(0),
+            self.height.unwrap_or(0),
+            self.quality,
+            self.format.extension(),
+        )
+    }
+
+    /// Output dimensions for a source of `(width, height)`.
+    pub fn output_size(&self, (src_w, src_h): (u32, u32)) -> (u32, u32) {
+        let box_w = self.width.unwrap_or(u32::MAX) as f64;
+        let box_h = self.height.unwrap_or(u32::MAX) as f64;
+        let (src_w, src_h) = (src_w.max(1) as f64, src_h.max(1) as f64);
+        let (w, h) = match self.fit {
+            TransformFit::Contain => {
+                let scale = (box_w / src_w).min(box_h / src_h).min(1.0);
+                (src_w * scale, src_h * scale)
+            }
+            TransformFit::Cover => {
+                let scale = (src_w / box_w).min(src_h / box_h).min(1.0);
+                (box_w * scale, box_h * scale)
+            }
+        };
+        ((w.round() as u32).max(1), (h.round() as u32).max(1))
+    }
+}
+
+/// Storage prefix holding every cached transform of one original.
+pub fn transform_prefix(file_key: &str) -> String {
+    format!("{file_key}.transforms/")
+}
+
+/// Decode, resize, and encode one image; `None` when the bytes are not an image.
+pub fn transform_image(bytes: &[u8], transform: &ImageTransform) -> Option<Vec<u8>> {
+    let image = crate::media::decode_image_bytes(bytes)?;
+    let (width, height) = transform.output_size((image.width(), image.height()));
+    let image = match transform.fit {
+        TransformFit::Contain if (width, height) == (image.width(), image.height()) => image,
+        TransformFit::Contain => image.resize_exact(width, height, FilterType::Lanczos3),
+        TransformFit::Cover => image.resize_to_fill(width, height, FilterType::Lanczos3),
+    };
+    encode(image, transform.format, transform.quality)
+}
+
+fn encode(image: DynamicImage, format: TransformFormat, quality: u8) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        TransformFormat::Webp => {
+            let rgba = image.to_rgba8();
+            let encoder = webp::Encoder::from_rgba(rgba.as_raw(), rgba.width(), rgba.height());
+            bytes = encoder.encode(quality as f32).to_vec();
+        }
+        TransformFormat::Avif => {
+            let encoder = AvifEncoder::new_with_speed_quality(&mut bytes, AVIF_SPEED, quality);
+            image.to_rgba8().write_with_encoder(encoder).ok()?;
+        }
+        TransformFormat::Jpeg => {
+            let encoder = JpegEncoder::new_with_quality(&mut bytes, quality);
+            image.to_rgb8().write_with_encoder(encoder).ok()?;
+        }
+    }
+    Some(bytes)
+}
+
+fn ladder_size(value: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|size| TRANSFORM_SIZES.contains(size))
+        .ok_or_else(|| format!("w and h must be one of {TRANSFORM_SIZES:?}"))
+}
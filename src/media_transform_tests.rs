@@ -0,0 +1,152 @@
+use super::media::transform_source_fits;
+use super::media_transform::*;
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+
+fn params<'a>(width: Option<&'a str>, height: Option<&'a str>) -> TransformParams<'a> {
+    TransformParams {
+        width,
+        height,
+        ..TransformParams::default()
+    }
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    DynamicImage::new_rgb8(width, height)
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .unwrap();
+    bytes
+}
+
+#[test]
+fn parse_applies_defaults_and_ladder() {
+    let transform = ImageTransform::parse(&params(Some("640"), None), 82).unwrap();
+    assert_eq!(transform.width, Some(640));
+    assert_eq!(transform.fit, TransformFit::Contain);
+    assert_eq!(transform.format, TransformFormat::Webp);
+    assert_eq!(transform.quality, 82);
+    assert!(ImageTransform::parse(&params(Some("641"), None), 82).is_err());
+    assert!(ImageTransform::parse(&params(Some("-1"), None), 82).is_err());
+}
+
+#[test]
+fn parse_rejects_unknown_fit_format_and_quality() {
+    let cases = [
+        TransformParams {
+            fit: Some("stretch"),
+            ..params(Some("640"), None)
+        },
+        TransformParams {
+            format: Some("gif"),
+            ..params(Some("640"), None)
+        },
+        TransformParams {
+            quality: Some("0"),
+            ..params(Some("640"), None)
+        },
+        TransformParams {
+            fit: Some("cover"),
+            ..params(Some("640"), None)
+        },
+    ];
+    for case in cases {
+        assert!(ImageTransform::parse(&case, 82).is_err(), "{case:?}");
+    }
+}
+
+#[test]
+fn quality_must_come_from_the_ladder() {
+    for q in ["50", "75", "90"] {
+        let case = TransformParams {
+            quality: Some(q),
+            ..params(Some("640"), None)
+        };
+        assert_eq!(
+            ImageTransform::parse(&case, 82)
+                .unwrap()
+                .quality
+                .to_string(),
+            q
+        );
+    }
+    for q in ["1", "60", "74", "100", "high"] {
+        let case = TransformParams {
+            quality: Some(q),
+            ..params(Some("640"), None)
+        };
+        assert!(ImageTransform::parse(&case, 82).is_err(), "{q}");
+    }
+}
+
+#[test]
+fn cache_key_is_deterministic_beside_the_original() {
+    let transform = ImageTransform::parse(
+        &TransformParams {
+            fit: Some("cover"),
+            format: Some("avif"),
+            quality: Some("75"),
+            ..params(Some("320"), Some("160"))
+        },
+        82,
+    )
+    .unwrap();
+    let key = transform.cache_key("media/abc/x-photo.jpg");
+    assert_eq!(
+        key,
+        "media/abc/x-photo.jpg.transforms/w320-h160-cover-q75.avif"
+    );
+    assert!(key.starts_with(&transform_prefix("media/abc/x-photo.jpg")));
+}
+
+#[test]
+fn output_size_never_upscales() {
+    let contain = ImageTransform::parse(&params(Some("640"), Some("640")), 82).unwrap();
+    assert_eq!(contain.output_size((1600, 1200)), (640, 480));
+    assert_eq!(contain.output_size((300, 200)), (300, 200));
+    let cover = ImageTransform::parse(
+        &TransformParams {
+            fit: Some("cover"),
+            ..params(Some("640"), Some("320"))
+        },
+        82,
+    )
+    .unwrap();
+    assert_eq!(cover.output_size((1600, 1200)), (640, 320));
+    assert_eq!(cover.output_size((320, 1000)), (320, 160));
+}
+
+#[test]
+fn transform_image_encodes_each_format() {
+    let source = png(400, 200);
+    for (format, expected) in [
+        ("webp", ImageFormat::WebP),
+        ("jpeg", ImageFormat::Jpeg),
+        ("avif", ImageFormat::Avif),
+    ] {
+        let transform = ImageTransform::parse(
+            &TransformParams {
+                format: Some(format),
+                ..params(Some("160"), None)
+            },
+            82,
+        )
+        .unwrap();
+        let bytes = transform_image(&source, &transform).unwrap();
+        assert_eq!(image::guess_format(&bytes).unwrap(), expected, "{format}");
+    }
+    assert!(transform_image(
+        b"not an image",
+        &ImageTransform::parse(&params(None, None), 82).unwrap()
+    )
+    .is_none());
+}
+
+#[test]
+fn source_pixel_limit_reads_headers_before_decoding() {
+    assert!(transform_source_fits(&png(640, 480)));
+    let huge_svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="20000" height="20000"/>"#;
+    assert!(!transform_source_fits(huge_svg));
+    let small_svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100"/>"#;
+    assert!(transform_source_fits(small_svg));
+}
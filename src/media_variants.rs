@@ -0,0 +1,155 @@
+//! Stored derivative metadata: still images, video renditions, and HLS packages
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Tallest rendition height picked for plain `mp4` and `webm` requests.
+const PREFERRED_HEIGHT: i32 = 720;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MediaVariants {
+    pub card: Option<MediaVariant>,
+    pub display: Option<MediaVariant>,
+    pub poster: Option<MediaVariant>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub renditions: Vec<MediaRendition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hls: Vec<HlsFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaVariant {
+    pub key: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Web-safe transcode of a video original at one height.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaRendition {
+    /// Variant name such as `mp4_720` or `webm_360`.
+    pub name: String,
+    pub key: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// One object of an HLS package; `name` is its path below `file/hls/`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HlsFile {
+    pub name: String,
+    pub key: String,
+    pub content_type: String,
+    pub byte_size: i64,
+}
+
+pub struct GeneratedVariant {
+    pub name: &'static str,
+    pub variant: MediaVariant,
+    pub bytes: Vec<u8>,
+}
+
+impl MediaVariants {
+    pub fn get(&self, name: &str) -> Option<&MediaVariant> {
+        match name {
+            "card" => self.card.as_ref(),
+            "display" => self.display.as_ref(),
+            "poster" => self.poster.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Rendition by exact name; `mp4` and `webm` pick the tallest one at or
+    /// below 720p, else the shortest.
+    pub fn rendition(&self, name: &str) -> Option<&MediaRendition> {
+        if !matches!(name, "mp4" | "webm") {
+            return self.renditions.iter().find(|item| item.name == name);
+        }
+        let prefix = format!("{name}_");
+        let matching: Vec<&MediaRendition> = self
+            .renditions
+            .iter()
+            .filter(|item| item.name.starts_with(&prefix))
+            .collect();
+        let preferred = matching
+            .iter()
+            .filter(|item| item.height <= PREFERRED_HEIGHT)
+            .max_by_key(|item| item.height);
+        preferred
+            .or_else(|| matching.iter().min_by_key(|item| item.height))
+            .copied()
+    }
+
+    /// Storage key and content type behind a `?variant=` name or `hls/<file>` path.
+    pub fn object(&self, name: &str) -> Option<(&str, &str)> {
+        if let Some(file) = name.strip_prefix("hls/") {
+            return self
+                .hls
+                .iter()
+                .find(|item| item.name == file)
+                .map(|item| (item.key.as_str(), item.content_type.as_str()));
+        }
+        if let Some(item) = self.get(name) {
+            return Some((&item.key, &item.content_type));
+        }
+        self.rendition(name)
+            .map(|item| (item.key.as_str(), item.content_type.as_str()))
+    }
+
+    /// Take every derivative `newer` carries, replacing same-named ones, and
+    /// keep the rest; derivative jobs record their output as it is stored.
+    pub fn merge(&mut self, newer: MediaVariants) {
+        self.card = newer.card.or(self.card.take());
+        self.display = newer.display.or(self.display.take());
+        self.poster = newer.poster.or(self.poster.take());
+        for rendition in newer.renditions {
+            self.renditions.retain(|item| item.name != rendition.name);
+            self.renditions.push(rendition);
+        }
+        for file in newer.hls {
+            self.hls.retain(|item| item.name != file.name);
+            self.hls.push(file);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.card.is_none()
+            && self.display.is_none()
+            && self.poster.is_none()
+            && self.renditions.is_empty()
+            && self.hls.is_empty()
+    }
+}
+
+/// Whether `name` is a derivative name the file route understands, present or not.
+pub fn is_variant_name(name: &str) -> bool {
+    let digits = |value: &str| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+    let file_name = |value: &str| {
+        !value.is_empty()
+            && !value.starts_with('.')
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+    };
+    matches!(name, "card" | "display" | "poster" | "mp4" | "webm")
+        || name.strip_prefix("mp4_").is_some_and(digits)
+        || name.strip_prefix("webm_").is_some_and(digits)
+        || name.strip_prefix("hls/").is_some_and(file_name)
+}
+
+pub fn media_variants_from_json(value: Option<Value>) -> Option<MediaVariants> {
+    value
+        .and_then(|value| serde_json::from_value(value).ok())
+        .filter(|variants: &MediaVariants| !variants.is_empty())
+}
+
+pub fn media_variants_to_json(variants: &Option<MediaVariants>) -> Option<Value> {
+    variants
+        .as_ref()
+        .filter(|variants| !variants.is_empty())
+        .and_then(|variants| serde_json::to_value(variants).ok())
+}
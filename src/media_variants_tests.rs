@@ -0,0 +1,104 @@
+use super::media_variants::*;
+
+fn rendition(name: &str, height: i32) -> MediaRendition {
+    let (format, _) = name.split_once('_').unwrap();
+    MediaRendition {
+        name: name.to_string(),
+        key: format!("media/clip/renditions/{height}p.{format}"),
+        content_type: format!("video/{format}"),
+        byte_size: 1,
+        width: height * 16 / 9,
+        height,
+    }
+}
+
+fn transcoded() -> MediaVariants {
+    MediaVariants {
+        renditions: vec![
+            rendition("mp4_360", 360),
+            rendition("mp4_720", 720),
+            rendition("mp4_1080", 1080),
+            rendition("webm_1080", 1080),
+        ],
+        hls: vec![HlsFile {
+            name: "master.m3u8".to_string(),
+            key: "media/clip/hls/master.m3u8".to_string(),
+            content_type: "application/vnd.apple.mpegurl".to_string(),
+            byte_size: 1,
+        }],
+        ..MediaVariants::default()
+    }
+}
+
+#[test]
+fn bare_format_prefers_tallest_rendition_up_to_720p() {
+    let variants = transcoded();
+    assert_eq!(variants.rendition("mp4").unwrap().name, "mp4_720");
+    assert_eq!(variants.rendition("webm").unwrap().name, "webm_1080");
+    assert_eq!(variants.rendition("mp4_1080").unwrap().height, 1080);
+    assert!(variants.rendition("webm_360").is_none());
+}
+
+#[test]
+fn object_resolves_renditions_and_hls_files() {
+    let variants = transcoded();
+    assert_eq!(
+        variants.object("mp4"),
+        Some(("media/clip/renditions/720p.mp4", "video/mp4"))
+    );
+    assert_eq!(
+        variants.object("hls/master.m3u8"),
+        Some((
+            "media/clip/hls/master.m3u8",
+            "application/vnd.apple.mpegurl"
+        ))
+    );
+    assert!(variants.object("hls/720p.ts").is_none());
+    assert!(variants.object("poster").is_none());
+}
+
+#[test]
+fn variant_names_reject_unknown_and_unsafe_values() {
+    for name in ["card", "poster", "mp4", "webm_720", "hls/720p.ts"] {
+        assert!(is_variant_name(name), "{name}");
+    }
+    for name in ["original", "mp4_", "mp4_x", "hls/", "hls/../x", "hls/a/b"] {
+        assert!(!is_variant_name(name), "{name}");
+    }
+}
+
+#[test]
+fn transcode_fields_round_trip_through_json() {
+    let variants = Some(transcoded());
+    let json = media_variants_to_json(&variants).unwrap();
+    assert_eq!(json["renditions"][0]["name"], "mp4_360");
+    let parsed = media_variants_from_json(Some(json.clone()));
+    assert_eq!(media_variants_to_json(&parsed), Some(json));
+    let images_only = serde_json::json!({"card": {
+        "key": "k", "content_type": "image/webp", "byte_size": 1, "width": 1, "height": 1
+    }});
+    let json = media_variants_to_json(&media_variants_from_json(Some(images_only))).unwrap();
+    assert!(json.get("renditions").is_none() && json.get("hls").is_none());
+}
+
+#[test]
+fn merge_replaces_same_named_derivatives_and_keeps_the_rest() {
+    let mut variants = transcoded();
+    let mut newer = MediaVariants {
+        renditions: vec![rendition("webm_360", 360), rendition("mp4_720", 720)],
+        ..MediaVariants::default()
+    };
+    newer.renditions[1].byte_size = 2;
+    variants.merge(newer);
+    let names: Vec<&str> = variants
+        .renditions
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        ["mp4_360", "mp4_1080", "webm_1080", "webm_360", "mp4_720"]
+    );
+    assert_eq!(variants.rendition("mp4_720").unwrap().byte_size, 2);
+    assert_eq!(variants.hls.len(), 1);
+}
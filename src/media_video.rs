@@ -0,0 +1,128 @@
+//! Web-safe video renditions: ladder, caps, and FFmpeg encodes
+
+use crate::media_command::output_within;
+use crate::media_probe::{probe_audio_video, MediaProbe};
+use crate::media_variants::{HlsFile, MediaRendition};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+use tokio::process::Command;
+use tokio::time::Instant;
+
+const LADDER: [i32; 3] = [360, 720, 1080];
+/// Wall-clock budget shared by every encode and remux of one source, kept
+/// below the job time limit; runs still going when it ends are killed.
+pub(crate) const TRANSCODE_BUDGET: Duration = Duration::from_secs(7 * 60);
+/// Longer sources keep only their original.
+const MAX_SOURCE_DURATION_MS: i64 = 20 * 60 * 1000;
+/// Sources above 4K UHD keep only their original.
+const MAX_SOURCE_PIXELS: i64 = 3840 * 2160;
+
+pub(crate) struct Container {
+    pub(crate) name: &'static str,
+    pub(crate) content_type: &'static str,
+    codec_args: &'static [&'static str],
+}
+
+#[rustfmt::skip]
+pub(crate) const MP4: Container = Container {
+    name: "mp4",
+    content_type: "video/mp4",
+    codec_args: &[
+        "-c:v", "libx264", "-preset", "veryfast", "-crf", "23", "-pix_fmt", "yuv420p",
+        "-c:a", "aac", "-b:a", "128k", "-ac", "2", "-movflags", "+faststart",
+    ],
+};
+
+#[rustfmt::skip]
+pub(crate) const WEBM: Container = Container {
+    name: "webm",
+    content_type: "video/webm",
+    codec_args: &[
+        "-c:v", "libvpx-vp9", "-crf", "33", "-b:v", "0", "-deadline", "realtime",
+        "-cpu-used", "8", "-row-mt", "1", "-pix_fmt", "yuv420p", "-c:a", "libopus",
+        "-b:a", "96k", "-ac", "2",
+    ],
+};
+
+/// A local file produced by transcoding, uploaded under `key`.
+pub struct StagedObject {
+    pub key: String,
+    pub content_type: String,
+    pub path: PathBuf,
+}
+
+/// One finished rendition with the HLS files it added, staged for upload.
+#[derive(Default)]
+pub struct VideoTranscode {
+    pub renditions: Vec<MediaRendition>,
+    pub hls: Vec<HlsFile>,
+    pub staged: Vec<StagedObject>,
+}
+
+/// Ladder heights at or below the source; short sources keep their own even height.
+pub fn rendition_heights(source_height: Option<i32>) -> Vec<i32> {
+    let Some(source) = source_height.filter(|height| *height > 1) else {
+        return Vec::new();
+    };
+    let heights: Vec<i32> = LADDER.into_iter().filter(|h| *h <= source).collect();
+    if heights.is_empty() {
+        vec![source - source % 2]
+    } else {
+        heights
+    }
+}
+
+/// Whether a probed source is within the duration and resolution caps;
+/// sources FFprobe cannot measure are refused.
+pub fn transcode_allowed(probe: &MediaProbe) -> bool {
+    let (Some(duration_ms), Some(width), Some(height)) =
+        (probe.duration_ms, probe.width, probe.height)
+    else {
+        return false;
+    };
+    duration_ms <= MAX_SOURCE_DURATION_MS
+        && i64::from(width) * i64::from(height) <= MAX_SOURCE_PIXELS
+}
+
+pub(crate) async fn encode(
+    id: &str,
+    source: &Path,
+    file: &Path,
+    height: i32,
+    container: &Container,
+    deadline: Instant,
+) -> Option<MediaRendition> {
+    let scale = format!("scale=-2:{height}");
+    let mut args = vec!["-i", source.to_str()?, "-map", "0:v:0", "-map", "0:a:0?"];
+    args.extend(["-vf", &scale]);
+    args.extend(container.codec_args);
+    args.push(file.to_str()?);
+    if !ffmpeg(&args, deadline).await {
+        return None;
+    }
+    let probe = probe_audio_video(file).await;
+    Some(MediaRendition {
+        name: format!("{}_{height}", container.name),
+        key: format!("media/{id}/renditions/{height}p.{}", container.name),
+        content_type: container.content_type.to_string(),
+        byte_size: fs::metadata(file).await.ok()?.len() as i64,
+        width: probe.width?,
+        height: probe.height?,
+    })
+}
+
+/// Run FFmpeg with whatever is left of the transcode budget.
+pub(crate) async fn ffmpeg(args: &[&str], deadline: Instant) -> bool {
+    let limit = deadline.saturating_duration_since(Instant::now());
+    if limit.is_zero() {
+        return false;
+    }
+    let mut command = Command::new("ffmpeg");
+    command
+        .args(["-hide_banner", "-loglevel", "error", "-y"])
+        .args(args);
+    output_within(&mut command, limit)
+        .await
+        .is_some_and(|output| output.status.success())
+}
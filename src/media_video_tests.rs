@@ -0,0 +1,56 @@
+use super::media_hls::{hls_master_playlist, HlsStream};
+use super::media_probe::MediaProbe;
+use super::media_video::{rendition_heights, transcode_allowed};
+
+#[test]
+fn rendition_ladder_never_upscales() {
+    assert_eq!(rendition_heights(Some(2160)), vec![360, 720, 1080]);
+    assert_eq!(rendition_heights(Some(720)), vec![360, 720]);
+    assert_eq!(rendition_heights(Some(241)), vec![240]);
+    assert!(rendition_heights(None).is_empty());
+    assert!(rendition_heights(Some(1)).is_empty());
+}
+
+#[test]
+fn transcodes_only_measured_sources_within_caps() {
+    let probe = |duration_ms, width, height| MediaProbe {
+        width: Some(width),
+        height: Some(height),
+        duration_ms,
+        details: None,
+    };
+    assert!(transcode_allowed(&probe(Some(60_000), 1920, 1080)));
+    assert!(transcode_allowed(&probe(Some(20 * 60 * 1000), 3840, 2160)));
+    assert!(!transcode_allowed(&probe(
+        Some(20 * 60 * 1000 + 1),
+        1920,
+        1080
+    )));
+    assert!(!transcode_allowed(&probe(Some(60_000), 7680, 4320)));
+    assert!(!transcode_allowed(&probe(None, 1920, 1080)));
+    assert!(!transcode_allowed(&MediaProbe::default()));
+}
+
+#[test]
+fn master_playlist_lists_each_stream() {
+    let playlist = hls_master_playlist(&[
+        HlsStream {
+            playlist: "360p.m3u8".to_string(),
+            bandwidth: 800_000,
+            width: 640,
+            height: 360,
+        },
+        HlsStream {
+            playlist: "720p.m3u8".to_string(),
+            bandwidth: 0,
+            width: 1280,
+            height: 720,
+        },
+    ]);
+    assert_eq!(
+        playlist,
+        "#EXTM3U\n\
+         #EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640x360\n360p.m3u8\n\
+         #EXT-X-STREAM-INF:BANDWIDTH=1,RESOLUTION=1280x720\n720p.m3u8\n"
+    );
+}
@@ -0,0 +1,102 @@
+//! Optional and file-backed object reads and prefix cleanup
+
+use super::service::{Storage, StoredObject};
+use crate::error::AppError;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+impl Storage {
+    /// Stream an object into a local file, the counterpart of `put_file`, so
+    /// large originals never sit in memory.
+    pub async fn get_file(&self, key: &str, path: impl AsRef<Path>) -> Result<(), AppError> {
+        let response = self
+            .client()
+            .get_object()
+            .bucket(self.bucket())
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| AppError::StorageError(format!("object fetch failed: {e}")))?;
+        let mut file = tokio::fs::File::create(path.as_ref())
+            .await
+            .map_err(|e| AppError::StorageError(format!("object file create failed: {e}")))?;
+        let mut body = response.body.into_async_read();
+        tokio::io::copy(&mut body, &mut file)
+            .await
+            .map_err(|e| AppError::StorageError(format!("object stream failed: {e}")))?;
+        file.flush()
+            .await
+            .map_err(|e| AppError::StorageError(format!("object file write failed: {e}")))
+    }
+
+    /// Stored size of an object in bytes, read without fetching it.
+    pub async fn object_size(&self, key: &str) -> Result<i64, AppError> {
+        self.client()
+            .head_object()
+            .bucket(self.bucket())
+            .key(key)
+            .send()
+            .await
+            .map(|head| head.content_length.unwrap_or_default())
+            .map_err(|e| AppError::StorageError(format!("object head failed: {e}")))
+    }
+
+    /// Fetch a whole object, or `None` when the key does not exist.
+    pub async fn get_object_if_exists(&self, key: &str) -> Result<Option<StoredObject>, AppError> {
+        let response = match self
+            .client()
+            .get_object()
+            .bucket(self.bucket())
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(error) if error.as_service_error().is_some_and(|e| e.is_no_such_key()) => {
+                return Ok(None);
+            }
+            Err(error) => {
+                return Err(AppError::StorageError(format!(
+                    "object fetch failed: {error}"
+                )))
+            }
+        };
+        let body = response
+            .body
+            .collect()
+            .await
+            .map_err(|e| AppError::StorageError(format!("object stream failed: {e}")))?
+            .into_bytes()
+            .to_vec();
+        Ok(Some(StoredObject {
+            content_length: body.len() as i64,
+            body,
+            content_range: None,
+        }))
+    }
+
+    /// Delete every object whose key starts with `prefix`; returns how many went.
+    pub async fn delete_prefix(&self, prefix: &str) -> Result<usize, AppError> {
+        let mut deleted = 0;
+        let mut token = None;
+        loop {
+            let page = self
+                .client()
+                .list_objects_v2()
+                .bucket(self.bucket())
+                .prefix(prefix)
+                .set_continuation_token(token)
+                .send()
+                .await
+                .map_err(|e| AppError::StorageError(format!("object listing failed: {e}")))?;
+            for key in page.contents().iter().filter_map(|object| object.key()) {
+                self.delete_object(key).await?;
+                deleted += 1;
+            }
+            token = page.next_continuation_token().map(str::to_string);
+            if token.is_none() {
+                return Ok(deleted);
+            }
+        }
+    }
+}
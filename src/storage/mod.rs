@@ -1,5 +1,6 @@
 //! SeaweedFS S3 object storage support
 
+mod listing;
 mod service;
 
 pub use service::{Storage, StoredObject};
@@ -32,7 +32,7 @@ export async function verifySettingsSearch(page) {
     await input.fill('webp');
     await expectVisibleSection(page, 'Media/WebP_quality', true);
     await expectVisibleSection(page, 'Search/Results_per_page', false);
-    await expectVisibleSection(page, 'Resources/New_resources_start_private', false);
+    await expectVisibleSection(page, 'Resources/New_resource_visibility', false);
     await input.fill('future uploads');
     await expectVisibleSection(page, 'Media/WebP_quality', true);
     await input.fill('icon');
@@ -87,7 +87,7 @@ export async function applySettingsScenario(page) {
     assert.equal(await page.locator('input[name="home_recent_limit"]').inputValue(), '5');
     assert.equal(await page.locator('input[name="home_favorite_limit"]').inputValue(), '5');
     assert.equal(await page.getByLabel('Session/Timeout_minutes').inputValue(), '1440');
-    assert.equal(await page.getByLabel('Resources/New_resources_start_private').isChecked(), false);
+    assert.equal(await page.getByLabel('Resources/New_resource_visibility').inputValue(), 'public');
     assert.equal(await page.getByRole('button', { name: /Move .* (up|down)/ }).count(), 0);
     assert.deepEqual(await settingsOrder(page), ['Recently updated', 'Favorites', 'Popular']);
     await page.getByLabel('Site_identity/Site_name').fill('Launchpad');
@@ -118,7 +118,7 @@ export async function applySettingsScenario(page) {
     assert.equal(await page.getByLabel('Media/WebP_quality').inputValue(), '67');
     assert.equal(await page.getByLabel('Embeds/Google_Maps_API_key').inputValue(), 'visual-maps-key');
     assert.equal(await page.getByLabel('Session/Timeout_minutes').inputValue(), '720');
-    assert.equal(await page.getByLabel('Resources/New_resources_start_private').isChecked(), false);
+    assert.equal(await page.getByLabel('Resources/New_resource_visibility').inputValue(), 'public');
     assert.equal(discovery.robotsStatus, 200);
     assert.equal(discovery.sitemapStatus, 200);
     assert.deepEqual(await settingsOrder(page), ['Favorites', 'Recently updated', 'Popular']);
@@ -129,7 +129,7 @@ export async function expectSettingsPage(page) {
     await page.getByLabel('Live/Default_quality').waitFor({ state: 'visible' });
     await page.getByLabel('Live/Default_fps').waitFor({ state: 'visible' });
     await page.getByLabel('Live/Microphone_default').waitFor({ state: 'visible' });
-    await page.getByLabel('Resources/New_resources_start_private').waitFor({ state: 'visible' });
+    await page.getByLabel('Resources/New_resource_visibility').waitFor({ state: 'visible' });
     await assertVisibleText(page, 'Home/Section_order');
     await assertVisibleText(page, 'Favorites');
     await assertVisibleText(page, 'Session/Timeout_minutes');
@@ -137,7 +137,7 @@ export async function expectSettingsPage(page) {
     await assertVisibleText(page, 'Media');
     await assertVisibleText(page, 'Live/Default_source');
     await assertVisibleText(page, 'Live/Default_camera_facing');
-    await assertVisibleText(page, 'Resources/New_resources_start_private');
+    await assertVisibleText(page, 'Resources/New_resource_visibility');
     await assertVisibleText(page, 'Password');
     assert.equal(await page.locator('.settings-section .section-head').count(), 0);
     await page.locator('[data-settings-order-list]').waitFor({ state: 'visible' });
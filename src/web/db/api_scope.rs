@@ -0,0 +1,64 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiScope {
+    ResourceRead,
+    ResourceWrite,
+}
+
+impl ApiScope {
+    pub const ALL: [Self; 2] = [Self::ResourceRead, Self::ResourceWrite];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "resource:read" => Some(Self::ResourceRead),
+            "resource:write" => Some(Self::ResourceWrite),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ResourceRead => "resource:read",
+            Self::ResourceWrite => "resource:write",
+        }
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Vec<Self> {
+        value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_str().and_then(Self::parse))
+            .collect()
+    }
+
+    pub fn to_json(scopes: &[Self]) -> serde_json::Value {
+        serde_json::Value::from(
+            scopes
+                .iter()
+                .map(|scope| scope.as_str())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiScope;
+    use serde_json::json;
+
+    #[test]
+    fn scopes_round_trip_through_json() {
+        let scopes = ApiScope::from_json(&json!(["resource:read", "bogus", "resource:write"]));
+        assert_eq!(scopes, ApiScope::ALL.to_vec());
+        assert_eq!(
+            ApiScope::to_json(&scopes),
+            json!(["resource:read", "resource:write"])
+        );
+    }
+
+    #[test]
+    fn unknown_scopes_are_rejected() {
+        assert_eq!(ApiScope::parse("settings:write"), None);
+        assert_eq!(ApiScope::from_json(&json!("resource:read")), Vec::new());
+    }
+}
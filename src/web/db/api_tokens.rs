@@ -0,0 +1,172 @@
+//! Service account and API token persistence
+
+use super::api_scope::ApiScope;
+use super::audit::{write_audit_event, AuditEvent};
+use super::write_actor::WriteActor;
+use super::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+const TOKEN_PREFIX: &str = "kjx_";
+/// A token's use is audited on first use and then at most once per interval.
+const USE_AUDIT_INTERVAL: &str = "1 hour";
+/// Longest lifetime a newly issued token may ask for.
+pub const MAX_TOKEN_LIFETIME_DAYS: i64 = 3650;
+
+/// Verified bearer token with its owning service account and space.
+#[derive(Clone, Debug)]
+pub struct ApiTokenGrant {
+    pub service_account_id: Uuid,
+    pub space_slug: String,
+    pub scopes: Vec<ApiScope>,
+}
+
+impl ApiTokenGrant {
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+pub async fn create_service_account(
+    pool: &DbPool,
+    space_slug: &str,
+    name: &str,
+    actor: &WriteActor,
+) -> Result<Uuid, AppError> {
+    let mut db = client(pool).await?;
+    let tx = db.transaction().await.map_err(db_error)?;
+    let id: Uuid = tx
+        .query_opt(
+            "INSERT INTO service_accounts (space_id, name, created_by) \
+             SELECT id, $2, $3 FROM spaces WHERE slug = $1::CITEXT RETURNING id",
+            &[&space_slug, &name, &actor.user_id],
+        )
+        .await
+        .map_err(db_error)?
+        .map(|row| row.get("id"))
+        .ok_or_else(|| AppError::NotFound(format!("space '{space_slug}' not found")))?;
+    let event = AuditEvent {
+        space_slug: Some(space_slug),
+        actor,
+        event_type: "service_account.created",
+        entity_type: "service_account",
+        entity_id: &id.to_string(),
+        payload: json!({ "name": name }),
+    };
+    write_audit_event(&tx, &event).await?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(id)
+}
+
+/// Mint a token for one service account and return the raw secret once.
+pub async fn issue_api_token(
+    pool: &DbPool,
+    space_slug: &str,
+    service_account_id: Uuid,
+    scopes: &[ApiScope],
+    expires_at: Option<DateTime<Utc>>,
+    actor: &WriteActor,
+) -> Result<String, AppError> {
+    let token = new_token();
+    let mut db = client(pool).await?;
+    let tx = db.transaction().await.map_err(db_error)?;
+    let token_id: Uuid = tx
+        .query_opt(
+            "INSERT INTO api_tokens (service_account_id, space_id, token_hash, scopes, expires_at) \
+             SELECT id, space_id, $2, $3, $4 FROM service_accounts \
+             WHERE id = $1 AND disabled_at IS NULL \
+             AND space_id = (SELECT id FROM spaces WHERE slug = $5::CITEXT) RETURNING id",
+            &[
+                &service_account_id,
+                &api_token_hash(&token),
+                &ApiScope::to_json(scopes),
+                &expires_at,
+                &space_slug,
+            ],
+        )
+        .await
+        .map_err(db_error)?
+        .map(|row| row.get("id"))
+        .ok_or_else(|| AppError::NotFound("service account not found".to_string()))?;
+    let event = AuditEvent {
+        space_slug: Some(space_slug),
+        actor,
+        event_type: "api_token.issued",
+        entity_type: "api_token",
+        entity_id: &token_id.to_string(),
+        payload: json!({
+            "service_account_id": service_account_id,
+            "scopes": ApiScope::to_json(scopes),
+            "expires_at": expires_at,
+        }),
+    };
+    write_audit_event(&tx, &event).await?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(token)
+}
+
+/// Resolve a raw bearer token and stamp its use. Only the first use and uses
+/// after `USE_AUDIT_INTERVAL` of quiet append an audit event, so busy clients
+/// do not flood the audit log.
+pub async fn validate_api_token(
+    pool: &DbPool,
+    token: &str,
+) -> Result<Option<ApiTokenGrant>, AppError> {
+    if !token.starts_with(TOKEN_PREFIX) {
+        return Ok(None);
+    }
+    let row = client(pool)
+        .await?
+        .query_opt(
+            &format!(
+                "WITH used AS (UPDATE api_tokens t SET last_used_at = NOW() \
+                 FROM service_accounts a, spaces s, \
+                 (SELECT id, last_used_at FROM api_tokens WHERE token_hash = $1) prev \
+                 WHERE t.token_hash = $1 AND prev.id = t.id \
+                 AND a.id = t.service_account_id AND s.id = t.space_id \
+                 AND t.revoked_at IS NULL AND (t.expires_at IS NULL OR t.expires_at > NOW()) \
+                 AND a.disabled_at IS NULL \
+                 RETURNING t.id, t.space_id, t.service_account_id, s.slug::TEXT AS space_slug, t.scopes, \
+                 prev.last_used_at AS previous_use), \
+                 audit AS (INSERT INTO audit_events (space_id, actor_service_account_id, event_type, \
+                 entity_type, entity_id, payload) SELECT space_id, service_account_id, 'api_token.used', \
+                 'api_token', id::TEXT, jsonb_build_object('scopes', scopes) FROM used \
+                 WHERE previous_use IS NULL \
+                 OR previous_use < NOW() - '{USE_AUDIT_INTERVAL}'::INTERVAL) \
+                 SELECT service_account_id, space_slug, scopes FROM used"
+            ),
+            &[&api_token_hash(token)],
+        )
+        .await
+        .map_err(db_error)?;
+    Ok(row.map(|row| ApiTokenGrant {
+        service_account_id: row.get("service_account_id"),
+        space_slug: row.get("space_slug"),
+        scopes: ApiScope::from_json(&row.get("scopes")),
+    }))
+}
+
+fn api_token_hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn new_token() -> String {
+    format!(
+        "{TOKEN_PREFIX}{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError> {
+    pool.get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+fn db_error(error: tokio_postgres::Error) -> AppError {
+    AppError::DatabaseError(error.to_string())
+}
@@ -0,0 +1,123 @@
+//! Audit event writes inside mutation transactions
+
+use super::models::Resource;
+use super::write_actor::WriteActor;
+use super::DbPool;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde_json::{json, Value};
+use tokio_postgres::types::ToSql;
+use uuid::Uuid;
+
+/// One mutation to append to `audit_events` inside the caller's transaction.
+pub struct AuditEvent<'a> {
+    pub space_slug: Option<&'a str>,
+    pub actor: &'a WriteActor,
+    pub event_type: &'a str,
+    pub entity_type: &'a str,
+    pub entity_id: &'a str,
+    pub payload: Value,
+}
+
+/// Append one audit event; space-less events fall back to the actor's own space.
+pub(super) async fn write_audit_event<C: GenericClient>(
+    db: &C,
+    event: &AuditEvent<'_>,
+) -> Result<(), AppError> {
+    db.execute(
+        "INSERT INTO audit_events (space_id, actor_user_id, actor_service_account_id, \
+         event_type, entity_type, entity_id, payload) \
+         VALUES (COALESCE((SELECT id FROM spaces WHERE slug = $1::CITEXT), \
+         (SELECT id FROM spaces WHERE owner_user_id = $2 ORDER BY created_at ASC LIMIT 1), \
+         default_space_id()), $2, $3, $4, $5, $6, $7)",
+        &[
+            &event.space_slug,
+            &event.actor.user_id,
+            &event.actor.service_account_id,
+            &event.event_type,
+            &event.entity_type,
+            &event.entity_id,
+            &event.payload,
+        ],
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Append one resource-level event with the resource's identity as payload.
+pub(super) async fn audit_resource<C: GenericClient>(
+    db: &C,
+    space_slug: &str,
+    actor: &WriteActor,
+    event_type: &str,
+    resource: &Resource,
+) -> Result<(), AppError> {
+    write_audit_event(
+        db,
+        &AuditEvent {
+            space_slug: Some(space_slug),
+            actor,
+            event_type,
+            entity_type: "resource",
+            entity_id: &resource.id,
+            payload: json!({
+                "kind": resource.kind.as_str(),
+                "alias": resource.alias,
+                "title": resource.title,
+                "visibility": resource.visibility,
+            }),
+        },
+    )
+    .await
+}
+
+/// Append one account event such as a login or password change.
+pub(super) async fn audit_user_event<C: GenericClient>(
+    db: &C,
+    user_id: Uuid,
+    event_type: &str,
+) -> Result<(), AppError> {
+    write_audit_event(
+        db,
+        &AuditEvent {
+            space_slug: None,
+            actor: &WriteActor::user(user_id),
+            event_type,
+            entity_type: "user",
+            entity_id: &user_id.to_string(),
+            payload: json!({}),
+        },
+    )
+    .await
+}
+
+/// Run one lifecycle update and append its audit event when a row matched.
+pub(super) async fn audited_update(
+    pool: &DbPool,
+    statement: &str,
+    params: &[&(dyn ToSql + Sync)],
+    event: AuditEvent<'_>,
+) -> Result<bool, AppError> {
+    let mut db = pool
+        .get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if tx
+        .execute(statement, params)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        == 0
+    {
+        return Ok(false);
+    }
+    write_audit_event(&tx, &event).await?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(true)
+}
@@ -0,0 +1,44 @@
+use crate::error::AppError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct AuditCursor {
+    created_at: DateTime<Utc>,
+    id: Uuid,
+}
+
+pub(super) fn decode_audit_cursor(
+    cursor: Option<&str>,
+) -> Result<Option<(DateTime<Utc>, Uuid)>, AppError> {
+    let Some(cursor) = cursor else {
+        return Ok(None);
+    };
+    let cursor: AuditCursor = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|raw| serde_json::from_slice(&raw).ok())
+        .ok_or_else(|| AppError::InvalidRequest("invalid cursor".to_string()))?;
+    Ok(Some((cursor.created_at, cursor.id)))
+}
+
+pub(super) fn encode_audit_cursor(created_at: DateTime<Utc>, id: Uuid) -> String {
+    URL_SAFE_NO_PAD.encode(serde_json::to_string(&AuditCursor { created_at, id }).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_audit_cursor, encode_audit_cursor};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn audit_cursor_round_trips() {
+        let (at, id) = (Utc::now(), Uuid::new_v4());
+        let cursor = encode_audit_cursor(at, id);
+        assert_eq!(decode_audit_cursor(Some(&cursor)).unwrap(), Some((at, id)));
+        assert!(decode_audit_cursor(Some("not-a-cursor")).is_err());
+    }
+}
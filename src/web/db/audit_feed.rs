@@ -0,0 +1,83 @@
+//! Space audit feed queries
+
+use super::audit_cursor::{decode_audit_cursor, encode_audit_cursor};
+use super::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+const MAX_LIMIT: i64 = 100;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub event_type: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_service_account_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditPage {
+    pub events: Vec<AuditEntry>,
+    pub next_cursor: Option<String>,
+}
+
+pub async fn list_audit_events(
+    pool: &DbPool,
+    space_slug: &str,
+    limit: i64,
+    cursor: Option<&str>,
+) -> Result<AuditPage, AppError> {
+    let limit = limit.clamp(1, MAX_LIMIT);
+    let (before_at, before_id) = decode_audit_cursor(cursor)?.unzip();
+    let rows = pool
+        .get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .query(
+            "SELECT e.id, e.event_type, e.entity_type, e.entity_id, e.actor_user_id, \
+             e.actor_service_account_id, COALESCE(u.username::TEXT, a.name) AS actor_name, \
+             e.payload, e.created_at FROM audit_events e \
+             LEFT JOIN users u ON u.id = e.actor_user_id \
+             LEFT JOIN service_accounts a ON a.id = e.actor_service_account_id \
+             WHERE e.space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+             AND ($2::TIMESTAMPTZ IS NULL OR (e.created_at, e.id) < ($2, $3)) \
+             ORDER BY e.created_at DESC, e.id DESC LIMIT $4",
+            &[&space_slug, &before_at, &before_id, &(limit + 1)],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let mut events = rows
+        .into_iter()
+        .map(|row| AuditEntry {
+            id: row.get("id"),
+            event_type: row.get("event_type"),
+            entity_type: row.get("entity_type"),
+            entity_id: row.get("entity_id"),
+            actor_user_id: row.get("actor_user_id"),
+            actor_service_account_id: row.get("actor_service_account_id"),
+            actor_name: row.get("actor_name"),
+            payload: row.get("payload"),
+            created_at: row.get("created_at"),
+        })
+        .collect::<Vec<_>>();
+    let next_cursor = if events.len() as i64 > limit {
+        events.pop();
+        events
+            .last()
+            .map(|event| encode_audit_cursor(event.created_at, event.id))
+    } else {
+        None
+    };
+    Ok(AuditPage {
+        events,
+        next_cursor,
+    })
+}
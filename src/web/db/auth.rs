@@ -1,5 +1,6 @@
 //! Authentication database operations
 
+use super::audit::audit_user_event;
 use super::password;
 use super::DbPool;
 use crate::error::AppError;
@@ -112,14 +113,18 @@ pub async fn verify_credentials(
 
 /// Create a new session
 pub async fn create_session(pool: &DbPool, user_id: Uuid, minutes: i32) -> Result<Uuid, AppError> {
-    let client = pool
+    let mut client = pool
         .get()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let tx = client
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
     let session_id = Uuid::new_v4();
     let token_hash = token_hash(&session_id);
-    let row = client
+    let row = tx
         .query_one(
             "INSERT INTO user_sessions (id, user_id, token_hash, csrf_secret_hash, expires_at) \
              VALUES ($1, $2, $3, $3, NOW() + make_interval(mins => $4)) \
@@ -128,6 +133,10 @@ pub async fn create_session(pool: &DbPool, user_id: Uuid, minutes: i32) -> Resul
         )
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    audit_user_event(&tx, user_id, "auth.login").await?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
     Ok(row.get("id"))
 }
@@ -176,7 +185,7 @@ fn token_hash(session_id: &Uuid) -> String {
     format!("{:x}", Sha256::digest(session_id.to_string().as_bytes()))
 }
 
-fn local_email(username: &str) -> String {
+pub(super) fn local_email(username: &str) -> String {
     if username.contains('@') {
         username.to_string()
     } else {
@@ -1,8 +1,13 @@
 //! Discovery queries
 
-use super::{DbPool, SitemapResource};
+use super::resource_support::{row_to_resource, SELECT_RECORD};
+use super::write_support::client;
+use super::{DbPool, Resource, SitemapResource};
 use crate::error::AppError;
 
+/// Resources guests may discover through the sitemap and feeds.
+const PUBLIC_RESOURCES: &str = "deleted_at IS NULL AND visibility = 'public'";
+
 pub async fn list_public_sitemap_resources(
     pool: &DbPool,
 ) -> Result<Vec<SitemapResource>, AppError> {
@@ -10,9 +15,11 @@ pub async fn list_public_sitemap_resources(
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?
         .query(
-            "SELECT id, alias, updated_at FROM resources \
-             WHERE deleted_at IS NULL AND visibility = 'public' \
-             ORDER BY updated_at DESC, id ASC",
+            &format!(
+                "SELECT id, alias, updated_at FROM resources \
+                 WHERE {PUBLIC_RESOURCES} \
+                 ORDER BY updated_at DESC, id ASC"
+            ),
             &[],
         )
         .await
@@ -27,3 +34,29 @@ pub async fn list_public_sitemap_resources(
         })
         .map_err(|e| AppError::DatabaseError(e.to_string()))
 }
+
+/// Newest public resources of one space, optionally limited to one
+/// normalized front matter tag.
+pub async fn list_public_feed_resources(
+    pool: &DbPool,
+    space_slug: &str,
+    tag: Option<&str>,
+    limit: i64,
+) -> Result<Vec<Resource>, AppError> {
+    client(pool)
+        .await?
+        .query(
+            &format!(
+                "{SELECT_RECORD} FROM resources \
+                 WHERE {PUBLIC_RESOURCES} \
+                 AND space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+                 AND ($2::TEXT IS NULL \
+                  OR metadata @> jsonb_build_object('tags', jsonb_build_array($2::TEXT))) \
+                 ORDER BY updated_at DESC, id ASC LIMIT $3"
+            ),
+            &[&space_slug, &tag, &limit],
+        )
+        .await
+        .map(|rows| rows.into_iter().map(row_to_resource).collect())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
@@ -1,35 +1,43 @@
 //! Favorite ordering queries
 
+use super::audit::{write_audit_event, AuditEvent};
 use super::listing_row::row_to_listed_resource;
-use super::{DbPool, ListedResource};
+use super::write_actor::WriteActor;
+use super::{DbPool, ListedResource, Visibility};
 use crate::error::AppError;
+use serde_json::json;
 use std::collections::HashSet;
 
 pub async fn list_all_favorite_resources(
     pool: &DbPool,
     space_slug: Option<&str>,
-    include_private: bool,
+    max_visibility: Visibility,
 ) -> Result<Vec<ListedResource>, AppError> {
     let rows = client(pool)
         .await?
         .query(
             "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = space_id) AS space_slug, \
              kind, alias, title, summary, body, media_family, file_key, content_type, \
-             byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, \
-             favorite_position, (visibility = 'private') AS is_private, view_count_total, last_viewed_at, created_at, updated_at, \
+             byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, media_details, owner_note_id, is_favorite, \
+             favorite_position, visibility::TEXT AS visibility, view_count_total, last_viewed_at, created_at, updated_at, \
              summary AS preview, NULL::BIGINT AS popular_views \
              FROM resources WHERE deleted_at IS NULL AND is_favorite = TRUE \
-             AND ($1 OR visibility = 'public') \
+             AND visibility <= $1::TEXT::resource_visibility \
              AND ($2::TEXT IS NULL OR space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT)) \
              ORDER BY favorite_position ASC NULLS LAST, id ASC",
-            &[&include_private, &space_slug],
+            &[&max_visibility.as_str(), &space_slug],
         )
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     Ok(rows.into_iter().map(row_to_listed_resource).collect())
 }
 
-pub async fn reorder_favorites(pool: &DbPool, ids: &[String]) -> Result<(), AppError> {
+pub async fn reorder_favorites(
+    pool: &DbPool,
+    space_slug: &str,
+    ids: &[String],
+    actor: &WriteActor,
+) -> Result<(), AppError> {
     let mut client = client(pool).await?;
     let transaction = client
         .transaction()
@@ -63,6 +71,18 @@ pub async fn reorder_favorites(pool: &DbPool, ids: &[String]) -> Result<(), AppE
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     }
+    write_audit_event(
+        &transaction,
+        &AuditEvent {
+            space_slug: Some(space_slug),
+            actor,
+            event_type: "favorites.reordered",
+            entity_type: "space",
+            entity_id: space_slug,
+            payload: json!({ "ids": ids }),
+        },
+    )
+    .await?;
     transaction
         .commit()
         .await
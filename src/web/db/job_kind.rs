@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    MediaDerivatives,
+    EmbedUnfurl,
+    SearchEmbedding,
+}
+
+impl JobKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MediaDerivatives => "media_derivatives",
+            Self::EmbedUnfurl => "embed_unfurl",
+            Self::SearchEmbedding => "search_embedding",
+        }
+    }
+
+    pub(super) fn from_db(value: &str) -> Self {
+        match value {
+            "media_derivatives" => Self::MediaDerivatives,
+            "search_embedding" => Self::SearchEmbedding,
+            _ => Self::EmbedUnfurl,
+        }
+    }
+}
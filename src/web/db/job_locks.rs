@@ -0,0 +1,123 @@
+//! Claiming, heartbeating, and settling jobs held by one worker
+
+use super::job_kind::JobKind;
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Running jobs whose lock is older than this are assumed lost and reclaimed.
+/// Workers refresh the lock well inside this window and stop runs before it.
+const STALE_LOCK_MINUTES: i32 = 15;
+
+/// A job claimed by one worker; `attempts` already counts this run.
+#[derive(Debug, Clone)]
+pub struct ClaimedJob {
+    pub id: i64,
+    pub kind: JobKind,
+    pub resource_id: Option<String>,
+    pub payload: Value,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// Worker holding the lock; status updates from anyone else are ignored.
+    pub locked_by: String,
+}
+
+/// Lock the oldest runnable job for `worker`, reclaiming runs whose lock
+/// stopped being refreshed. A lost run that used the last attempt is marked
+/// failed instead, so a job that keeps crashing its worker stops retrying.
+pub async fn claim_job(pool: &DbPool, worker: &str) -> Result<Option<ClaimedJob>, AppError> {
+    claim_job_on(&client(pool).await?, worker).await
+}
+
+pub(super) async fn claim_job_on<C: GenericClient>(
+    db: &C,
+    worker: &str,
+) -> Result<Option<ClaimedJob>, AppError> {
+    let row = db
+        .query_opt(
+            "WITH exhausted AS (UPDATE jobs SET status = 'failed', locked_at = NULL, \
+              locked_by = NULL, last_error = 'job lock lost on its last attempt', \
+              updated_at = NOW() \
+              WHERE status = 'running' AND locked_at < NOW() - make_interval(mins => $1) \
+              AND attempts >= max_attempts) \
+             UPDATE jobs SET status = 'running', attempts = attempts + 1, \
+             locked_at = NOW(), locked_by = $2, updated_at = NOW() \
+             WHERE id = (SELECT id FROM jobs \
+              WHERE (status = 'queued' AND run_at <= NOW()) \
+              OR (status = 'running' AND locked_at < NOW() - make_interval(mins => $1) \
+               AND attempts < max_attempts) \
+              ORDER BY run_at, id LIMIT 1 FOR UPDATE SKIP LOCKED) \
+             RETURNING id, kind, resource_id, payload, attempts, max_attempts, locked_by",
+            &[&STALE_LOCK_MINUTES, &worker],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(row.map(|row| ClaimedJob {
+        id: row.get("id"),
+        kind: JobKind::from_db(&row.get::<_, String>("kind")),
+        resource_id: row.get("resource_id"),
+        payload: row.get("payload"),
+        attempts: row.get("attempts"),
+        max_attempts: row.get("max_attempts"),
+        locked_by: row.get("locked_by"),
+    }))
+}
+
+/// Refresh the lock of a running job; `false` means another worker took it.
+pub async fn heartbeat_job(pool: &DbPool, job: &ClaimedJob) -> Result<bool, AppError> {
+    client(pool)
+        .await?
+        .execute(
+            "UPDATE jobs SET locked_at = NOW() \
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            &[&job.id, &job.locked_by],
+        )
+        .await
+        .map(|updated| updated == 1)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Mark the job done; `false` means the lock was lost and nothing changed.
+pub async fn complete_job(pool: &DbPool, job: &ClaimedJob) -> Result<bool, AppError> {
+    client(pool)
+        .await?
+        .execute(
+            "UPDATE jobs SET status = 'succeeded', locked_at = NULL, locked_by = NULL, \
+             last_error = NULL, updated_at = NOW() \
+             WHERE id = $1 AND status = 'running' AND locked_by = $2",
+            &[&job.id, &job.locked_by],
+        )
+        .await
+        .map(|updated| updated == 1)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Schedule a retry after `delay`, or give up once attempts run out or a
+/// newer queued job for the same resource supersedes this one. Like
+/// `complete_job`, this is a no-op returning `false` once the lock is lost.
+pub async fn fail_job(
+    pool: &DbPool,
+    job: &ClaimedJob,
+    error: &str,
+    delay: Duration,
+) -> Result<bool, AppError> {
+    let delay_secs = delay.as_secs_f64();
+    client(pool)
+        .await?
+        .execute(
+            "UPDATE jobs SET status = CASE WHEN attempts >= max_attempts OR EXISTS ( \
+              SELECT 1 FROM jobs newer WHERE newer.kind = jobs.kind \
+              AND newer.resource_id = jobs.resource_id AND newer.status = 'queued') \
+             THEN 'failed' ELSE 'queued' END, \
+             run_at = NOW() + make_interval(secs => $2), locked_at = NULL, locked_by = NULL, \
+             last_error = $3, updated_at = NOW() \
+             WHERE id = $1 AND status = 'running' AND locked_by = $4",
+            &[&job.id, &delay_secs, &error, &job.locked_by],
+        )
+        .await
+        .map(|updated| updated == 1)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
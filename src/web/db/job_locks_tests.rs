@@ -0,0 +1,37 @@
+use super::create_pool;
+use super::job_locks::claim_job_on;
+use super::migrations::run_migrations;
+
+const STALE_RUN: &str = "INSERT INTO jobs \
+     (kind, status, attempts, max_attempts, run_at, locked_at, locked_by) \
+     VALUES ('embed_unfurl', 'running', $1, 3, 'epoch', NOW() - INTERVAL '1 day', 'lost') \
+     RETURNING id";
+
+/// Runs against `DATABASE_URL` inside a rolled-back transaction; skipped
+/// where no database is configured.
+#[tokio::test]
+async fn stale_runs_without_attempts_left_fail_instead_of_being_reclaimed() {
+    let Ok(url) = std::env::var("DATABASE_URL") else {
+        return;
+    };
+    let pool = create_pool(&url).await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let mut client = pool.get().await.unwrap();
+    let tx = client.transaction().await.unwrap();
+    let exhausted: i64 = tx.query_one(STALE_RUN, &[&3i32]).await.unwrap().get(0);
+    let retryable: i64 = tx.query_one(STALE_RUN, &[&2i32]).await.unwrap().get(0);
+
+    let claimed = claim_job_on(&tx, "worker").await.unwrap().unwrap();
+    assert_eq!((claimed.id, claimed.attempts), (retryable, 3));
+    assert_eq!(claimed.locked_by, "worker");
+    let row = tx
+        .query_one(
+            "SELECT status, locked_by FROM jobs WHERE id = $1",
+            &[&exhausted],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, String>(0), "failed");
+    assert_eq!(row.get::<_, Option<String>>(1), None);
+    tx.rollback().await.unwrap();
+}
@@ -0,0 +1,40 @@
+use super::job_kind::JobKind;
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Newest job of each kind for one resource, as shown on resource payloads.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceJob {
+    pub kind: JobKind,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub run_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn list_resource_jobs(pool: &DbPool, id: &str) -> Result<Vec<ResourceJob>, AppError> {
+    let rows = client(pool)
+        .await?
+        .query(
+            "SELECT DISTINCT ON (kind) kind, status, attempts, last_error, run_at, updated_at \
+             FROM jobs WHERE resource_id = $1 ORDER BY kind, id DESC",
+            &[&id],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| ResourceJob {
+            kind: JobKind::from_db(&row.get::<_, String>("kind")),
+            status: row.get("status"),
+            attempts: row.get("attempts"),
+            last_error: row.get("last_error"),
+            run_at: row.get("run_at"),
+            updated_at: row.get("updated_at"),
+        })
+        .collect())
+}
@@ -0,0 +1,96 @@
+//! Postgres-backed background job queue
+
+use super::job_kind::JobKind;
+use super::models::{MediaFamily, Resource};
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde_json::{json, Value};
+
+const FINISHED_RETENTION_DAYS: i32 = 14;
+
+/// Queue a job; a still-queued job of the same kind for the resource is
+/// replaced so repeated saves collapse into one run.
+pub(super) async fn enqueue_job<C: GenericClient>(
+    db: &C,
+    kind: JobKind,
+    resource_id: Option<&str>,
+    payload: &Value,
+) -> Result<(), AppError> {
+    db.execute(
+        "INSERT INTO jobs (kind, resource_id, payload) VALUES ($1, $2, $3) \
+         ON CONFLICT (kind, resource_id) WHERE status = 'queued' DO UPDATE \
+         SET payload = EXCLUDED.payload, attempts = 0, run_at = NOW(), \
+         last_error = NULL, updated_at = NOW()",
+        &[&kind.as_str(), &resource_id, payload],
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Queue derivative generation for new media; plain files have none and
+/// duplicate uploads arrive with the derivatives of their twin.
+pub(super) async fn enqueue_media_derivatives<C: GenericClient>(
+    db: &C,
+    resource: &Resource,
+) -> Result<(), AppError> {
+    if matches!(resource.media_family, None | Some(MediaFamily::File))
+        || resource.media_variants.is_some()
+    {
+        return Ok(());
+    }
+    enqueue_job(
+        db,
+        JobKind::MediaDerivatives,
+        Some(&resource.id),
+        &json!({}),
+    )
+    .await
+}
+
+/// Queue unfurling for external URLs found in a resource body or the home intro.
+pub async fn enqueue_embed_unfurl(
+    pool: &DbPool,
+    resource_id: Option<&str>,
+    urls: &[String],
+) -> Result<(), AppError> {
+    let db = client(pool).await?;
+    enqueue_job(
+        &db,
+        JobKind::EmbedUnfurl,
+        resource_id,
+        &json!({ "urls": urls }),
+    )
+    .await
+}
+
+/// Queue derivative regeneration for every live image, video, and audio resource.
+pub async fn requeue_media_derivatives(pool: &DbPool) -> Result<u64, AppError> {
+    client(pool)
+        .await?
+        .execute(
+            "INSERT INTO jobs (kind, resource_id) \
+             SELECT 'media_derivatives', id FROM resources \
+             WHERE kind = 'media' AND media_family IN ('image', 'video', 'audio') \
+             AND deleted_at IS NULL \
+             ON CONFLICT (kind, resource_id) WHERE status = 'queued' DO NOTHING",
+            &[],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Drop succeeded and failed jobs past the retention window.
+pub async fn prune_finished_jobs(pool: &DbPool) -> Result<u64, AppError> {
+    client(pool)
+        .await?
+        .execute(
+            "DELETE FROM jobs WHERE status IN ('succeeded', 'failed') \
+             AND updated_at < NOW() - make_interval(days => $1)",
+            &[&FINISHED_RETENTION_DAYS],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
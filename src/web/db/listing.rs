@@ -2,7 +2,10 @@
 
 use super::listing_cursor::decode_cursor;
 use super::listing_queries::{browse_resources, search_resources, top_resources, ListingQuery};
-use super::{DbPool, ListKind, ListScope, ListedResource, PopularWindow};
+use super::{
+    DbPool, ListKind, ListScope, ListedResource, PopularWindow, QueryEmbedding, Visibility,
+};
+use crate::core::SearchFilter;
 use crate::error::AppError;
 
 pub use super::listing_direction::ListDirection;
@@ -14,7 +17,7 @@ const MAX_LIMIT: i64 = 100;
 #[derive(Clone, Debug)]
 pub struct ListRequest {
     pub space_slug: Option<String>,
-    pub include_private: bool,
+    pub max_visibility: Visibility,
     pub limit: i64,
     pub query: Option<String>,
     pub direction: ListDirection,
@@ -23,6 +26,15 @@ pub struct ListRequest {
     pub sort: ListSort,
     pub popular_window: PopularWindow,
     pub cursor: Option<String>,
+    /// Only resources whose front matter lists this tag.
+    pub tag: Option<String>,
+    /// Front matter `key = value` pairs every resource must match.
+    pub meta: Vec<(String, String)>,
+    /// Field filters parsed from the query syntax, such as `is:favorite`.
+    pub filters: Vec<SearchFilter>,
+    /// Query vector for `ListSort::Semantic`; without one the hybrid score
+    /// falls back to lexical rank.
+    pub query_embedding: Option<QueryEmbedding>,
 }
 
 #[derive(Clone, Debug)]
@@ -69,42 +81,42 @@ pub async fn list_resources(pool: &DbPool, request: &ListRequest) -> Result<List
 
 pub async fn list_recent_resources(
     pool: &DbPool,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
 ) -> Result<Vec<ListedResource>, AppError> {
-    top_resources(pool, None, include_private, limit, false).await
+    top_resources(pool, None, max_visibility, limit, false).await
 }
 
 pub async fn list_favorite_resources(
     pool: &DbPool,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
 ) -> Result<Vec<ListedResource>, AppError> {
-    top_resources(pool, None, include_private, limit, true).await
+    top_resources(pool, None, max_visibility, limit, true).await
 }
 
 pub async fn list_recent_resources_in_space(
     pool: &DbPool,
     space_slug: &str,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
 ) -> Result<Vec<ListedResource>, AppError> {
-    top_resources(pool, Some(space_slug), include_private, limit, false).await
+    top_resources(pool, Some(space_slug), max_visibility, limit, false).await
 }
 
 pub async fn list_favorite_resources_in_space(
     pool: &DbPool,
     space_slug: &str,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
 ) -> Result<Vec<ListedResource>, AppError> {
-    top_resources(pool, Some(space_slug), include_private, limit, true).await
+    top_resources(pool, Some(space_slug), max_visibility, limit, true).await
 }
 
 impl Default for ListRequest {
     fn default() -> Self {
         Self {
-            include_private: false,
+            max_visibility: Visibility::Public,
             space_slug: None,
             limit: DEFAULT_LIMIT,
             query: None,
@@ -114,6 +126,10 @@ impl Default for ListRequest {
             sort: ListSort::UpdatedDesc,
             popular_window: PopularWindow::Days30,
             cursor: None,
+            tag: None,
+            meta: Vec::new(),
+            filters: Vec::new(),
+            query_embedding: None,
         }
     }
 }
@@ -126,7 +142,7 @@ fn query_request<'a>(
     cursor: Option<&'a super::listing_cursor::Cursor>,
 ) -> ListingQuery<'a> {
     ListingQuery {
-        include_private: request.include_private,
+        max_visibility: request.max_visibility,
         space_slug: request.space_slug.as_deref(),
         limit,
         query: (!query.is_empty()).then_some(query),
@@ -136,5 +152,9 @@ fn query_request<'a>(
         sort: &request.sort,
         popular_window: request.popular_window,
         cursor,
+        tag: request.tag.as_deref(),
+        meta: &request.meta,
+        filters: &request.filters,
+        query_embedding: request.query_embedding.as_ref(),
     }
 }
@@ -21,6 +21,8 @@ pub(super) struct Cursor {
     pub(super) favorite_position: Option<i64>,
     pub(super) popular_views: Option<i64>,
     pub(super) view_count_total: Option<i64>,
+    #[serde(default)]
+    pub(super) semantic: Option<f64>,
 }
 
 pub(super) struct PageCursorContext<'a> {
@@ -124,6 +126,7 @@ fn cursor_from_row(
         favorite_position: row.get("favorite_position"),
         popular_views: row.try_get("popular_views").ok(),
         view_count_total: Some(row.get("view_count_total")),
+        semantic: matches!(sort, ListSort::Semantic).then(|| row.get("semantic")),
     }
 }
 
@@ -0,0 +1,112 @@
+//! Search query filters compiled into listing predicates
+
+use crate::core::{Comparison, FilterTerm, SearchFilter, TimeBound, TimeValue};
+use chrono::{Datelike, Days, NaiveDate};
+
+/// SQL conditions for `filters` plus the values they read from the `TEXT[]`
+/// bound as `$param`. Fixed words such as kinds are inlined; user text and
+/// dates only travel as bound values. The array is always referenced so
+/// Postgres can type it even when there are no filters.
+pub(super) fn search_filters(filters: &[SearchFilter], param: usize) -> (String, Vec<String>) {
+    let mut args = Vec::new();
+    let mut sql = format!(" AND ${param}::TEXT[] IS NOT NULL");
+    for filter in filters {
+        let predicate = predicate(&filter.term, &mut args, param);
+        let not = if filter.negated { "NOT " } else { "" };
+        sql.push_str(&format!(" AND {not}COALESCE(({predicate}), FALSE)"));
+    }
+    (sql, args)
+}
+
+fn predicate(term: &FilterTerm, args: &mut Vec<String>, param: usize) -> String {
+    let mut bind = |value: String| {
+        args.push(value);
+        format!("(${param}::TEXT[])[{}]", args.len())
+    };
+    match term {
+        FilterTerm::Title(value) => {
+            format!("strpos(LOWER(r.title), LOWER({})) > 0", bind(value.clone()))
+        }
+        FilterTerm::Alias(value) => format!(
+            "strpos(LOWER(COALESCE(r.alias, '')), LOWER({})) > 0",
+            bind(value.clone())
+        ),
+        FilterTerm::Tag(value) => format!(
+            "r.metadata @> jsonb_build_object('tags', jsonb_build_array({}))",
+            bind(value.clone())
+        ),
+        FilterTerm::Kind(kind) => format!("r.kind = '{kind}'"),
+        FilterTerm::Family(family) => format!("r.media_family = '{family}'"),
+        FilterTerm::Is("favorite") => "r.is_favorite".to_string(),
+        FilterTerm::Is(visibility) => format!("r.visibility = '{visibility}'"),
+        FilterTerm::Has("attachment") => "EXISTS (SELECT 1 FROM resources a \
+             WHERE a.owner_note_id = r.id AND a.deleted_at IS NULL)"
+            .to_string(),
+        FilterTerm::Has("links") => {
+            "EXISTS (SELECT 1 FROM resource_links l WHERE l.source_id = r.id)".to_string()
+        }
+        FilterTerm::Has(_) => {
+            "jsonb_typeof(r.metadata->'tags') = 'array' AND r.metadata->'tags' <> '[]'::JSONB"
+                .to_string()
+        }
+        FilterTerm::Created(bound) => time_predicate("r.created_at", bound, &mut bind),
+        FilterTerm::Updated(bound) => time_predicate("r.updated_at", bound, &mut bind),
+    }
+}
+
+/// Dates compare whole UTC days; ages compare against `NOW()` minus the age,
+/// so `<7d` keeps timestamps newer than seven days ago. The day after
+/// 9999-12-31 is past what Postgres accepts, so bounds that would need it
+/// are left open instead.
+fn time_predicate(
+    column: &str,
+    bound: &TimeBound,
+    bind: &mut impl FnMut(String) -> String,
+) -> String {
+    match bound.value {
+        TimeValue::Date(date) => {
+            let next = date
+                .checked_add_days(Days::new(1))
+                .filter(|next| next.year() <= 9999);
+            let mut day = |day: NaiveDate| format!("{}::TIMESTAMPTZ", bind(midnight(day)));
+            match (bound.op, next) {
+                (Comparison::Lt, _) => format!("{column} < {}", day(date)),
+                (Comparison::Le, Some(next)) => format!("{column} < {}", day(next)),
+                (Comparison::Le, None) => format!("{column} IS NOT NULL"),
+                (Comparison::Eq, Some(next)) => {
+                    let start = day(date);
+                    format!("{column} >= {start} AND {column} < {}", day(next))
+                }
+                (Comparison::Eq, None) | (Comparison::Ge, _) => {
+                    format!("{column} >= {}", day(date))
+                }
+                (Comparison::Gt, Some(next)) => format!("{column} >= {}", day(next)),
+                (Comparison::Gt, None) => "FALSE".to_string(),
+            }
+        }
+        TimeValue::Age { amount, unit } => {
+            let threshold = format!("NOW() - {}::INTERVAL", bind(interval(amount, unit)));
+            let op = match bound.op {
+                Comparison::Lt => ">",
+                Comparison::Le => ">=",
+                Comparison::Ge => "<=",
+                Comparison::Gt | Comparison::Eq => "<",
+            };
+            format!("{column} {op} {threshold}")
+        }
+    }
+}
+
+fn midnight(date: NaiveDate) -> String {
+    format!("{date}T00:00:00Z")
+}
+
+fn interval(amount: u32, unit: char) -> String {
+    let unit = match unit {
+        'h' => "hours",
+        'w' => "weeks",
+        'y' => "years",
+        _ => "days",
+    };
+    format!("{amount} {unit}")
+}
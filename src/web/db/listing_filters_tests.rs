@@ -0,0 +1,29 @@
+use super::listing_filters::search_filters;
+use crate::core::parse_search_query;
+
+fn compile(input: &str) -> (String, Vec<String>) {
+    search_filters(&parse_search_query(input).filters, 3)
+}
+
+#[test]
+fn dates_compile_to_bound_day_ranges() {
+    let (sql, args) = compile("created:2026-01-31");
+    assert!(sql.contains("r.created_at >= ($3::TEXT[])[1]::TIMESTAMPTZ"));
+    assert!(sql.contains("r.created_at < ($3::TEXT[])[2]::TIMESTAMPTZ"));
+    assert_eq!(args, ["2026-01-31T00:00:00Z", "2026-02-01T00:00:00Z"]);
+}
+
+#[test]
+fn the_last_representable_day_leaves_the_upper_bound_open() {
+    let (sql, args) = compile("created:<=9999-12-31");
+    assert!(sql.contains("COALESCE((r.created_at IS NOT NULL), FALSE)"));
+    assert!(args.is_empty());
+
+    let (sql, args) = compile("created:9999-12-31");
+    assert!(sql.contains("COALESCE((r.created_at >= ($3::TEXT[])[1]::TIMESTAMPTZ), FALSE)"));
+    assert_eq!(args, ["9999-12-31T00:00:00Z"]);
+
+    let (sql, args) = compile("updated:>9999-12-31");
+    assert!(sql.contains("COALESCE((FALSE), FALSE)"));
+    assert!(args.is_empty());
+}
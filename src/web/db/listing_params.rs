@@ -1,7 +1,40 @@
+use super::listing_filters::search_filters;
 use super::listing_queries::ListingQuery;
+use crate::core::normalize_tag;
+use serde_json::{json, Map, Value};
+
+/// Metadata conditions bound as `$tag` (containment document) and `$meta`
+/// (object of required key/value pairs; list fields match any element).
+pub(super) fn metadata_filter(tag: usize, meta: usize) -> String {
+    format!(
+        "AND (${tag}::JSONB IS NULL OR r.metadata @> ${tag}::JSONB) \
+         AND (${meta}::JSONB IS NULL OR NOT EXISTS (SELECT 1 FROM jsonb_each_text(${meta}::JSONB) f \
+          WHERE NOT COALESCE((r.metadata->>f.key) = f.value OR (jsonb_typeof(r.metadata->f.key) = 'array' \
+          AND EXISTS (SELECT 1 FROM jsonb_array_elements_text(r.metadata->f.key) v WHERE v = f.value)), FALSE)))"
+    )
+}
+
+fn tag_document(tag: Option<&str>) -> Option<Value> {
+    let tag = tag.map(str::trim).filter(|tag| !tag.is_empty())?;
+    let tag = normalize_tag(tag).unwrap_or_else(|| tag.to_lowercase());
+    Some(json!({ "tags": [tag] }))
+}
+
+fn meta_document(meta: &[(String, String)]) -> Option<Value> {
+    let fields: Map<String, Value> = meta
+        .iter()
+        .map(|(key, value)| {
+            (
+                key.trim().to_lowercase(),
+                Value::String(value.trim().to_string()),
+            )
+        })
+        .collect();
+    (!fields.is_empty()).then_some(Value::Object(fields))
+}
 
 pub(super) struct BrowseParams<'a> {
-    include_private: bool,
+    max_visibility: &'static str,
     space_slug: Option<&'a str>,
     updated_at: Option<chrono::DateTime<chrono::Utc>>,
     created_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -12,13 +45,17 @@ pub(super) struct BrowseParams<'a> {
     favorite_position: Option<i64>,
     popular_views: Option<i64>,
     view_count_total: Option<i64>,
+    semantic: Option<f64>,
     limit: i64,
+    tag: Option<Value>,
+    meta: Option<Value>,
+    filters: Vec<String>,
 }
 
 impl<'a> BrowseParams<'a> {
     pub(super) fn new(request: &'a ListingQuery<'a>) -> Self {
         Self {
-            include_private: request.include_private,
+            max_visibility: request.max_visibility.as_str(),
             space_slug: request.space_slug,
             updated_at: request.cursor.and_then(|item| item.updated_at),
             created_at: request.cursor.and_then(|item| item.created_at),
@@ -29,13 +66,17 @@ impl<'a> BrowseParams<'a> {
             favorite_position: request.cursor.and_then(|item| item.favorite_position),
             popular_views: request.cursor.and_then(|item| item.popular_views),
             view_count_total: request.cursor.and_then(|item| item.view_count_total),
+            semantic: request.cursor.and_then(|item| item.semantic),
             limit: request.limit + 1,
+            tag: tag_document(request.tag),
+            meta: meta_document(request.meta),
+            filters: search_filters(request.filters, 0).1,
         }
     }
 
-    pub(super) fn refs(&'a self) -> [&'a (dyn tokio_postgres::types::ToSql + Sync); 12] {
+    pub(super) fn refs(&'a self) -> [&'a (dyn tokio_postgres::types::ToSql + Sync); 16] {
         [
-            &self.include_private,
+            &self.max_visibility,
             &self.updated_at,
             &self.created_at,
             &self.title_key,
@@ -45,8 +86,12 @@ impl<'a> BrowseParams<'a> {
             &self.favorite_position,
             &self.popular_views,
             &self.view_count_total,
+            &self.semantic,
             &self.limit,
             &self.space_slug,
+            &self.tag,
+            &self.meta,
+            &self.filters,
         ]
     }
 }
@@ -54,6 +99,9 @@ impl<'a> BrowseParams<'a> {
 pub(super) struct SearchParams<'a> {
     query: &'a str,
     browse: BrowseParams<'a>,
+    embedding: Option<&'a [f32]>,
+    model: Option<&'a str>,
+    min_similarity: Option<f64>,
 }
 
 impl<'a> SearchParams<'a> {
@@ -61,12 +109,15 @@ impl<'a> SearchParams<'a> {
         Self {
             query,
             browse: BrowseParams::new(request),
+            embedding: request.query_embedding.map(|item| item.vector.as_slice()),
+            model: request.query_embedding.map(|item| item.model.as_str()),
+            min_similarity: request.query_embedding.map(|item| item.min_similarity),
         }
     }
 
-    pub(super) fn refs(&'a self) -> [&'a (dyn tokio_postgres::types::ToSql + Sync); 13] {
+    pub(super) fn refs(&'a self) -> [&'a (dyn tokio_postgres::types::ToSql + Sync); 20] {
         [
-            &self.browse.include_private,
+            &self.browse.max_visibility,
             &self.query,
             &self.browse.updated_at,
             &self.browse.created_at,
@@ -77,8 +128,15 @@ impl<'a> SearchParams<'a> {
             &self.browse.favorite_position,
             &self.browse.popular_views,
             &self.browse.view_count_total,
+            &self.browse.semantic,
             &self.browse.limit,
             &self.browse.space_slug,
+            &self.browse.tag,
+            &self.browse.meta,
+            &self.embedding,
+            &self.model,
+            &self.min_similarity,
+            &self.browse.filters,
         ]
     }
 }
@@ -1,13 +1,22 @@
 use super::listing::{ListDirection, ListPage, ListSort};
 use super::listing_cursor::{page_from_rows, Cursor, PageCursorContext};
-use super::listing_params::{BrowseParams, SearchParams};
+use super::listing_filters::search_filters;
+use super::listing_params::{metadata_filter, BrowseParams, SearchParams};
 use super::listing_row::row_to_listed_resource;
-use super::{DbPool, ListKind, ListScope, ListedResource, PopularWindow};
+use super::{
+    DbPool, ListKind, ListScope, ListedResource, PopularWindow, QueryEmbedding, Visibility,
+};
+use crate::core::SearchFilter;
 use crate::error::AppError;
 
+/// Share of the hybrid score from lexical rank, squashed into `0..1`, and
+/// from cosine similarity to the query embedding.
+const LEXICAL_WEIGHT: f64 = 0.4;
+const SEMANTIC_WEIGHT: f64 = 0.6;
+
 pub(super) struct ListingQuery<'a> {
     pub(super) space_slug: Option<&'a str>,
-    pub(super) include_private: bool,
+    pub(super) max_visibility: Visibility,
     pub(super) limit: i64,
     pub(super) query: Option<&'a str>,
     pub(super) direction: &'a ListDirection,
@@ -16,6 +25,10 @@ pub(super) struct ListingQuery<'a> {
     pub(super) sort: &'a ListSort,
     pub(super) popular_window: PopularWindow,
     pub(super) cursor: Option<&'a Cursor>,
+    pub(super) tag: Option<&'a str>,
+    pub(super) meta: &'a [(String, String)],
+    pub(super) filters: &'a [SearchFilter],
+    pub(super) query_embedding: Option<&'a QueryEmbedding>,
 }
 
 pub(super) async fn browse_resources(
@@ -35,15 +48,18 @@ pub(super) async fn browse_resources(
          listed AS (SELECT r.id, (SELECT slug::TEXT FROM spaces WHERE id = r.space_id) AS space_slug, \
          r.kind, r.alias, r.title, r.summary, r.body, r.media_family, r.file_key, \
          r.content_type, r.byte_size, r.sha256_hex, r.original_filename, r.width, r.height, \
-         r.duration_ms, r.media_variants, r.is_favorite, r.favorite_position, (r.visibility = 'private') AS is_private, r.view_count_total, \
+         r.duration_ms, r.media_variants, r.media_details, r.is_favorite, r.favorite_position, r.visibility::TEXT AS visibility, r.view_count_total, \
          r.owner_note_id, \
          r.last_viewed_at, r.created_at, r.updated_at, r.summary AS preview, COALESCE(p.popular_views, 0)::BIGINT AS popular_views, \
-         LOWER(r.title) AS title_key, 0::DOUBLE PRECISION AS rank, 0::DOUBLE PRECISION AS fuzzy \
+         LOWER(r.title) AS title_key, 0::DOUBLE PRECISION AS rank, 0::DOUBLE PRECISION AS fuzzy, \
+         0::DOUBLE PRECISION AS semantic \
          FROM resources r LEFT JOIN popular p ON p.resource_id = r.id \
-         WHERE r.deleted_at IS NULL AND ($1 OR r.visibility = 'public') \
-         AND ($12::TEXT IS NULL OR r.space_id = (SELECT id FROM spaces WHERE slug = $12::CITEXT)) \
-         {favorite_filter} {kind_filter}) \
-         SELECT * FROM listed WHERE {} AND {} ORDER BY {} LIMIT $11",
+         WHERE r.deleted_at IS NULL AND r.visibility <= $1::TEXT::resource_visibility \
+         AND ($13::TEXT IS NULL OR r.space_id = (SELECT id FROM spaces WHERE slug = $13::CITEXT)) \
+         {favorite_filter} {kind_filter} {}{}) \
+         SELECT * FROM listed WHERE {} AND {} ORDER BY {} LIMIT $12",
+        metadata_filter(14, 15),
+        search_filters(request.filters, 16).0,
         request.sort.binding_clause(2),
         request.sort.cursor_filter(request.direction, 2),
         request.sort.order_clause(request.direction)
@@ -70,28 +86,36 @@ pub(super) async fn search_resources(
     let params = SearchParams::new(request, query);
     let popular = popular_cte(request.popular_window);
     let sql = format!(
-        "WITH q AS (SELECT websearch_to_tsquery('simple', $2) AS tsq, $2::TEXT AS raw), \
+        "WITH lang AS (SELECT COALESCE(MAX(search_language), 'simple')::REGCONFIG AS config, COALESCE(BOOL_OR(search_cjk), FALSE) AS cjk \
+          FROM space_settings WHERE space_id = COALESCE((SELECT id FROM spaces WHERE slug = $14::CITEXT), default_space_id())), \
+         q AS (SELECT websearch_to_tsquery(config, cjk_search_text($2, cjk, FALSE)) AS tsq, $2::TEXT AS raw, config FROM lang), \
          popular AS ({popular}), \
          matched AS (SELECT r.id, (SELECT slug::TEXT FROM spaces WHERE id = r.space_id) AS space_slug, \
          r.kind, r.alias, r.title, r.summary, r.body, r.media_family, r.file_key, \
-         r.content_type, r.byte_size, r.sha256_hex, r.original_filename, r.width, r.height, r.duration_ms, r.media_variants, \
-         r.is_favorite, r.favorite_position, (r.visibility = 'private') AS is_private, r.view_count_total, r.last_viewed_at, r.created_at, r.updated_at, \
+         r.content_type, r.byte_size, r.sha256_hex, r.original_filename, r.width, r.height, r.duration_ms, r.media_variants, r.media_details, \
+         r.is_favorite, r.favorite_position, r.visibility::TEXT AS visibility, r.view_count_total, r.last_viewed_at, r.created_at, r.updated_at, \
          r.owner_note_id, \
-         COALESCE(NULLIF(TRIM(ts_headline('simple', body, (SELECT tsq FROM q), 'StartSel=,StopSel=,MaxWords=18,MinWords=8,ShortWord=2,FragmentDelimiter= ... ')), ''), summary) AS preview, \
+         COALESCE(NULLIF(TRIM(ts_headline((SELECT config FROM q), body, (SELECT tsq FROM q), 'StartSel=,StopSel=,MaxWords=18,MinWords=8,ShortWord=2,FragmentDelimiter= ... ')), ''), summary) AS preview, \
          COALESCE(p.popular_views, 0)::BIGINT AS popular_views, LOWER(r.title) AS title_key, \
          ts_rank_cd(r.search_document, (SELECT tsq FROM q))::DOUBLE PRECISION AS rank, \
          GREATEST(similarity(COALESCE(r.alias, ''), (SELECT raw FROM q)), similarity(r.title, (SELECT raw FROM q)), \
-         similarity(r.body, (SELECT raw FROM q)), similarity(COALESCE(r.original_filename, ''), (SELECT raw FROM q)))::DOUBLE PRECISION AS fuzzy \
+         similarity(r.body, (SELECT raw FROM q)), similarity(COALESCE(r.original_filename, ''), (SELECT raw FROM q)))::DOUBLE PRECISION AS fuzzy, \
+         COALESCE(CASE WHEN r.embedding_model = $18::TEXT THEN cosine_similarity(r.embedding, $17::REAL[]) END, 0)::DOUBLE PRECISION AS similarity \
          FROM resources r LEFT JOIN popular p ON p.resource_id = r.id \
-         WHERE r.deleted_at IS NULL AND ($1 OR r.visibility = 'public') \
-         AND ($13::TEXT IS NULL OR r.space_id = (SELECT id FROM spaces WHERE slug = $13::CITEXT)) \
-         {favorite_filter} {kind_filter} \
+         WHERE r.deleted_at IS NULL AND r.visibility <= $1::TEXT::resource_visibility \
+         AND ($14::TEXT IS NULL OR r.space_id = (SELECT id FROM spaces WHERE slug = $14::CITEXT)) \
+         {favorite_filter} {kind_filter} {}{} \
          AND (r.search_document @@ (SELECT tsq FROM q) OR r.alias ILIKE '%' || (SELECT raw FROM q) || '%' \
          OR r.title ILIKE '%' || (SELECT raw FROM q) || '%' OR r.body ILIKE '%' || (SELECT raw FROM q) || '%' \
          OR COALESCE(r.original_filename, '') ILIKE '%' || (SELECT raw FROM q) || '%' \
          OR similarity(COALESCE(r.alias, ''), (SELECT raw FROM q)) >= 0.15 \
-         OR similarity(r.title, (SELECT raw FROM q)) >= 0.15 OR similarity(r.body, (SELECT raw FROM q)) >= 0.05)) \
-         SELECT * FROM matched WHERE {} AND {} ORDER BY {} LIMIT $12",
+         OR similarity(r.title, (SELECT raw FROM q)) >= 0.15 OR similarity(r.body, (SELECT raw FROM q)) >= 0.05 \
+         OR (r.embedding_model = $18::TEXT AND cosine_similarity(r.embedding, $17::REAL[]) >= $19::DOUBLE PRECISION))), \
+         scored AS (SELECT *, ({LEXICAL_WEIGHT} * GREATEST(rank / (rank + 1), fuzzy) \
+          + {SEMANTIC_WEIGHT} * GREATEST(similarity, 0))::DOUBLE PRECISION AS semantic FROM matched) \
+         SELECT * FROM scored WHERE {} AND {} ORDER BY {} LIMIT $13",
+        metadata_filter(15, 16),
+        search_filters(request.filters, 20).0,
         request.sort.binding_clause(3),
         request.sort.cursor_filter(request.direction, 3),
         request.sort.order_clause(request.direction)
@@ -107,7 +131,7 @@ pub(super) async fn search_resources(
 pub(super) async fn top_resources(
     pool: &DbPool,
     space_slug: Option<&str>,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
     favorites_only: bool,
 ) -> Result<Vec<ListedResource>, AppError> {
@@ -122,15 +146,15 @@ pub(super) async fn top_resources(
     let sql = format!(
         "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = space_id) AS space_slug, \
          kind, alias, title, summary, body, media_family, file_key, content_type, byte_size, \
-         sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, \
-         (visibility = 'private') AS is_private, view_count_total, last_viewed_at, created_at, updated_at, summary AS preview, NULL::BIGINT AS popular_views \
-         FROM resources WHERE deleted_at IS NULL AND ($1 OR visibility = 'public') \
+         sha256_hex, original_filename, width, height, duration_ms, media_variants, media_details, owner_note_id, is_favorite, favorite_position, \
+         visibility::TEXT AS visibility, view_count_total, last_viewed_at, created_at, updated_at, summary AS preview, NULL::BIGINT AS popular_views \
+         FROM resources WHERE deleted_at IS NULL AND visibility <= $1::TEXT::resource_visibility \
          AND ($3::TEXT IS NULL OR space_id = (SELECT id FROM spaces WHERE slug = $3::CITEXT)) \
          {filter} ORDER BY {order} LIMIT $2"
     );
     client(pool)
         .await?
-        .query(&sql, &[&include_private, &limit, &space_slug])
+        .query(&sql, &[&max_visibility.as_str(), &limit, &space_slug])
         .await
         .map(|rows| rows.into_iter().map(row_to_listed_resource).collect())
         .map_err(db_err)
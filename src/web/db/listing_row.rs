@@ -1,6 +1,6 @@
 use super::models::{MediaFamily, ResourceKind};
-use super::{ListedResource, Resource};
-use crate::media::media_variants_from_json;
+use super::{ListedResource, Resource, Visibility};
+use crate::media::{media_details_from_json, media_variants_from_json};
 
 pub(crate) fn row_to_listed_resource(row: tokio_postgres::Row) -> ListedResource {
     ListedResource {
@@ -22,10 +22,11 @@ pub(crate) fn row_to_listed_resource(row: tokio_postgres::Row) -> ListedResource
             height: row.get("height"),
             duration_ms: row.get("duration_ms"),
             media_variants: media_variants_from_json(row.get("media_variants")),
+            media_details: media_details_from_json(row.get("media_details")),
             owner_note_id: row.get("owner_note_id"),
             is_favorite: row.get("is_favorite"),
             favorite_position: row.get("favorite_position"),
-            is_private: row.get("is_private"),
+            visibility: Visibility::from_db(row.get("visibility")),
             view_count_total: row.get("view_count_total"),
             last_viewed_at: row.get("last_viewed_at"),
             created_at: row.get("created_at"),
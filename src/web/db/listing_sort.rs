@@ -2,6 +2,8 @@ use super::list_scope::ListScope;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ListSort {
     Relevance,
+    /// Hybrid ranking fusing lexical rank with embedding similarity.
+    Semantic,
     UpdatedDesc,
     UpdatedAsc,
     CreatedDesc,
@@ -34,6 +36,7 @@ impl ListSort {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Relevance => "relevance",
+            Self::Semantic => "semantic",
             Self::UpdatedDesc => "updated_desc",
             Self::UpdatedAsc => "updated_asc",
             Self::CreatedDesc => "created_desc",
@@ -63,6 +66,7 @@ impl ListSort {
     fn parse(value: &str) -> Option<Self> {
         match value {
             "relevance" => Some(Self::Relevance),
+            "semantic" => Some(Self::Semantic),
             "updated_desc" => Some(Self::UpdatedDesc),
             "updated_asc" => Some(Self::UpdatedAsc),
             "created_desc" => Some(Self::CreatedDesc),
@@ -10,6 +10,8 @@ impl ListSort {
             (Self::Relevance, ListDirection::Prev) => {
                 "rank ASC, fuzzy ASC, updated_at ASC, id DESC"
             }
+            (Self::Semantic, ListDirection::Next) => "semantic DESC, updated_at DESC, id ASC",
+            (Self::Semantic, ListDirection::Prev) => "semantic ASC, updated_at ASC, id DESC",
             (Self::UpdatedDesc, ListDirection::Next) => "updated_at DESC, id ASC",
             (Self::UpdatedDesc, ListDirection::Prev) => "updated_at ASC, id DESC",
             (Self::UpdatedAsc, ListDirection::Next) => "updated_at ASC, id ASC",
@@ -48,7 +50,8 @@ impl ListSort {
     }
 
     pub fn cursor_filter(&self, direction: &ListDirection, base: i32) -> String {
-        let (updated, created, title, rank, fuzzy, id, favorite, popular, total) = offsets(base);
+        let (updated, created, title, rank, fuzzy, id, favorite, popular, total, semantic) =
+            offsets(base);
         match (self, direction) {
             (Self::Relevance, ListDirection::Next) => format!(
                 "(${rank}::DOUBLE PRECISION IS NULL OR rank < ${rank} \
@@ -62,6 +65,16 @@ impl ListSort {
                  OR (rank = ${rank} AND fuzzy = ${fuzzy} AND updated_at > ${updated}) \
                  OR (rank = ${rank} AND fuzzy = ${fuzzy} AND updated_at = ${updated} AND id < ${id}))"
             ),
+            (Self::Semantic, ListDirection::Next) => format!(
+                "(${semantic}::DOUBLE PRECISION IS NULL OR semantic < ${semantic} \
+                 OR (semantic = ${semantic} AND updated_at < ${updated}) \
+                 OR (semantic = ${semantic} AND updated_at = ${updated} AND id > ${id}))"
+            ),
+            (Self::Semantic, ListDirection::Prev) => format!(
+                "(${semantic}::DOUBLE PRECISION IS NULL OR semantic > ${semantic} \
+                 OR (semantic = ${semantic} AND updated_at > ${updated}) \
+                 OR (semantic = ${semantic} AND updated_at = ${updated} AND id < ${id}))"
+            ),
             (Self::UpdatedDesc, ListDirection::Next) => format!(
                 "(${updated}::TIMESTAMPTZ IS NULL OR updated_at < ${updated} OR (updated_at = ${updated} AND id > ${id}))"
             ),
@@ -120,7 +133,8 @@ impl ListSort {
     }
 
     pub fn binding_clause(&self, base: i32) -> String {
-        let (updated, created, title, rank, fuzzy, id, favorite, popular, total) = offsets(base);
+        let (updated, created, title, rank, fuzzy, id, favorite, popular, total, semantic) =
+            offsets(base);
         format!(
             "(${updated}::TIMESTAMPTZ IS NULL OR ${updated}::TIMESTAMPTZ IS NOT NULL) \
              AND (${created}::TIMESTAMPTZ IS NULL OR ${created}::TIMESTAMPTZ IS NOT NULL) \
@@ -130,12 +144,14 @@ impl ListSort {
              AND (${id}::TEXT IS NULL OR ${id}::TEXT IS NOT NULL) \
              AND (${favorite}::BIGINT IS NULL OR ${favorite}::BIGINT IS NOT NULL) \
              AND (${popular}::BIGINT IS NULL OR ${popular}::BIGINT IS NOT NULL) \
-             AND (${total}::BIGINT IS NULL OR ${total}::BIGINT IS NOT NULL)"
+             AND (${total}::BIGINT IS NULL OR ${total}::BIGINT IS NOT NULL) \
+             AND (${semantic}::DOUBLE PRECISION IS NULL OR ${semantic}::DOUBLE PRECISION IS NOT NULL)"
         )
     }
 }
 
-fn offsets(base: i32) -> (i32, i32, i32, i32, i32, i32, i32, i32, i32) {
+#[allow(clippy::type_complexity)]
+fn offsets(base: i32) -> (i32, i32, i32, i32, i32, i32, i32, i32, i32, i32) {
     (
         base,
         base + 1,
@@ -146,5 +162,6 @@ fn offsets(base: i32) -> (i32, i32, i32, i32, i32, i32, i32, i32, i32) {
         base + 6,
         base + 7,
         base + 8,
+        base + 9,
     )
 }
@@ -1,10 +1,13 @@
+use super::audit::audit_resource;
+use super::jobs::enqueue_media_derivatives;
 use super::models::{MediaFamily, Resource, ResourceKind};
 use super::resource_support::{map_write_error, next_position, row_to_resource, RETURNING_RECORD};
+use super::write_actor::{stamp_write_actor, WriteActor};
 use super::write_support::create_snapshot;
-use super::DbPool;
+use super::{DbPool, Visibility};
 use crate::core::{derive_summary, derive_title_with_fallback};
 use crate::error::AppError;
-use crate::media::{media_variants_to_json, MediaVariants};
+use crate::media::{media_details_to_json, media_variants_to_json, MediaDetails, MediaVariants};
 
 pub struct MediaBlob<'a> {
     pub media_family: MediaFamily,
@@ -16,6 +19,8 @@ pub struct MediaBlob<'a> {
     pub width: Option<i32>,
     pub height: Option<i32>,
     pub duration_ms: Option<i64>,
+    pub media_details: Option<MediaDetails>,
+    /// Derivatives reused from an identical earlier upload; skips the derivative job.
     pub media_variants: Option<MediaVariants>,
 }
 
@@ -26,7 +31,8 @@ pub struct ScopedMediaCreate<'a> {
     pub body: &'a str,
     pub blob: &'a MediaBlob<'a>,
     pub is_favorite: bool,
-    pub is_private: bool,
+    pub visibility: Visibility,
+    pub actor: &'a WriteActor,
 }
 
 pub async fn create_media(
@@ -36,21 +42,22 @@ pub async fn create_media(
     body: &str,
     blob: &MediaBlob<'_>,
     is_favorite: bool,
-    is_private: bool,
+    visibility: Visibility,
 ) -> Result<Resource, AppError> {
     let mut db = client(pool).await?;
     let tx = db
         .transaction()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let media_details = media_details_to_json(&blob.media_details);
     let media_variants = media_variants_to_json(&blob.media_variants);
     let row = tx
         .query_one(
             &format!(
                 "INSERT INTO resources (id, space_id, kind, alias, title, summary, body, media_family, file_key, content_type, \
-                 byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, visibility) \
-                 VALUES ($1, default_space_id(), $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, NULL, $17, $18, \
-                 CASE WHEN $19 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END) {RETURNING_RECORD}"
+                 byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, visibility, media_details) \
+                 VALUES ($1, default_space_id(), $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $20, NULL, $16, $17, \
+                 $18::TEXT::resource_visibility, $19) {RETURNING_RECORD}"
             ),
             &[
                 &id,
@@ -68,16 +75,18 @@ pub async fn create_media(
                 &blob.width,
                 &blob.height,
                 &blob.duration_ms,
-                &media_variants,
                 &is_favorite,
                 &next_position(&tx, is_favorite).await?,
-                &is_private,
+                &visibility.as_str(),
+                &media_details,
+                &media_variants,
             ],
         )
         .await
         .map_err(map_write_error)?;
     let resource = row_to_resource(row);
     create_snapshot(&tx, &resource, 1).await?;
+    enqueue_media_derivatives(&tx, &resource).await?;
     tx.commit()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -93,14 +102,15 @@ pub async fn create_media_in_space(
         .transaction()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let media_details = media_details_to_json(&input.blob.media_details);
     let media_variants = media_variants_to_json(&input.blob.media_variants);
     let row = tx
         .query_one(
             &format!(
                 "INSERT INTO resources (id, space_id, kind, alias, title, summary, body, media_family, file_key, content_type, \
-                 byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, visibility) \
-                 SELECT $1, id, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, NULL, $18, $19, \
-                 CASE WHEN $20 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END \
+                 byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, visibility, media_details) \
+                 SELECT $1, id, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $21, NULL, $17, $18, \
+                 $19::TEXT::resource_visibility, $20 \
                  FROM spaces WHERE slug = $2::CITEXT {RETURNING_RECORD}"
             ),
             &[
@@ -109,21 +119,76 @@ pub async fn create_media_in_space(
                 &derive_summary(input.body), &input.body, &input.blob.media_family.as_str(),
                 &input.blob.file_key, &input.blob.content_type, &input.blob.byte_size,
                 &input.blob.sha256_hex, &input.blob.original_filename, &input.blob.width,
-                &input.blob.height, &input.blob.duration_ms, &media_variants,
+                &input.blob.height, &input.blob.duration_ms,
                 &input.is_favorite, &next_position(&tx, input.is_favorite).await?,
-                &input.is_private,
+                &input.visibility.as_str(), &media_details, &media_variants,
             ],
         )
         .await
         .map_err(map_write_error)?;
     let resource = row_to_resource(row);
     create_snapshot(&tx, &resource, 1).await?;
+    enqueue_media_derivatives(&tx, &resource).await?;
+    stamp_write_actor(&tx, &resource.id, input.actor, true).await?;
+    audit_resource(
+        &tx,
+        input.space_slug,
+        input.actor,
+        "media.created",
+        &resource,
+    )
+    .await?;
     tx.commit()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     Ok(resource)
 }
 
+/// Point a media resource and its snapshots at freshly generated derivatives,
+/// but only while they still reference the same original object.
+pub async fn update_media_variants(
+    pool: &DbPool,
+    id: &str,
+    file_key: &str,
+    variants: &MediaVariants,
+) -> Result<(), AppError> {
+    let media_variants = media_variants_to_json(&Some(variants.clone()));
+    client(pool)
+        .await?
+        .execute(
+            "WITH live AS (UPDATE resources SET media_variants = $3 \
+              WHERE id = $1 AND file_key = $2) \
+             UPDATE resource_snapshots SET media_variants = $3 \
+             WHERE resource_id = $1 AND file_key = $2",
+            &[&id, &file_key, &media_variants],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Replace probed details, e.g. capture metadata found by a derivative rerun.
+pub async fn update_media_details(
+    pool: &DbPool,
+    id: &str,
+    file_key: &str,
+    details: &Option<MediaDetails>,
+) -> Result<(), AppError> {
+    let media_details = media_details_to_json(details);
+    client(pool)
+        .await?
+        .execute(
+            "WITH live AS (UPDATE resources SET media_details = $3 \
+              WHERE id = $1 AND file_key = $2) \
+             UPDATE resource_snapshots SET media_details = $3 \
+             WHERE resource_id = $1 AND file_key = $2",
+            &[&id, &file_key, &media_details],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
 async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError> {
     pool.get()
         .await
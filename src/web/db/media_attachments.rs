@@ -1,14 +1,17 @@
-use super::media::MediaBlob;
+use super::audit::{write_audit_event, AuditEvent};
+use super::jobs::enqueue_media_derivatives;
 use super::models::{Resource, ResourceKind};
 use super::resource_support::{
     map_write_error, resolve_position, row_to_resource, RETURNING_RECORD,
 };
+use super::write_actor::{stamp_write_actor, WriteActor};
 use super::write_support::{client, create_snapshot, next_snapshot_number};
-use super::{DbPool, MediaFamily};
+use super::{DbPool, MediaFamily, Visibility};
 use crate::core::{derive_summary, derive_title, derive_title_with_fallback};
 use crate::error::AppError;
-use crate::media::{media_variants_to_json, GeneratedVariant};
+use crate::media::{media_details_to_json, media_variants_to_json, MediaProbe, MediaVariants};
 use deadpool_postgres::GenericClient;
+use serde_json::json;
 
 pub struct AttachmentCreate {
     pub media_id: String,
@@ -19,12 +22,13 @@ pub struct AttachmentCreate {
     pub byte_size: i64,
     pub sha256_hex: String,
     pub original_filename: String,
-    pub media_variants: Option<crate::media::MediaVariants>,
-    pub generated_variants: Vec<GeneratedVariant>,
+    pub probe: MediaProbe,
+    /// Derivatives reused from an identical earlier upload.
+    pub media_variants: Option<MediaVariants>,
 }
 
 #[rustfmt::skip]
-pub struct NoteAttachmentUpdate<'a> { pub body: &'a str, pub alias: Option<&'a str>, pub is_favorite: bool, pub is_private: bool }
+pub struct NoteAttachmentUpdate<'a> { pub space_slug: Option<&'a str>, pub body: &'a str, pub alias: Option<&'a str>, pub is_favorite: bool, pub visibility: Visibility, pub actor: &'a WriteActor }
 
 #[rustfmt::skip]
 pub struct AttachmentBatchResult { pub current_resource: Resource, pub created_media: Vec<Resource> }
@@ -52,9 +56,29 @@ pub async fn attach_media_to_note(
         ));
     }
     let created_media =
-        create_media_resources(&tx, note_id, attachments, update.is_private).await?;
+        create_media_resources(&tx, note_id, attachments, update.visibility).await?;
     let current_resource =
         update_target_note(&tx, note_id, update, was_favorite, current_position).await?;
+    for media in &created_media {
+        stamp_write_actor(&tx, &media.id, update.actor, true).await?;
+    }
+    stamp_write_actor(&tx, note_id, update.actor, false).await?;
+    let media_ids = created_media
+        .iter()
+        .map(|media| media.id.as_str())
+        .collect::<Vec<_>>();
+    write_audit_event(
+        &tx,
+        &AuditEvent {
+            space_slug: update.space_slug.or(Some(&current_resource.space_slug)),
+            actor: update.actor,
+            event_type: "media.attached",
+            entity_type: "resource",
+            entity_id: note_id,
+            payload: json!({ "media_ids": media_ids }),
+        },
+    )
+    .await?;
     tx.commit()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -91,30 +115,17 @@ async fn create_media_resources<C: GenericClient>(
     db: &C,
     note_id: &str,
     attachments: &[AttachmentCreate],
-    is_private: bool,
+    visibility: Visibility,
 ) -> Result<Vec<Resource>, AppError> {
     let mut resources = Vec::with_capacity(attachments.len());
     for attachment in attachments {
-        let blob = MediaBlob {
-            media_family: attachment.media_family,
-            file_key: &attachment.file_key,
-            content_type: &attachment.content_type,
-            byte_size: attachment.byte_size,
-            sha256_hex: &attachment.sha256_hex,
-            original_filename: &attachment.original_filename,
-            width: None,
-            height: None,
-            duration_ms: None,
-            media_variants: attachment.media_variants.clone(),
-        };
-        let media_variants = media_variants_to_json(&blob.media_variants);
         let row = db
             .query_one(
                 &format!(
                     "INSERT INTO resources (id, space_id, kind, title, summary, body, media_family, file_key, content_type, \
-                     byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, visibility) \
-                     VALUES ($1, (SELECT space_id FROM resources WHERE id = $13), $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NULL, NULL, NULL, $12, $13, FALSE, NULL, \
-                     CASE WHEN $14 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END) {RETURNING_RECORD}"
+                     byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, visibility, media_details) \
+                     VALUES ($1, (SELECT space_id FROM resources WHERE id = $12), $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $14, $15, $16, $18, $12, FALSE, NULL, \
+                     $13::TEXT::resource_visibility, $17) {RETURNING_RECORD}"
                 ),
                 &[
                     &attachment.media_id,
@@ -122,21 +133,26 @@ async fn create_media_resources<C: GenericClient>(
                     &derive_title_with_fallback(&attachment.media_body, "Untitled media"),
                     &derive_summary(&attachment.media_body),
                     &attachment.media_body,
-                    &blob.media_family.as_str(),
-                    &blob.file_key,
-                    &blob.content_type,
-                    &blob.byte_size,
-                    &blob.sha256_hex,
-                    &blob.original_filename,
-                    &media_variants,
+                    &attachment.media_family.as_str(),
+                    &attachment.file_key,
+                    &attachment.content_type,
+                    &attachment.byte_size,
+                    &attachment.sha256_hex,
+                    &attachment.original_filename,
                     &note_id,
-                    &is_private,
+                    &visibility.as_str(),
+                    &attachment.probe.width,
+                    &attachment.probe.height,
+                    &attachment.probe.duration_ms,
+                    &media_details_to_json(&attachment.probe.details),
+                    &media_variants_to_json(&attachment.media_variants),
                 ],
             )
             .await
             .map_err(map_write_error)?;
         let resource = row_to_resource(row);
         create_snapshot(db, &resource, 1).await?;
+        enqueue_media_derivatives(db, &resource).await?;
         resources.push(resource);
     }
     Ok(resources)
@@ -154,7 +170,7 @@ async fn update_target_note<C: GenericClient>(
             &format!(
                 "UPDATE resources SET alias = $2, title = $3, summary = $4, body = $5, \
                  is_favorite = $6, favorite_position = $7, \
-                 visibility = CASE WHEN $8 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END, \
+                 visibility = $8::TEXT::resource_visibility, \
                  updated_at = NOW() \
                  WHERE id = $1 AND deleted_at IS NULL {RETURNING_RECORD}"
             ),
@@ -166,7 +182,7 @@ async fn update_target_note<C: GenericClient>(
                 &update.body,
                 &update.is_favorite,
                 &resolve_position(db, was_favorite, current_position, update.is_favorite).await?,
-                &update.is_private,
+                &update.visibility.as_str(),
             ],
         )
         .await
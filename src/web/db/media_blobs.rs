@@ -0,0 +1,147 @@
+//! Content-addressed media objects shared across resources and snapshots
+//!
+//! `media_blobs.ref_count` is kept by triggers on `resources` and
+//! `resource_snapshots`, so every insert, purge, or cascade adjusts it.
+//! Uploads in flight add one pin each until they settle.
+
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use crate::media::{media_variants_from_json, MediaVariants};
+use deadpool_postgres::GenericClient;
+use std::future::Future;
+
+/// The stored object an upload points at, pinned by `pin_media_blob`.
+#[derive(Clone, Debug)]
+pub struct PinnedBlob {
+    pub file_key: String,
+    /// Derivatives of a reused object; `None` for a fresh key.
+    pub media_variants: Option<MediaVariants>,
+    /// An existing object was reused, so the caller need not upload one.
+    pub reused: bool,
+}
+
+/// Pin the object an upload will reference by counting it in `ref_count`
+/// until `settle_media_blob` releases it, so purge cleanup cannot delete it
+/// meanwhile. A referenced object with this content is reused under its row
+/// lock, preferring content-addressed keys; otherwise `fresh_key` is pinned.
+pub async fn pin_media_blob(
+    pool: &DbPool,
+    sha256_hex: &str,
+    byte_size: i64,
+    fresh_key: &str,
+) -> Result<PinnedBlob, AppError> {
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let reusable = tx
+        .query_opt(
+            "SELECT b.file_key, (SELECT media_variants FROM ( \
+              SELECT media_variants, updated_at FROM resources WHERE file_key = b.file_key \
+              UNION ALL SELECT media_variants, created_at FROM resource_snapshots \
+              WHERE file_key = b.file_key) refs \
+              WHERE media_variants IS NOT NULL ORDER BY updated_at DESC LIMIT 1) AS media_variants \
+             FROM media_blobs b WHERE b.sha256_hex = $1 AND b.ref_count > 0 \
+             ORDER BY b.file_key LIKE 'blobs/%' DESC, b.created_at LIMIT 1 FOR UPDATE OF b",
+            &[&sha256_hex],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let pinned = match reusable {
+        Some(row) => PinnedBlob {
+            file_key: row.get("file_key"),
+            media_variants: media_variants_from_json(row.get("media_variants")),
+            reused: true,
+        },
+        None => PinnedBlob {
+            file_key: fresh_key.to_string(),
+            media_variants: None,
+            reused: false,
+        },
+    };
+    tx.execute(
+        "INSERT INTO media_blobs (file_key, sha256_hex, byte_size, ref_count) \
+         VALUES ($1, $2, $3, 1) ON CONFLICT (file_key) DO UPDATE \
+         SET ref_count = media_blobs.ref_count + 1, updated_at = NOW()",
+        &[&pinned.file_key, &sha256_hex, &byte_size],
+    )
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(pinned)
+}
+
+/// Drop `released_pins` pins from `file_key`, then run `delete_object` and
+/// forget the row if nothing references the object any more. The row stays
+/// locked throughout, so a concurrent upload of the same content waits and
+/// then stores the object again. Returns whether the object was deleted.
+pub async fn settle_media_blob<F, Fut>(
+    pool: &DbPool,
+    file_key: &str,
+    released_pins: i32,
+    delete_object: F,
+) -> Result<bool, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let ref_count: i32 = tx
+        .query_one(
+            "INSERT INTO media_blobs (file_key, sha256_hex, byte_size, ref_count) \
+             VALUES ($1, '', 0, 0) ON CONFLICT (file_key) DO UPDATE \
+             SET ref_count = media_blobs.ref_count - $2, updated_at = NOW() \
+             RETURNING ref_count",
+            &[&file_key, &released_pins],
+        )
+        .await
+        .map(|row| row.get(0))
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let unreferenced = ref_count <= 0;
+    if unreferenced {
+        delete_object().await;
+        tx.execute("DELETE FROM media_blobs WHERE file_key = $1", &[&file_key])
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(unreferenced)
+}
+
+/// Oldest live media in the space with identical content; `None` space is the default one.
+pub async fn find_duplicate_media(
+    pool: &DbPool,
+    space_slug: Option<&str>,
+    sha256_hex: &str,
+) -> Result<Option<String>, AppError> {
+    client(pool)
+        .await?
+        .query_opt(
+            "SELECT id FROM resources WHERE kind = 'media' AND deleted_at IS NULL \
+             AND sha256_hex = $2 AND space_id = COALESCE( \
+              (SELECT id FROM spaces WHERE slug = $1::CITEXT), default_space_id()) \
+             ORDER BY created_at, id LIMIT 1",
+            &[&space_slug, &sha256_hex],
+        )
+        .await
+        .map(|row| row.map(|row| row.get("id")))
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Forget objects nothing references any more; their keys are purged from storage.
+pub(super) async fn prune_unreferenced_blobs<C: GenericClient>(db: &C) -> Result<(), AppError> {
+    db.execute("DELETE FROM media_blobs WHERE ref_count <= 0", &[])
+        .await
+        .map(|_| ())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
@@ -0,0 +1,116 @@
+//! Space member invites for existing and new local users
+
+use super::audit::write_audit_event;
+use super::auth::local_email;
+use super::memberships::{client, db_error, member_event};
+use super::password::hash_secret;
+use super::password_reset::new_token;
+use super::space_role::SpaceRole;
+use super::write_actor::WriteActor;
+use super::DbPool;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Invite request for one existing or new local user.
+pub struct MemberInvite<'a> {
+    pub login: &'a str,
+    pub role: SpaceRole,
+    pub actor: &'a WriteActor,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InvitedMember {
+    pub user_id: Uuid,
+    pub role: SpaceRole,
+    /// One-time password token for users created by this invite.
+    pub invite_token: Option<String>,
+}
+
+/// Add a member, creating an invited local user when the login is new.
+pub async fn invite_member(
+    pool: &DbPool,
+    space_slug: &str,
+    invite: &MemberInvite<'_>,
+) -> Result<InvitedMember, AppError> {
+    let mut db = client(pool).await?;
+    let tx = db.transaction().await.map_err(db_error)?;
+    let existing = tx
+        .query_opt(
+            "SELECT id FROM users WHERE username = $1::CITEXT OR email = $1::CITEXT",
+            &[&invite.login],
+        )
+        .await
+        .map_err(db_error)?;
+    let (user_id, invite_token) = match existing {
+        Some(row) => (row.get("id"), None),
+        None => {
+            let (user_id, token) = create_invited_user(&tx, invite.login).await?;
+            (user_id, Some(token))
+        }
+    };
+    let added = tx
+        .execute(
+            "INSERT INTO space_memberships (space_id, user_id, role) \
+             VALUES ((SELECT id FROM spaces WHERE slug = $1::CITEXT), $2, $3::TEXT::space_role) \
+             ON CONFLICT DO NOTHING",
+            &[&space_slug, &user_id, &invite.role.as_str()],
+        )
+        .await
+        .map_err(db_error)?;
+    if added == 0 {
+        return Err(AppError::InvalidRequest(format!(
+            "'{}' is already a member of this space",
+            invite.login
+        )));
+    }
+    write_audit_event(
+        &tx,
+        &member_event(
+            space_slug,
+            invite.actor,
+            "member.invited",
+            &user_id.to_string(),
+            invite.role,
+        ),
+    )
+    .await?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(InvitedMember {
+        user_id,
+        role: invite.role,
+        invite_token,
+    })
+}
+
+async fn create_invited_user<C: GenericClient>(
+    db: &C,
+    login: &str,
+) -> Result<(Uuid, String), AppError> {
+    let row = db
+        .query_one(
+            "INSERT INTO users (email, username, display_name, status) \
+             VALUES ($1, $2, $3, 'invited') RETURNING id",
+            &[&local_email(login), &login, &login],
+        )
+        .await
+        .map_err(db_error)?;
+    let user_id: Uuid = row.get("id");
+    // The invite token doubles as the first password-set token.
+    let token = new_token();
+    db.execute(
+        "INSERT INTO user_local_credentials (user_id, password_hash) VALUES ($1, $2)",
+        &[&user_id, &hash_secret(&new_token())?],
+    )
+    .await
+    .map_err(db_error)?;
+    db.execute(
+        "INSERT INTO password_reset_tokens (user_id, token_hash, expires_at) \
+         VALUES ($1, $2, NOW() + INTERVAL '7 days')",
+        &[&user_id, &hash_secret(&token)?],
+    )
+    .await
+    .map_err(db_error)?;
+    Ok((user_id, token))
+}
@@ -0,0 +1,151 @@
+//! Space membership lookups, invites, and role changes
+
+use super::audit::{audited_update, AuditEvent};
+use super::space_role::SpaceRole;
+use super::write_actor::WriteActor;
+use super::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SpaceMember {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub status: String,
+    pub role: SpaceRole,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Role of one active user in a space; `None` slug means the default space.
+pub async fn space_role(
+    pool: &DbPool,
+    space_slug: Option<&str>,
+    user_id: Uuid,
+) -> Result<Option<SpaceRole>, AppError> {
+    let row = client(pool)
+        .await?
+        .query_opt(
+            "SELECT m.role::TEXT AS role FROM space_memberships m \
+             JOIN users u ON u.id = m.user_id AND u.status = 'active' \
+             WHERE m.user_id = $1 AND m.space_id = CASE WHEN $2::TEXT IS NULL \
+             THEN default_space_id() ELSE (SELECT id FROM spaces WHERE slug = $2::CITEXT) END",
+            &[&user_id, &space_slug],
+        )
+        .await
+        .map_err(db_error)?;
+    Ok(row.and_then(|row| SpaceRole::parse(row.get("role"))))
+}
+
+pub async fn list_space_members(
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<Vec<SpaceMember>, AppError> {
+    let rows = client(pool)
+        .await?
+        .query(
+            "SELECT u.id, u.username::TEXT AS username, u.display_name, u.status, \
+             m.role::TEXT AS role, m.created_at FROM space_memberships m \
+             JOIN users u ON u.id = m.user_id \
+             WHERE m.space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+             ORDER BY m.role ASC, u.username ASC",
+            &[&space_slug],
+        )
+        .await
+        .map_err(db_error)?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(SpaceMember {
+                user_id: row.get("id"),
+                username: row.get("username"),
+                display_name: row.get("display_name"),
+                status: row.get("status"),
+                role: SpaceRole::parse(row.get("role"))?,
+                created_at: row.get("created_at"),
+            })
+        })
+        .collect())
+}
+
+/// Change a non-owner member's role.
+pub async fn set_member_role(
+    pool: &DbPool,
+    space_slug: &str,
+    user_id: Uuid,
+    role: SpaceRole,
+    actor: &WriteActor,
+) -> Result<bool, AppError> {
+    audited_update(
+        pool,
+        "UPDATE space_memberships SET role = $3::TEXT::space_role \
+         WHERE space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+         AND user_id = $2 AND role <> 'owner'",
+        &[&space_slug, &user_id, &role.as_str()],
+        member_event(
+            space_slug,
+            actor,
+            "member.role_changed",
+            &user_id.to_string(),
+            role,
+        ),
+    )
+    .await
+}
+
+/// Remove a non-owner member from one space.
+pub async fn remove_member(
+    pool: &DbPool,
+    space_slug: &str,
+    user_id: Uuid,
+    actor: &WriteActor,
+) -> Result<bool, AppError> {
+    audited_update(
+        pool,
+        "DELETE FROM space_memberships \
+         WHERE space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+         AND user_id = $2 AND role <> 'owner'",
+        &[&space_slug, &user_id],
+        AuditEvent {
+            payload: json!({}),
+            ..member_event(
+                space_slug,
+                actor,
+                "member.removed",
+                &user_id.to_string(),
+                SpaceRole::Viewer,
+            )
+        },
+    )
+    .await
+}
+
+pub(super) fn member_event<'a>(
+    space_slug: &'a str,
+    actor: &'a WriteActor,
+    event_type: &'a str,
+    entity_id: &'a str,
+    role: SpaceRole,
+) -> AuditEvent<'a> {
+    AuditEvent {
+        space_slug: Some(space_slug),
+        actor,
+        event_type,
+        entity_type: "space_membership",
+        entity_id,
+        payload: json!({ "role": role.as_str() }),
+    }
+}
+
+pub(super) async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError> {
+    pool.get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+pub(super) fn db_error(error: tokio_postgres::Error) -> AppError {
+    AppError::DatabaseError(error.to_string())
+}
@@ -1,5 +1,8 @@
 //! Database migrations
 
+use super::resource_links::backfill_resource_links;
+use super::resource_metadata::backfill_resource_metadata;
+use super::summary_backfill::backfill_wiki_summaries;
 use super::DbPool;
 use crate::error::AppError;
 
@@ -9,6 +12,20 @@ const MIGRATIONS_SQL: &[&str] = &[
     include_str!("migrations/020_spaces.sql"),
     include_str!("migrations/030_resources.sql"),
     include_str!("migrations/040_api_and_indexes.sql"),
+    include_str!("migrations/050_trash.sql"),
+    include_str!("migrations/060_resource_links.sql"),
+    include_str!("migrations/070_audio.sql"),
+    include_str!("migrations/080_media_details.sql"),
+    include_str!("migrations/090_jobs.sql"),
+    include_str!("migrations/100_media_blobs.sql"),
+    include_str!("migrations/110_upload_sessions.sql"),
+    include_str!("migrations/120_media_location.sql"),
+    include_str!("migrations/130_resource_metadata.sql"),
+    include_str!("migrations/140_search_embeddings.sql"),
+    include_str!("migrations/150_search_language.sql"),
+    include_str!("migrations/160_storage_ref_indexes.sql"),
+    include_str!("migrations/170_job_lock_owner.sql"),
+    include_str!("migrations/180_upload_session_owner.sql"),
 ];
 
 pub async fn run_migrations(pool: &DbPool) -> Result<(), AppError> {
@@ -17,5 +34,8 @@ pub async fn run_migrations(pool: &DbPool) -> Result<(), AppError> {
         .map_err(|e| AppError::DatabaseError(format!("Connection failed: {e}")))?
         .batch_execute(&MIGRATIONS_SQL.join("\n"))
         .await
-        .map_err(|e| AppError::DatabaseError(format!("Migration failed: {e}")))
+        .map_err(|e| AppError::DatabaseError(format!("Migration failed: {e}")))?;
+    backfill_resource_links(pool).await?;
+    backfill_resource_metadata(pool).await?;
+    backfill_wiki_summaries(pool).await
 }
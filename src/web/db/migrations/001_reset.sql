@@ -18,6 +18,10 @@ BEGIN
         DROP TABLE IF EXISTS audit_events CASCADE;
         DROP TABLE IF EXISTS external_embed_cache CASCADE;
         DROP TABLE IF EXISTS resource_daily_views CASCADE;
+        DROP TABLE IF EXISTS upload_sessions CASCADE;
+        DROP TABLE IF EXISTS media_blobs CASCADE;
+        DROP TABLE IF EXISTS jobs CASCADE;
+        DROP TABLE IF EXISTS resource_links CASCADE;
         DROP TABLE IF EXISTS resource_snapshots CASCADE;
         DROP TABLE IF EXISTS resources CASCADE;
         DROP TABLE IF EXISTS space_settings CASCADE;
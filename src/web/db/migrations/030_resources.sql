@@ -12,7 +12,7 @@ CREATE TABLE IF NOT EXISTS resources (
     title TEXT NOT NULL,
     summary TEXT NOT NULL,
     body TEXT NOT NULL DEFAULT '',
-    media_family TEXT CHECK (media_family IN ('image', 'video', 'file')),
+    media_family TEXT CHECK (media_family IN ('image', 'video', 'audio', 'file')),
     file_key TEXT,
     content_type TEXT,
     byte_size BIGINT,
@@ -22,7 +22,8 @@ CREATE TABLE IF NOT EXISTS resources (
     height INTEGER,
     duration_ms BIGINT,
     media_variants JSONB,
-    owner_note_id CHAR(26) REFERENCES resources(id),
+    media_details JSONB,
+    owner_note_id CHAR(26) REFERENCES resources(id) ON DELETE SET NULL,
     is_favorite BOOLEAN NOT NULL DEFAULT FALSE,
     favorite_position BIGINT,
     visibility resource_visibility NOT NULL DEFAULT 'public',
@@ -54,7 +55,7 @@ CREATE TABLE IF NOT EXISTS resource_snapshots (
     title TEXT NOT NULL,
     summary TEXT NOT NULL,
     body TEXT NOT NULL,
-    media_family TEXT CHECK (media_family IN ('image', 'video', 'file')),
+    media_family TEXT CHECK (media_family IN ('image', 'video', 'audio', 'file')),
     file_key TEXT,
     content_type TEXT,
     byte_size BIGINT,
@@ -64,7 +65,8 @@ CREATE TABLE IF NOT EXISTS resource_snapshots (
     height INTEGER,
     duration_ms BIGINT,
     media_variants JSONB,
-    owner_note_id CHAR(26) REFERENCES resources(id),
+    media_details JSONB,
+    owner_note_id CHAR(26) REFERENCES resources(id) ON DELETE SET NULL,
     visibility resource_visibility NOT NULL,
     snapshot_number INTEGER NOT NULL,
     created_by_user_id UUID REFERENCES users(id),
@@ -75,5 +75,7 @@ CREATE INDEX IF NOT EXISTS idx_resources_search
     ON resources USING GIN(search_document);
 CREATE INDEX IF NOT EXISTS idx_resource_daily_views_rank
     ON resource_daily_views(space_id, view_date, view_count DESC, resource_id);
+CREATE INDEX IF NOT EXISTS idx_audit_events_space_created
+    ON audit_events(space_id, created_at DESC, id DESC);
 CREATE INDEX IF NOT EXISTS idx_external_embed_cache_expires
     ON external_embed_cache(space_id, expires_at);
@@ -0,0 +1,22 @@
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'resources_owner_note_id_fkey' AND confdeltype <> 'n'
+    ) THEN
+        ALTER TABLE resources DROP CONSTRAINT resources_owner_note_id_fkey;
+        ALTER TABLE resources ADD CONSTRAINT resources_owner_note_id_fkey
+            FOREIGN KEY (owner_note_id) REFERENCES resources(id) ON DELETE SET NULL;
+    END IF;
+    IF EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'resource_snapshots_owner_note_id_fkey' AND confdeltype <> 'n'
+    ) THEN
+        ALTER TABLE resource_snapshots DROP CONSTRAINT resource_snapshots_owner_note_id_fkey;
+        ALTER TABLE resource_snapshots ADD CONSTRAINT resource_snapshots_owner_note_id_fkey
+            FOREIGN KEY (owner_note_id) REFERENCES resources(id) ON DELETE SET NULL;
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS idx_resources_space_trash
+    ON resources(space_id, deleted_at DESC, id ASC) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS resource_links (
+    source_id CHAR(26) NOT NULL REFERENCES resources(id) ON DELETE CASCADE,
+    target_space CITEXT NOT NULL,
+    target_ref TEXT NOT NULL,
+    is_file BOOLEAN NOT NULL DEFAULT FALSE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (source_id, target_space, target_ref, is_file)
+);
+
+CREATE INDEX IF NOT EXISTS idx_resource_links_target
+    ON resource_links(target_space, target_ref);
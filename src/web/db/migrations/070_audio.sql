@@ -0,0 +1,21 @@
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'resources_media_family_check'
+          AND pg_get_constraintdef(oid) NOT LIKE '%audio%'
+    ) THEN
+        ALTER TABLE resources DROP CONSTRAINT resources_media_family_check;
+        ALTER TABLE resources ADD CONSTRAINT resources_media_family_check
+            CHECK (media_family IN ('image', 'video', 'audio', 'file'));
+    END IF;
+    IF EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'resource_snapshots_media_family_check'
+          AND pg_get_constraintdef(oid) NOT LIKE '%audio%'
+    ) THEN
+        ALTER TABLE resource_snapshots DROP CONSTRAINT resource_snapshots_media_family_check;
+        ALTER TABLE resource_snapshots ADD CONSTRAINT resource_snapshots_media_family_check
+            CHECK (media_family IN ('image', 'video', 'audio', 'file'));
+    END IF;
+END $$;
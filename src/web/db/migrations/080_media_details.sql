@@ -0,0 +1,2 @@
+ALTER TABLE resources ADD COLUMN IF NOT EXISTS media_details JSONB;
+ALTER TABLE resource_snapshots ADD COLUMN IF NOT EXISTS media_details JSONB;
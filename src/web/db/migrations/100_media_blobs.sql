@@ -0,0 +1,58 @@
+CREATE TABLE IF NOT EXISTS media_blobs (
+    file_key TEXT PRIMARY KEY,
+    sha256_hex TEXT NOT NULL,
+    byte_size BIGINT NOT NULL,
+    ref_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_blobs_sha256
+    ON media_blobs(sha256_hex) WHERE ref_count > 0;
+
+CREATE OR REPLACE FUNCTION media_blob_refs()
+RETURNS TRIGGER
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' AND OLD.file_key IS NOT NULL THEN
+        UPDATE media_blobs SET ref_count = ref_count - 1, updated_at = NOW()
+        WHERE file_key = OLD.file_key;
+    END IF;
+    IF TG_OP <> 'DELETE' AND NEW.file_key IS NOT NULL AND NEW.sha256_hex IS NOT NULL THEN
+        INSERT INTO media_blobs (file_key, sha256_hex, byte_size, ref_count)
+        VALUES (NEW.file_key, NEW.sha256_hex, COALESCE(NEW.byte_size, 0), 1)
+        ON CONFLICT (file_key) DO UPDATE
+        SET ref_count = media_blobs.ref_count + 1, updated_at = NOW();
+    END IF;
+    RETURN NULL;
+END
+$$;
+
+CREATE OR REPLACE TRIGGER resources_media_blob_refs
+    AFTER INSERT OR DELETE OR UPDATE OF file_key ON resources
+    FOR EACH ROW EXECUTE FUNCTION media_blob_refs();
+
+CREATE OR REPLACE TRIGGER resource_snapshots_media_blob_refs
+    AFTER INSERT OR DELETE OR UPDATE OF file_key ON resource_snapshots
+    FOR EACH ROW EXECUTE FUNCTION media_blob_refs();
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM platform_migrations WHERE key = 'media_blobs_backfill'
+    ) THEN
+        INSERT INTO media_blobs (file_key, sha256_hex, byte_size, ref_count)
+        SELECT file_key, MIN(sha256_hex), MAX(COALESCE(byte_size, 0)), COUNT(*)
+        FROM (
+            SELECT file_key, sha256_hex, byte_size FROM resources
+            UNION ALL
+            SELECT file_key, sha256_hex, byte_size FROM resource_snapshots
+        ) refs
+        WHERE file_key IS NOT NULL AND sha256_hex IS NOT NULL
+        GROUP BY file_key
+        ON CONFLICT (file_key) DO UPDATE SET ref_count = EXCLUDED.ref_count;
+        INSERT INTO platform_migrations (key) VALUES ('media_blobs_backfill');
+    END IF;
+END
+$$;
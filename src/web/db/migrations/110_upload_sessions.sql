@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS upload_sessions (
+    id CHAR(26) PRIMARY KEY,
+    space_id UUID NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    original_filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    byte_size BIGINT NOT NULL CHECK (byte_size > 0),
+    sha256_hex TEXT CHECK (sha256_hex ~ '^[0-9a-f]{64}$'),
+    received_bytes BIGINT NOT NULL DEFAULT 0
+        CHECK (received_bytes >= 0 AND received_bytes <= byte_size),
+    locked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_expires
+    ON upload_sessions(expires_at);
@@ -0,0 +1 @@
+ALTER TABLE space_settings ADD COLUMN IF NOT EXISTS media_strip_location BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,5 @@
+ALTER TABLE resources ADD COLUMN IF NOT EXISTS metadata JSONB NOT NULL DEFAULT '{}'::JSONB;
+ALTER TABLE resource_snapshots ADD COLUMN IF NOT EXISTS metadata JSONB NOT NULL DEFAULT '{}'::JSONB;
+
+CREATE INDEX IF NOT EXISTS idx_resources_metadata
+    ON resources USING GIN (metadata jsonb_path_ops);
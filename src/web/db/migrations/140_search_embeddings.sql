@@ -0,0 +1,25 @@
+ALTER TABLE resources ADD COLUMN IF NOT EXISTS embedding REAL[];
+ALTER TABLE resources ADD COLUMN IF NOT EXISTS embedding_model TEXT;
+
+CREATE OR REPLACE FUNCTION cosine_similarity(a REAL[], b REAL[])
+RETURNS DOUBLE PRECISION
+LANGUAGE SQL IMMUTABLE STRICT PARALLEL SAFE AS $$
+    SELECT CASE WHEN cardinality(a) = cardinality(b) AND cardinality(a) > 0 THEN (
+        SELECT SUM(x::DOUBLE PRECISION * y)
+            / NULLIF(SQRT(SUM(x::DOUBLE PRECISION * x)) * SQRT(SUM(y::DOUBLE PRECISION * y)), 0)
+        FROM unnest(a, b) AS pairs(x, y)
+    ) END
+$$;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'jobs_kind_check'
+        AND pg_get_constraintdef(oid) LIKE '%search_embedding%'
+    ) THEN
+        ALTER TABLE jobs DROP CONSTRAINT IF EXISTS jobs_kind_check;
+        ALTER TABLE jobs ADD CONSTRAINT jobs_kind_check
+            CHECK (kind IN ('media_derivatives', 'embed_unfurl', 'search_embedding'));
+    END IF;
+END $$;
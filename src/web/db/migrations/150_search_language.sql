@@ -0,0 +1,81 @@
+ALTER TABLE space_settings ADD COLUMN IF NOT EXISTS search_language TEXT NOT NULL DEFAULT 'simple';
+ALTER TABLE space_settings ADD COLUMN IF NOT EXISTS search_cjk BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE resources ADD COLUMN IF NOT EXISTS search_config REGCONFIG NOT NULL DEFAULT 'simple';
+ALTER TABLE resources ADD COLUMN IF NOT EXISTS search_cjk BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Splits runs of CJK characters into overlapping bigrams so the word parser
+-- indexes substrings; documents also keep unigrams for one-character queries.
+CREATE OR REPLACE FUNCTION cjk_search_text(input TEXT, enabled BOOLEAN, unigrams BOOLEAN)
+RETURNS TEXT
+LANGUAGE plpgsql IMMUTABLE PARALLEL SAFE
+AS $$
+DECLARE
+    piece TEXT;
+    chars TEXT[];
+    grams TEXT[];
+    output TEXT := '';
+BEGIN
+    IF input IS NULL OR enabled IS NOT TRUE THEN
+        RETURN COALESCE(input, '');
+    END IF;
+    FOR piece IN
+        SELECT m[1] FROM regexp_matches(input,
+            '([\u3040-\u30ff\u3400-\u4dbf\u4e00-\u9fff\uac00-\ud7af\uf900-\ufaff]+|[^\u3040-\u30ff\u3400-\u4dbf\u4e00-\u9fff\uac00-\ud7af\uf900-\ufaff]+)',
+            'g') AS m
+    LOOP
+        IF piece ~ '^[\u3040-\u30ff\u3400-\u4dbf\u4e00-\u9fff\uac00-\ud7af\uf900-\ufaff]' THEN
+            chars := regexp_split_to_array(piece, '');
+            grams := ARRAY(SELECT chars[i] || chars[i + 1] FROM generate_series(1, cardinality(chars) - 1) AS i);
+            IF unigrams OR cardinality(chars) = 1 THEN
+                grams := grams || chars;
+            END IF;
+            output := output || ' ' || array_to_string(grams, ' ') || ' ';
+        ELSE
+            output := output || piece;
+        END IF;
+    END LOOP;
+    RETURN output;
+END
+$$;
+
+CREATE OR REPLACE FUNCTION resource_search_language()
+RETURNS TRIGGER
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    SELECT search_language::REGCONFIG, search_cjk INTO NEW.search_config, NEW.search_cjk
+    FROM space_settings WHERE space_id = NEW.space_id;
+    NEW.search_config := COALESCE(NEW.search_config, 'simple'::REGCONFIG);
+    NEW.search_cjk := COALESCE(NEW.search_cjk, FALSE);
+    RETURN NEW;
+END
+$$;
+
+CREATE OR REPLACE TRIGGER resources_search_language
+    BEFORE INSERT ON resources
+    FOR EACH ROW EXECUTE FUNCTION resource_search_language();
+
+UPDATE resources r SET search_config = s.search_language::REGCONFIG, search_cjk = s.search_cjk
+FROM space_settings s
+WHERE s.space_id = r.space_id
+  AND (r.search_config IS DISTINCT FROM s.search_language::REGCONFIG OR r.search_cjk IS DISTINCT FROM s.search_cjk);
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'resources' AND column_name = 'search_document'
+          AND generation_expression LIKE '%search_config%'
+    ) THEN
+        ALTER TABLE resources DROP COLUMN IF EXISTS search_document;
+        ALTER TABLE resources ADD COLUMN search_document TSVECTOR GENERATED ALWAYS AS (
+            setweight(to_tsvector(search_config, cjk_search_text(alias, search_cjk, TRUE)), 'A') ||
+            setweight(to_tsvector(search_config, cjk_search_text(title, search_cjk, TRUE)), 'A') ||
+            setweight(to_tsvector(search_config, cjk_search_text(body, search_cjk, TRUE)), 'B') ||
+            setweight(to_tsvector(search_config, cjk_search_text(original_filename, search_cjk, TRUE)), 'B') ||
+            setweight(to_tsvector('simple', kind), 'C')
+        ) STORED;
+        CREATE INDEX IF NOT EXISTS idx_resources_search ON resources USING GIN(search_document);
+    END IF;
+END
+$$;
@@ -0,0 +1,5 @@
+-- Back the purge's orphan check and blob reuse lookups by storage key.
+CREATE INDEX IF NOT EXISTS idx_resources_file_key
+    ON resources (file_key) WHERE file_key IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_resource_snapshots_file_key
+    ON resource_snapshots (file_key) WHERE file_key IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Name the worker holding a running job so only it can refresh or finish it.
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS locked_by TEXT;
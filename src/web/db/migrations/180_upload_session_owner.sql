@@ -0,0 +1,8 @@
+-- Tie each upload session to the member or service account that opened it.
+ALTER TABLE upload_sessions
+    ADD COLUMN IF NOT EXISTS created_by_user_id UUID REFERENCES users(id) ON DELETE CASCADE;
+ALTER TABLE upload_sessions ADD COLUMN IF NOT EXISTS created_by_service_account_id UUID
+    REFERENCES service_accounts(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_space
+    ON upload_sessions(space_id, expires_at);
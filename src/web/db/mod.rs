@@ -1,15 +1,29 @@
 //! PostgreSQL database adapter
 
 mod analytics;
+mod api_scope;
+mod api_tokens;
+mod audit;
+mod audit_cursor;
+mod audit_feed;
 mod auth;
 mod discovery;
 mod external_embeds;
 mod favorites;
+mod job_kind;
+mod job_locks;
+#[cfg(test)]
+mod job_locks_tests;
+mod job_status;
+mod jobs;
 mod list_kind;
 mod list_scope;
 mod listing;
 mod listing_cursor;
 mod listing_direction;
+mod listing_filters;
+#[cfg(test)]
+mod listing_filters_tests;
 mod listing_params;
 mod listing_queries;
 mod listing_row;
@@ -17,6 +31,9 @@ mod listing_sort;
 mod listing_sort_sql;
 mod media;
 mod media_attachments;
+mod media_blobs;
+mod member_invites;
+mod memberships;
 mod migrations;
 mod models;
 mod navigation;
@@ -24,36 +41,70 @@ mod password;
 mod password_reset;
 mod popular_window;
 mod resource_ids;
+mod resource_links;
+mod resource_metadata;
+mod resource_patch;
 mod resource_support;
 mod resources;
+mod resources_by_ref;
 mod resources_scoped;
+mod resources_trash;
+mod search_embeddings;
+mod service_accounts;
 mod settings;
 mod settings_model;
+mod settings_write;
+mod snapshot_restore;
 mod snapshots;
 mod snapshots_cursor;
+mod space_role;
 mod spaces;
+mod summary_backfill;
+mod trash_purge;
+mod upload_session_create;
+mod upload_sessions;
+mod visibility;
+mod write_actor;
 mod write_support;
 
 pub use analytics::{count_resource_view, get_resource_view_stats, list_popular_resources};
+pub use api_scope::ApiScope;
+pub use api_tokens::{
+    create_service_account, issue_api_token, validate_api_token, ApiTokenGrant,
+    MAX_TOKEN_LIFETIME_DAYS,
+};
+pub use audit_feed::{list_audit_events, AuditEntry, AuditPage};
 pub use auth::{
     create_admin, create_session, delete_session, is_setup, validate_session, verify_credentials,
 };
-pub use discovery::list_public_sitemap_resources;
+pub use discovery::{list_public_feed_resources, list_public_sitemap_resources};
 pub use external_embeds::{
     list_external_embeds, stale_external_embed_urls, upsert_external_embed,
     upsert_external_embed_error,
 };
 pub use favorites::{list_all_favorite_resources, reorder_favorites};
+pub use job_kind::JobKind;
+pub use job_locks::{claim_job, complete_job, fail_job, heartbeat_job, ClaimedJob};
+pub use job_status::{list_resource_jobs, ResourceJob};
+pub use jobs::{enqueue_embed_unfurl, prune_finished_jobs, requeue_media_derivatives};
 pub use list_kind::ListKind;
 pub use list_scope::ListScope;
 pub use listing::{
     list_favorite_resources, list_favorite_resources_in_space, list_recent_resources,
     list_recent_resources_in_space, list_resources, ListDirection, ListRequest, ListSort,
 };
-pub use media::{create_media, create_media_in_space, MediaBlob, ScopedMediaCreate};
+pub use media::{
+    create_media, create_media_in_space, update_media_details, update_media_variants, MediaBlob,
+    ScopedMediaCreate,
+};
 pub use media_attachments::{
     attach_media_to_note, AttachmentBatchResult, AttachmentCreate, NoteAttachmentUpdate,
 };
+pub use media_blobs::{find_duplicate_media, pin_media_blob, settle_media_blob};
+pub use member_invites::{invite_member, MemberInvite};
+pub use memberships::{
+    list_space_members, remove_member, set_member_role, space_role, SpaceMember,
+};
 pub use models::{
     ExternalEmbed, ListedResource, MediaFamily, Resource, ResourceKind, ResourceSnapshot,
     ResourceStats, ResourceViewStats, SitemapResource,
@@ -64,21 +115,43 @@ pub use password_reset::{
 };
 pub use popular_window::PopularWindow;
 pub use resource_ids::generate_resource_id;
+pub use resource_links::{list_backlinks, list_outgoing_links, ResourceLink};
+pub use resource_metadata::{list_space_tags, TagCount};
+pub use resource_patch::{patch_resource_in_space, PatchOperation, ResourcePatch};
 pub use resources::{
     create_resource, delete_resource, get_resource, get_resource_by_alias, get_resource_by_ref,
     update_resource,
 };
+pub use resources_by_ref::get_resources_by_refs_in_space;
 pub use resources_scoped::{
-    create_resource_in_space, delete_resource_in_space, get_resource_by_ref_in_space,
-    update_resource_in_space,
+    create_resource_in_space, get_resource_by_ref_in_space, update_resource_in_space,
+    ScopedResourceWrite,
 };
-pub use settings::{
-    get_resource_stats, get_settings, get_settings_in_space, update_settings,
-    update_settings_in_space,
+pub use resources_trash::{
+    delete_resource_in_space, list_trashed_resources, restore_resource_in_space, TrashedResource,
 };
+pub use search_embeddings::{requeue_search_embeddings, store_search_embedding, QueryEmbedding};
+pub use service_accounts::{
+    list_service_accounts, revoke_api_token, set_service_account_disabled, ServiceAccount,
+};
+pub use settings::{get_resource_stats, get_settings, get_settings_in_space};
 pub use settings_model::AppSettings;
-pub use snapshots::{get_snapshot_target, list_resource_snapshots, SnapshotTarget};
-pub use spaces::require_space;
+pub use settings_write::{update_settings, update_settings_in_space, SettingsChange};
+pub use snapshot_restore::{restore_snapshot_in_space, SnapshotRestore};
+pub use snapshots::{
+    get_resource_snapshot, get_snapshot_target, list_resource_snapshots, SnapshotTarget,
+};
+pub use space_role::SpaceRole;
+pub use spaces::{default_space_slug, require_space};
+pub use trash_purge::{purge_expired_trash, purge_resource_in_space, PurgedResource};
+pub use upload_session_create::{create_upload_session, UploadSessionCreate};
+pub use upload_sessions::{
+    claim_upload_session, delete_expired_upload_sessions, delete_upload_session,
+    get_upload_session, release_upload_session, UploadSession,
+};
+pub use visibility::Visibility;
+pub use write_actor::WriteActor;
+pub use write_support::current_snapshot_number;
 
 use crate::error::AppError;
 use deadpool_postgres::{Manager, Pool, Runtime};
@@ -3,7 +3,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::media::MediaVariants;
+use super::Visibility;
+use crate::media::{MediaDetails, MediaVariants};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -33,6 +34,7 @@ impl ResourceKind {
 pub enum MediaFamily {
     Image,
     Video,
+    Audio,
     File,
 }
 
@@ -41,6 +43,7 @@ impl MediaFamily {
         match self {
             Self::Image => "image",
             Self::Video => "video",
+            Self::Audio => "audio",
             Self::File => "file",
         }
     }
@@ -49,6 +52,7 @@ impl MediaFamily {
         match value.as_deref() {
             Some("video") => Some(Self::Video),
             Some("image") => Some(Self::Image),
+            Some("audio") => Some(Self::Audio),
             Some("file") => Some(Self::File),
             _ => None,
         }
@@ -74,10 +78,11 @@ pub struct Resource {
     pub height: Option<i32>,
     pub duration_ms: Option<i64>,
     pub media_variants: Option<MediaVariants>,
+    pub media_details: Option<MediaDetails>,
     pub owner_note_id: Option<String>,
     pub is_favorite: bool,
     pub favorite_position: Option<i64>,
-    pub is_private: bool,
+    pub visibility: Visibility,
     pub view_count_total: i64,
     pub last_viewed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -104,8 +109,9 @@ pub struct ResourceSnapshot {
     pub height: Option<i32>,
     pub duration_ms: Option<i64>,
     pub media_variants: Option<MediaVariants>,
+    pub media_details: Option<MediaDetails>,
     pub owner_note_id: Option<String>,
-    pub is_private: bool,
+    pub visibility: Visibility,
     pub created_at: DateTime<Utc>,
 }
 
@@ -120,6 +126,7 @@ pub struct ListedResource {
 pub struct ResourceStats {
     pub total: i64,
     pub public_count: i64,
+    pub space_count: i64,
     pub private_count: i64,
     pub favorite_count: i64,
     pub updated_this_month: i64,
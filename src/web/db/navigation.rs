@@ -1,52 +1,52 @@
 use super::resource_support::row_to_resource;
-use super::DbPool;
+use super::{DbPool, Visibility};
 use crate::error::AppError;
 use crate::web::db::Resource;
 
 pub async fn get_previous_resource(
     pool: &DbPool,
     id: &str,
-    include_private: bool,
+    max_visibility: Visibility,
 ) -> Result<Option<Resource>, AppError> {
-    adjacent_resource(pool, id, include_private, true).await
+    adjacent_resource(pool, id, max_visibility, true).await
 }
 
 pub async fn get_next_resource(
     pool: &DbPool,
     id: &str,
-    include_private: bool,
+    max_visibility: Visibility,
 ) -> Result<Option<Resource>, AppError> {
-    adjacent_resource(pool, id, include_private, false).await
+    adjacent_resource(pool, id, max_visibility, false).await
 }
 
 async fn adjacent_resource(
     pool: &DbPool,
     id: &str,
-    include_private: bool,
+    max_visibility: Visibility,
     older: bool,
 ) -> Result<Option<Resource>, AppError> {
     let query = if older {
         "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = space_id) AS space_slug, \
          kind, alias, title, summary, body, media_family, file_key, content_type, \
-         byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, \
-         favorite_position, (visibility = 'private') AS is_private, view_count_total, last_viewed_at, created_at, updated_at \
-         FROM resources WHERE deleted_at IS NULL AND ($2 OR visibility = 'public') \
+         byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, media_details, owner_note_id, is_favorite, \
+         favorite_position, visibility::TEXT AS visibility, view_count_total, last_viewed_at, created_at, updated_at \
+         FROM resources WHERE deleted_at IS NULL AND visibility <= $2::TEXT::resource_visibility \
          AND ((created_at < (SELECT created_at FROM resources WHERE id = $1 AND deleted_at IS NULL)) \
            OR (created_at = (SELECT created_at FROM resources WHERE id = $1 AND deleted_at IS NULL) AND id < $1)) \
          ORDER BY created_at DESC, id DESC LIMIT 1"
     } else {
         "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = space_id) AS space_slug, \
          kind, alias, title, summary, body, media_family, file_key, content_type, \
-         byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, is_favorite, \
-         favorite_position, (visibility = 'private') AS is_private, view_count_total, last_viewed_at, created_at, updated_at \
-         FROM resources WHERE deleted_at IS NULL AND ($2 OR visibility = 'public') \
+         byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, media_details, owner_note_id, is_favorite, \
+         favorite_position, visibility::TEXT AS visibility, view_count_total, last_viewed_at, created_at, updated_at \
+         FROM resources WHERE deleted_at IS NULL AND visibility <= $2::TEXT::resource_visibility \
          AND ((created_at > (SELECT created_at FROM resources WHERE id = $1 AND deleted_at IS NULL)) \
            OR (created_at = (SELECT created_at FROM resources WHERE id = $1 AND deleted_at IS NULL) AND id > $1)) \
          ORDER BY created_at ASC, id ASC LIMIT 1"
     };
     client(pool)
         .await?
-        .query_opt(query, &[&id, &include_private])
+        .query_opt(query, &[&id, &max_visibility.as_str()])
         .await
         .map(|row| row.map(row_to_resource))
         .map_err(|e| AppError::DatabaseError(e.to_string()))
@@ -1,3 +1,4 @@
+use super::audit::audit_user_event;
 use super::password;
 use super::DbPool;
 use crate::error::AppError;
@@ -47,7 +48,7 @@ pub async fn reset_admin_password(
         return Ok(false);
     };
     let user_id: Uuid = row.get("user_id");
-    update_password_in_tx(&tx, user_id, password).await?;
+    update_password_in_tx(&tx, user_id, password, "auth.password_reset").await?;
     tx.execute(
         "UPDATE password_reset_tokens SET used_at = NOW() WHERE id = $1",
         &[&row.get::<_, Uuid>("id")],
@@ -65,7 +66,7 @@ pub async fn update_admin_password(
 ) -> Result<(), AppError> {
     let mut db = client(pool).await?;
     let tx = db.transaction().await.map_err(db_error)?;
-    update_password_in_tx(&tx, user_id, password).await?;
+    update_password_in_tx(&tx, user_id, password, "auth.password_changed").await?;
     tx.commit().await.map_err(db_error)
 }
 
@@ -90,10 +91,19 @@ pub async fn verify_admin_password(
 }
 
 async fn update_password_in_tx(
-    tx: &tokio_postgres::Transaction<'_>,
+    tx: &deadpool_postgres::Transaction<'_>,
     user_id: Uuid,
     password: &str,
+    event_type: &str,
 ) -> Result<(), AppError> {
+    audit_user_event(tx, user_id, event_type).await?;
+    tx.execute(
+        "UPDATE users SET status = 'active', updated_at = NOW() \
+         WHERE id = $1 AND status = 'invited'",
+        &[&user_id],
+    )
+    .await
+    .map_err(db_error)?;
     let password_hash = password::hash_secret(password)?;
     tx.execute(
         "UPDATE user_local_credentials SET password_hash = $2, password_updated_at = NOW() \
@@ -111,7 +121,7 @@ async fn update_password_in_tx(
     .map_err(db_error)
 }
 
-fn new_token() -> String {
+pub(super) fn new_token() -> String {
     format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
 }
 
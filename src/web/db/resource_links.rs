@@ -0,0 +1,166 @@
+//! Markdown link graph between resources
+
+use super::models::Resource;
+use super::resource_support::{row_to_resource, SELECT_RECORD};
+use super::write_support::client;
+use super::{DbPool, Visibility};
+use crate::core::local_link_targets;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde::Serialize;
+
+const BACKLINK_LIMIT: i64 = 50;
+
+/// One outgoing local link and where it currently resolves.
+#[derive(Clone, Debug, Serialize)]
+pub struct ResourceLink {
+    pub space_slug: String,
+    pub reference: String,
+    pub is_file: bool,
+    /// Live target id, present only when the reader may see the target.
+    pub target_id: Option<String>,
+    pub target_title: Option<String>,
+    /// No live resource answers to the reference, e.g. deleted or renamed.
+    pub broken: bool,
+}
+
+/// Replace the stored outgoing links of one resource from its current body.
+pub(super) async fn sync_resource_links<C: GenericClient>(
+    db: &C,
+    resource: &Resource,
+) -> Result<(), AppError> {
+    db.execute(
+        "DELETE FROM resource_links WHERE source_id = $1",
+        &[&resource.id],
+    )
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let targets = local_link_targets(&resource.body);
+    if targets.is_empty() {
+        return Ok(());
+    }
+    let spaces: Vec<&str> = targets
+        .iter()
+        .map(|target| target.space.as_deref().unwrap_or(&resource.space_slug))
+        .collect();
+    let references: Vec<&str> = targets.iter().map(|t| t.reference.as_str()).collect();
+    let files: Vec<bool> = targets.iter().map(|target| target.is_file).collect();
+    let positions: Vec<i32> = (0..targets.len() as i32).collect();
+    db.execute(
+        "INSERT INTO resource_links (source_id, target_space, target_ref, is_file, position) \
+         SELECT $1, space, reference, is_file, position \
+         FROM UNNEST($2::TEXT[], $3::TEXT[], $4::BOOL[], $5::INT[]) \
+         AS links(space, reference, is_file, position) \
+         ON CONFLICT DO NOTHING",
+        &[&resource.id, &spaces, &references, &files, &positions],
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Outgoing links of one resource in body order.
+///
+/// Targets resolve by alias first, then id. Targets outside `max_visibility`
+/// in the resource's own space, or non-public ones elsewhere, stay unnamed.
+pub async fn list_outgoing_links(
+    pool: &DbPool,
+    resource: &Resource,
+    max_visibility: Visibility,
+) -> Result<Vec<ResourceLink>, AppError> {
+    let rows = client(pool)
+        .await?
+        .query(
+            "SELECT l.target_space::TEXT AS target_space, l.target_ref, l.is_file, \
+             t.id AS target_id, t.title AS target_title, \
+             (t.visibility = 'public' OR (l.target_space = $2::CITEXT \
+              AND t.visibility <= $3::TEXT::resource_visibility)) AS visible \
+             FROM resource_links l \
+             LEFT JOIN spaces s ON s.slug = l.target_space \
+             LEFT JOIN LATERAL (SELECT id, title, visibility FROM resources r \
+              WHERE r.space_id = s.id AND (r.alias = l.target_ref OR r.id = l.target_ref) \
+              AND r.deleted_at IS NULL \
+              ORDER BY (r.alias = l.target_ref) IS TRUE DESC LIMIT 1) t ON TRUE \
+             WHERE l.source_id = $1 ORDER BY l.position",
+            &[&resource.id, &resource.space_slug, &max_visibility.as_str()],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let target_id: Option<String> = row.get("target_id");
+            let visible = row.get::<_, Option<bool>>("visible").unwrap_or(false);
+            ResourceLink {
+                space_slug: row.get("target_space"),
+                reference: row.get("target_ref"),
+                is_file: row.get("is_file"),
+                broken: target_id.is_none(),
+                target_title: row
+                    .get::<_, Option<String>>("target_title")
+                    .filter(|_| visible),
+                target_id: target_id.filter(|_| visible),
+            }
+        })
+        .collect())
+}
+
+/// Live resources whose bodies link to this one by id or current alias.
+pub async fn list_backlinks(
+    pool: &DbPool,
+    resource: &Resource,
+    max_visibility: Visibility,
+) -> Result<Vec<Resource>, AppError> {
+    client(pool)
+        .await?
+        .query(
+            &format!(
+                "{SELECT_RECORD} FROM resources WHERE deleted_at IS NULL AND id <> $1::TEXT \
+                 AND id IN (SELECT source_id FROM resource_links \
+                  WHERE target_space = $2::CITEXT AND (target_ref = $1::TEXT OR target_ref = $3)) \
+                 AND (visibility = 'public' OR (space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
+                  AND visibility <= $4::TEXT::resource_visibility)) \
+                 ORDER BY updated_at DESC, id ASC LIMIT $5"
+            ),
+            &[
+                &resource.id,
+                &resource.space_slug,
+                &resource.alias,
+                &max_visibility.as_str(),
+                &BACKLINK_LIMIT,
+            ],
+        )
+        .await
+        .map(|rows| rows.into_iter().map(row_to_resource).collect())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Index links for resources written before the link table existed; runs once.
+pub(super) async fn backfill_resource_links(pool: &DbPool) -> Result<(), AppError> {
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let claimed = tx
+        .execute(
+            "INSERT INTO platform_migrations (key) VALUES ('resource_links_backfill') \
+             ON CONFLICT DO NOTHING",
+            &[],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if claimed == 0 {
+        return Ok(());
+    }
+    let rows = tx
+        .query(&format!("{SELECT_RECORD} FROM resources"), &[])
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    for resource in rows.into_iter().map(row_to_resource) {
+        sync_resource_links(&tx, &resource).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
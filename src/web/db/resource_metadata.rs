@@ -0,0 +1,99 @@
+//! Front matter metadata stored on resources for tag and field filters
+
+use super::models::Resource;
+use super::resource_support::{row_to_resource, SELECT_RECORD};
+use super::write_support::client;
+use super::{DbPool, Visibility};
+use crate::core::parse_front_matter;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde::Serialize;
+use serde_json::Value;
+
+/// One tag of a space and how many visible resources carry it.
+#[derive(Clone, Debug, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// Metadata object for one body; `{}` when it has no front matter.
+pub(super) fn body_metadata(body: &str) -> Value {
+    parse_front_matter(body)
+        .map(|front| front.to_json())
+        .unwrap_or_else(|| Value::Object(Default::default()))
+}
+
+/// Replace the stored metadata of one resource from its current body.
+pub(super) async fn sync_resource_metadata<C: GenericClient>(
+    db: &C,
+    resource: &Resource,
+) -> Result<(), AppError> {
+    db.execute(
+        "UPDATE resources SET metadata = $2 WHERE id = $1",
+        &[&resource.id, &body_metadata(&resource.body)],
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Tags of live resources in one space the reader may see, most used first.
+pub async fn list_space_tags(
+    pool: &DbPool,
+    space_slug: &str,
+    max_visibility: Visibility,
+) -> Result<Vec<TagCount>, AppError> {
+    client(pool)
+        .await?
+        .query(
+            "SELECT tag, COUNT(*) AS count FROM resources, \
+             jsonb_array_elements_text(CASE WHEN jsonb_typeof(metadata->'tags') = 'array' \
+              THEN metadata->'tags' ELSE '[]'::JSONB END) AS tag \
+             WHERE deleted_at IS NULL \
+             AND space_id = COALESCE((SELECT id FROM spaces WHERE slug = $1::CITEXT), default_space_id()) \
+             AND visibility <= $2::TEXT::resource_visibility \
+             GROUP BY tag ORDER BY count DESC, tag ASC",
+            &[&space_slug, &max_visibility.as_str()],
+        )
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|row| TagCount {
+                    tag: row.get("tag"),
+                    count: row.get("count"),
+                })
+                .collect()
+        })
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Parse front matter of resources written before metadata existed; runs once.
+pub(super) async fn backfill_resource_metadata(pool: &DbPool) -> Result<(), AppError> {
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let claimed = tx
+        .execute(
+            "INSERT INTO platform_migrations (key) VALUES ('resource_metadata_backfill') \
+             ON CONFLICT DO NOTHING",
+            &[],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if claimed == 0 {
+        return Ok(());
+    }
+    let rows = tx
+        .query(&format!("{SELECT_RECORD} FROM resources"), &[])
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    for resource in rows.into_iter().map(row_to_resource) {
+        sync_resource_metadata(&tx, &resource).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
@@ -0,0 +1,197 @@
+//! Atomic partial updates for live resources
+
+use super::models::Resource;
+use super::resource_support::{row_to_resource, SELECT_RECORD};
+use super::resources_scoped::{write_update, ScopedResourceWrite};
+use super::write_actor::WriteActor;
+use super::write_support::{check_base_snapshot, client, next_snapshot_number};
+use super::{DbPool, Visibility};
+use crate::core::body_patch;
+use crate::core::normalize_alias;
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// One partial update step; body edits and JSON-Patch-style field edits share the list.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOperation {
+    Append {
+        text: String,
+    },
+    Prepend {
+        text: String,
+    },
+    ReplaceSection {
+        heading: String,
+        text: String,
+    },
+    FindReplace {
+        find: String,
+        replace: String,
+        #[serde(default = "one_match")]
+        expected_count: usize,
+    },
+    Replace {
+        path: String,
+        value: Value,
+    },
+    Remove {
+        path: String,
+    },
+    Test {
+        path: String,
+        value: Value,
+    },
+}
+
+fn one_match() -> usize {
+    1
+}
+
+/// Partial update request for one live resource inside a personal space.
+pub struct ResourcePatch<'a> {
+    pub space_slug: &'a str,
+    pub id: &'a str,
+    pub operations: &'a [PatchOperation],
+    pub actor: &'a WriteActor,
+    /// Snapshot the caller patched from; stale values fail with a conflict.
+    pub base_snapshot: Option<i32>,
+}
+
+/// Writable fields a patch may change, seeded from the locked live row.
+struct PatchedFields {
+    body: String,
+    alias: Option<String>,
+    is_favorite: bool,
+    visibility: Visibility,
+}
+
+/// Apply every operation to the locked live resource and save one new snapshot.
+///
+/// Any failing operation rolls the whole patch back. Only a failed `test` or a
+/// stale base snapshot is a conflict carrying the newest snapshot number;
+/// missing headings, match-count mismatches, and bad paths or values are
+/// invalid requests.
+pub async fn patch_resource_in_space(
+    pool: &DbPool,
+    input: &ResourcePatch<'_>,
+) -> Result<Option<Resource>, AppError> {
+    if input.operations.is_empty() {
+        return Err(AppError::InvalidRequest(
+            "operations must not be empty".to_string(),
+        ));
+    }
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let Some(current) = tx
+        .query_opt(
+            &format!(
+                "{SELECT_RECORD} FROM resources WHERE id = $1 \
+                 AND space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
+                 AND deleted_at IS NULL FOR UPDATE"
+            ),
+            &[&input.id, &input.space_slug],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .map(row_to_resource)
+    else {
+        return Ok(None);
+    };
+    let latest = next_snapshot_number(&tx, input.id).await? - 1;
+    check_base_snapshot(input.base_snapshot, latest + 1)?;
+    let mut fields = PatchedFields {
+        body: current.body,
+        alias: current.alias,
+        is_favorite: current.is_favorite,
+        visibility: current.visibility,
+    };
+    for operation in input.operations {
+        fields.apply(operation, latest)?;
+    }
+    let alias = normalize_alias(fields.alias.as_deref())?;
+    let resource = write_update(
+        &tx,
+        &ScopedResourceWrite {
+            space_slug: input.space_slug,
+            id: input.id,
+            alias: alias.as_deref(),
+            body: &fields.body,
+            is_favorite: fields.is_favorite,
+            visibility: fields.visibility,
+            actor: input.actor,
+            base_snapshot: None,
+        },
+    )
+    .await?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(resource)
+}
+
+impl PatchedFields {
+    fn apply(&mut self, operation: &PatchOperation, latest: i32) -> Result<(), AppError> {
+        let edited = match operation {
+            PatchOperation::Append { text } => Ok(body_patch::append(&self.body, text)),
+            PatchOperation::Prepend { text } => Ok(body_patch::prepend(&self.body, text)),
+            PatchOperation::ReplaceSection { heading, text } => {
+                body_patch::replace_section(&self.body, heading, text)
+            }
+            PatchOperation::FindReplace {
+                find,
+                replace,
+                expected_count,
+            } => body_patch::find_replace(&self.body, find, replace, *expected_count),
+            PatchOperation::Replace { path, value } => return self.replace(path, value),
+            PatchOperation::Remove { path } => return self.replace(path, &Value::Null),
+            PatchOperation::Test { path, value } if self.field(path)? == *value => return Ok(()),
+            PatchOperation::Test { path, .. } => {
+                return Err(conflict(format!("test failed for {path}"), latest))
+            }
+        };
+        self.body = edited.map_err(|error| AppError::InvalidRequest(error.to_string()))?;
+        Ok(())
+    }
+
+    fn field(&self, path: &str) -> Result<Value, AppError> {
+        match path {
+            "/body" => Ok(Value::from(self.body.as_str())),
+            "/alias" => Ok(self.alias.clone().map_or(Value::Null, Value::from)),
+            "/is_favorite" => Ok(Value::from(self.is_favorite)),
+            "/visibility" => Ok(Value::from(self.visibility.as_str())),
+            _ => Err(unsupported(path)),
+        }
+    }
+
+    fn replace(&mut self, path: &str, value: &Value) -> Result<(), AppError> {
+        let invalid = || AppError::InvalidRequest(format!("invalid value for {path}"));
+        match (path, value) {
+            ("/body", Value::String(body)) => self.body = body.clone(),
+            ("/alias", Value::Null) => self.alias = None,
+            ("/alias", Value::String(alias)) => self.alias = Some(alias.clone()),
+            ("/is_favorite", Value::Bool(flag)) => self.is_favorite = *flag,
+            ("/visibility", Value::String(level)) => {
+                self.visibility = Visibility::parse(level).ok_or_else(invalid)?
+            }
+            ("/body" | "/alias" | "/is_favorite" | "/visibility", _) => return Err(invalid()),
+            _ => return Err(unsupported(path)),
+        }
+        Ok(())
+    }
+}
+
+fn unsupported(path: &str) -> AppError {
+    AppError::InvalidRequest(format!("unsupported patch path '{path}'"))
+}
+
+fn conflict(message: String, latest: i32) -> AppError {
+    AppError::Conflict {
+        message,
+        current_snapshot: Some(latest),
+    }
+}
@@ -1,19 +1,20 @@
 use crate::error::AppError;
-use crate::media::media_variants_from_json;
+use crate::media::{media_details_from_json, media_variants_from_json};
 use crate::web::db::models::{MediaFamily, Resource, ResourceKind};
+use crate::web::db::Visibility;
 use deadpool_postgres::GenericClient;
 use tokio_postgres::error::SqlState;
 
 pub(super) const RETURNING_RECORD: &str = "RETURNING id, kind, alias, title, summary, body, \
 media_family, file_key, content_type, byte_size, sha256_hex, original_filename, width, height, \
-duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, \
-(visibility = 'private') AS is_private, view_count_total, last_viewed_at, \
+duration_ms, media_variants, media_details, owner_note_id, is_favorite, favorite_position, \
+visibility::TEXT AS visibility, view_count_total, last_viewed_at, \
 created_at, updated_at, \
 (SELECT slug::TEXT FROM spaces WHERE id = resources.space_id) AS space_slug";
 pub(super) const SELECT_RECORD: &str = "SELECT id, kind, alias, title, summary, body, \
 media_family, file_key, content_type, byte_size, sha256_hex, original_filename, width, height, \
-duration_ms, media_variants, owner_note_id, is_favorite, favorite_position, \
-(visibility = 'private') AS is_private, view_count_total, last_viewed_at, \
+duration_ms, media_variants, media_details, owner_note_id, is_favorite, favorite_position, \
+visibility::TEXT AS visibility, view_count_total, last_viewed_at, \
 created_at, updated_at, \
 (SELECT slug::TEXT FROM spaces WHERE id = resources.space_id) AS space_slug";
 
@@ -22,7 +23,7 @@ pub(super) async fn current_favorite_state<C: GenericClient>(
     id: &str,
 ) -> Result<Option<(ResourceKind, bool, Option<i64>)>, AppError> {
     db.query_opt(
-        "SELECT kind, is_favorite, favorite_position FROM resources WHERE id = $1 AND deleted_at IS NULL",
+        "SELECT kind, is_favorite, favorite_position FROM resources WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
         &[&id],
     )
     .await
@@ -97,10 +98,11 @@ pub(crate) fn row_to_resource(row: tokio_postgres::Row) -> Resource {
         height: row.get("height"),
         duration_ms: row.get("duration_ms"),
         media_variants: media_variants_from_json(row.get("media_variants")),
+        media_details: media_details_from_json(row.get("media_details")),
         owner_note_id: row.get("owner_note_id"),
         is_favorite: row.get("is_favorite"),
         favorite_position: row.get("favorite_position"),
-        is_private: row.get("is_private"),
+        visibility: Visibility::from_db(row.get("visibility")),
         view_count_total: row.get("view_count_total"),
         last_viewed_at: row.get("last_viewed_at"),
         created_at: row.get("created_at"),
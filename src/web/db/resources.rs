@@ -4,7 +4,7 @@ use super::resource_support::{
     RETURNING_RECORD, SELECT_RECORD,
 };
 use super::write_support::{create_snapshot, next_snapshot_number};
-use super::DbPool;
+use super::{DbPool, Visibility};
 use crate::core::{derive_summary, derive_title, derive_title_with_fallback};
 use crate::error::AppError;
 
@@ -35,7 +35,7 @@ pub async fn create_resource(
     alias: Option<&str>,
     body: &str,
     is_favorite: bool,
-    is_private: bool,
+    visibility: Visibility,
 ) -> Result<Resource, AppError> {
     let mut db = client(pool).await?;
     let tx = db
@@ -47,7 +47,7 @@ pub async fn create_resource(
             &format!(
                 "INSERT INTO resources (id, space_id, kind, alias, title, summary, body, is_favorite, favorite_position, visibility) \
                  VALUES ($1, default_space_id(), $2, $3, $4, $5, $6, $7, $8, \
-                 CASE WHEN $9 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END) {RETURNING_RECORD}"
+                 $9::TEXT::resource_visibility) {RETURNING_RECORD}"
             ),
             &[
                 &id,
@@ -58,7 +58,7 @@ pub async fn create_resource(
                 &body,
                 &is_favorite,
                 &next_position(&tx, is_favorite).await?,
-                &is_private,
+                &visibility.as_str(),
             ],
         )
         .await
@@ -82,7 +82,7 @@ pub async fn update_resource(
     alias: Option<&str>,
     body: &str,
     is_favorite: bool,
-    is_private: bool,
+    visibility: Visibility,
 ) -> Result<Option<Resource>, AppError> {
     let mut db = client(pool).await?;
     let tx = db
@@ -98,7 +98,7 @@ pub async fn update_resource(
             &format!(
                 "UPDATE resources SET alias = $2, title = $3, summary = $4, body = $5, \
                  is_favorite = $6, favorite_position = $7, \
-                 visibility = CASE WHEN $8 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END, \
+                 visibility = $8::TEXT::resource_visibility, \
                  updated_at = NOW() \
                  WHERE id = $1 AND deleted_at IS NULL {RETURNING_RECORD}"
             ),
@@ -110,7 +110,7 @@ pub async fn update_resource(
                 &body,
                 &is_favorite,
                 &resolve_position(&tx, was_favorite, current_position, is_favorite).await?,
-                &is_private,
+                &visibility.as_str(),
             ],
         )
         .await
@@ -0,0 +1,43 @@
+//! Batched id-or-alias lookups inside one space
+
+use super::resource_support::{row_to_resource, SELECT_RECORD};
+use super::write_support::client;
+use super::{DbPool, Resource};
+use crate::error::AppError;
+use std::collections::HashMap;
+
+/// Resolve many references in one query, keyed by reference. An alias match
+/// wins over an id match, as in `get_resource_by_ref_in_space`.
+pub async fn get_resources_by_refs_in_space(
+    pool: &DbPool,
+    space_slug: &str,
+    references: &[String],
+) -> Result<HashMap<String, Resource>, AppError> {
+    if references.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows = client(pool)
+        .await?
+        .query(
+            &format!(
+                "{SELECT_RECORD} FROM resources \
+                 WHERE space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+                 AND deleted_at IS NULL AND (alias = ANY($2) OR id = ANY($2))"
+            ),
+            &[&space_slug, &references],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let resources: Vec<Resource> = rows.into_iter().map(row_to_resource).collect();
+    Ok(references
+        .iter()
+        .filter_map(|reference| {
+            let by_alias = resources
+                .iter()
+                .find(|resource| resource.alias.as_deref() == Some(reference.as_str()));
+            by_alias
+                .or_else(|| resources.iter().find(|resource| resource.id == *reference))
+                .map(|resource| (reference.clone(), resource.clone()))
+        })
+        .collect())
+}
@@ -1,12 +1,28 @@
+use super::audit::audit_resource;
 use super::models::{Resource, ResourceKind};
 use super::resource_support::{
     current_favorite_state, map_write_error, next_position, resolve_position, row_to_resource,
     RETURNING_RECORD, SELECT_RECORD,
 };
-use super::write_support::{create_snapshot, next_snapshot_number};
-use super::DbPool;
+use super::write_actor::{stamp_write_actor, WriteActor};
+use super::write_support::{check_base_snapshot, create_snapshot, next_snapshot_number};
+use super::{DbPool, Visibility};
 use crate::core::{derive_summary, derive_title, derive_title_with_fallback};
 use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+
+/// Note create or update request inside one personal space.
+pub struct ScopedResourceWrite<'a> {
+    pub space_slug: &'a str,
+    pub id: &'a str,
+    pub alias: Option<&'a str>,
+    pub body: &'a str,
+    pub is_favorite: bool,
+    pub visibility: Visibility,
+    pub actor: &'a WriteActor,
+    /// Snapshot the caller edited from; stale values fail with a conflict.
+    pub base_snapshot: Option<i32>,
+}
 
 pub async fn get_resource_by_ref_in_space(
     pool: &DbPool,
@@ -32,13 +48,9 @@ pub async fn get_resource_by_ref_in_space(
 
 pub async fn create_resource_in_space(
     pool: &DbPool,
-    space_slug: &str,
-    id: &str,
-    alias: Option<&str>,
-    body: &str,
-    is_favorite: bool,
-    is_private: bool,
+    input: &ScopedResourceWrite<'_>,
 ) -> Result<Resource, AppError> {
+    let body = input.body;
     let mut db = client(pool).await?;
     let tx = db
         .transaction()
@@ -49,13 +61,13 @@ pub async fn create_resource_in_space(
             &format!(
                 "INSERT INTO resources (id, space_id, kind, alias, title, summary, body, is_favorite, favorite_position, visibility) \
                  SELECT $1, id, $3, $4, $5, $6, $7, $8, $9, \
-                 CASE WHEN $10 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END \
+                 $10::TEXT::resource_visibility \
                  FROM spaces WHERE slug = $2::CITEXT {RETURNING_RECORD}"
             ),
             &[
-                &id, &space_slug, &ResourceKind::Note.as_str(), &alias,
-                &derive_title(body), &derive_summary(body), &body, &is_favorite,
-                &next_position(&tx, is_favorite).await?, &is_private,
+                &input.id, &input.space_slug, &ResourceKind::Note.as_str(), &input.alias,
+                &derive_title(body), &derive_summary(body), &body, &input.is_favorite,
+                &next_position(&tx, input.is_favorite).await?, &input.visibility.as_str(),
             ],
         )
         .await
@@ -67,6 +79,15 @@ pub async fn create_resource_in_space(
         next_snapshot_number(&tx, &resource.id).await?,
     )
     .await?;
+    stamp_write_actor(&tx, &resource.id, input.actor, true).await?;
+    audit_resource(
+        &tx,
+        input.space_slug,
+        input.actor,
+        "resource.created",
+        &resource,
+    )
+    .await?;
     tx.commit()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -75,72 +96,69 @@ pub async fn create_resource_in_space(
 
 pub async fn update_resource_in_space(
     pool: &DbPool,
-    space_slug: &str,
-    id: &str,
-    alias: Option<&str>,
-    body: &str,
-    is_favorite: bool,
-    is_private: bool,
+    input: &ScopedResourceWrite<'_>,
 ) -> Result<Option<Resource>, AppError> {
     let mut db = client(pool).await?;
     let tx = db
         .transaction()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let Some((kind, was_favorite, current_position)) = current_favorite_state(&tx, id).await?
-    else {
+    let resource = write_update(&tx, input).await?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(resource)
+}
+
+/// Overwrite one live resource, save its next snapshot, and audit the change.
+pub(super) async fn write_update<C: GenericClient>(
+    tx: &C,
+    input: &ScopedResourceWrite<'_>,
+) -> Result<Option<Resource>, AppError> {
+    let (id, body) = (input.id, input.body);
+    let Some((kind, was_favorite, current_position)) = current_favorite_state(tx, id).await? else {
         return Ok(None);
     };
+    let number = next_snapshot_number(tx, id).await?;
+    check_base_snapshot(input.base_snapshot, number)?;
     let row = tx
         .query_one(
             &format!(
                 "UPDATE resources SET alias = $3, title = $4, summary = $5, body = $6, \
                  is_favorite = $7, favorite_position = $8, \
-                 visibility = CASE WHEN $9 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END, \
+                 visibility = $9::TEXT::resource_visibility, \
                  updated_at = NOW() \
                  WHERE id = $1 AND space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
                  AND deleted_at IS NULL {RETURNING_RECORD}"
             ),
             &[
-                &id, &space_slug, &alias, &derive_title_for_kind(kind, body),
-                &derive_summary(body), &body, &is_favorite,
-                &resolve_position(&tx, was_favorite, current_position, is_favorite).await?,
-                &is_private,
+                &id,
+                &input.space_slug,
+                &input.alias,
+                &derive_title_for_kind(kind, body),
+                &derive_summary(body),
+                &body,
+                &input.is_favorite,
+                &resolve_position(tx, was_favorite, current_position, input.is_favorite).await?,
+                &input.visibility.as_str(),
             ],
         )
         .await
         .map_err(map_write_error)?;
     let resource = row_to_resource(row);
-    create_snapshot(
-        &tx,
+    create_snapshot(tx, &resource, number).await?;
+    stamp_write_actor(tx, &resource.id, input.actor, false).await?;
+    audit_resource(
+        tx,
+        input.space_slug,
+        input.actor,
+        "resource.updated",
         &resource,
-        next_snapshot_number(&tx, &resource.id).await?,
     )
     .await?;
-    tx.commit()
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     Ok(Some(resource))
 }
 
-pub async fn delete_resource_in_space(
-    pool: &DbPool,
-    space_slug: &str,
-    id: &str,
-) -> Result<bool, AppError> {
-    let count = client(pool)
-        .await?
-        .execute(
-            "UPDATE resources SET deleted_at = NOW() WHERE id = $1 \
-             AND space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
-             AND deleted_at IS NULL",
-            &[&id, &space_slug],
-        )
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    Ok(count > 0)
-}
-
 fn derive_title_for_kind(kind: ResourceKind, body: &str) -> String {
     match kind {
         ResourceKind::Note => derive_title(body),
@@ -0,0 +1,127 @@
+//! Resource deletion, trash listing, and restore inside one personal space
+
+use super::audit::audit_resource;
+use super::models::{Resource, ResourceKind};
+use super::resource_support::{row_to_resource, RETURNING_RECORD};
+use super::write_actor::WriteActor;
+use super::write_support::client;
+use super::{DbPool, Visibility};
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio_postgres::error::SqlState;
+
+/// One soft-deleted resource waiting in the space trash.
+#[derive(Clone, Debug, Serialize)]
+pub struct TrashedResource {
+    pub id: String,
+    pub kind: ResourceKind,
+    pub alias: Option<String>,
+    pub title: String,
+    pub visibility: Visibility,
+    pub byte_size: Option<i64>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+pub async fn delete_resource_in_space(
+    pool: &DbPool,
+    space_slug: &str,
+    id: &str,
+    actor: &WriteActor,
+) -> Result<bool, AppError> {
+    set_deleted_at(pool, space_slug, id, actor, true)
+        .await
+        .map(|resource| resource.is_some())
+}
+
+/// Bring one trashed resource back; its alias must still be free in the space.
+pub async fn restore_resource_in_space(
+    pool: &DbPool,
+    space_slug: &str,
+    id: &str,
+    actor: &WriteActor,
+) -> Result<Option<Resource>, AppError> {
+    set_deleted_at(pool, space_slug, id, actor, false).await
+}
+
+/// Trashed resources in one space, newest deletion first.
+pub async fn list_trashed_resources(
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<Vec<TrashedResource>, AppError> {
+    client(pool)
+        .await?
+        .query(
+            "SELECT id, kind, alias, title, visibility::TEXT AS visibility, byte_size, deleted_at \
+             FROM resources WHERE deleted_at IS NOT NULL \
+             AND space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+             ORDER BY deleted_at DESC, id ASC",
+            &[&space_slug],
+        )
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|row| TrashedResource {
+                    id: row.get("id"),
+                    kind: ResourceKind::from_db(&row.get::<_, String>("kind")),
+                    alias: row.get("alias"),
+                    title: row.get("title"),
+                    visibility: Visibility::from_db(row.get("visibility")),
+                    byte_size: row.get("byte_size"),
+                    deleted_at: row.get("deleted_at"),
+                })
+                .collect()
+        })
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+async fn set_deleted_at(
+    pool: &DbPool,
+    space_slug: &str,
+    id: &str,
+    actor: &WriteActor,
+    trash: bool,
+) -> Result<Option<Resource>, AppError> {
+    let (assignment, filter, event_type) = if trash {
+        ("NOW()", "deleted_at IS NULL", "resource.deleted")
+    } else {
+        ("NULL", "deleted_at IS NOT NULL", "resource.restored")
+    };
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let Some(row) = tx
+        .query_opt(
+            &format!(
+                "UPDATE resources SET deleted_at = {assignment} WHERE id = $1 \
+                 AND space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
+                 AND {filter} {RETURNING_RECORD}"
+            ),
+            &[&id, &space_slug],
+        )
+        .await
+        .map_err(map_restore_error)?
+    else {
+        return Ok(None);
+    };
+    let resource = row_to_resource(row);
+    audit_resource(&tx, space_slug, actor, event_type, &resource).await?;
+    tx.commit()
+        .await
+        .map(|_| Some(resource))
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Only a restore can collide with `idx_resources_space_alias`.
+fn map_restore_error(error: tokio_postgres::Error) -> AppError {
+    if error.code() == Some(&SqlState::UNIQUE_VIOLATION) {
+        return AppError::Conflict {
+            message: "alias is now used by another resource; rename that resource before restoring"
+                .to_string(),
+            current_snapshot: None,
+        };
+    }
+    AppError::DatabaseError(error.to_string())
+}
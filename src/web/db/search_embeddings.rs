@@ -0,0 +1,60 @@
+//! Stored resource embeddings for semantic search
+
+use super::job_kind::JobKind;
+use super::jobs::enqueue_job;
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use serde_json::json;
+
+/// Query vector from the active provider. Only resources embedded by the same
+/// `model` are compared, and `min_similarity` admits matches that share no
+/// words with the query.
+#[derive(Clone, Debug)]
+pub struct QueryEmbedding {
+    pub model: String,
+    pub vector: Vec<f32>,
+    pub min_similarity: f64,
+}
+
+/// Queue re-embedding after a write; repeated saves collapse into one job.
+pub(super) async fn enqueue_search_embedding<C: GenericClient>(
+    db: &C,
+    resource_id: &str,
+) -> Result<(), AppError> {
+    enqueue_job(db, JobKind::SearchEmbedding, Some(resource_id), &json!({})).await
+}
+
+pub async fn store_search_embedding(
+    pool: &DbPool,
+    resource_id: &str,
+    model: &str,
+    vector: &[f32],
+) -> Result<(), AppError> {
+    client(pool)
+        .await?
+        .execute(
+            "UPDATE resources SET embedding = $2, embedding_model = $3 WHERE id = $1",
+            &[&resource_id, &vector, &model],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Queue embedding for live resources lacking a vector from `model`, which
+/// covers both resources written before embeddings and a provider change.
+pub async fn requeue_search_embeddings(pool: &DbPool, model: &str) -> Result<u64, AppError> {
+    client(pool)
+        .await?
+        .execute(
+            "INSERT INTO jobs (kind, resource_id) \
+             SELECT 'search_embedding', id FROM resources \
+             WHERE deleted_at IS NULL AND embedding_model IS DISTINCT FROM $1 \
+             ON CONFLICT (kind, resource_id) WHERE status = 'queued' DO NOTHING",
+            &[&model],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
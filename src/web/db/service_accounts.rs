@@ -0,0 +1,143 @@
+//! Service account listing and lifecycle queries
+
+use super::api_scope::ApiScope;
+use super::audit::{audited_update, AuditEvent};
+use super::write_actor::WriteActor;
+use super::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ServiceAccount {
+    pub id: Uuid,
+    pub name: String,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub tokens: Vec<ApiTokenSummary>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ApiTokenSummary {
+    pub id: Uuid,
+    pub scopes: Vec<&'static str>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn list_service_accounts(
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<Vec<ServiceAccount>, AppError> {
+    let db = client(pool).await?;
+    let mut accounts = db
+        .query(
+            "SELECT id, name, disabled_at, created_at FROM service_accounts \
+             WHERE space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+             ORDER BY created_at ASC, id ASC",
+            &[&space_slug],
+        )
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| ServiceAccount {
+            id: row.get("id"),
+            name: row.get("name"),
+            disabled_at: row.get("disabled_at"),
+            created_at: row.get("created_at"),
+            tokens: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    let tokens = db
+        .query(
+            "SELECT id, service_account_id, scopes, expires_at, last_used_at, revoked_at, created_at \
+             FROM api_tokens WHERE space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT) \
+             ORDER BY created_at DESC, id ASC",
+            &[&space_slug],
+        )
+        .await
+        .map_err(db_error)?;
+    for row in tokens {
+        let owner: Uuid = row.get("service_account_id");
+        if let Some(account) = accounts.iter_mut().find(|account| account.id == owner) {
+            account.tokens.push(ApiTokenSummary {
+                id: row.get("id"),
+                scopes: ApiScope::from_json(&row.get("scopes"))
+                    .into_iter()
+                    .map(ApiScope::as_str)
+                    .collect(),
+                expires_at: row.get("expires_at"),
+                last_used_at: row.get("last_used_at"),
+                revoked_at: row.get("revoked_at"),
+                created_at: row.get("created_at"),
+            });
+        }
+    }
+    Ok(accounts)
+}
+
+pub async fn set_service_account_disabled(
+    pool: &DbPool,
+    space_slug: &str,
+    service_account_id: Uuid,
+    disabled: bool,
+    actor: &WriteActor,
+) -> Result<bool, AppError> {
+    let event_type = if disabled {
+        "service_account.disabled"
+    } else {
+        "service_account.enabled"
+    };
+    audited_update(
+        pool,
+        "UPDATE service_accounts SET disabled_at = CASE WHEN $3 THEN COALESCE(disabled_at, NOW()) ELSE NULL END \
+         WHERE id = $2 AND space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT)",
+        &[&space_slug, &service_account_id, &disabled],
+        AuditEvent {
+            space_slug: Some(space_slug),
+            actor,
+            event_type,
+            entity_type: "service_account",
+            entity_id: &service_account_id.to_string(),
+            payload: json!({}),
+        },
+    )
+    .await
+}
+
+pub async fn revoke_api_token(
+    pool: &DbPool,
+    space_slug: &str,
+    token_id: Uuid,
+    actor: &WriteActor,
+) -> Result<bool, AppError> {
+    audited_update(
+        pool,
+        "UPDATE api_tokens SET revoked_at = COALESCE(revoked_at, NOW()) \
+         WHERE id = $2 AND space_id = (SELECT id FROM spaces WHERE slug = $1::CITEXT)",
+        &[&space_slug, &token_id],
+        AuditEvent {
+            space_slug: Some(space_slug),
+            actor,
+            event_type: "api_token.revoked",
+            entity_type: "api_token",
+            entity_id: &token_id.to_string(),
+            payload: json!({}),
+        },
+    )
+    .await
+}
+
+async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError> {
+    pool.get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+fn db_error(error: tokio_postgres::Error) -> AppError {
+    AppError::DatabaseError(error.to_string())
+}
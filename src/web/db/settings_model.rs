@@ -1,8 +1,10 @@
 //! App settings model
 
+use super::Visibility;
+use serde::Serialize;
 use serde_json::{json, Value};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AppSettings {
     pub home_recent_limit: i64,
     pub home_favorite_limit: i64,
@@ -15,9 +17,15 @@ pub struct AppSettings {
     pub home_favorite_position: i64,
     pub home_popular_position: i64,
     pub search_results_per_page: i64,
+    /// Postgres text search configuration for the space's resources.
+    pub search_language: String,
+    /// Index CJK text as overlapping bigrams so substrings match.
+    pub search_cjk: bool,
     pub session_timeout_minutes: i64,
-    pub default_new_resource_is_private: bool,
+    pub default_new_resource_visibility: Visibility,
     pub media_webp_quality: i64,
+    /// Serve public originals without EXIF GPS or XMP location data.
+    pub media_strip_location: bool,
     pub site_name: String,
     pub site_description: String,
     pub public_base_url: String,
@@ -47,9 +55,12 @@ impl Default for AppSettings {
             home_favorite_position: 2,
             home_popular_position: 3,
             search_results_per_page: 20,
+            search_language: "simple".to_string(),
+            search_cjk: false,
             session_timeout_minutes: 1440,
-            default_new_resource_is_private: false,
+            default_new_resource_visibility: Visibility::Public,
             media_webp_quality: 82,
+            media_strip_location: true,
             site_name: "kjxlkj".to_string(),
             site_description: "Markdown-first resource system for LLM-operated workflows."
                 .to_string(),
@@ -67,3 +78,35 @@ impl Default for AppSettings {
         }
     }
 }
+
+impl AppSettings {
+    /// Sorted names of the fields whose values differ in `next`.
+    pub fn changed_fields(&self, next: &Self) -> Vec<String> {
+        let (Value::Object(before), Value::Object(after)) = (json!(self), json!(next)) else {
+            return Vec::new();
+        };
+        after
+            .into_iter()
+            .filter(|(key, value)| before.get(key) != Some(value))
+            .map(|(key, _)| key)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AppSettings;
+
+    #[test]
+    fn changed_fields_lists_only_differing_values() {
+        let before = AppSettings::default();
+        let mut after = before.clone();
+        after.site_name = "Notes".to_string();
+        after.media_webp_quality = 90;
+        assert_eq!(
+            before.changed_fields(&after),
+            vec!["media_webp_quality".to_string(), "site_name".to_string()]
+        );
+        assert!(before.changed_fields(&before).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Personal-space settings writes
+
+use super::audit::{write_audit_event, AuditEvent};
+use super::write_actor::WriteActor;
+use super::write_support::client;
+use super::{AppSettings, DbPool};
+use crate::error::AppError;
+use serde_json::json;
+
+/// Settings write plus the actor and field names it changed.
+pub struct SettingsChange<'a> {
+    pub actor: &'a WriteActor,
+    pub changed: &'a [String],
+}
+
+pub async fn update_settings(
+    pool: &DbPool,
+    settings: &AppSettings,
+    change: &SettingsChange<'_>,
+) -> Result<(), AppError> {
+    update_settings_for(pool, None, settings, change).await
+}
+
+pub async fn update_settings_in_space(
+    pool: &DbPool,
+    space_slug: &str,
+    settings: &AppSettings,
+    change: &SettingsChange<'_>,
+) -> Result<(), AppError> {
+    update_settings_for(pool, Some(space_slug), settings, change).await
+}
+
+async fn update_settings_for(
+    pool: &DbPool,
+    space_slug: Option<&str>,
+    settings: &AppSettings,
+    change: &SettingsChange<'_>,
+) -> Result<(), AppError> {
+    let mut db = client(pool).await?;
+    let tx = db.transaction().await.map_err(db_err)?;
+    tx.execute(
+            "UPDATE space_settings SET home_recent_limit = $1, home_favorite_limit = $2, \
+             home_popular_limit = $3, home_intro_markdown = $4, home_recent_visible = $5, \
+             home_favorite_visible = $6, home_popular_visible = $7, home_recent_position = $8, \
+             home_favorite_position = $9, home_popular_position = $10, search_results_per_page = $11, \
+             default_new_resource_visibility = $12::TEXT::resource_visibility, \
+             media_webp_quality = $13, site_name = $14, site_description = $15, public_base_url = $16, \
+             nostr_names = $17, nostr_relays = $18, live_default_source = $19, live_default_camera_facing = $20, \
+             live_default_height = $21, live_default_fps = $22, live_default_microphone_enabled = $23, \
+             site_icon_key = $24, site_icon_content_type = $25, media_strip_location = $27, \
+             search_language = $28, search_cjk = $29, \
+             site_icon_updated_at = CASE WHEN site_icon_key IS DISTINCT FROM $24 THEN NOW() ELSE site_icon_updated_at END, \
+             updated_at = NOW() WHERE ($26::TEXT IS NULL AND space_id = default_space_id()) \
+             OR space_id = (SELECT id FROM spaces WHERE slug = $26::CITEXT)",
+            &[
+                &settings.home_recent_limit,
+                &settings.home_favorite_limit,
+                &settings.home_popular_limit,
+                &settings.home_intro_markdown,
+                &settings.home_recent_visible,
+                &settings.home_favorite_visible,
+                &settings.home_popular_visible,
+                &settings.home_recent_position,
+                &settings.home_favorite_position,
+                &settings.home_popular_position,
+                &settings.search_results_per_page,
+                &settings.default_new_resource_visibility.as_str(),
+                &settings.media_webp_quality,
+                &settings.site_name,
+                &settings.site_description,
+                &settings.public_base_url,
+                &settings.nostr_names,
+                &settings.nostr_relays,
+                &settings.live_default_source,
+                &settings.live_default_camera_facing,
+                &settings.live_default_height,
+                &settings.live_default_fps,
+                &settings.live_default_microphone_enabled,
+                &settings.site_icon_key,
+                &settings.site_icon_content_type,
+                &space_slug,
+                &settings.media_strip_location,
+                &settings.search_language,
+                &settings.search_cjk,
+            ],
+        )
+        .await
+        .map_err(db_err)?;
+    reindex_search_documents(&tx, space_slug).await?;
+    write_audit_event(
+        &tx,
+        &AuditEvent {
+            space_slug,
+            actor: change.actor,
+            event_type: "settings.updated",
+            entity_type: "settings",
+            entity_id: space_slug.unwrap_or("default"),
+            payload: json!({ "changed": change.changed }),
+        },
+    )
+    .await?;
+    tx.commit().await.map_err(db_err)
+}
+
+/// Move the space's resources to its search language; rewriting a row
+/// regenerates `search_document` and its index entries.
+async fn reindex_search_documents(
+    tx: &deadpool_postgres::Transaction<'_>,
+    space_slug: Option<&str>,
+) -> Result<(), AppError> {
+    tx.execute(
+        "UPDATE resources r SET search_config = s.search_language::REGCONFIG, search_cjk = s.search_cjk \
+         FROM space_settings s WHERE s.space_id = r.space_id \
+         AND s.space_id = COALESCE((SELECT id FROM spaces WHERE slug = $1::CITEXT), default_space_id()) \
+         AND (r.search_config <> s.search_language::REGCONFIG OR r.search_cjk <> s.search_cjk)",
+        &[&space_slug],
+    )
+    .await
+    .map(|_| ())
+    .map_err(db_err)
+}
+
+fn db_err(error: tokio_postgres::Error) -> AppError {
+    AppError::DatabaseError(error.to_string())
+}
@@ -0,0 +1,94 @@
+//! Revert a live resource to one of its saved snapshots
+
+use super::audit::{write_audit_event, AuditEvent};
+use super::models::Resource;
+use super::resource_support::{
+    current_favorite_state, map_write_error, row_to_resource, RETURNING_RECORD,
+};
+use super::write_actor::{stamp_write_actor, WriteActor};
+use super::write_support::{check_base_snapshot, client, create_snapshot, next_snapshot_number};
+use super::DbPool;
+use crate::error::AppError;
+use serde_json::json;
+
+/// Snapshot columns copied back onto the live row; `owner_note_id` stays immutable.
+const RESTORED_COLUMNS: &str = "alias, title, summary, body, visibility, media_family, \
+file_key, content_type, byte_size, sha256_hex, original_filename, width, height, \
+duration_ms, media_variants, media_details";
+
+/// Request to copy one saved snapshot back onto its live resource.
+pub struct SnapshotRestore<'a> {
+    pub space_slug: &'a str,
+    pub resource_id: &'a str,
+    pub snapshot_number: i32,
+    pub actor: &'a WriteActor,
+    /// Snapshot the caller restored from; stale values fail with a conflict.
+    pub base_snapshot: Option<i32>,
+}
+
+/// Copy a saved snapshot onto the live resource and save the result as a new snapshot.
+///
+/// Returns `None` when the live resource is missing from the space and
+/// `NotFound` when it has no snapshot with that number.
+pub async fn restore_snapshot_in_space(
+    pool: &DbPool,
+    input: &SnapshotRestore<'_>,
+) -> Result<Option<Resource>, AppError> {
+    let id = input.resource_id;
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if current_favorite_state(&tx, id).await?.is_none() {
+        return Ok(None);
+    }
+    let number = next_snapshot_number(&tx, id).await?;
+    check_base_snapshot(input.base_snapshot, number)?;
+    let Some(row) = tx
+        .query_opt(
+            &format!(
+                "UPDATE resources SET ({RESTORED_COLUMNS}, updated_at) = \
+                 (SELECT {RESTORED_COLUMNS}, NOW() FROM resource_snapshots \
+                  WHERE resource_id = $1 AND snapshot_number = $3) \
+                 WHERE id = $1 AND space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
+                 AND deleted_at IS NULL AND EXISTS (SELECT 1 FROM resource_snapshots \
+                  WHERE resource_id = $1 AND snapshot_number = $3) {RETURNING_RECORD}"
+            ),
+            &[&id, &input.space_slug, &input.snapshot_number],
+        )
+        .await
+        .map_err(map_write_error)?
+    else {
+        return Err(AppError::NotFound(format!(
+            "snapshot {} not found for resource '{id}'",
+            input.snapshot_number
+        )));
+    };
+    let resource = row_to_resource(row);
+    create_snapshot(&tx, &resource, number).await?;
+    stamp_write_actor(&tx, &resource.id, input.actor, false).await?;
+    write_audit_event(
+        &tx,
+        &AuditEvent {
+            space_slug: Some(input.space_slug),
+            actor: input.actor,
+            event_type: "resource.snapshot_restored",
+            entity_type: "resource",
+            entity_id: &resource.id,
+            payload: json!({
+                "kind": resource.kind.as_str(),
+                "alias": resource.alias,
+                "title": resource.title,
+                "visibility": resource.visibility,
+                "restored_snapshot": input.snapshot_number,
+                "snapshot_number": number,
+            }),
+        },
+    )
+    .await?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(Some(resource))
+}
@@ -1,7 +1,7 @@
 use super::listing_direction::ListDirection;
 use super::models::{Resource, ResourceSnapshot};
 use super::snapshots_cursor::{decode_snapshot_cursor, encode_snapshot_cursor};
-use super::DbPool;
+use super::{DbPool, Visibility};
 use crate::error::AppError;
 use serde::Serialize;
 
@@ -26,7 +26,7 @@ pub struct SnapshotTarget {
 pub async fn list_resource_snapshots(
     pool: &DbPool,
     resource_id: &str,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
     direction: &ListDirection,
     cursor: Option<&str>,
@@ -34,7 +34,7 @@ pub async fn list_resource_snapshots(
     let limit = limit.clamp(1, MAX_LIMIT);
     let cursor = decode_snapshot_cursor(cursor)?;
     let mut snapshots =
-        query_page(pool, resource_id, include_private, limit, direction, cursor).await?;
+        query_page(pool, resource_id, max_visibility, limit, direction, cursor).await?;
     if snapshots.len() as i64 > limit {
         snapshots.pop();
     }
@@ -45,7 +45,7 @@ pub async fn list_resource_snapshots(
         previous_cursor: edge_cursor(
             pool,
             resource_id,
-            include_private,
+            max_visibility,
             snapshots.first().map(|item| item.snapshot_number),
             true,
         )
@@ -53,7 +53,7 @@ pub async fn list_resource_snapshots(
         next_cursor: edge_cursor(
             pool,
             resource_id,
-            include_private,
+            max_visibility,
             snapshots.last().map(|item| item.snapshot_number),
             false,
         )
@@ -69,24 +69,47 @@ pub async fn get_snapshot_target(
     client(pool).await?.query_opt(
         "SELECT r.id, (SELECT slug::TEXT FROM spaces WHERE id = r.space_id) AS space_slug, \
          r.kind, r.alias, r.title, r.summary, r.body, r.media_family, r.file_key, r.content_type, \
-         r.byte_size, r.sha256_hex, r.original_filename, r.width, r.height, r.duration_ms, r.media_variants, r.owner_note_id, r.is_favorite, r.favorite_position, \
-         (r.visibility = 'private') AS is_private, r.view_count_total, r.last_viewed_at, r.created_at, r.updated_at, s.id AS snapshot_id, s.kind AS snapshot_kind, \
+         r.byte_size, r.sha256_hex, r.original_filename, r.width, r.height, r.duration_ms, r.media_variants, r.media_details, r.owner_note_id, r.is_favorite, r.favorite_position, \
+         r.visibility::TEXT AS visibility, r.view_count_total, r.last_viewed_at, r.created_at, r.updated_at, s.id AS snapshot_id, s.kind AS snapshot_kind, \
          s.snapshot_number, s.alias AS snapshot_alias, s.title AS snapshot_title, s.summary AS snapshot_summary, s.body AS snapshot_body, \
          s.media_family AS snapshot_media_family, s.file_key AS snapshot_file_key, s.content_type AS snapshot_content_type, \
          s.byte_size AS snapshot_byte_size, s.sha256_hex AS snapshot_sha256_hex, s.original_filename AS snapshot_original_filename, \
-         s.width AS snapshot_width, s.height AS snapshot_height, s.duration_ms AS snapshot_duration_ms, s.media_variants AS snapshot_media_variants, \
+         s.width AS snapshot_width, s.height AS snapshot_height, s.duration_ms AS snapshot_duration_ms, s.media_variants AS snapshot_media_variants, s.media_details AS snapshot_media_details, \
          s.owner_note_id AS snapshot_owner_note_id, \
          (SELECT slug::TEXT FROM spaces WHERE id = s.space_id) AS snapshot_space_slug, \
-         (s.visibility = 'private') AS snapshot_is_private, s.created_at AS snapshot_created_at \
+         s.visibility::TEXT AS snapshot_visibility, s.created_at AS snapshot_created_at \
          FROM resource_snapshots s JOIN resources r ON r.id = s.resource_id WHERE s.id = $1 AND r.deleted_at IS NULL",
         &[&snapshot_id],
     ).await.map(|row| row.map(row_to_snapshot_target)).map_err(|e| AppError::DatabaseError(e.to_string()))
 }
 
+/// One saved snapshot by number when the reader's ceiling covers its visibility.
+pub async fn get_resource_snapshot(
+    pool: &DbPool,
+    resource_id: &str,
+    snapshot_number: i32,
+    max_visibility: Visibility,
+) -> Result<Option<ResourceSnapshot>, AppError> {
+    client(pool)
+        .await?
+        .query_opt(
+            "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = space_id) AS space_slug, \
+             kind, snapshot_number, alias, title, summary, body, media_family, file_key, content_type, byte_size, \
+             sha256_hex, original_filename, width, height, duration_ms, media_variants, media_details, owner_note_id, \
+             visibility::TEXT AS visibility, created_at \
+             FROM resource_snapshots WHERE resource_id = $1 AND snapshot_number = $2 \
+             AND visibility <= $3::TEXT::resource_visibility",
+            &[&resource_id, &snapshot_number, &max_visibility.as_str()],
+        )
+        .await
+        .map(|row| row.map(row_to_snapshot))
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
 async fn query_page(
     pool: &DbPool,
     resource_id: &str,
-    include_private: bool,
+    max_visibility: Visibility,
     limit: i64,
     direction: &ListDirection,
     cursor: Option<i32>,
@@ -98,15 +121,20 @@ async fn query_page(
     let sql = format!(
         "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = space_id) AS space_slug, \
          kind, snapshot_number, alias, title, summary, body, media_family, file_key, content_type, byte_size, \
-         sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, \
-         (visibility = 'private') AS is_private, created_at \
-         FROM resource_snapshots WHERE resource_id = $1 AND ($2 OR visibility = 'public') AND ($3::INT IS NULL OR {predicate}) ORDER BY {order} LIMIT $4"
+         sha256_hex, original_filename, width, height, duration_ms, media_variants, media_details, owner_note_id, \
+         visibility::TEXT AS visibility, created_at \
+         FROM resource_snapshots WHERE resource_id = $1 AND visibility <= $2::TEXT::resource_visibility AND ($3::INT IS NULL OR {predicate}) ORDER BY {order} LIMIT $4"
     );
     client(pool)
         .await?
         .query(
             &sql,
-            &[&resource_id, &include_private, &cursor, &(limit + 1)],
+            &[
+                &resource_id,
+                &max_visibility.as_str(),
+                &cursor,
+                &(limit + 1),
+            ],
         )
         .await
         .map(|rows| rows.into_iter().map(row_to_snapshot).collect())
@@ -116,7 +144,7 @@ async fn query_page(
 async fn edge_cursor(
     pool: &DbPool,
     resource_id: &str,
-    include_private: bool,
+    max_visibility: Visibility,
     snapshot_number: Option<i32>,
     previous: bool,
 ) -> Result<Option<String>, AppError> {
@@ -129,11 +157,14 @@ async fn edge_cursor(
         "snapshot_number < $3"
     };
     let sql = format!(
-        "SELECT 1 FROM resource_snapshots WHERE resource_id = $1 AND ($2 OR visibility = 'public') AND {predicate} LIMIT 1"
+        "SELECT 1 FROM resource_snapshots WHERE resource_id = $1 AND visibility <= $2::TEXT::resource_visibility AND {predicate} LIMIT 1"
     );
     client(pool)
         .await?
-        .query_opt(&sql, &[&resource_id, &include_private, &snapshot_number])
+        .query_opt(
+            &sql,
+            &[&resource_id, &max_visibility.as_str(), &snapshot_number],
+        )
         .await
         .map(|row| row.map(|_| encode_snapshot_cursor(snapshot_number)))
         .map_err(|e| AppError::DatabaseError(e.to_string()))
@@ -1,7 +1,8 @@
 use super::SnapshotTarget;
-use crate::media::media_variants_from_json;
+use crate::media::{media_details_from_json, media_variants_from_json};
 use crate::web::db::models::{MediaFamily, ResourceKind, ResourceSnapshot};
 use crate::web::db::resource_support::row_to_resource;
+use crate::web::db::Visibility;
 
 pub(super) fn row_to_snapshot(row: tokio_postgres::Row) -> ResourceSnapshot {
     ResourceSnapshot {
@@ -23,8 +24,9 @@ pub(super) fn row_to_snapshot(row: tokio_postgres::Row) -> ResourceSnapshot {
         height: row.get("height"),
         duration_ms: row.get("duration_ms"),
         media_variants: media_variants_from_json(row.get("media_variants")),
+        media_details: media_details_from_json(row.get("media_details")),
         owner_note_id: row.get("owner_note_id"),
-        is_private: row.get("is_private"),
+        visibility: Visibility::from_db(row.get("visibility")),
         created_at: row.get("created_at"),
     }
 }
@@ -51,8 +53,9 @@ pub(super) fn row_to_snapshot_target(row: tokio_postgres::Row) -> SnapshotTarget
             height: row.get("snapshot_height"),
             duration_ms: row.get("snapshot_duration_ms"),
             media_variants: media_variants_from_json(row.get("snapshot_media_variants")),
+            media_details: media_details_from_json(row.get("snapshot_media_details")),
             owner_note_id: row.get("snapshot_owner_note_id"),
-            is_private: row.get("snapshot_is_private"),
+            visibility: Visibility::from_db(row.get("snapshot_visibility")),
             created_at: row.get("snapshot_created_at"),
         },
     }
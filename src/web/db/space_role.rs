@@ -0,0 +1,62 @@
+//! Space membership roles and their permission ladder
+
+use serde::Serialize;
+
+/// Roles ordered from least to most privileged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpaceRole {
+    Viewer,
+    Editor,
+    Admin,
+    Owner,
+}
+
+impl SpaceRole {
+    /// Roles an admin may grant; ownership never moves through invites.
+    pub const ASSIGNABLE: [Self; 3] = [Self::Viewer, Self::Editor, Self::Admin];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "viewer" => Some(Self::Viewer),
+            "editor" => Some(Self::Editor),
+            "admin" => Some(Self::Admin),
+            "owner" => Some(Self::Owner),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Editor => "editor",
+            Self::Admin => "admin",
+            Self::Owner => "owner",
+        }
+    }
+
+    /// Editors and above may change resources.
+    pub fn can_write(self) -> bool {
+        self >= Self::Editor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpaceRole;
+
+    #[test]
+    fn roles_round_trip_and_order() {
+        for role in [
+            SpaceRole::Owner,
+            SpaceRole::Admin,
+            SpaceRole::Editor,
+            SpaceRole::Viewer,
+        ] {
+            assert_eq!(SpaceRole::parse(role.as_str()), Some(role));
+        }
+        assert_eq!(SpaceRole::parse("guest"), None);
+        assert!(SpaceRole::Editor.can_write() && !SpaceRole::Viewer.can_write());
+        assert!(SpaceRole::Owner > SpaceRole::Admin && SpaceRole::Admin > SpaceRole::Editor);
+    }
+}
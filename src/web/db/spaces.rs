@@ -18,6 +18,19 @@ pub async fn require_space(pool: &DbPool, slug: &str) -> Result<(), AppError> {
     }
 }
 
+/// Slug of the space that unscoped routes such as `/` serve.
+pub async fn default_space_slug(pool: &DbPool) -> Result<String, AppError> {
+    client(pool)
+        .await?
+        .query_one(
+            "SELECT slug::TEXT FROM spaces WHERE id = default_space_id()",
+            &[],
+        )
+        .await
+        .map(|row| row.get(0))
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
 async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError> {
     pool.get()
         .await
@@ -0,0 +1,48 @@
+//! One-time rewrite of summaries that still carry wiki-link markup
+
+use super::write_support::client;
+use super::DbPool;
+use crate::core::derive_summary;
+use crate::error::AppError;
+
+/// Re-derive summaries saved before wiki references were reduced to plain
+/// text; runs once.
+pub(super) async fn backfill_wiki_summaries(pool: &DbPool) -> Result<(), AppError> {
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let claimed = tx
+        .execute(
+            "INSERT INTO platform_migrations (key) VALUES ('wiki_summary_backfill') \
+             ON CONFLICT DO NOTHING",
+            &[],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if claimed == 0 {
+        return Ok(());
+    }
+    for table in ["resources", "resource_snapshots"] {
+        let rows = tx
+            .query(
+                &format!("SELECT id, body FROM {table} WHERE strpos(summary, '[[') > 0"),
+                &[],
+            )
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        for row in rows {
+            let (id, body): (String, String) = (row.get("id"), row.get("body"));
+            tx.execute(
+                &format!("UPDATE {table} SET summary = $2 WHERE id = $1"),
+                &[&id, &derive_summary(&body)],
+            )
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+    }
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
@@ -0,0 +1,140 @@
+//! Permanent removal of trashed resources and their orphaned storage keys
+
+use super::audit::audit_resource;
+use super::media_blobs::prune_unreferenced_blobs;
+use super::resource_support::{row_to_resource, SELECT_RECORD};
+use super::write_actor::WriteActor;
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use tracing::warn;
+
+/// Storage keys of the resource and snapshot rows matching `filter` on
+/// `src(resource_id, file_key)`, including rendition and HLS keys nested in
+/// `media_variants`, as `refs(resource_id, key)`. The filter is pushed into
+/// both tables so only matching rows are expanded.
+fn storage_refs(filter: &str) -> String {
+    format!(
+        "(SELECT src.resource_id, k.key FROM (\
+         SELECT id AS resource_id, file_key, media_variants FROM resources \
+         UNION ALL SELECT resource_id, file_key, media_variants FROM resource_snapshots) src \
+         CROSS JOIN LATERAL (SELECT src.file_key AS key UNION ALL \
+         SELECT jsonb_path_query(COALESCE(src.media_variants, '{{}}'::JSONB), 'strict $.**.key') \
+         #>> '{{}}') k WHERE {filter} AND k.key IS NOT NULL) refs"
+    )
+}
+
+/// Outcome of purging one trashed resource.
+#[derive(Clone, Debug, Default)]
+pub struct PurgedResource {
+    pub id: String,
+    /// Storage keys that no remaining resource or snapshot references.
+    pub orphaned_keys: Vec<String>,
+}
+
+/// Permanently delete one trashed resource with its snapshots.
+pub async fn purge_resource_in_space(
+    pool: &DbPool,
+    space_slug: &str,
+    id: &str,
+    actor: &WriteActor,
+) -> Result<Option<PurgedResource>, AppError> {
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let Some(row) = tx
+        .query_opt(
+            &format!(
+                "{SELECT_RECORD} FROM resources WHERE id = $1 \
+                 AND space_id = (SELECT id FROM spaces WHERE slug = $2::CITEXT) \
+                 AND deleted_at IS NOT NULL FOR UPDATE"
+            ),
+            &[&id, &space_slug],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let resource = row_to_resource(row);
+    let orphaned_keys = delete_with_orphans(&tx, &resource.id).await?;
+    audit_resource(&tx, space_slug, actor, "resource.purged", &resource).await?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(Some(PurgedResource {
+        id: resource.id,
+        orphaned_keys,
+    }))
+}
+
+/// Purge every resource trashed longer than `retention_days` ago, across
+/// spaces. Each resource commits on its own; one that fails is logged and
+/// left for the next sweep instead of blocking the rest.
+pub async fn purge_expired_trash(
+    pool: &DbPool,
+    retention_days: i32,
+) -> Result<Vec<PurgedResource>, AppError> {
+    let expired = client(pool)
+        .await?
+        .query(
+            "SELECT id, (SELECT slug::TEXT FROM spaces WHERE id = resources.space_id) AS space_slug \
+             FROM resources WHERE deleted_at < NOW() - make_interval(days => $1) \
+             ORDER BY deleted_at ASC, id ASC",
+            &[&retention_days],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let mut purged = Vec::new();
+    for row in expired {
+        let (id, space_slug): (String, String) = (row.get("id"), row.get("space_slug"));
+        let operator = WriteActor::default();
+        match purge_resource_in_space(pool, &space_slug, &id, &operator).await {
+            Ok(Some(item)) => purged.push(item),
+            Ok(None) => {}
+            Err(error) => warn!(resource = %id, error = %error, "trash purge failed"),
+        }
+    }
+    Ok(purged)
+}
+
+async fn delete_with_orphans<C: GenericClient>(db: &C, id: &str) -> Result<Vec<String>, AppError> {
+    let candidates: Vec<String> = db
+        .query(
+            &format!(
+                "SELECT DISTINCT key FROM {}",
+                storage_refs("src.resource_id = $1")
+            ),
+            &[&id],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|row| row.get("key"))
+        .collect();
+    db.execute("DELETE FROM resources WHERE id = $1", &[&id])
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    prune_unreferenced_blobs(db).await?;
+    if candidates.is_empty() {
+        return Ok(candidates);
+    }
+    // Renditions are only ever stored next to the original they were made
+    // from, so rows sharing one of the candidate originals hold every
+    // remaining reference.
+    let remaining = storage_refs("src.file_key = ANY($1::TEXT[])");
+    db.query(
+        &format!(
+            "SELECT c.key FROM UNNEST($1::TEXT[]) AS c(key) \
+             WHERE NOT EXISTS (SELECT 1 FROM {remaining} WHERE refs.key = c.key) \
+             ORDER BY c.key"
+        ),
+        &[&candidates],
+    )
+    .await
+    .map(|rows| rows.into_iter().map(|row| row.get("key")).collect())
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
@@ -0,0 +1,91 @@
+//! Opening upload sessions within each space's quota
+
+use super::upload_sessions::{row_to_session, UploadSession, SESSION_COLUMNS, SESSION_TTL_HOURS};
+use super::write_actor::WriteActor;
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use uuid::Uuid;
+
+/// Unexpired sessions one space may hold at once.
+const MAX_OPEN_UPLOAD_SESSIONS: i64 = 16;
+
+pub struct UploadSessionCreate<'a> {
+    pub id: &'a str,
+    pub space_slug: Option<&'a str>,
+    pub original_filename: &'a str,
+    pub content_type: &'a str,
+    pub byte_size: i64,
+    pub sha256_hex: Option<&'a str>,
+    pub actor: &'a WriteActor,
+    /// Declared bytes all unexpired sessions of the space may add up to.
+    pub space_byte_limit: i64,
+}
+
+/// Open a session owned by `input.actor`. The space row is locked while its
+/// open sessions are counted, so concurrent creates cannot overshoot the caps.
+pub async fn create_upload_session(
+    pool: &DbPool,
+    input: &UploadSessionCreate<'_>,
+) -> Result<UploadSession, AppError> {
+    let mut db = client(pool).await?;
+    let tx = db
+        .transaction()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let usage = tx
+        .query_one(
+            "WITH space AS (SELECT id FROM spaces WHERE id = COALESCE( \
+              (SELECT id FROM spaces WHERE slug = $1::CITEXT), default_space_id()) \
+              FOR NO KEY UPDATE) \
+             SELECT space.id, COUNT(s.id) AS open, COALESCE(SUM(s.byte_size), 0)::BIGINT AS reserved \
+             FROM space LEFT JOIN upload_sessions s \
+              ON s.space_id = space.id AND s.expires_at > NOW() \
+             GROUP BY space.id",
+            &[&input.space_slug],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if usage.get::<_, i64>("open") >= MAX_OPEN_UPLOAD_SESSIONS {
+        return Err(AppError::Conflict {
+            message: format!(
+                "this space already has {MAX_OPEN_UPLOAD_SESSIONS} open uploads; \
+                 finish or cancel one first"
+            ),
+            current_snapshot: None,
+        });
+    }
+    if usage.get::<_, i64>("reserved") + input.byte_size > input.space_byte_limit {
+        return Err(AppError::PayloadTooLarge(
+            "open uploads in this space would exceed its spool limit".to_string(),
+        ));
+    }
+    let space_id: Uuid = usage.get("id");
+    let row = tx
+        .query_one(
+            &format!(
+                "INSERT INTO upload_sessions (id, space_id, original_filename, content_type, \
+                 byte_size, sha256_hex, expires_at, created_by_user_id, \
+                 created_by_service_account_id) \
+                 VALUES ($1, $2, $3, $4, $5, $6, NOW() + make_interval(hours => $7), $8, $9) \
+                 RETURNING {SESSION_COLUMNS}"
+            ),
+            &[
+                &input.id,
+                &space_id,
+                &input.original_filename,
+                &input.content_type,
+                &input.byte_size,
+                &input.sha256_hex,
+                &SESSION_TTL_HOURS,
+                &input.actor.user_id,
+                &input.actor.service_account_id,
+            ],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    tx.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(row_to_session(row))
+}
@@ -0,0 +1,145 @@
+//! Resumable upload sessions spooled on local disk until finalized
+
+use super::write_actor::WriteActor;
+use super::write_support::client;
+use super::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+
+/// Idle sessions expire this long after their last chunk.
+pub(super) const SESSION_TTL_HOURS: i32 = 24;
+/// A chunk claim older than this is assumed abandoned by a dropped request.
+const STALE_CHUNK_MINUTES: i32 = 15;
+
+pub(super) const SESSION_COLUMNS: &str =
+    "id, original_filename, content_type, byte_size, sha256_hex, \
+     received_bytes, locked_at, expires_at";
+
+#[derive(Debug, Clone)]
+pub struct UploadSession {
+    pub id: String,
+    pub original_filename: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    /// Client-declared checksum verified when the session is finalized.
+    pub sha256_hex: Option<String>,
+    pub received_bytes: i64,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl UploadSession {
+    pub fn is_complete(&self) -> bool {
+        self.received_bytes == self.byte_size
+    }
+}
+
+/// Unexpired session `actor` created in the space; `None` space is the
+/// default one. Sessions of other members look missing.
+pub async fn get_upload_session(
+    pool: &DbPool,
+    space_slug: Option<&str>,
+    id: &str,
+    actor: &WriteActor,
+) -> Result<Option<UploadSession>, AppError> {
+    let row = client(pool)
+        .await?
+        .query_opt(
+            &format!(
+                "SELECT {SESSION_COLUMNS} FROM upload_sessions \
+                 WHERE id = $2 AND expires_at > NOW() AND space_id = COALESCE( \
+                  (SELECT id FROM spaces WHERE slug = $1::CITEXT), default_space_id()) \
+                 AND created_by_user_id IS NOT DISTINCT FROM $3 \
+                 AND created_by_service_account_id IS NOT DISTINCT FROM $4"
+            ),
+            &[&space_slug, &id, &actor.user_id, &actor.service_account_id],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(row.map(row_to_session))
+}
+
+/// Lock the session at `offset` for one chunk, or at `byte_size` for
+/// finalizing; `None` when the offset is stale or the session is busy.
+pub async fn claim_upload_session(
+    pool: &DbPool,
+    id: &str,
+    offset: i64,
+) -> Result<Option<UploadSession>, AppError> {
+    let row = client(pool)
+        .await?
+        .query_opt(
+            &format!(
+                "UPDATE upload_sessions SET locked_at = NOW(), updated_at = NOW() \
+                 WHERE id = $1 AND received_bytes = $2 AND expires_at > NOW() \
+                 AND (locked_at IS NULL OR locked_at < NOW() - make_interval(mins => $3)) \
+                 RETURNING {SESSION_COLUMNS}"
+            ),
+            &[&id, &offset, &STALE_CHUNK_MINUTES],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(row.map(row_to_session))
+}
+
+/// Record bytes written under `claim`, unlock, and extend the expiry;
+/// `None` when a newer claim took the session over.
+pub async fn release_upload_session(
+    pool: &DbPool,
+    claim: &UploadSession,
+    received_bytes: i64,
+) -> Result<Option<UploadSession>, AppError> {
+    let row = client(pool)
+        .await?
+        .query_opt(
+            &format!(
+                "UPDATE upload_sessions SET received_bytes = $3, locked_at = NULL, \
+                 updated_at = NOW(), expires_at = NOW() + make_interval(hours => $4) \
+                 WHERE id = $1 AND locked_at = $2 RETURNING {SESSION_COLUMNS}"
+            ),
+            &[
+                &claim.id,
+                &claim.locked_at,
+                &received_bytes,
+                &SESSION_TTL_HOURS,
+            ],
+        )
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(row.map(row_to_session))
+}
+
+pub async fn delete_upload_session(pool: &DbPool, id: &str) -> Result<(), AppError> {
+    client(pool)
+        .await?
+        .execute("DELETE FROM upload_sessions WHERE id = $1", &[&id])
+        .await
+        .map(|_| ())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Delete expired sessions, returning their ids so spooled bytes can go too.
+pub async fn delete_expired_upload_sessions(pool: &DbPool) -> Result<Vec<String>, AppError> {
+    client(pool)
+        .await?
+        .query(
+            "DELETE FROM upload_sessions WHERE expires_at <= NOW() RETURNING id",
+            &[],
+        )
+        .await
+        .map(|rows| rows.iter().map(|row| row.get("id")).collect())
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+pub(super) fn row_to_session(row: tokio_postgres::Row) -> UploadSession {
+    UploadSession {
+        id: row.get("id"),
+        original_filename: row.get("original_filename"),
+        content_type: row.get("content_type"),
+        byte_size: row.get("byte_size"),
+        sha256_hex: row.get("sha256_hex"),
+        received_bytes: row.get("received_bytes"),
+        locked_at: row.get("locked_at"),
+        expires_at: row.get("expires_at"),
+    }
+}
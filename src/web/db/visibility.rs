@@ -0,0 +1,78 @@
+//! Resource visibility levels stored as `resource_visibility`
+
+use serde::{Deserialize, Serialize};
+
+/// Visibility levels ordered from widest to narrowest audience.
+///
+/// The order matches the `resource_visibility` enum, so readers filter with
+/// `visibility <= $n::TEXT::resource_visibility` against their ceiling.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Space,
+    Private,
+}
+
+impl Visibility {
+    pub const ALL: [Self; 3] = [Self::Public, Self::Space, Self::Private];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "public" => Some(Self::Public),
+            "space" => Some(Self::Space),
+            "private" => Some(Self::Private),
+            _ => None,
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        Self::parse(value).unwrap_or(Self::Private)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Space => "space",
+            Self::Private => "private",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Public => "Public",
+            Self::Space => "Space",
+            Self::Private => "Private",
+        }
+    }
+
+    /// Legacy boolean inputs only distinguish private from public.
+    pub fn from_is_private(is_private: bool) -> Self {
+        if is_private {
+            Self::Private
+        } else {
+            Self::Public
+        }
+    }
+
+    pub fn is_public(self) -> bool {
+        self == Self::Public
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Visibility;
+
+    #[test]
+    fn visibility_round_trips_and_orders_by_audience() {
+        for visibility in Visibility::ALL {
+            assert_eq!(Visibility::parse(visibility.as_str()), Some(visibility));
+        }
+        assert_eq!(Visibility::parse("members"), None);
+        assert_eq!(Visibility::from_db("unknown"), Visibility::Private);
+        assert!(Visibility::Public < Visibility::Space && Visibility::Space < Visibility::Private);
+        assert_eq!(Visibility::from_is_private(true), Visibility::Private);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::AppError;
+use deadpool_postgres::GenericClient;
+use uuid::Uuid;
+
+/// Browser user or service account responsible for one write.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WriteActor {
+    pub user_id: Option<Uuid>,
+    pub service_account_id: Option<Uuid>,
+}
+
+impl WriteActor {
+    pub fn user(user_id: Uuid) -> Self {
+        Self {
+            user_id: Some(user_id),
+            service_account_id: None,
+        }
+    }
+
+    pub fn service(service_account_id: Uuid) -> Self {
+        Self {
+            user_id: None,
+            service_account_id: Some(service_account_id),
+        }
+    }
+}
+
+/// Stamp actor columns on a resource and its newest saved snapshot.
+pub(super) async fn stamp_write_actor<C: GenericClient>(
+    db: &C,
+    resource_id: &str,
+    actor: &WriteActor,
+    created: bool,
+) -> Result<(), AppError> {
+    db.execute(
+        "UPDATE resources SET updated_by_user_id = $2, updated_by_service_account_id = $3, \
+         created_by_user_id = CASE WHEN $4 THEN $2 ELSE created_by_user_id END, \
+         created_by_service_account_id = CASE WHEN $4 THEN $3 ELSE created_by_service_account_id END \
+         WHERE id = $1",
+        &[&resource_id, &actor.user_id, &actor.service_account_id, &created],
+    )
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    db.execute(
+        "UPDATE resource_snapshots SET created_by_user_id = $2, created_by_service_account_id = $3 \
+         WHERE resource_id = $1 AND snapshot_number = \
+         (SELECT MAX(snapshot_number) FROM resource_snapshots WHERE resource_id = $1)",
+        &[&resource_id, &actor.user_id, &actor.service_account_id],
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
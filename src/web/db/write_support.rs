@@ -1,8 +1,11 @@
 use super::models::Resource;
 use super::resource_ids::next_resource_id;
+use super::resource_links::sync_resource_links;
+use super::resource_metadata::{body_metadata, sync_resource_metadata};
+use super::search_embeddings::enqueue_search_embedding;
 use super::DbPool;
 use crate::error::AppError;
-use crate::media::media_variants_to_json;
+use crate::media::{media_details_to_json, media_variants_to_json};
 use deadpool_postgres::GenericClient;
 
 pub async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError> {
@@ -11,6 +14,27 @@ pub async fn client(pool: &DbPool) -> Result<deadpool_postgres::Object, AppError
         .map_err(|e| AppError::DatabaseError(e.to_string()))
 }
 
+/// Newest saved snapshot number for one resource, or `0` before the first save.
+pub async fn current_snapshot_number(pool: &DbPool, resource_id: &str) -> Result<i32, AppError> {
+    next_snapshot_number(&client(pool).await?, resource_id)
+        .await
+        .map(|next| next - 1)
+}
+
+/// Reject a write whose base snapshot is no longer the newest saved snapshot.
+pub(super) fn check_base_snapshot(base: Option<i32>, next_number: i32) -> Result<(), AppError> {
+    match base {
+        Some(base) if base != next_number - 1 => Err(AppError::Conflict {
+            message: format!(
+                "resource changed since snapshot {base}; latest snapshot is {}",
+                next_number - 1
+            ),
+            current_snapshot: Some(next_number - 1),
+        }),
+        _ => Ok(()),
+    }
+}
+
 pub async fn next_snapshot_number<C: GenericClient>(
     db: &C,
     resource_id: &str,
@@ -25,6 +49,8 @@ pub async fn next_snapshot_number<C: GenericClient>(
     .map_err(|e| AppError::DatabaseError(e.to_string()))
 }
 
+/// Save one immutable snapshot, refresh the outgoing links and front matter
+/// metadata of the live body, and queue its search embedding.
 pub async fn create_snapshot<C: GenericClient>(
     db: &C,
     resource: &Resource,
@@ -35,9 +61,10 @@ pub async fn create_snapshot<C: GenericClient>(
     db.execute(
         "INSERT INTO resource_snapshots \
          (id, space_id, resource_id, kind, snapshot_number, alias, title, summary, body, media_family, file_key, \
-          content_type, byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, visibility) \
+          content_type, byte_size, sha256_hex, original_filename, width, height, duration_ms, media_variants, owner_note_id, visibility, media_details, \
+          metadata) \
          VALUES ($1, (SELECT space_id FROM resources WHERE id = $2), $2, $3, $4, $5, $6, $7, $8, $9, \
-          $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, CASE WHEN $20 THEN 'private'::resource_visibility ELSE 'public'::resource_visibility END)",
+          $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20::TEXT::resource_visibility, $21, $22)",
         &[
             &snapshot_id,
             &resource.id,
@@ -58,10 +85,33 @@ pub async fn create_snapshot<C: GenericClient>(
             &resource.duration_ms,
             &media_variants,
             &resource.owner_note_id,
-            &resource.is_private,
+            &resource.visibility.as_str(),
+            &media_details_to_json(&resource.media_details),
+            &body_metadata(&resource.body),
         ],
     )
     .await
-    .map(|_| ())
-    .map_err(|e| AppError::DatabaseError(e.to_string()))
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    sync_resource_links(db, resource).await?;
+    sync_resource_metadata(db, resource).await?;
+    enqueue_search_embedding(db, &resource.id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_base_snapshot;
+    use crate::error::AppError;
+
+    #[test]
+    fn base_snapshot_must_match_latest() {
+        assert!(check_base_snapshot(None, 4).is_ok());
+        assert!(check_base_snapshot(Some(3), 4).is_ok());
+        assert!(matches!(
+            check_base_snapshot(Some(2), 4),
+            Err(AppError::Conflict {
+                current_snapshot: Some(3),
+                ..
+            })
+        ));
+    }
 }
@@ -11,19 +11,35 @@ mod metadata;
 
 const MAX_BYTES: usize = 262_144;
 const USER_AGENT: &str = "kjxlkj-embed-unfurler/1.0";
+const PREVIEW_LIMIT: usize = 8;
 
+/// Preview path: unfurl a few stale URLs inline so the rendered preview shows cards.
 pub async fn refresh_body_embeds(
     pool: &DbPool,
     body: &str,
     public_base_url: Option<&str>,
 ) -> Result<(), AppError> {
-    let urls = crate::core::external_embed_urls(body, public_base_url);
-    let stale = db::stale_external_embed_urls(pool, &urls).await?;
-    for url in stale
-        .into_iter()
-        .filter(|url| metadata::should_unfurl(url))
-        .take(8)
-    {
+    let stale = stale_body_urls(pool, body, public_base_url).await?;
+    unfurl_urls(pool, &stale[..stale.len().min(PREVIEW_LIMIT)]).await
+}
+
+/// Save path: hand every stale URL in the body to the background job queue.
+pub async fn queue_body_embeds(
+    pool: &DbPool,
+    resource_id: Option<&str>,
+    body: &str,
+    public_base_url: Option<&str>,
+) -> Result<(), AppError> {
+    let stale = stale_body_urls(pool, body, public_base_url).await?;
+    if stale.is_empty() {
+        return Ok(());
+    }
+    db::enqueue_embed_unfurl(pool, resource_id, &stale).await
+}
+
+/// Fetch and cache the URLs that are still stale; per-URL failures are cached as errors.
+pub async fn unfurl_urls(pool: &DbPool, urls: &[String]) -> Result<(), AppError> {
+    for url in db::stale_external_embed_urls(pool, urls).await? {
         let provider = metadata::provider_label(&url);
         match fetch_embed(&url).await {
             Ok(embed) => db::upsert_external_embed(pool, &embed).await?,
@@ -33,6 +49,19 @@ pub async fn refresh_body_embeds(
     Ok(())
 }
 
+async fn stale_body_urls(
+    pool: &DbPool,
+    body: &str,
+    public_base_url: Option<&str>,
+) -> Result<Vec<String>, AppError> {
+    let urls = crate::core::external_embed_urls(body, public_base_url);
+    Ok(db::stale_external_embed_urls(pool, &urls)
+        .await?
+        .into_iter()
+        .filter(|url| metadata::should_unfurl(url))
+        .collect())
+}
+
 async fn fetch_embed(url: &str) -> Result<ExternalEmbed, String> {
     let parsed = validate_url(url)?;
     let host = parsed.host_str().ok_or("missing host")?.to_string();
@@ -0,0 +1,183 @@
+//! Atom, RSS, and JSON Feed documents for public space resources
+
+use super::markdown_cards::esc;
+use super::site::SiteContext;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use url::form_urlencoded::byte_serialize;
+
+/// Newest resources listed in one feed document.
+pub(crate) const FEED_LIMIT: i64 = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FeedFormat {
+    Atom,
+    Rss,
+    Json,
+}
+
+impl FeedFormat {
+    pub(crate) const ALL: [Self; 3] = [Self::Atom, Self::Rss, Self::Json];
+
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Self::Atom => "atom",
+            Self::Rss => "rss",
+            Self::Json => "json",
+        }
+    }
+
+    pub(crate) fn mime(self) -> &'static str {
+        match self {
+            Self::Atom => "application/atom+xml",
+            Self::Rss => "application/rss+xml",
+            Self::Json => "application/feed+json",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Atom => "Atom",
+            Self::Rss => "RSS",
+            Self::Json => "JSON Feed",
+        }
+    }
+}
+
+/// A feed with every URL already absolute.
+pub(crate) struct Feed {
+    pub title: String,
+    pub description: String,
+    pub home_url: String,
+    /// Feed URL without its format extension.
+    pub feed_url: String,
+    pub updated: DateTime<Utc>,
+    pub entries: Vec<FeedEntry>,
+}
+
+pub(crate) struct FeedEntry {
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+    pub content_html: String,
+    pub tags: Vec<String>,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+/// Feed path without extension: `/{space}/feed` or `/{space}/tags/{tag}/feed`.
+pub(crate) fn feed_path(space_slug: &str, tag: Option<&str>) -> String {
+    match tag {
+        Some(tag) => format!(
+            "/{space_slug}/tags/{}/feed",
+            byte_serialize(tag.as_bytes()).collect::<String>()
+        ),
+        None => format!("/{space_slug}/feed"),
+    }
+}
+
+/// `<link rel="alternate">` tags for every format, or nothing when the site
+/// has no public base URL to build feed URLs from.
+pub(crate) fn alternate_links(site: &SiteContext, space_slug: &str, tag: Option<&str>) -> String {
+    let Some(feed_url) = site.absolute_url(&feed_path(space_slug, tag)) else {
+        return String::new();
+    };
+    let title = feed_title(&site.site_name, tag);
+    FeedFormat::ALL
+        .iter()
+        .map(|format| {
+            format!(
+                r#"<link rel="alternate" type="{}" title="{} ({})" href="{}.{}">"#,
+                format.mime(),
+                esc(&title),
+                format.label(),
+                esc(&feed_url),
+                format.extension(),
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn feed_title(site_name: &str, tag: Option<&str>) -> String {
+    match tag {
+        Some(tag) => format!("{site_name} #{tag}"),
+        None => site_name.to_string(),
+    }
+}
+
+pub(crate) fn feed_body(feed: &Feed, format: FeedFormat) -> String {
+    match format {
+        FeedFormat::Atom => super::feed_xml::atom(feed),
+        FeedFormat::Rss => super::feed_xml::rss(feed),
+        FeedFormat::Json => json_feed(feed),
+    }
+}
+
+fn json_feed(feed: &Feed) -> String {
+    let items = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "id": entry.url,
+                "url": entry.url,
+                "title": entry.title,
+                "summary": entry.summary,
+                "content_html": entry.content_html,
+                "date_published": entry.published.to_rfc3339(),
+                "date_modified": entry.updated.to_rfc3339(),
+                "tags": entry.tags,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "description": feed.description,
+        "home_page_url": feed.home_url,
+        "feed_url": format!("{}.json", feed.feed_url),
+        "items": items,
+    })
+    .to_string()
+}
+
+/// Rewrite root-relative `href`, `src`, `poster`, and `srcset` URLs so the
+/// HTML still resolves when a reader shows it outside the site.
+pub(crate) fn absolute_html(html: &str, base: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some((at, name)) = next_url_attribute(rest) {
+        let value_start = at + name.len() + 2;
+        out.push_str(&rest[..value_start]);
+        rest = &rest[value_start..];
+        let end = rest.find('"').unwrap_or(rest.len());
+        let value = &rest[..end];
+        if name == "srcset" {
+            let candidates = value.split(", ").map(|item| absolute_path(item, base));
+            out.push_str(&candidates.collect::<Vec<_>>().join(", "));
+        } else {
+            out.push_str(&absolute_path(value, base));
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn next_url_attribute(html: &str) -> Option<(usize, &'static str)> {
+    ["href", "src", "poster", "srcset"]
+        .into_iter()
+        .filter_map(|name| {
+            let marker = format!(r#" {name}=""#);
+            html.find(&marker).map(|at| (at + 1, name))
+        })
+        .min_by_key(|(at, _)| *at)
+}
+
+fn absolute_path(value: &str, base: &str) -> String {
+    if value.starts_with('/') && !value.starts_with("//") {
+        format!("{base}{value}")
+    } else {
+        value.to_string()
+    }
+}
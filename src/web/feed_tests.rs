@@ -0,0 +1,85 @@
+use super::feed::*;
+use super::site::SiteContext;
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+
+fn sample_feed() -> Feed {
+    let published = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+    let updated = Utc.with_ymd_and_hms(2026, 3, 2, 10, 30, 0).unwrap();
+    Feed {
+        title: "Launchpad".to_string(),
+        description: "Notes & media".to_string(),
+        home_url: "https://example.com/alice".to_string(),
+        feed_url: "https://example.com/alice/feed".to_string(),
+        updated,
+        entries: vec![FeedEntry {
+            url: "https://example.com/alice/trip-log".to_string(),
+            title: "Trip <Log>".to_string(),
+            summary: "Day one".to_string(),
+            content_html: r#"<p>Hi <img src="https://example.com/alice/x/file"></p>"#.to_string(),
+            tags: vec!["travel".to_string()],
+            published,
+            updated,
+        }],
+    }
+}
+
+fn site(public_base_url: Option<&str>) -> SiteContext {
+    SiteContext {
+        site_name: "Launchpad".to_string(),
+        site_description: String::new(),
+        public_base_url: public_base_url.map(str::to_string),
+    }
+}
+
+#[test]
+fn absolute_html_rewrites_root_relative_urls_only() {
+    let html = r##"<a href="/alice/plan">x</a><img src="/alice/p/file?variant=display" srcset="/alice/p/file?w=480 480w, /alice/p/file?w=960 960w"><video poster="/alice/v/file"></video><a href="https://other.test/">y</a><img src="//cdn.test/a.png"><a href="#top">z</a>"##;
+    assert_eq!(
+        absolute_html(html, "https://example.com"),
+        r##"<a href="https://example.com/alice/plan">x</a><img src="https://example.com/alice/p/file?variant=display" srcset="https://example.com/alice/p/file?w=480 480w, https://example.com/alice/p/file?w=960 960w"><video poster="https://example.com/alice/v/file"></video><a href="https://other.test/">y</a><img src="//cdn.test/a.png"><a href="#top">z</a>"##
+    );
+}
+
+#[test]
+fn feed_paths_and_links_follow_space_and_tag() {
+    assert_eq!(feed_path("alice", None), "/alice/feed");
+    assert_eq!(feed_path("alice", Some("c++")), "/alice/tags/c%2B%2B/feed");
+    assert!(alternate_links(&site(None), "alice", None).is_empty());
+    let links = alternate_links(&site(Some("https://example.com")), "alice", None);
+    assert!(links.contains(
+        r#"<link rel="alternate" type="application/atom+xml" title="Launchpad (Atom)" href="https://example.com/alice/feed.atom">"#
+    ));
+    assert!(links.contains(r#"type="application/rss+xml""#));
+    assert!(links.contains(r#"href="https://example.com/alice/feed.json""#));
+}
+
+#[test]
+fn atom_and_rss_escape_entry_html() {
+    let feed = sample_feed();
+    let atom = feed_body(&feed, FeedFormat::Atom);
+    assert!(atom.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(atom.contains(r#"<link rel="self" type="application/atom+xml" href="https://example.com/alice/feed.atom"/>"#));
+    assert!(atom.contains("<title>Trip &lt;Log&gt;</title>"));
+    assert!(atom.contains("<updated>2026-03-02T10:30:00+00:00</updated>"));
+    assert!(atom.contains(r#"<content type="html">&lt;p&gt;Hi &lt;img src=&quot;https://example.com/alice/x/file&quot;&gt;&lt;/p&gt;</content>"#));
+    assert!(atom.contains(r#"<category term="travel"/>"#));
+    let rss = feed_body(&feed, FeedFormat::Rss);
+    assert!(rss.contains("<description>Notes &amp; media</description>"));
+    assert!(rss.contains(r#"<guid isPermaLink="true">https://example.com/alice/trip-log</guid>"#));
+    assert!(rss.contains("<pubDate>Sun, 1 Mar 2026 09:00:00 +0000</pubDate>"));
+}
+
+#[test]
+fn json_feed_lists_items_with_html_content() {
+    let body: Value = serde_json::from_str(&feed_body(&sample_feed(), FeedFormat::Json)).unwrap();
+    assert_eq!(body["version"], "https://jsonfeed.org/version/1.1");
+    assert_eq!(body["feed_url"], "https://example.com/alice/feed.json");
+    assert_eq!(body["items"][0]["id"], "https://example.com/alice/trip-log");
+    assert_eq!(body["items"][0]["title"], "Trip <Log>");
+    assert_eq!(body["items"][0]["tags"][0], "travel");
+    assert_eq!(
+        body["items"][0]["date_modified"],
+        "2026-03-02T10:30:00+00:00"
+    );
+}
@@ -0,0 +1,67 @@
+//! Atom 1.0 and RSS 2.0 serializations of a feed
+
+use super::feed::Feed;
+use super::markdown_cards::esc;
+
+pub(super) fn atom(feed: &Feed) -> String {
+    let entries = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            let categories = entry
+                .tags
+                .iter()
+                .map(|tag| format!(r#"<category term="{}"/>"#, esc(tag)))
+                .collect::<String>();
+            format!(
+                r#"<entry><title>{}</title><id>{url}</id><link rel="alternate" type="text/html" href="{url}"/><published>{}</published><updated>{}</updated><summary>{}</summary><content type="html">{}</content>{categories}</entry>"#,
+                esc(&entry.title),
+                entry.published.to_rfc3339(),
+                entry.updated.to_rfc3339(),
+                esc(&entry.summary),
+                esc(&entry.content_html),
+                url = esc(&entry.url),
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>{}</title><subtitle>{}</subtitle><id>{self_url}</id><link rel="self" type="application/atom+xml" href="{self_url}"/><link rel="alternate" type="text/html" href="{}"/><updated>{}</updated><author><name>{}</name></author>{entries}</feed>"#,
+        esc(&feed.title),
+        esc(&feed.description),
+        esc(&feed.home_url),
+        feed.updated.to_rfc3339(),
+        esc(&feed.title),
+        self_url = esc(&format!("{}.atom", feed.feed_url)),
+    )
+}
+
+pub(super) fn rss(feed: &Feed) -> String {
+    let items = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            let categories = entry
+                .tags
+                .iter()
+                .map(|tag| format!("<category>{}</category>", esc(tag)))
+                .collect::<String>();
+            format!(
+                r#"<item><title>{}</title><link>{url}</link><guid isPermaLink="true">{url}</guid><pubDate>{}</pubDate><description>{}</description>{categories}</item>"#,
+                esc(&entry.title),
+                entry.published.to_rfc2822(),
+                esc(&entry.content_html),
+                url = esc(&entry.url),
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel><title>{}</title><link>{}</link><description>{}</description><atom:link rel="self" type="application/rss+xml" href="{}"/><lastBuildDate>{}</lastBuildDate>{items}</channel></rss>"#,
+        esc(&feed.title),
+        esc(&feed.home_url),
+        esc(&feed.description),
+        esc(&format!("{}.rss", feed.feed_url)),
+        feed.updated.to_rfc2822(),
+    )
+}
@@ -1,9 +1,9 @@
 //! Admin dashboard handlers
 
 use crate::error::AppError;
-use crate::web::db::{self, PopularWindow};
+use crate::web::db::{self, PopularWindow, SpaceRole, Visibility};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::{session, space_access};
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
 use crate::web::templates;
@@ -40,28 +40,39 @@ async fn admin_inner(
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    if !session::check_session(&headers, pool).await? {
+    let access = space_access::space_access(&headers, pool, space_slug.as_deref()).await?;
+    if access.user_id.is_none() {
         return Ok(http::redirect(&session::login_url(&uri)));
     }
+    access.require(SpaceRole::Editor)?;
     let settings = db::get_settings(pool).await?;
     let site = SiteContext::from_settings(&settings);
     let window = PopularWindow::Days30;
     let popular = db::list_popular_resources(
         pool,
         space_slug.as_deref(),
-        true,
+        Visibility::Private,
         settings.home_popular_limit,
         window,
     )
     .await?;
     let recent = match space_slug.as_deref() {
         Some(slug) => {
-            db::list_recent_resources_in_space(pool, slug, true, settings.home_recent_limit).await?
+            db::list_recent_resources_in_space(
+                pool,
+                slug,
+                Visibility::Private,
+                settings.home_recent_limit,
+            )
+            .await?
+        }
+        None => {
+            db::list_recent_resources(pool, Visibility::Private, settings.home_recent_limit).await?
         }
-        None => db::list_recent_resources(pool, true, settings.home_recent_limit).await?,
     };
-    let favorites = db::list_all_favorite_resources(pool, space_slug.as_deref(), true).await?;
-    let stats = db::get_resource_stats(pool, space_slug.as_deref(), true).await?;
+    let favorites =
+        db::list_all_favorite_resources(pool, space_slug.as_deref(), Visibility::Private).await?;
+    let stats = db::get_resource_stats(pool, space_slug.as_deref(), Visibility::Private).await?;
     Ok(http::html(templates::admin_page(
         &stats,
         &settings,
@@ -0,0 +1,65 @@
+//! Audit feed and admin audit log handlers
+
+use super::{http, machine_auth, session, space_access};
+use crate::error::AppError;
+use crate::web::db::{self, SpaceRole};
+use crate::web::routes::AppState;
+use crate::web::site::SiteContext;
+use crate::web::templates;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::Response;
+use serde::Deserialize;
+
+const DEFAULT_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct AuditParams {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+pub async fn api_feed(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+    Query(params): Query<AuditParams>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    machine_auth::reject_bearer(&headers)?;
+    space_access::require_role(&headers, pool, Some(&user), SpaceRole::Admin).await?;
+    let page = db::list_audit_events(
+        pool,
+        &user,
+        params.limit.unwrap_or(DEFAULT_LIMIT),
+        params.cursor.as_deref(),
+    )
+    .await?;
+    Ok(http::json_status(StatusCode::OK, page))
+}
+
+pub async fn audit_page(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    Path(user): Path<String>,
+    Query(params): Query<AuditParams>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let access = space_access::space_access(&headers, pool, Some(&user)).await?;
+    if access.user_id.is_none() {
+        return Ok(http::redirect(&session::login_url(&uri)));
+    }
+    access.require(SpaceRole::Admin)?;
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+    let page = db::list_audit_events(pool, &user, limit, params.cursor.as_deref()).await?;
+    let settings = db::get_settings_in_space(pool, &user).await?;
+    Ok(http::html(templates::audit_page(
+        &user,
+        &page,
+        limit,
+        &SiteContext::from_settings(&settings),
+    )))
+}
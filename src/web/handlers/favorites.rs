@@ -1,11 +1,11 @@
 //! Favorite ordering handler
 
 use crate::error::AppError;
-use crate::web::db;
+use crate::web::db::{self, SpaceRole, WriteActor};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::space_access;
 use crate::web::routes::AppState;
-use axum::extract::{Json, State};
+use axum::extract::{Json, Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
 use serde::Deserialize;
@@ -18,9 +18,13 @@ pub struct FavoriteOrderInput {
 pub async fn reorder(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Path(user): Path<String>,
     Json(body): Json<FavoriteOrderInput>,
 ) -> Result<Response, AppError> {
-    session::require_session(&headers, &state.pool).await?;
-    db::reorder_favorites(&state.pool, &body.ids).await?;
+    db::require_space(&state.pool, &user).await?;
+    let actor = WriteActor::user(
+        space_access::require_role(&headers, &state.pool, Some(&user), SpaceRole::Editor).await?,
+    );
+    db::reorder_favorites(&state.pool, &user, &body.ids, &actor).await?;
     Ok(http::empty(StatusCode::NO_CONTENT))
 }
@@ -0,0 +1,131 @@
+//! Atom, RSS, and JSON Feed handlers for public space resources
+
+use crate::core::{normalize_tag, parse_front_matter};
+use crate::error::AppError;
+use crate::web::db::{self, Visibility};
+use crate::web::feed::{
+    absolute_html, feed_body, feed_path, feed_title, Feed, FeedEntry, FeedFormat, FEED_LIMIT,
+};
+use crate::web::handlers::http;
+use crate::web::markdown;
+use crate::web::routes::AppState;
+use crate::web::site::SiteContext;
+use crate::web::view;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Response;
+use chrono::Utc;
+use url::form_urlencoded::byte_serialize;
+
+pub async fn space_atom(
+    State(state): State<AppState>,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    feed_response(&state, &user, None, FeedFormat::Atom).await
+}
+
+pub async fn space_rss(
+    State(state): State<AppState>,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    feed_response(&state, &user, None, FeedFormat::Rss).await
+}
+
+pub async fn space_json(
+    State(state): State<AppState>,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    feed_response(&state, &user, None, FeedFormat::Json).await
+}
+
+pub async fn tag_atom(
+    State(state): State<AppState>,
+    Path((user, tag)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    feed_response(&state, &user, Some(&tag), FeedFormat::Atom).await
+}
+
+pub async fn tag_rss(
+    State(state): State<AppState>,
+    Path((user, tag)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    feed_response(&state, &user, Some(&tag), FeedFormat::Rss).await
+}
+
+pub async fn tag_json(
+    State(state): State<AppState>,
+    Path((user, tag)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    feed_response(&state, &user, Some(&tag), FeedFormat::Json).await
+}
+
+/// Feeds need absolute URLs, so like the sitemap they 404 until
+/// `public_base_url` is configured.
+async fn feed_response(
+    state: &AppState,
+    user: &str,
+    tag: Option<&str>,
+    format: FeedFormat,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, user).await?;
+    let settings = db::get_settings(pool).await?;
+    let site = SiteContext::from_settings(&settings);
+    let Some(base) = site.public_base_url.as_deref() else {
+        return Ok(http::empty(StatusCode::NOT_FOUND));
+    };
+    let tag = match tag {
+        Some(raw) => Some(
+            normalize_tag(raw)
+                .ok_or_else(|| AppError::NotFound(format!("tag '{raw}' not found")))?,
+        ),
+        None => None,
+    };
+    let resources = db::list_public_feed_resources(pool, user, tag.as_deref(), FEED_LIMIT).await?;
+    let mut entries = Vec::with_capacity(resources.len());
+    for resource in &resources {
+        let html = markdown::render_markdown_page(
+            pool,
+            &resource.body,
+            Some(&resource.space_slug),
+            Some(&resource.id),
+            Visibility::Public,
+            Some(base),
+            Some(&settings.google_maps_embed_api_key),
+        )
+        .await?;
+        entries.push(FeedEntry {
+            url: format!("{base}{}", view::resource_href(resource)),
+            title: view::title_for(resource),
+            summary: resource.summary.clone(),
+            content_html: absolute_html(&html, base),
+            tags: parse_front_matter(&resource.body)
+                .map(|front| front.tags)
+                .unwrap_or_default(),
+            published: resource.created_at,
+            updated: resource.updated_at,
+        });
+    }
+    let home_path = match tag.as_deref() {
+        Some(tag) => format!(
+            "/{user}/search?tag={}",
+            byte_serialize(tag.as_bytes()).collect::<String>()
+        ),
+        None => format!("/{user}"),
+    };
+    let feed = Feed {
+        title: feed_title(&site.site_name, tag.as_deref()),
+        description: site.site_description.clone(),
+        home_url: format!("{base}{home_path}"),
+        feed_url: format!("{base}{}", feed_path(user, tag.as_deref())),
+        updated: resources
+            .first()
+            .map_or_else(Utc::now, |resource| resource.updated_at),
+        entries,
+    };
+    Ok(http::text_with_type(
+        StatusCode::OK,
+        &format!("{}; charset=utf-8", format.mime()),
+        feed_body(&feed, format),
+    ))
+}
@@ -4,11 +4,11 @@ use crate::error::AppError;
 use crate::web::db::{self, ListDirection};
 use crate::web::handlers::http;
 use crate::web::handlers::resource_history::HistoryParams;
-use crate::web::handlers::session;
+use crate::web::handlers::{session, space_access};
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
 use crate::web::templates;
-use crate::web::view;
+use crate::web::{view, view_chrome};
 use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode, Uri};
 use axum::response::Response;
@@ -24,8 +24,7 @@ pub async fn history_page(
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    let is_admin = session::check_session(&headers, pool).await?;
-    if !is_admin {
+    if !session::check_session(&headers, pool).await? {
         return Ok(http::redirect(&session::login_url(&uri)));
     }
     let settings = db::get_settings(pool).await?;
@@ -33,6 +32,10 @@ pub async fn history_page(
     let Some(resource) = db::get_resource_by_ref(pool, &reference).await? else {
         return Ok(not_found(&site));
     };
+    let access = space_access::space_access(&headers, pool, Some(&resource.space_slug)).await?;
+    if access.role.is_none() || !access.can_read(resource.visibility) {
+        return Ok(not_found(&site));
+    }
     if resource
         .alias
         .as_deref()
@@ -47,13 +50,13 @@ pub async fn history_page(
     let page = db::list_resource_snapshots(
         pool,
         &resource.id,
-        true,
+        access.max_visibility(),
         params.limit.unwrap_or(settings.search_results_per_page),
         &ListDirection::resolve(params.direction.as_deref(), params.cursor.as_deref()),
         params.cursor.as_deref(),
     )
     .await?;
-    let chrome = view::resource_chrome(pool, &resource, true).await?;
+    let chrome = view_chrome::resource_chrome(pool, &resource, access.max_visibility()).await?;
     let history = view::history_links(&page.snapshots, params.cursor.is_none());
     Ok(http::html(templates::history_page(
         &resource,
@@ -64,7 +67,7 @@ pub async fn history_page(
             next_cursor: page.next_cursor.as_deref(),
             limit: params.limit.unwrap_or(settings.search_results_per_page),
         },
-        true,
+        access.can_write(),
         &site,
     )))
 }
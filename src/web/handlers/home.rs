@@ -3,7 +3,7 @@
 use crate::error::AppError;
 use crate::web::db::{self, PopularWindow};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::{session, space_access};
 use crate::web::markdown;
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
@@ -42,54 +42,73 @@ async fn home_page_inner(
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    let is_admin = session::check_session(&headers, pool).await?;
+    let access = space_access::space_access(&headers, pool, space_slug.as_deref()).await?;
+    let max_visibility = access.max_visibility();
+    let is_member = access.role.is_some();
     let settings = db::get_settings(pool).await?;
     let site = SiteContext::from_settings(&settings);
     let window = PopularWindow::Days30;
     let popular = db::list_popular_resources(
         pool,
         space_slug.as_deref(),
-        is_admin,
+        max_visibility,
         settings.home_popular_limit,
         window,
     )
     .await?;
     let recent = match space_slug.as_deref() {
         Some(slug) => {
-            db::list_recent_resources_in_space(pool, slug, is_admin, settings.home_recent_limit)
-                .await?
+            db::list_recent_resources_in_space(
+                pool,
+                slug,
+                max_visibility,
+                settings.home_recent_limit,
+            )
+            .await?
         }
-        None => db::list_recent_resources(pool, is_admin, settings.home_recent_limit).await?,
+        None => db::list_recent_resources(pool, max_visibility, settings.home_recent_limit).await?,
     };
     let favorites = match space_slug.as_deref() {
         Some(slug) => {
-            db::list_favorite_resources_in_space(pool, slug, is_admin, settings.home_favorite_limit)
-                .await?
+            db::list_favorite_resources_in_space(
+                pool,
+                slug,
+                max_visibility,
+                settings.home_favorite_limit,
+            )
+            .await?
+        }
+        None => {
+            db::list_favorite_resources(pool, max_visibility, settings.home_favorite_limit).await?
         }
-        None => db::list_favorite_resources(pool, is_admin, settings.home_favorite_limit).await?,
     };
     let popular_items = popular
         .iter()
-        .map(|resource| view::popular_index_item(resource, is_admin, window))
+        .map(|resource| view::popular_index_item(resource, is_member, window))
         .collect::<Vec<_>>();
     let recent_items = recent
         .iter()
-        .map(|resource| view::index_item(resource, is_admin))
+        .map(|resource| view::index_item(resource, is_member))
         .collect::<Vec<_>>();
     let favorite_items = favorites
         .iter()
-        .map(|resource| view::index_item(resource, is_admin))
+        .map(|resource| view::index_item(resource, is_member))
         .collect::<Vec<_>>();
     let guest_login_href = session::login_url(&uri);
     let intro_html = markdown::render_markdown_page(
         pool,
         &settings.home_intro_markdown,
+        space_slug.as_deref(),
         None,
-        is_admin,
+        max_visibility,
         site.public_base_url.as_deref(),
         Some(&settings.google_maps_embed_api_key),
     )
     .await?;
+    let feed_space = match space_slug {
+        Some(slug) => slug,
+        None => db::default_space_slug(pool).await?,
+    };
     Ok(http::html(templates::home_page(HomeView {
         settings: &settings,
         intro_html: &intro_html,
@@ -97,8 +116,9 @@ async fn home_page_inner(
         recent: &recent_items,
         favorites: &favorite_items,
         window,
-        is_admin,
+        is_admin: access.can_write(),
         guest_login_href: &guest_login_href,
         site: &site,
+        feed_space: &feed_space,
     })))
 }
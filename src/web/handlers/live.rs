@@ -3,13 +3,13 @@
 use crate::error::AppError;
 use crate::web::db;
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::{session, space_access};
 use crate::web::live::{client_addr, LiveRole};
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
 use crate::web::templates;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::{ConnectInfo, State};
+use axum::extract::{ConnectInfo, Path, State};
 use axum::http::{HeaderMap, Uri};
 use axum::response::Response;
 use futures_util::{SinkExt, StreamExt};
@@ -23,12 +23,16 @@ pub async fn live_page(
     State(state): State<AppState>,
     headers: HeaderMap,
     uri: Uri,
+    Path(user): Path<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    let is_admin = session::check_session(&headers, pool).await?;
+    db::require_space(pool, &user).await?;
+    let is_admin = space_access::space_access(&headers, pool, Some(&user))
+        .await?
+        .can_write();
     let settings = db::get_settings(pool).await?;
     let site = SiteContext::from_settings(&settings);
     Ok(http::html(templates::live_page(
@@ -43,9 +47,13 @@ pub async fn live_ws(
     State(state): State<AppState>,
     ConnectInfo(direct_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
+    Path(user): Path<String>,
     ws: WebSocketUpgrade,
 ) -> Result<Response, AppError> {
-    let is_admin = session::check_session(&headers, &state.pool).await?;
+    db::require_space(&state.pool, &user).await?;
+    let is_admin = space_access::space_access(&headers, &state.pool, Some(&user))
+        .await?
+        .can_write();
     let client_ip = client_addr::client_ip(&headers, direct_addr, &state.live_trusted_proxy_ips);
     Ok(ws.on_upgrade(move |socket| handle_socket(state, is_admin, client_ip, socket)))
 }
@@ -92,7 +100,7 @@ async fn register_role(
             .map_err(|message| send_error(tx, &message))
             .ok(),
         "broadcaster" => {
-            send_error(tx, "Admin session required.");
+            send_error(tx, "Editor session required.");
             None
         }
         "viewer" => Some(
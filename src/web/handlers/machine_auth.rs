@@ -0,0 +1,167 @@
+//! Bearer-token and session authorization for machine routes
+
+use crate::error::AppError;
+use crate::web::db::{self, ApiScope, ApiTokenGrant, DbPool, SpaceRole, Visibility, WriteActor};
+use crate::web::handlers::space_access;
+use axum::http::{header, HeaderMap};
+
+/// Resolve the write actor for one space from a bearer token or editor session.
+pub async fn require_write(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<WriteActor, AppError> {
+    match bearer_grant(headers, pool).await? {
+        Some(grant) => {
+            require_grant(&grant, space_slug, ApiScope::ResourceWrite)?;
+            Ok(WriteActor::service(grant.service_account_id))
+        }
+        None => Ok(WriteActor::user(
+            space_access::require_role(headers, pool, Some(space_slug), SpaceRole::Editor).await?,
+        )),
+    }
+}
+
+/// Report the widest visibility the caller may read in one space.
+pub async fn read_access(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<Visibility, AppError> {
+    match bearer_grant(headers, pool).await? {
+        Some(grant) => {
+            require_grant(&grant, space_slug, ApiScope::ResourceRead)?;
+            Ok(grant_visibility(&grant))
+        }
+        None => Ok(space_access::space_access(headers, pool, Some(space_slug))
+            .await?
+            .max_visibility()),
+    }
+}
+
+/// Require a member session or a read-scoped token for member-only JSON reads.
+pub async fn require_read(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<Visibility, AppError> {
+    if bearer_token(headers)?.is_some() {
+        return read_access(headers, pool, space_slug).await;
+    }
+    let access = space_access::space_access(headers, pool, Some(space_slug)).await?;
+    access.require(SpaceRole::Viewer)?;
+    Ok(access.max_visibility())
+}
+
+/// Require a browser session for settings routes that tokens never reach.
+pub fn reject_bearer(headers: &HeaderMap) -> Result<(), AppError> {
+    if headers.contains_key(header::AUTHORIZATION) {
+        return Err(AppError::Forbidden(
+            "service tokens cannot manage settings".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn bearer_grant(
+    headers: &HeaderMap,
+    pool: &DbPool,
+) -> Result<Option<ApiTokenGrant>, AppError> {
+    let Some(token) = bearer_token(headers)? else {
+        return Ok(None);
+    };
+    db::validate_api_token(pool, token)
+        .await?
+        .map(Some)
+        .ok_or_else(|| AppError::Unauthorized("invalid or expired API token".to_string()))
+}
+
+/// Tokens read like the member they stand in for: read-only tokens stop at
+/// `space` as viewers do, and write-scoped tokens also read `private`.
+fn grant_visibility(grant: &ApiTokenGrant) -> Visibility {
+    if grant.allows(ApiScope::ResourceWrite) {
+        Visibility::Private
+    } else {
+        Visibility::Space
+    }
+}
+
+fn require_grant(grant: &ApiTokenGrant, space_slug: &str, scope: ApiScope) -> Result<(), AppError> {
+    if !grant.space_slug.eq_ignore_ascii_case(space_slug) {
+        return Err(AppError::Forbidden(
+            "API token does not belong to this space".to_string(),
+        ));
+    }
+    if !grant.allows(scope) {
+        return Err(AppError::Forbidden(format!(
+            "API token lacks the {} scope",
+            scope.as_str()
+        )));
+    }
+    Ok(())
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<Option<&str>, AppError> {
+    let Some(value) = headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim())
+        .filter(|token| !token.is_empty())
+        .map(Some)
+        .ok_or_else(|| AppError::Unauthorized("malformed Authorization header".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bearer_token, grant_visibility};
+    use crate::web::db::{ApiScope, ApiTokenGrant, Visibility};
+    use axum::http::{header, HeaderMap, HeaderValue};
+    use uuid::Uuid;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn bearer_token_reads_case_insensitive_scheme() {
+        assert_eq!(
+            bearer_token(&headers("Bearer kjx_abc")).unwrap(),
+            Some("kjx_abc")
+        );
+        assert_eq!(
+            bearer_token(&headers("bearer  kjx_abc ")).unwrap(),
+            Some("kjx_abc")
+        );
+        assert_eq!(bearer_token(&HeaderMap::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn bearer_token_rejects_other_schemes() {
+        assert!(bearer_token(&headers("Basic dXNlcjpwYXNz")).is_err());
+        assert!(bearer_token(&headers("Bearer ")).is_err());
+    }
+
+    #[test]
+    fn only_write_scoped_tokens_read_private_resources() {
+        let grant = |scopes: &[ApiScope]| ApiTokenGrant {
+            service_account_id: Uuid::nil(),
+            space_slug: "alice".to_string(),
+            scopes: scopes.to_vec(),
+        };
+        assert_eq!(
+            grant_visibility(&grant(&[ApiScope::ResourceRead])),
+            Visibility::Space
+        );
+        assert_eq!(
+            grant_visibility(&grant(&[ApiScope::ResourceRead, ApiScope::ResourceWrite])),
+            Visibility::Private
+        );
+    }
+}
@@ -1,22 +1,35 @@
+use super::media_dedup::{release_uploads, store_upload};
 use super::media_input::parse_media_form;
-use super::media_support::{detect_media_family, initial_body, object_key, space_object_key};
+use super::media_support::{detect_media_family, initial_body};
 use super::resource_payload::ResourcePayload;
+use super::upload_finalize::{resolve_uploads, settle_uploads};
+use super::uploaded_file::UploadedFile;
 use crate::core::normalize_alias;
 use crate::error::AppError;
-use crate::storage::Storage;
-use crate::web::db::{self, MediaBlob, ScopedMediaCreate};
+use crate::web::db::{self, MediaBlob, ScopedMediaCreate, SpaceRole, WriteActor};
 use crate::web::handlers::http;
 use crate::web::routes::AppState;
 use axum::extract::{Multipart, Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
 
+/// Media create input once any upload session is resolved to a file.
+struct MediaForm {
+    file: UploadedFile,
+    alias: Option<String>,
+    is_favorite: Option<bool>,
+    visibility: Option<db::Visibility>,
+}
+
 pub async fn create(
     State(state): State<AppState>,
     headers: HeaderMap,
     payload: Multipart,
 ) -> Result<Response, AppError> {
-    create_inner(State(state), headers, payload, None).await
+    let actor = WriteActor::user(
+        super::space_access::require_role(&headers, &state.pool, None, SpaceRole::Editor).await?,
+    );
+    create_inner(State(state), actor, payload, None).await
 }
 
 pub async fn create_scoped(
@@ -26,56 +39,66 @@ pub async fn create_scoped(
     payload: Multipart,
 ) -> Result<Response, AppError> {
     db::require_space(&state.pool, &user).await?;
-    create_inner(State(state), headers, payload, Some(user)).await
+    let actor = super::machine_auth::require_write(&headers, &state.pool, &user).await?;
+    create_inner(State(state), actor, payload, Some(user)).await
 }
 
 async fn create_inner(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    actor: WriteActor,
     payload: Multipart,
     space_slug: Option<String>,
+) -> Result<Response, AppError> {
+    let form = parse_media_form(payload, state.media_upload_max_bytes).await?;
+    let part = vec![form.file];
+    let (files, sessions) = resolve_uploads(&state, space_slug.as_deref(), &actor, part).await?;
+    let result = match files.into_iter().next() {
+        Some(file) => {
+            let form = MediaForm {
+                file,
+                alias: form.alias,
+                is_favorite: form.is_favorite,
+                visibility: form.visibility,
+            };
+            create_media_resource(&state, actor, form, space_slug).await
+        }
+        None => Err(AppError::InvalidRequest("file is required".to_string())),
+    };
+    settle_uploads(&state, &sessions, result.is_ok()).await;
+    result
+}
+
+async fn create_media_resource(
+    state: &AppState,
+    actor: WriteActor,
+    form: MediaForm,
+    space_slug: Option<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     let storage = &state.storage;
-    super::session::require_session(&headers, pool).await?;
-    let form = parse_media_form(payload, state.media_upload_max_bytes).await?;
     let settings = db::get_settings(pool).await?;
     let id = db::generate_resource_id(pool).await?;
-    let file_key = match space_slug.as_deref() {
-        Some(slug) => space_object_key(slug, &id, &form.file.original_filename),
-        None => object_key(&id, &form.file.original_filename),
-    };
     let body = initial_body(&form.file.original_filename);
     let media_family = detect_media_family(&form.file.content_type, &form.file.original_filename)?;
-    let generated_variants = super::media_derivatives::build_variants(
-        &id,
-        media_family,
-        form.file.path(),
-        settings.media_webp_quality,
-    )
-    .await;
-    storage
-        .put_file(&file_key, form.file.path(), &form.file.content_type)
-        .await?;
-    let (media_variants, stored_variant_keys) =
-        super::media_derivatives::store_variants(storage, &generated_variants).await;
+    let probe = super::media_derivatives::probe_media(media_family, form.file.path()).await;
+    let alias = normalize_alias(form.alias.as_deref())?;
+    let stored = store_upload(pool, storage, space_slug.as_deref(), &form.file).await?;
     let blob = MediaBlob {
         media_family,
-        file_key: &file_key,
+        file_key: &stored.file_key,
         content_type: &form.file.content_type,
         byte_size: form.file.byte_size,
         sha256_hex: &form.file.sha256_hex,
         original_filename: &form.file.original_filename,
-        width: None,
-        height: None,
-        duration_ms: None,
-        media_variants,
+        width: probe.width,
+        height: probe.height,
+        duration_ms: probe.duration_ms,
+        media_details: probe.details,
+        media_variants: stored.media_variants.clone(),
     };
-    let stored_keys = stored_keys(file_key.clone(), stored_variant_keys);
-    let alias = normalize_alias(form.alias.as_deref())?;
-    let is_private = form
-        .is_private
-        .unwrap_or(settings.default_new_resource_is_private);
+    let visibility = form
+        .visibility
+        .unwrap_or(settings.default_new_resource_visibility);
     let result = match space_slug.as_deref() {
         Some(slug) => {
             db::create_media_in_space(
@@ -87,7 +110,8 @@ async fn create_inner(
                     body: &body,
                     blob: &blob,
                     is_favorite: form.is_favorite.unwrap_or(false),
-                    is_private,
+                    visibility,
+                    actor: &actor,
                 },
             )
             .await
@@ -100,29 +124,17 @@ async fn create_inner(
                 &body,
                 &blob,
                 form.is_favorite.unwrap_or(false),
-                is_private,
+                visibility,
             )
             .await
         }
     };
-    match result {
-        Ok(resource) => Ok(http::json_status(
-            StatusCode::CREATED,
-            ResourcePayload::from_resource(resource),
-        )),
-        Err(error) => {
-            cleanup_objects(storage, &stored_keys).await;
-            Err(error)
-        }
-    }
-}
-
-fn stored_keys(file_key: String, variant_keys: Vec<String>) -> Vec<String> {
-    std::iter::once(file_key).chain(variant_keys).collect()
-}
-
-async fn cleanup_objects(storage: &Storage, keys: &[String]) {
-    for key in keys {
-        let _ = storage.delete_object(key).await;
-    }
+    release_uploads(pool, storage, &[stored.file_key]).await;
+    let resource = result?;
+    Ok(http::json_status(
+        StatusCode::CREATED,
+        ResourcePayload::with_jobs(pool, resource)
+            .await?
+            .with_duplicate_of(stored.duplicate_of),
+    ))
 }
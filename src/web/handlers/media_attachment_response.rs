@@ -1,3 +1,4 @@
+use super::media_dedup::release_uploads;
 use super::media_insert::InsertResult;
 use super::{http, resource_payload::ResourcePayload};
 use crate::error::AppError;
@@ -14,6 +15,18 @@ struct AttachmentRefPayload {
     alias: Option<String>,
     owner_note_id: Option<String>,
     file_href: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicate_of: Option<String>,
+}
+
+/// Request state the attachment response reports or cleans up.
+pub(super) struct AttachmentOutcome {
+    /// Object keys this request pinned; released once the write settles.
+    pub pinned: Vec<String>,
+    /// `duplicate_of` hint per attachment, in upload order.
+    pub duplicates: Vec<Option<String>>,
+    pub inserted_markdown: String,
+    pub insertion: InsertResult,
 }
 
 #[derive(Serialize)]
@@ -26,42 +39,37 @@ struct AttachmentResponse {
 }
 
 pub(super) async fn attachment_response(
+    pool: &db::DbPool,
     storage: &Storage,
-    keys: Vec<String>,
     result: Result<db::AttachmentBatchResult, AppError>,
-    inserted_markdown: String,
-    insertion: InsertResult,
+    outcome: AttachmentOutcome,
 ) -> Result<Response, AppError> {
-    match result {
-        Ok(result) => Ok(http::json_status(
-            StatusCode::OK,
-            AttachmentResponse {
-                current_resource: ResourcePayload::from_resource(result.current_resource),
-                inserted_markdown,
-                selection_fallback: insertion.selection_fallback,
-                cursor_utf8: insertion.cursor_utf8,
-                created_media: result.created_media.into_iter().map(media_ref).collect(),
-            },
-        )),
-        Err(error) => {
-            cleanup_objects(storage, &keys).await;
-            Err(error)
-        }
-    }
+    release_uploads(pool, storage, &outcome.pinned).await;
+    let result = result?;
+    Ok(http::json_status(
+        StatusCode::OK,
+        AttachmentResponse {
+            current_resource: ResourcePayload::from_resource(result.current_resource),
+            inserted_markdown: outcome.inserted_markdown,
+            selection_fallback: outcome.insertion.selection_fallback,
+            cursor_utf8: outcome.insertion.cursor_utf8,
+            created_media: result
+                .created_media
+                .into_iter()
+                .zip(outcome.duplicates)
+                .map(|(resource, duplicate_of)| media_ref(resource, duplicate_of))
+                .collect(),
+        },
+    ))
 }
 
-fn media_ref(resource: db::Resource) -> AttachmentRefPayload {
+fn media_ref(resource: db::Resource, duplicate_of: Option<String>) -> AttachmentRefPayload {
     AttachmentRefPayload {
         file_href: Some(view::file_href(&resource)),
         id: resource.id,
         kind: resource.kind,
         alias: resource.alias,
         owner_note_id: resource.owner_note_id,
-    }
-}
-
-async fn cleanup_objects(storage: &Storage, keys: &[String]) {
-    for key in keys {
-        let _ = storage.delete_object(key).await;
+        duplicate_of,
     }
 }
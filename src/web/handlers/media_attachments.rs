@@ -1,13 +1,14 @@
-use super::media_attachment_response::attachment_response;
+use super::media_attachment_response::{attachment_response, AttachmentOutcome};
+use super::media_dedup::{release_uploads, store_upload};
 use super::media_insert::apply_insert;
-use super::media_support::{
-    detect_media_family, embed_markdown, initial_body, object_key, space_object_key,
-};
-use super::note_media_input::parse_note_media_form;
+use super::media_support::{detect_media_family, embed_markdown, initial_body};
+use super::note_media_input::{parse_note_media_form, NoteMediaAttachmentInput};
+use super::upload_finalize::{resolve_uploads, settle_uploads};
+use super::uploaded_file::UploadedFile;
 use crate::core::{normalize_alias, validate_id};
 use crate::error::AppError;
 use crate::storage::Storage;
-use crate::web::db::{self, AttachmentCreate, NoteAttachmentUpdate};
+use crate::web::db::{self, AttachmentCreate, NoteAttachmentUpdate, SpaceRole, WriteActor};
 use crate::web::routes::AppState;
 use axum::extract::{Multipart, Path, State};
 use axum::http::HeaderMap;
@@ -39,8 +40,10 @@ async fn attach_inner(
     space_slug: Option<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    let storage = &state.storage;
-    super::session::require_session(&headers, pool).await?;
+    let actor = WriteActor::user(
+        super::space_access::require_role(&headers, pool, space_slug.as_deref(), SpaceRole::Editor)
+            .await?,
+    );
     validate_id(&id)?;
     if let Some(slug) = space_slug.as_deref() {
         db::require_space(pool, slug).await?;
@@ -48,96 +51,91 @@ async fn attach_inner(
             .await?
             .ok_or_else(|| AppError::NotFound(format!("resource '{id}' not found")))?;
     }
-    let form = parse_note_media_form(payload, state.media_upload_max_bytes).await?;
+    let mut form = parse_note_media_form(payload, state.media_upload_max_bytes).await?;
+    let parts = std::mem::take(&mut form.files);
+    let (files, sessions) = resolve_uploads(&state, space_slug.as_deref(), &actor, parts).await?;
+    let result = attach_files(&state, actor, &id, &form, &files, space_slug.as_deref()).await;
+    settle_uploads(&state, &sessions, result.is_ok()).await;
+    result
+}
+
+async fn attach_files(
+    state: &AppState,
+    actor: WriteActor,
+    id: &str,
+    form: &NoteMediaAttachmentInput,
+    files: &[UploadedFile],
+    space_slug: Option<&str>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    let storage = &state.storage;
     let alias = normalize_alias(form.alias.as_deref())?;
-    let settings = db::get_settings(pool).await?;
-    let mut attachments = build_attachments(
-        pool,
-        &form.files,
-        settings.media_webp_quality,
-        space_slug.as_deref(),
-    )
-    .await?;
-    let inserted_markdown = inserted_markdown(&attachments, space_slug.as_deref());
+    let mut pinned = Vec::new();
+    let built = build_attachments(pool, storage, files, space_slug, &mut pinned);
+    let (attachments, duplicates) = match built.await {
+        Ok(built) => built,
+        Err(error) => {
+            release_uploads(pool, storage, &pinned).await;
+            return Err(error);
+        }
+    };
+    let inserted_markdown = inserted_markdown(&attachments, space_slug);
     let insertion = apply_insert(
         &form.body,
         form.insert_start,
         form.insert_end,
         &inserted_markdown,
     );
-    let keys = store_uploads(storage, &form.files, &mut attachments).await?;
     let update = NoteAttachmentUpdate {
+        space_slug,
         body: &insertion.body,
         alias: alias.as_deref(),
         is_favorite: form.is_favorite,
-        is_private: form.is_private,
+        visibility: form.visibility,
+        actor: &actor,
+    };
+    let result = db::attach_media_to_note(pool, id, &update, &attachments).await;
+    let response = AttachmentOutcome {
+        pinned,
+        duplicates,
+        inserted_markdown,
+        insertion,
     };
-    let result = db::attach_media_to_note(pool, &id, &update, &attachments).await;
-    attachment_response(storage, keys, result, inserted_markdown, insertion).await
+    attachment_response(pool, storage, result, response).await
 }
 
+/// Store or reuse each upload, recording the keys it pinned in `pinned`.
 async fn build_attachments(
     pool: &db::DbPool,
-    files: &[super::media_input::UploadedFile],
-    webp_quality: i64,
+    storage: &Storage,
+    files: &[UploadedFile],
     space_slug: Option<&str>,
-) -> Result<Vec<AttachmentCreate>, AppError> {
+    pinned: &mut Vec<String>,
+) -> Result<(Vec<AttachmentCreate>, Vec<Option<String>>), AppError> {
     let mut attachments = Vec::with_capacity(files.len());
+    let mut duplicates = Vec::with_capacity(files.len());
     for file in files {
         let media_id = db::generate_resource_id(pool).await?;
         let media_family = detect_media_family(&file.content_type, &file.original_filename)?;
         let media_body = initial_body(&file.original_filename);
-        let generated_variants = super::media_derivatives::build_variants(
-            &media_id,
-            media_family,
-            file.path(),
-            webp_quality,
-        )
-        .await;
+        let probe = super::media_derivatives::probe_media(media_family, file.path()).await;
+        let stored = store_upload(pool, storage, space_slug, file).await?;
+        pinned.push(stored.file_key.clone());
+        duplicates.push(stored.duplicate_of);
         attachments.push(AttachmentCreate {
-            media_id: media_id.clone(),
+            media_id,
             media_body,
             media_family,
-            file_key: space_slug.map_or_else(
-                || object_key(&media_id, &file.original_filename),
-                |slug| space_object_key(slug, &media_id, &file.original_filename),
-            ),
+            file_key: stored.file_key,
             content_type: file.content_type.clone(),
             byte_size: file.byte_size,
             sha256_hex: file.sha256_hex.clone(),
             original_filename: file.original_filename.clone(),
-            media_variants: None,
-            generated_variants,
+            probe,
+            media_variants: stored.media_variants,
         });
     }
-    Ok(attachments)
-}
-
-async fn store_uploads(
-    storage: &Storage,
-    uploads: &[super::media_input::UploadedFile],
-    attachments: &mut [AttachmentCreate],
-) -> Result<Vec<String>, AppError> {
-    let mut stored_keys = Vec::with_capacity(attachments.len());
-    for (upload, attachment) in uploads.iter().zip(attachments.iter_mut()) {
-        if let Err(error) = storage
-            .put_file(
-                &attachment.file_key,
-                upload.path(),
-                &attachment.content_type,
-            )
-            .await
-        {
-            cleanup_objects(storage, &stored_keys).await;
-            return Err(error);
-        }
-        stored_keys.push(attachment.file_key.clone());
-        let (variants, variant_keys) =
-            super::media_derivatives::store_variants(storage, &attachment.generated_variants).await;
-        attachment.media_variants = variants;
-        stored_keys.extend(variant_keys);
-    }
-    Ok(stored_keys)
+    Ok((attachments, duplicates))
 }
 
 fn inserted_markdown(attachments: &[AttachmentCreate], space_slug: Option<&str>) -> String {
@@ -156,9 +154,3 @@ fn inserted_markdown(attachments: &[AttachmentCreate], space_slug: Option<&str>)
         .collect::<Vec<_>>()
         .join("\n\n")
 }
-
-async fn cleanup_objects(storage: &Storage, keys: &[String]) {
-    for key in keys {
-        let _ = storage.delete_object(key).await;
-    }
-}
@@ -0,0 +1,72 @@
+//! Content-hash reuse of stored media objects for uploads
+
+use super::uploaded_file::UploadedFile;
+use crate::error::AppError;
+use crate::media::MediaVariants;
+use crate::storage::Storage;
+use crate::web::db::{self, DbPool};
+use crate::web::trash_sweep::delete_if_unreferenced;
+use tracing::warn;
+
+/// Where one upload's bytes live after storing or reusing them.
+pub(super) struct StoredUpload {
+    /// Pinned until `release_uploads` runs for it.
+    pub file_key: String,
+    pub media_variants: Option<MediaVariants>,
+    /// Live media in the same space with identical content.
+    pub duplicate_of: Option<String>,
+}
+
+/// Content-addressed key for an original, fanned out by the first hash byte.
+pub(super) fn blob_object_key(sha256_hex: &str) -> String {
+    format!("blobs/sha256/{}/{sha256_hex}", &sha256_hex[..2])
+}
+
+/// Pin a referenced object with the same SHA-256 for reuse, or pin the
+/// upload's content-addressed key and store it there. Every returned key must
+/// go through `release_uploads` once the write commits or fails.
+pub(super) async fn store_upload(
+    pool: &DbPool,
+    storage: &Storage,
+    space_slug: Option<&str>,
+    file: &UploadedFile,
+) -> Result<StoredUpload, AppError> {
+    let duplicate_of = db::find_duplicate_media(pool, space_slug, &file.sha256_hex).await?;
+    let fresh_key = blob_object_key(&file.sha256_hex);
+    let blob = db::pin_media_blob(pool, &file.sha256_hex, file.byte_size, &fresh_key).await?;
+    if !blob.reused {
+        let stored = storage
+            .put_file(&blob.file_key, file.path(), &file.content_type)
+            .await;
+        if let Err(error) = stored {
+            release_uploads(pool, storage, &[blob.file_key]).await;
+            return Err(error);
+        }
+    }
+    Ok(StoredUpload {
+        file_key: blob.file_key,
+        media_variants: blob.media_variants,
+        duplicate_of,
+    })
+}
+
+/// Drop the pins `store_upload` took, whether the write committed or not;
+/// objects nothing else references, like those of a failed write, are deleted.
+pub(super) async fn release_uploads(pool: &DbPool, storage: &Storage, keys: &[String]) {
+    for key in keys {
+        if let Err(error) = delete_if_unreferenced(pool, storage, key, 1).await {
+            warn!(key = %key, error = %error, "upload pin release failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::blob_object_key;
+
+    #[test]
+    fn blob_keys_fan_out_by_first_hash_byte() {
+        let sha = "9f6d302f1fe8f0a6c049cb630b345c3fa815026dfa7577a902944090c194b824";
+        assert_eq!(blob_object_key(sha), format!("blobs/sha256/9f/{sha}"));
+    }
+}
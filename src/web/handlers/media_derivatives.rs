@@ -1,5 +1,6 @@
 use crate::media::{
-    image_variants_from_path, video_stills_from_path, GeneratedVariant, MediaVariants,
+    audio_waveform_from_path, image_variants_from_path, probe_audio_video, probe_image,
+    video_stills_from_path, GeneratedVariant, MediaProbe, MediaVariants,
 };
 use crate::storage::Storage;
 use crate::web::db::MediaFamily;
@@ -15,10 +16,20 @@ pub async fn build_variants(
     match family {
         MediaFamily::Image => image_variants_from_path(id, path, quality).await,
         MediaFamily::Video => video_stills_from_path(id, path, quality).await,
+        MediaFamily::Audio => audio_waveform_from_path(id, path, quality).await,
         MediaFamily::File => Vec::new(),
     }
 }
 
+/// Display dimensions, duration, and codec details; file-family media are not probed.
+pub async fn probe_media(family: MediaFamily, path: &Path) -> MediaProbe {
+    match family {
+        MediaFamily::Image => probe_image(path).await,
+        MediaFamily::Video | MediaFamily::Audio => probe_audio_video(path).await,
+        MediaFamily::File => MediaProbe::default(),
+    }
+}
+
 pub async fn store_variants(
     storage: &Storage,
     generated: &[GeneratedVariant],
@@ -1,32 +1,22 @@
+use super::uploaded_file::{read_uploaded_file, UploadedFile};
 use crate::error::AppError;
+use crate::web::db::Visibility;
 use axum::extract::multipart::Field;
 use axum::extract::Multipart;
-use sha2::{Digest, Sha256};
-use std::io::Write;
-use std::path::Path;
-use tempfile::NamedTempFile;
 
 const MAX_TEXT_BYTES: usize = 16 * 1024;
 
-pub struct UploadedFile {
-    temp_file: NamedTempFile,
-    pub byte_size: i64,
-    pub sha256_hex: String,
-    pub original_filename: String,
-    pub content_type: String,
-}
-
-impl UploadedFile {
-    pub fn path(&self) -> &Path {
-        self.temp_file.path()
-    }
+/// One file part: bytes sent inline, or the id of a finished upload session.
+pub enum UploadPart {
+    File(UploadedFile),
+    Session(String),
 }
 
 pub struct MediaFormInput {
-    pub file: UploadedFile,
+    pub file: UploadPart,
     pub alias: Option<String>,
     pub is_favorite: Option<bool>,
-    pub is_private: Option<bool>,
+    pub visibility: Option<Visibility>,
 }
 
 pub async fn parse_media_form(
@@ -37,6 +27,7 @@ pub async fn parse_media_form(
     let mut alias = None;
     let mut is_favorite = None;
     let mut is_private = None;
+    let mut visibility = None;
     while let Some(field) = payload
         .next_field()
         .await
@@ -47,71 +38,30 @@ pub async fn parse_media_form(
                 if file.is_some() {
                     return Err(invalid("file may only be provided once"));
                 }
-                file = Some(read_uploaded_file(field, max_file_bytes).await?);
+                file = Some(UploadPart::File(
+                    read_uploaded_file(field, max_file_bytes).await?,
+                ));
+            }
+            Some("upload") => {
+                if file.is_some() {
+                    return Err(invalid("file may only be provided once"));
+                }
+                file = Some(UploadPart::Session(upload_id(field).await?));
             }
             Some("alias") => alias = text_value(field).await?,
             Some("is_favorite") => is_favorite = Some(bool_value(field).await?),
             Some("is_private") => is_private = Some(bool_value(field).await?),
+            Some("visibility") => visibility = Some(parse_visibility(text_value(field).await?)?),
             _ => {
                 discard_field(field, MAX_TEXT_BYTES).await?;
             }
         }
     }
     Ok(MediaFormInput {
-        file: file.ok_or_else(|| invalid("file is required"))?,
+        file: file.ok_or_else(|| invalid("file or upload is required"))?,
         alias,
         is_favorite,
-        is_private,
-    })
-}
-
-pub(super) async fn read_uploaded_file(
-    mut field: Field<'_>,
-    max_file_bytes: usize,
-) -> Result<UploadedFile, AppError> {
-    let original_filename = field
-        .file_name()
-        .map(str::to_string)
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or_else(|| "upload.bin".to_string());
-    let content_type = field
-        .content_type()
-        .map(str::to_string)
-        .unwrap_or_else(|| "application/octet-stream".to_string());
-    let mut temp_file = NamedTempFile::new()
-        .map_err(|e| AppError::StorageError(format!("upload temp file create failed: {e}")))?;
-    let mut hasher = Sha256::new();
-    let mut byte_size = 0usize;
-    while let Some(chunk) = field
-        .chunk()
-        .await
-        .map_err(|e| invalid(&format!("could not read upload: {e}")))?
-    {
-        byte_size = byte_size
-            .checked_add(chunk.len())
-            .ok_or_else(|| AppError::PayloadTooLarge("file exceeds upload limit".to_string()))?;
-        if byte_size > max_file_bytes {
-            return Err(AppError::PayloadTooLarge(
-                "file exceeds upload limit".to_string(),
-            ));
-        }
-        hasher.update(&chunk);
-        temp_file
-            .write_all(&chunk)
-            .map_err(|e| AppError::StorageError(format!("upload temp file write failed: {e}")))?;
-    }
-    temp_file
-        .flush()
-        .map_err(|e| AppError::StorageError(format!("upload temp file flush failed: {e}")))?;
-    if byte_size == 0 {
-        return Err(invalid("file is required"));
-    }
-    Ok(UploadedFile {
-        temp_file,
-        byte_size: byte_size as i64,
-        sha256_hex: format!("{:x}", hasher.finalize()),
-        original_filename,
-        content_type,
+        visibility: visibility.or(is_private.map(Visibility::from_is_private)),
     })
 }
 
@@ -151,6 +101,12 @@ async fn text_value(field: Field<'_>) -> Result<Option<String>, AppError> {
     Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
 }
 
+pub(super) async fn upload_id(field: Field<'_>) -> Result<String, AppError> {
+    text_value(field)
+        .await?
+        .ok_or_else(|| invalid("upload must name an upload session"))
+}
+
 async fn bool_value(field: Field<'_>) -> Result<bool, AppError> {
     let value = text_value(field).await?.unwrap_or_default();
     match value.as_str() {
@@ -160,6 +116,13 @@ async fn bool_value(field: Field<'_>) -> Result<bool, AppError> {
     }
 }
 
-fn invalid(message: &str) -> AppError {
+pub(super) fn parse_visibility(value: Option<String>) -> Result<Visibility, AppError> {
+    value
+        .as_deref()
+        .and_then(Visibility::parse)
+        .ok_or_else(|| invalid("visibility must be public, space, or private"))
+}
+
+pub(super) fn invalid(message: &str) -> AppError {
     AppError::InvalidRequest(message.to_string())
 }
@@ -1,4 +1,3 @@
-use crate::core::generate_id;
 use crate::error::AppError;
 use crate::web::db::MediaFamily;
 use std::path::Path;
@@ -13,14 +12,18 @@ pub fn detect_media_family(content_type: &str, filename: &str) -> Result<MediaFa
     if content_type.starts_with("video/") {
         return Ok(MediaFamily::Video);
     }
+    if content_type.starts_with("audio/") {
+        return Ok(MediaFamily::Audio);
+    }
     match extension(filename).as_deref() {
         Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "svg") => Ok(MediaFamily::Image),
         Some(
             "mp4" | "webm" | "mov" | "m4v" | "ogg" | "ogv" | "mkv" | "avi" | "wmv" | "mpeg" | "mpg"
             | "3gp",
         ) => Ok(MediaFamily::Video),
+        Some("mp3" | "flac" | "opus" | "wav" | "m4a" | "aac" | "oga") => Ok(MediaFamily::Audio),
         _ => Err(AppError::InvalidRequest(
-            "unsupported media type; use an image, video, or supported file".to_string(),
+            "unsupported media type; use an image, video, audio, or supported file".to_string(),
         )),
     }
 }
@@ -33,37 +36,11 @@ pub fn embed_markdown(media_ref: &str, family: MediaFamily, filename: &str) -> S
     match family {
         MediaFamily::Image => format!("![](/{media_ref}/file)"),
         MediaFamily::Video => format!(r#"<video controls src="/{media_ref}/file"></video>"#),
+        MediaFamily::Audio => format!(r#"<audio controls src="/{media_ref}/file"></audio>"#),
         MediaFamily::File => format!("[{}](/{media_ref})", escape_link_label(filename)),
     }
 }
 
-pub fn object_key(id: &str, filename: &str) -> String {
-    format!("media/{id}/{}-{}", generate_id(), safe_name(filename))
-}
-
-pub fn space_object_key(space_slug: &str, id: &str, filename: &str) -> String {
-    format!(
-        "spaces/{space_slug}/media/{id}/{}-{}",
-        generate_id(),
-        safe_name(filename)
-    )
-}
-
-fn safe_name(filename: &str) -> String {
-    filename
-        .chars()
-        .map(|ch| {
-            if ch.is_ascii_alphanumeric() || ".-_".contains(ch) {
-                ch
-            } else {
-                '-'
-            }
-        })
-        .collect::<String>()
-        .trim_matches('-')
-        .to_string()
-}
-
 fn pretty_stem(filename: &str) -> String {
     let stem = Path::new(filename)
         .file_stem()
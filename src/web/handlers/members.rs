@@ -0,0 +1,131 @@
+//! Space membership management handlers
+
+use super::http;
+use super::space_access::require_manager;
+use crate::error::AppError;
+use crate::web::db::{self, MemberInvite, SpaceRole};
+use crate::web::routes::AppState;
+use axum::extract::{Json, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_LOGIN_LEN: usize = 64;
+
+#[derive(Deserialize)]
+pub struct InviteInput {
+    pub login: String,
+    pub role: String,
+}
+
+#[derive(Deserialize)]
+pub struct RoleInput {
+    pub role: String,
+}
+
+#[derive(Serialize)]
+struct MemberList {
+    members: Vec<db::SpaceMember>,
+}
+
+pub async fn list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    require_manager(&headers, &state.pool, &user).await?;
+    let members = db::list_space_members(&state.pool, &user).await?;
+    Ok(http::json_status(StatusCode::OK, MemberList { members }))
+}
+
+pub async fn invite(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+    Json(input): Json<InviteInput>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    let invited = db::invite_member(
+        &state.pool,
+        &user,
+        &MemberInvite {
+            login: validate_login(&input.login)?,
+            role: assignable_role(&input.role)?,
+            actor: &actor,
+        },
+    )
+    .await?;
+    Ok(http::json_status(StatusCode::CREATED, invited))
+}
+
+pub async fn update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, member_id)): Path<(String, Uuid)>,
+    Json(input): Json<RoleInput>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    let role = assignable_role(&input.role)?;
+    if db::set_member_role(&state.pool, &user, member_id, role, &actor).await? {
+        Ok(http::empty(StatusCode::NO_CONTENT))
+    } else {
+        Err(AppError::NotFound("member not found".to_string()))
+    }
+}
+
+pub async fn remove(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, member_id)): Path<(String, Uuid)>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    if db::remove_member(&state.pool, &user, member_id, &actor).await? {
+        Ok(http::empty(StatusCode::NO_CONTENT))
+    } else {
+        Err(AppError::NotFound("member not found".to_string()))
+    }
+}
+
+fn validate_login(value: &str) -> Result<&str, AppError> {
+    let login = value.trim();
+    if login.is_empty()
+        || login.chars().count() > MAX_LOGIN_LEN
+        || login.contains(|ch: char| ch.is_whitespace() || ch == '/')
+    {
+        return Err(AppError::InvalidRequest(format!(
+            "login must be 1 to {MAX_LOGIN_LEN} characters without spaces or '/'"
+        )));
+    }
+    Ok(login)
+}
+
+/// Owners are fixed at setup; invites and role changes stop at admin.
+fn assignable_role(value: &str) -> Result<SpaceRole, AppError> {
+    SpaceRole::parse(value)
+        .filter(|role| SpaceRole::ASSIGNABLE.contains(role))
+        .ok_or_else(|| {
+            AppError::InvalidRequest("role must be viewer, editor, or admin".to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assignable_role, validate_login};
+    use crate::web::db::SpaceRole;
+
+    #[test]
+    fn invites_reject_owner_and_unknown_roles() {
+        assert_eq!(assignable_role("editor").unwrap(), SpaceRole::Editor);
+        assert!(assignable_role("owner").is_err());
+        assert!(assignable_role("root").is_err());
+    }
+
+    #[test]
+    fn invite_logins_are_trimmed_and_validated() {
+        assert_eq!(validate_login("  bob ").unwrap(), "bob");
+        assert!(validate_login("").is_err());
+        assert!(validate_login("bob smith").is_err());
+        assert!(validate_login("a/b").is_err());
+    }
+}
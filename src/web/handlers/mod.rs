@@ -2,8 +2,10 @@
 
 pub mod admin;
 pub mod assets;
+pub mod audit;
 pub mod discoverability;
 pub mod favorites;
+pub mod feeds;
 pub mod health;
 pub mod history;
 pub mod home;
@@ -11,26 +13,36 @@ mod http;
 pub mod live;
 pub mod login;
 pub mod logout;
+mod machine_auth;
 pub mod media;
 mod media_attachment_response;
 pub mod media_attachments;
-mod media_derivatives;
+mod media_dedup;
+pub(crate) mod media_derivatives;
 mod media_input;
 mod media_insert;
 mod media_support;
+pub mod members;
 mod note_media_input;
 pub mod password_reset;
 pub mod popular_sections;
 pub mod preview;
 pub mod resource;
 pub mod resource_api;
+mod resource_etag;
 pub mod resource_file;
+mod resource_file_location;
 mod resource_file_support;
+mod resource_file_transform;
 pub mod resource_history;
+pub mod resource_links;
+pub mod resource_patch;
 mod resource_payload;
+mod resource_target;
 pub mod resources;
 pub mod resources_scoped;
 pub mod search;
+pub mod service_accounts;
 pub mod session;
 pub mod settings;
 mod settings_input;
@@ -38,3 +50,10 @@ mod settings_input;
 mod settings_input_tests;
 pub mod setup;
 pub mod site_icon;
+pub mod snapshot_diff;
+mod space_access;
+pub mod tags;
+pub mod trash;
+mod upload_finalize;
+pub mod upload_sessions;
+mod uploaded_file;
@@ -1,16 +1,20 @@
 use crate::error::AppError;
 use axum::extract::Multipart;
 
-use super::media_input::{discard_field, field_bytes_limited, read_uploaded_file, UploadedFile};
+use super::media_input::{
+    discard_field, field_bytes_limited, parse_visibility, upload_id, UploadPart,
+};
+use super::uploaded_file::read_uploaded_file;
+use crate::web::db::Visibility;
 
 const MAX_TEXT_BYTES: usize = 2 * 1024 * 1024;
 
 pub struct NoteMediaAttachmentInput {
-    pub files: Vec<UploadedFile>,
+    pub files: Vec<UploadPart>,
     pub body: String,
     pub alias: Option<String>,
     pub is_favorite: bool,
-    pub is_private: bool,
+    pub visibility: Visibility,
     pub insert_start: usize,
     pub insert_end: usize,
 }
@@ -24,6 +28,7 @@ pub async fn parse_note_media_form(
     let mut alias = None;
     let mut is_favorite = None;
     let mut is_private = None;
+    let mut visibility = None;
     let mut insert_start = None;
     let mut insert_end = None;
     while let Some(field) = payload
@@ -32,11 +37,15 @@ pub async fn parse_note_media_form(
         .map_err(|e| AppError::InvalidRequest(format!("invalid multipart payload: {e}")))?
     {
         match field.name() {
-            Some("file") => files.push(read_uploaded_file(field, max_file_bytes).await?),
+            Some("file") => files.push(UploadPart::File(
+                read_uploaded_file(field, max_file_bytes).await?,
+            )),
+            Some("upload") => files.push(UploadPart::Session(upload_id(field).await?)),
             Some("body") => body = Some(raw_text(field).await?),
             Some("alias") => alias = text_value(field).await?,
             Some("is_favorite") => is_favorite = Some(bool_value(field).await?),
             Some("is_private") => is_private = Some(bool_value(field).await?),
+            Some("visibility") => visibility = Some(parse_visibility(text_value(field).await?)?),
             Some("insert_start") => insert_start = Some(usize_value(field).await?),
             Some("insert_end") => insert_end = Some(usize_value(field).await?),
             _ => {
@@ -45,14 +54,16 @@ pub async fn parse_note_media_form(
         }
     }
     if files.is_empty() {
-        return Err(invalid("at least one file is required"));
+        return Err(invalid("at least one file or upload is required"));
     }
     Ok(NoteMediaAttachmentInput {
         files,
         body: body.ok_or_else(|| invalid("body is required"))?,
         alias,
         is_favorite: is_favorite.ok_or_else(|| invalid("is_favorite is required"))?,
-        is_private: is_private.ok_or_else(|| invalid("is_private is required"))?,
+        visibility: visibility
+            .or(is_private.map(Visibility::from_is_private))
+            .ok_or_else(|| invalid("visibility is required"))?,
         insert_start: insert_start.ok_or_else(|| invalid("insert_start is required"))?,
         insert_end: insert_end.ok_or_else(|| invalid("insert_end is required"))?,
     })
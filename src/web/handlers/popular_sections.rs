@@ -1,9 +1,9 @@
 //! Popular-note fragment handlers
 
 use crate::error::AppError;
-use crate::web::db::{self, DbPool, PopularWindow};
+use crate::web::db::{self, DbPool, PopularWindow, Visibility};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::space_access;
 use crate::web::routes::AppState;
 use crate::web::templates;
 use crate::web::view;
@@ -41,12 +41,14 @@ async fn render_home_fragment(
     headers: &HeaderMap,
     window: PopularWindow,
 ) -> Result<Response, AppError> {
-    let is_admin = session::check_session(headers, pool).await?;
+    let max_visibility = space_access::space_access(headers, pool, None)
+        .await?
+        .max_visibility();
     let settings = db::get_settings(pool).await?;
     if !settings.home_popular_visible {
         return Ok(not_found());
     }
-    let items = popular_items(pool, is_admin, settings.home_popular_limit, window).await?;
+    let items = popular_items(pool, max_visibility, settings.home_popular_limit, window).await?;
     Ok(http::html(templates::home_popular_section(&items, window)))
 }
 
@@ -55,25 +57,30 @@ async fn render_admin_fragment(
     headers: &HeaderMap,
     window: PopularWindow,
 ) -> Result<Response, AppError> {
-    if !session::check_session(headers, pool).await? {
+    if !space_access::space_access(headers, pool, None)
+        .await?
+        .can_write()
+    {
         return Ok(unauthorized());
     }
     let limit = db::get_settings(pool).await?.home_popular_limit;
-    let items = popular_items(pool, true, limit, window).await?;
+    let items = popular_items(pool, Visibility::Private, limit, window).await?;
     Ok(http::html(templates::admin_popular_section(&items, window)))
 }
 
 async fn popular_items(
     pool: &DbPool,
-    is_admin: bool,
+    max_visibility: Visibility,
     limit: i64,
     window: PopularWindow,
 ) -> Result<Vec<templates::IndexItem>, AppError> {
     Ok(
-        db::list_popular_resources(pool, None, is_admin, limit, window)
+        db::list_popular_resources(pool, None, max_visibility, limit, window)
             .await?
             .iter()
-            .map(|resource| view::popular_index_item(resource, is_admin, window))
+            .map(|resource| {
+                view::popular_index_item(resource, max_visibility > Visibility::Public, window)
+            })
             .collect(),
     )
 }
@@ -1,13 +1,13 @@
 //! Markdown preview handler
 
 use crate::error::AppError;
-use crate::web::db;
+use crate::web::db::{self, SpaceRole, Visibility};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::space_access;
 use crate::web::markdown;
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
-use axum::extract::{Json, State};
+use axum::extract::{Json, Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
 use serde::{Deserialize, Serialize};
@@ -26,9 +26,11 @@ pub struct PreviewOutput {
 pub async fn render_markdown_preview(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Path(user): Path<String>,
     Json(body): Json<PreviewInput>,
 ) -> Result<Response, AppError> {
-    session::require_session(&headers, &state.pool).await?;
+    db::require_space(&state.pool, &user).await?;
+    space_access::require_role(&headers, &state.pool, Some(&user), SpaceRole::Editor).await?;
     let settings = db::get_settings(&state.pool).await?;
     let site = SiteContext::from_settings(&settings);
     crate::web::embed_unfurl::refresh_body_embeds(
@@ -43,8 +45,9 @@ pub async fn render_markdown_preview(
             html: markdown::render_markdown_page(
                 &state.pool,
                 &body.body,
+                Some(&user),
                 body.current_resource_id.as_deref(),
-                true,
+                Visibility::Private,
                 site.public_base_url.as_deref(),
                 Some(&settings.google_maps_embed_api_key),
             )
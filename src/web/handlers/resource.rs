@@ -1,24 +1,21 @@
 //! Resource page handler
 
-use crate::core::looks_like_id;
 use crate::error::AppError;
 use crate::web::db::{self, DbPool};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::resource_target::{
+    resolve_root_resource, resolve_space_resource, RootResource,
+};
+use crate::web::handlers::space_access::{self, SpaceAccess};
 use crate::web::markdown;
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
 use crate::web::templates;
-use crate::web::view;
+use crate::web::{view, view_chrome};
 use axum::extract::{Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
 
-enum RootResource {
-    Current(Box<db::Resource>),
-    Snapshot(Box<db::SnapshotTarget>),
-}
-
 pub async fn resource_page(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -28,21 +25,13 @@ pub async fn resource_page(
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    let is_admin = session::check_session(&headers, pool).await?;
     let settings = db::get_settings(pool).await?;
     let site = SiteContext::from_settings(&settings);
     let resource = match resolve_root_resource(pool, &reference).await? {
         Some(resource) => resource,
         None => return Ok(not_found(&site)),
     };
-    match resource {
-        RootResource::Current(resource) => {
-            render_current_resource(pool, &reference, resource.as_ref(), is_admin, &site).await
-        }
-        RootResource::Snapshot(resource) => {
-            render_snapshot(pool, resource.as_ref(), is_admin, &site).await
-        }
-    }
+    render_root_resource(pool, &headers, &reference, resource, &site).await
 }
 
 pub async fn resource_page_scoped(
@@ -55,70 +44,45 @@ pub async fn resource_page_scoped(
         return Ok(http::redirect("/setup"));
     }
     db::require_space(pool, &user).await?;
-    let is_admin = session::check_session(&headers, pool).await?;
     let settings = db::get_settings(pool).await?;
     let site = SiteContext::from_settings(&settings);
     let resource = match resolve_space_resource(pool, &user, &reference).await? {
         Some(resource) => resource,
         None => return Ok(not_found(&site)),
     };
+    render_root_resource(pool, &headers, &reference, resource, &site).await
+}
+
+/// Authorize against the resource's own space, then render it.
+async fn render_root_resource(
+    pool: &DbPool,
+    headers: &HeaderMap,
+    reference: &str,
+    resource: RootResource,
+    site: &SiteContext,
+) -> Result<Response, AppError> {
+    let access = space_access::space_access(headers, pool, Some(resource.space_slug())).await?;
     match resource {
         RootResource::Current(resource) => {
-            render_current_resource(pool, &reference, resource.as_ref(), is_admin, &site).await
+            render_current_resource(pool, reference, resource.as_ref(), access, site).await
         }
         RootResource::Snapshot(resource) => {
-            render_snapshot(pool, resource.as_ref(), is_admin, &site).await
+            render_snapshot(pool, resource.as_ref(), access, site).await
         }
     }
 }
 
-async fn resolve_root_resource(
-    pool: &DbPool,
-    reference: &str,
-) -> Result<Option<RootResource>, AppError> {
-    if !looks_like_id(reference) {
-        return Ok(db::get_resource_by_alias(pool, reference)
-            .await?
-            .map(Box::new)
-            .map(RootResource::Current));
-    }
-    if let Some(resource) = db::get_resource(pool, reference).await? {
-        return Ok(Some(RootResource::Current(Box::new(resource))));
-    }
-    Ok(db::get_snapshot_target(pool, reference)
-        .await?
-        .map(Box::new)
-        .map(RootResource::Snapshot))
-}
-
-async fn resolve_space_resource(
-    pool: &DbPool,
-    space_slug: &str,
-    reference: &str,
-) -> Result<Option<RootResource>, AppError> {
-    if let Some(resource) = db::get_resource_by_ref_in_space(pool, space_slug, reference).await? {
-        return Ok(Some(RootResource::Current(Box::new(resource))));
-    }
-    if looks_like_id(reference) {
-        return Ok(db::get_snapshot_target(pool, reference)
-            .await?
-            .filter(|target| target.resource.space_slug == space_slug)
-            .map(Box::new)
-            .map(RootResource::Snapshot));
-    }
-    Ok(None)
-}
-
 async fn render_current_resource(
     pool: &DbPool,
     reference: &str,
     resource: &db::Resource,
-    is_admin: bool,
+    access: SpaceAccess,
     site: &SiteContext,
 ) -> Result<Response, AppError> {
-    if resource.is_private && !is_admin {
+    if !access.can_read(resource.visibility) {
         return Ok(not_found(site));
     }
+    let is_admin = access.can_write();
     if resource
         .alias
         .as_deref()
@@ -127,16 +91,17 @@ async fn render_current_resource(
     {
         return Ok(http::redirect(&view::resource_href(resource)));
     }
-    if !is_admin {
+    if access.role.is_none() {
         db::count_resource_view(pool, &resource.id).await?;
     }
-    let chrome = view::resource_chrome(pool, resource, is_admin).await?;
+    let chrome = view_chrome::resource_chrome(pool, resource, access.max_visibility()).await?;
     let maps_key = db::get_settings(pool).await?.google_maps_embed_api_key;
     let body_html = markdown::render_markdown_page(
         pool,
         &resource.body,
+        Some(&resource.space_slug),
         Some(&resource.id),
-        is_admin,
+        access.max_visibility(),
         site.public_base_url.as_deref(),
         Some(&maps_key),
     )
@@ -161,19 +126,22 @@ async fn render_current_resource(
 async fn render_snapshot(
     pool: &DbPool,
     target: &db::SnapshotTarget,
-    is_admin: bool,
+    access: SpaceAccess,
     site: &SiteContext,
 ) -> Result<Response, AppError> {
-    if target.snapshot.is_private && !is_admin {
+    if !access.can_read(target.snapshot.visibility) {
         return Ok(not_found(site));
     }
-    let chrome = view::resource_chrome(pool, &target.resource, is_admin).await?;
+    let is_admin = access.can_write();
+    let chrome =
+        view_chrome::resource_chrome(pool, &target.resource, access.max_visibility()).await?;
     let maps_key = db::get_settings(pool).await?.google_maps_embed_api_key;
     let body_html = markdown::render_markdown_page(
         pool,
         &target.snapshot.body,
+        Some(&target.resource.space_slug),
         Some(&target.resource.id),
-        is_admin,
+        access.max_visibility(),
         site.public_base_url.as_deref(),
         Some(&maps_key),
     )
@@ -1,19 +1,22 @@
+use crate::core::SearchQuery;
 use crate::error::AppError;
 use crate::web::db::{
-    self, ListDirection, ListKind, ListRequest, ListScope, ListSort, ListedResource, PopularWindow,
+    self, ListDirection, ListKind, ListRequest, ListScope, ListedResource, PopularWindow,
+    Visibility,
 };
-use crate::web::handlers::http;
 use crate::web::handlers::resource_payload::ResourcePayload;
-use crate::web::handlers::search::SearchParams;
-use crate::web::handlers::session;
+use crate::web::handlers::search::{meta_filters, query_embedding, SearchParams};
+use crate::web::handlers::space_access;
+use crate::web::handlers::{http, machine_auth, resource_etag};
 use crate::web::routes::AppState;
 use axum::extract::{Path, Query, State};
-use axum::http::{HeaderMap, StatusCode};
+use axum::http::{HeaderMap, StatusCode, Uri};
 use axum::response::Response;
 use serde::Serialize;
 
 #[derive(Serialize)]
 struct SearchResponse {
+    query: SearchQuery,
     resources: Vec<SearchItem>,
     previous_cursor: Option<String>,
     next_cursor: Option<String>,
@@ -29,45 +32,57 @@ struct SearchItem {
 pub async fn search(
     State(state): State<AppState>,
     headers: HeaderMap,
+    uri: Uri,
     Query(params): Query<SearchParams>,
 ) -> Result<Response, AppError> {
-    search_inner(State(state), headers, Query(params), None).await
+    let max_visibility = space_access::space_access(&headers, &state.pool, None)
+        .await?
+        .max_visibility();
+    let meta = meta_filters(&uri);
+    search_inner(State(state), max_visibility, Query(params), meta, None).await
 }
 
 pub async fn search_scoped(
     State(state): State<AppState>,
     headers: HeaderMap,
+    uri: Uri,
     Path(user): Path<String>,
     Query(params): Query<SearchParams>,
 ) -> Result<Response, AppError> {
     db::require_space(&state.pool, &user).await?;
-    search_inner(State(state), headers, Query(params), Some(user)).await
+    let max_visibility = machine_auth::read_access(&headers, &state.pool, &user).await?;
+    let meta = meta_filters(&uri);
+    search_inner(
+        State(state),
+        max_visibility,
+        Query(params),
+        meta,
+        Some(user),
+    )
+    .await
 }
 
 async fn search_inner(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    max_visibility: Visibility,
     Query(params): Query<SearchParams>,
+    meta: Vec<(String, String)>,
     space_slug: Option<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    let is_admin = session::check_session(&headers, pool).await?;
     let settings = db::get_settings(pool).await?;
-    let query = params
-        .q
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string);
+    let parsed = params.parsed_query();
+    let query = (!parsed.text.is_empty()).then(|| parsed.text.clone());
     let direction = ListDirection::resolve(params.direction.as_deref(), params.cursor.as_deref());
     let kind = ListKind::resolve(params.kind.as_deref());
     let scope = ListScope::resolve(params.scope.as_deref());
-    let sort = ListSort::resolve(params.sort.as_deref(), query.is_some(), &scope);
+    let sort = params.sort(query.is_some(), &scope);
+    let query_embedding = query_embedding(&state, &sort, query.as_deref()).await;
     let page = db::list_resources(
         pool,
         &ListRequest {
             space_slug,
-            include_private: is_admin,
+            max_visibility,
             limit: params.limit.unwrap_or(settings.search_results_per_page),
             query,
             direction,
@@ -76,12 +91,17 @@ async fn search_inner(
             sort: sort.clone(),
             popular_window: sort.popular_window().unwrap_or(PopularWindow::Days30),
             cursor: params.cursor,
+            tag: params.tag,
+            meta,
+            filters: parsed.filters.clone(),
+            query_embedding,
         },
     )
     .await?;
     Ok(http::json_status(
         StatusCode::OK,
         SearchResponse {
+            query: parsed,
             resources: page
                 .resources
                 .into_iter()
@@ -99,11 +119,13 @@ pub async fn fetch(
     Path(reference): Path<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    let is_admin = session::check_session(&headers, pool).await?;
+    let max_visibility = space_access::space_access(&headers, pool, None)
+        .await?
+        .max_visibility();
     match db::get_resource_by_ref(pool, &reference).await? {
-        Some(resource) if is_admin || !resource.is_private => Ok(http::json_status(
+        Some(resource) if resource.visibility <= max_visibility => Ok(http::json_status(
             StatusCode::OK,
-            ResourcePayload::from_resource(resource),
+            ResourcePayload::with_jobs(pool, resource).await?,
         )),
         _ => Err(AppError::NotFound(format!(
             "resource '{reference}' not found"
@@ -118,12 +140,18 @@ pub async fn fetch_scoped(
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     db::require_space(pool, &user).await?;
-    let is_admin = session::check_session(&headers, pool).await?;
+    let max_visibility = machine_auth::read_access(&headers, pool, &user).await?;
     match db::get_resource_by_ref_in_space(pool, &user, &reference).await? {
-        Some(resource) if is_admin || !resource.is_private => Ok(http::json_status(
-            StatusCode::OK,
-            ResourcePayload::from_resource(resource),
-        )),
+        Some(resource) if resource.visibility <= max_visibility => {
+            let snapshot = db::current_snapshot_number(pool, &resource.id).await?;
+            Ok(resource_etag::with_etag(
+                http::json_status(
+                    StatusCode::OK,
+                    ResourcePayload::with_jobs(pool, resource).await?,
+                ),
+                snapshot,
+            ))
+        }
         _ => Err(AppError::NotFound(format!(
             "resource '{reference}' not found"
         ))),
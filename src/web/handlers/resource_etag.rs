@@ -0,0 +1,74 @@
+//! Snapshot-based ETag and If-Match handling for note writes
+
+use crate::error::AppError;
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::response::Response;
+
+/// Attach the strong ETag for one saved snapshot number.
+pub fn with_etag(mut response: Response, snapshot: i32) -> Response {
+    if let Ok(value) = HeaderValue::from_str(&format!("\"{snapshot}\"")) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response
+}
+
+/// Resolve the base snapshot from `If-Match` and the JSON `base_snapshot` field.
+pub fn base_snapshot(headers: &HeaderMap, body: Option<i32>) -> Result<Option<i32>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(body);
+    };
+    let header = value
+        .to_str()
+        .ok()
+        .and_then(parse_if_match)
+        .ok_or_else(|| AppError::InvalidRequest("If-Match must be a snapshot ETag".to_string()))?;
+    match (header, body) {
+        (Some(header), Some(body)) if header != body => Err(AppError::InvalidRequest(
+            "If-Match and base_snapshot disagree".to_string(),
+        )),
+        (header, body) => Ok(header.or(body)),
+    }
+}
+
+/// `*` matches any snapshot; otherwise expect one quoted number.
+fn parse_if_match(value: &str) -> Option<Option<i32>> {
+    let value = value.trim();
+    if value == "*" {
+        return Some(None);
+    }
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(|value| value.parse().ok())
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::base_snapshot;
+    use axum::http::{header, HeaderMap, HeaderValue};
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn base_snapshot_reads_header_or_body() {
+        assert_eq!(base_snapshot(&HeaderMap::new(), Some(3)).unwrap(), Some(3));
+        assert_eq!(base_snapshot(&headers("\"4\""), None).unwrap(), Some(4));
+        assert_eq!(
+            base_snapshot(&headers("W/\"4\""), Some(4)).unwrap(),
+            Some(4)
+        );
+        assert_eq!(base_snapshot(&headers("*"), None).unwrap(), None);
+    }
+
+    #[test]
+    fn base_snapshot_rejects_bad_or_mismatched_values() {
+        assert!(base_snapshot(&headers("abc"), None).is_err());
+        assert!(base_snapshot(&headers("\"4\""), Some(5)).is_err());
+    }
+}
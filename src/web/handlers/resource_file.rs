@@ -1,20 +1,39 @@
-use crate::core::looks_like_id;
 use crate::error::AppError;
-use crate::media::MediaVariants;
-use crate::storage::Storage;
-use crate::web::db::{self, DbPool, ResourceKind};
-use crate::web::handlers::http;
-use crate::web::handlers::resource_file_support::inline_image_fallback_allowed;
-use crate::web::handlers::session;
+use crate::media::{MediaDetails, TransformParams};
+use crate::web::db::{self, ResourceKind, Visibility};
+use crate::web::handlers::resource_file_location::{
+    location_stripped_file, strips_location, untouched_original,
+};
+use crate::web::handlers::resource_file_support::{stream_file, variant_file, ResourceFileRef};
+use crate::web::handlers::resource_file_transform::{image_transform, transformed_file};
+use crate::web::handlers::resource_target::{resolve_space_resource, RootResource};
+use crate::web::handlers::space_access;
 use crate::web::routes::AppState;
 use axum::extract::{Path, Query, State};
-use axum::http::{header, HeaderMap, StatusCode};
+use axum::http::{header, HeaderMap};
 use axum::response::Response;
 use serde::Deserialize;
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct FileQuery {
     variant: Option<String>,
+    w: Option<String>,
+    h: Option<String>,
+    fit: Option<String>,
+    format: Option<String>,
+    q: Option<String>,
+}
+
+impl FileQuery {
+    fn transform_params(&self) -> TransformParams<'_> {
+        TransformParams {
+            width: self.w.as_deref(),
+            height: self.h.as_deref(),
+            fit: self.fit.as_deref(),
+            format: self.format.as_deref(),
+            quality: self.q.as_deref(),
+        }
+    }
 }
 
 pub async fn current_file(
@@ -23,169 +42,142 @@ pub async fn current_file(
     Path(reference): Path<String>,
     Query(query): Query<FileQuery>,
 ) -> Result<Response, AppError> {
-    let pool = &state.pool;
-    let is_admin = session::check_session(&headers, pool).await?;
-    let variant = query.variant.as_deref();
-    let file = if looks_like_id(&reference) {
-        resolve_id_backed_file(pool, &reference, is_admin, variant).await?
-    } else {
-        db::get_resource_by_alias(pool, &reference)
-            .await?
-            .map(|resource| file_from_resource(resource, is_admin, variant))
-            .transpose()?
-            .flatten()
-    };
-    let Some(file) = file else {
-        return Err(AppError::NotFound("resource file not found".to_string()));
-    };
-    stream_file(
-        &state.storage,
-        file.file_key.as_deref(),
-        file.content_type.as_deref(),
-        headers
-            .get(header::RANGE)
-            .and_then(|value| value.to_str().ok()),
-    )
-    .await
+    current_file_inner(State(state), headers, reference, query, None).await
 }
 
 pub async fn current_file_scoped(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Path((_user, reference)): Path<(String, String)>,
+    Path((user, reference)): Path<(String, String)>,
     Query(query): Query<FileQuery>,
 ) -> Result<Response, AppError> {
-    current_file(State(state), headers, Path(reference), Query(query)).await
+    current_file_inner(State(state), headers, reference, query, Some(user)).await
 }
 
-async fn resolve_id_backed_file(
-    pool: &DbPool,
-    reference: &str,
-    is_admin: bool,
-    variant: Option<&str>,
-) -> Result<Option<ResourceFileRef>, AppError> {
-    if let Some(resource) = db::get_resource(pool, reference).await? {
-        return file_from_resource(resource, is_admin, variant);
-    }
-    db::get_snapshot_target(pool, reference)
-        .await?
-        .map(|resource| file_from_snapshot(resource, is_admin, variant))
-        .transpose()
-        .map(Option::flatten)
+/// Files of the HLS package; relative playlist entries resolve below `file/hls/`.
+pub async fn hls_file_scoped(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, reference, name)): Path<(String, String, String)>,
+) -> Result<Response, AppError> {
+    let query = FileQuery {
+        variant: Some(format!("hls/{name}")),
+        ..FileQuery::default()
+    };
+    current_file_inner(State(state), headers, reference, query, Some(user)).await
 }
 
-async fn stream_file(
-    storage: &Storage,
-    file_key: Option<&str>,
-    content_type: Option<&str>,
-    range: Option<&str>,
+async fn current_file_inner(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    reference: String,
+    query: FileQuery,
+    space_slug: Option<String>,
 ) -> Result<Response, AppError> {
-    let object = storage
-        .get_object(
-            file_key.ok_or_else(|| AppError::NotFound("file not found".to_string()))?,
-            range,
-        )
-        .await?;
-    let status = if object.content_range.is_some() {
-        StatusCode::PARTIAL_CONTENT
-    } else {
-        StatusCode::OK
+    let pool = &state.pool;
+    let access = space_access::space_access(&headers, pool, space_slug.as_deref()).await?;
+    let max_visibility = access.max_visibility();
+    let (untouched, variant) = untouched_original(access, query.variant.as_deref())?;
+    // Access was granted for this space only, so the file must live in it.
+    let space_slug = match space_slug {
+        Some(slug) => slug,
+        None => db::default_space_slug(pool).await?,
     };
-    let mut response = http::bytes_with_type(
-        status,
-        content_type.unwrap_or("application/octet-stream"),
-        object.body,
-    );
-    http::set_header(&mut response, header::ACCEPT_RANGES, "bytes");
-    http::set_header(
-        &mut response,
-        header::CONTENT_LENGTH,
-        &object.content_length.to_string(),
-    );
-    http::set_header(&mut response, header::CONTENT_ENCODING, "identity");
-    if let Some(range) = object.content_range {
-        http::set_header(&mut response, header::CONTENT_RANGE, &range);
+    let transform = image_transform(pool, &space_slug, query.transform_params(), variant).await?;
+    let file = match resolve_space_resource(pool, &space_slug, &reference).await? {
+        Some(RootResource::Current(resource)) => {
+            file_from_resource(*resource, max_visibility, variant)?
+        }
+        Some(RootResource::Snapshot(target)) => {
+            file_from_snapshot(*target, max_visibility, variant)?
+        }
+        None => None,
+    };
+    let Some(file) = file else {
+        return Err(AppError::NotFound("resource file not found".to_string()));
+    };
+    if let Some(transform) = transform {
+        return transformed_file(
+            &state.storage,
+            &state.image_transforms,
+            file.file_key.as_deref(),
+            file.content_type.as_deref(),
+            &transform,
+        )
+        .await;
     }
-    Ok(response)
-}
-
-struct ResourceFileRef {
-    file_key: Option<String>,
-    content_type: Option<String>,
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    if let Some(file_key) = file.file_key.as_deref().filter(|_| file.has_location) {
+        if !untouched && strips_location(pool, Some(&space_slug)).await? {
+            let content_type = file.content_type.as_deref();
+            return location_stripped_file(&state.storage, file_key, content_type, range).await;
+        }
+    }
+    stream_file(
+        &state.storage,
+        file.file_key.as_deref(),
+        file.content_type.as_deref(),
+        range,
+    )
+    .await
 }
 
 fn file_from_resource(
     resource: db::Resource,
-    is_admin: bool,
+    max_visibility: Visibility,
     variant: Option<&str>,
 ) -> Result<Option<ResourceFileRef>, AppError> {
-    if resource.kind != ResourceKind::Media || (resource.is_private && !is_admin) {
+    if resource.kind != ResourceKind::Media || resource.visibility > max_visibility {
         return Ok(None);
     }
+    let has_location = located(&resource.media_details);
     if let Some(variant) = variant {
         return variant_file(
             resource.file_key,
             resource.content_type,
             resource.original_filename,
             resource.media_variants,
+            has_location,
             variant,
         );
     }
     Ok(Some(ResourceFileRef {
         file_key: resource.file_key,
         content_type: resource.content_type,
+        has_location,
     }))
 }
 
 fn file_from_snapshot(
     resource: db::SnapshotTarget,
-    is_admin: bool,
+    max_visibility: Visibility,
     variant: Option<&str>,
 ) -> Result<Option<ResourceFileRef>, AppError> {
-    if resource.snapshot.kind != ResourceKind::Media || (resource.snapshot.is_private && !is_admin)
+    if resource.snapshot.kind != ResourceKind::Media
+        || resource.snapshot.visibility > max_visibility
     {
         return Ok(None);
     }
+    let has_location = located(&resource.snapshot.media_details);
     if let Some(variant) = variant {
         return variant_file(
             resource.snapshot.file_key,
             resource.snapshot.content_type,
             resource.snapshot.original_filename,
             resource.snapshot.media_variants,
+            has_location,
             variant,
         );
     }
     Ok(Some(ResourceFileRef {
         file_key: resource.snapshot.file_key,
         content_type: resource.snapshot.content_type,
+        has_location,
     }))
 }
 
-fn variant_file(
-    file_key: Option<String>,
-    content_type: Option<String>,
-    original_filename: Option<String>,
-    variants: Option<MediaVariants>,
-    variant: &str,
-) -> Result<Option<ResourceFileRef>, AppError> {
-    if !matches!(variant, "card" | "display" | "poster") {
-        return Err(AppError::InvalidRequest(
-            "unknown media variant".to_string(),
-        ));
-    }
-    if let Some(item) = variants.as_ref().and_then(|variants| variants.get(variant)) {
-        return Ok(Some(ResourceFileRef {
-            file_key: Some(item.key.clone()),
-            content_type: Some(item.content_type.clone()),
-        }));
-    }
-    if matches!(variant, "card" | "display")
-        && inline_image_fallback_allowed(content_type.as_deref(), original_filename.as_deref())
-    {
-        return Ok(file_key.map(|key| ResourceFileRef {
-            file_key: Some(key),
-            content_type,
-        }));
-    }
-    Ok(None)
+fn located(details: &Option<MediaDetails>) -> bool {
+    details.as_ref().is_some_and(|details| details.has_location)
 }
@@ -0,0 +1,81 @@
+//! Location-stripped copies of image originals that carry GPS metadata
+
+use crate::error::AppError;
+use crate::media::{strip_location, transform_prefix};
+use crate::storage::{Storage, StoredObject};
+use crate::web::db::{self, DbPool, SpaceRole};
+use crate::web::handlers::resource_file_support::{object_response, stream_file};
+use crate::web::handlers::space_access::SpaceAccess;
+use axum::response::Response;
+use tracing::warn;
+
+/// `?variant=original` name for the untouched upload.
+pub(super) const ORIGINAL_VARIANT: &str = "original";
+
+/// Split `?variant=original` off the variant name; only editors may ask for it.
+pub(super) fn untouched_original(
+    access: SpaceAccess,
+    variant: Option<&str>,
+) -> Result<(bool, Option<&str>), AppError> {
+    if variant != Some(ORIGINAL_VARIANT) {
+        return Ok((false, variant));
+    }
+    access.require(SpaceRole::Editor)?;
+    Ok((true, None))
+}
+
+/// Whether the space hides locations of originals from readers.
+pub(super) async fn strips_location(
+    pool: &DbPool,
+    space_slug: Option<&str>,
+) -> Result<bool, AppError> {
+    let settings = match space_slug {
+        Some(slug) => db::get_settings_in_space(pool, slug).await?,
+        None => db::get_settings(pool).await?,
+    };
+    Ok(settings.media_strip_location)
+}
+
+/// Serve the cached sanitized copy, writing it beside the original's
+/// transforms on first request; formats without a sanitizer stream as-is.
+pub(super) async fn location_stripped_file(
+    storage: &Storage,
+    file_key: &str,
+    content_type: Option<&str>,
+    range: Option<&str>,
+) -> Result<Response, AppError> {
+    let key = format!("{}location-stripped", transform_prefix(file_key));
+    if let Some(object) = storage.get_object_if_exists(&key).await? {
+        return match range {
+            None => Ok(object_response(object, content_type)),
+            Some(_) => stream_file(storage, Some(&key), content_type, range).await,
+        };
+    }
+    let original = storage.get_object(file_key, None).await?;
+    let stripped = tokio::task::spawn_blocking(move || strip_location(&original.body))
+        .await
+        .ok()
+        .flatten();
+    let Some(stripped) = stripped else {
+        return stream_file(storage, Some(file_key), content_type, range).await;
+    };
+    let content_length = stripped.len() as i64;
+    let output_type = content_type.unwrap_or("application/octet-stream");
+    match storage
+        .put_object(&key, stripped.clone(), output_type)
+        .await
+    {
+        Ok(()) if range.is_some() => stream_file(storage, Some(&key), content_type, range).await,
+        stored => {
+            if let Err(error) = stored {
+                warn!(key = %key, error = %error, "location-stripped copy write failed");
+            }
+            let object = StoredObject {
+                body: stripped,
+                content_length,
+                content_range: None,
+            };
+            Ok(object_response(object, content_type))
+        }
+    }
+}
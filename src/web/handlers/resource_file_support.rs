@@ -1,3 +1,17 @@
+use crate::error::AppError;
+use crate::media::{is_variant_name, MediaVariants};
+use crate::storage::{Storage, StoredObject};
+use crate::web::handlers::http;
+use axum::http::{header, StatusCode};
+use axum::response::Response;
+
+pub(super) struct ResourceFileRef {
+    pub(super) file_key: Option<String>,
+    pub(super) content_type: Option<String>,
+    /// The object is an original whose metadata carries a location.
+    pub(super) has_location: bool,
+}
+
 pub fn inline_image_fallback_allowed(
     content_type: Option<&str>,
     original_filename: Option<&str>,
@@ -27,3 +41,76 @@ fn browser_safe_image_filename(value: &str) -> bool {
             )
         })
 }
+
+pub async fn stream_file(
+    storage: &Storage,
+    file_key: Option<&str>,
+    content_type: Option<&str>,
+    range: Option<&str>,
+) -> Result<Response, AppError> {
+    let object = storage
+        .get_object(
+            file_key.ok_or_else(|| AppError::NotFound("file not found".to_string()))?,
+            range,
+        )
+        .await?;
+    Ok(object_response(object, content_type))
+}
+
+/// Byte-range aware response for one fetched object.
+pub(super) fn object_response(object: StoredObject, content_type: Option<&str>) -> Response {
+    let status = if object.content_range.is_some() {
+        StatusCode::PARTIAL_CONTENT
+    } else {
+        StatusCode::OK
+    };
+    let mut response = http::bytes_with_type(
+        status,
+        content_type.unwrap_or("application/octet-stream"),
+        object.body,
+    );
+    http::set_header(&mut response, header::ACCEPT_RANGES, "bytes");
+    http::set_header(
+        &mut response,
+        header::CONTENT_LENGTH,
+        &object.content_length.to_string(),
+    );
+    http::set_header(&mut response, header::CONTENT_ENCODING, "identity");
+    if let Some(range) = object.content_range {
+        http::set_header(&mut response, header::CONTENT_RANGE, &range);
+    }
+    response
+}
+
+/// Stored object behind a `?variant=` name, with the inline image fallback.
+pub(super) fn variant_file(
+    file_key: Option<String>,
+    content_type: Option<String>,
+    original_filename: Option<String>,
+    variants: Option<MediaVariants>,
+    has_location: bool,
+    variant: &str,
+) -> Result<Option<ResourceFileRef>, AppError> {
+    if !is_variant_name(variant) {
+        return Err(AppError::InvalidRequest(
+            "unknown media variant".to_string(),
+        ));
+    }
+    if let Some((key, content_type)) = variants.as_ref().and_then(|items| items.object(variant)) {
+        return Ok(Some(ResourceFileRef {
+            file_key: Some(key.to_string()),
+            content_type: Some(content_type.to_string()),
+            has_location: false,
+        }));
+    }
+    if matches!(variant, "card" | "display")
+        && inline_image_fallback_allowed(content_type.as_deref(), original_filename.as_deref())
+    {
+        return Ok(file_key.map(|key| ResourceFileRef {
+            file_key: Some(key),
+            content_type,
+            has_location,
+        }));
+    }
+    Ok(None)
+}
@@ -0,0 +1,108 @@
+//! Lazily generated, storage-cached image transforms of media originals
+
+use crate::error::AppError;
+use crate::media::{transform_image, transform_source_fits, ImageTransform, TransformParams};
+use crate::storage::Storage;
+use crate::web::db::{self, DbPool};
+use crate::web::handlers::http;
+use crate::web::image_transforms::ImageTransforms;
+use axum::http::StatusCode;
+use axum::response::Response;
+use tracing::warn;
+
+/// Transform parameters apply to the original and cannot name a variant too;
+/// the quality default comes from the settings of the file's space.
+pub(super) async fn image_transform(
+    pool: &DbPool,
+    space_slug: &str,
+    params: TransformParams<'_>,
+    variant: Option<&str>,
+) -> Result<Option<ImageTransform>, AppError> {
+    if params.is_empty() {
+        return Ok(None);
+    }
+    if variant.is_some() {
+        return Err(AppError::InvalidRequest(
+            "variant cannot be combined with transform parameters".to_string(),
+        ));
+    }
+    let quality = db::get_settings_in_space(pool, space_slug)
+        .await?
+        .media_webp_quality;
+    ImageTransform::parse(&params, quality)
+        .map(Some)
+        .map_err(AppError::InvalidRequest)
+}
+
+/// Originals larger than this are not fetched for a transform.
+const MAX_TRANSFORM_SOURCE_BYTES: i64 = 64 * 1024 * 1024;
+
+/// Serve the cached transform, generating and storing it on first request.
+/// Misses run through the shared `ImageTransforms` limiter, so concurrent
+/// requests for one key wait on a single encode.
+pub(super) async fn transformed_file(
+    storage: &Storage,
+    transforms: &ImageTransforms,
+    file_key: Option<&str>,
+    content_type: Option<&str>,
+    transform: &ImageTransform,
+) -> Result<Response, AppError> {
+    let Some(file_key) = file_key else {
+        return Err(AppError::NotFound("file not found".to_string()));
+    };
+    if !content_type.is_some_and(|value| value.starts_with("image/")) {
+        return Err(AppError::InvalidRequest(
+            "transform parameters need image media".to_string(),
+        ));
+    }
+    let output_type = transform.format.content_type();
+    let key = transform.cache_key(file_key);
+    if let Some(object) = storage.get_object_if_exists(&key).await? {
+        return Ok(http::bytes_with_type(
+            StatusCode::OK,
+            output_type,
+            object.body,
+        ));
+    }
+    let encode = encode_and_store(
+        storage.clone(),
+        file_key.to_string(),
+        key.clone(),
+        transform.clone(),
+    );
+    let bytes = transforms.run(&key, encode).await?;
+    Ok(http::bytes_with_type(StatusCode::OK, output_type, bytes))
+}
+
+/// Fetch the original, refusing it by size and header dimensions before
+/// decoding, then transform it and write the result to the cache.
+async fn encode_and_store(
+    storage: Storage,
+    file_key: String,
+    key: String,
+    transform: ImageTransform,
+) -> Result<Vec<u8>, AppError> {
+    if storage.object_size(&file_key).await? > MAX_TRANSFORM_SOURCE_BYTES {
+        return Err(too_large());
+    }
+    let original = storage.get_object(&file_key, None).await?;
+    let job = transform.clone();
+    let bytes = tokio::task::spawn_blocking(move || {
+        if !transform_source_fits(&original.body) {
+            return Err(too_large());
+        }
+        transform_image(&original.body, &job)
+            .ok_or_else(|| AppError::InvalidRequest("image could not be transformed".to_string()))
+    })
+    .await
+    .map_err(|e| AppError::StorageError(format!("image transform task failed: {e}")))??;
+    let output_type = transform.format.content_type();
+    if let Err(error) = storage.put_object(&key, bytes.clone(), output_type).await {
+        warn!(key = %key, error = %error, "image transform cache write failed");
+    }
+    Ok(bytes)
+}
+
+fn too_large() -> AppError {
+    AppError::InvalidRequest("image is too large to transform".to_string())
+}
@@ -2,9 +2,9 @@
 
 use crate::core::validate_id;
 use crate::error::AppError;
-use crate::web::db::{self, DbPool, ListDirection};
-use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::db::{self, DbPool, ListDirection, SpaceRole, Visibility};
+use crate::web::handlers::{http, machine_auth};
+use crate::web::handlers::{session, space_access};
 use crate::web::routes::AppState;
 use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
@@ -32,14 +32,15 @@ pub async fn history(
     let pool = &state.pool;
     session::require_session(&headers, pool).await?;
     validate_id(&id)?;
-    if db::get_resource(pool, &id).await?.is_none() {
-        return Err(AppError::NotFound(format!("resource '{id}' not found")));
-    }
+    let resource = db::get_resource(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("resource '{id}' not found")))?;
+    let max_visibility = require_viewer(&headers, pool, &resource).await?;
     let settings = db::get_settings(pool).await?;
     let page = db::list_resource_snapshots(
         pool,
         &id,
-        true,
+        max_visibility,
         params.limit.unwrap_or(settings.search_results_per_page),
         &ListDirection::resolve(params.direction.as_deref(), params.cursor.as_deref()),
         params.cursor.as_deref(),
@@ -68,11 +69,12 @@ pub async fn api_history(
     let resource = db::get_resource_by_ref(pool, &reference)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let max_visibility = require_viewer(&headers, pool, &resource).await?;
     let settings = db::get_settings(pool).await?;
     let page = db::list_resource_snapshots(
         pool,
         &resource.id,
-        true,
+        max_visibility,
         params.limit.unwrap_or(settings.search_results_per_page),
         &ListDirection::resolve(params.direction.as_deref(), params.cursor.as_deref()),
         params.cursor.as_deref(),
@@ -84,10 +86,27 @@ pub async fn api_history(
 pub async fn api_history_scoped(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Path((_user, reference)): Path<(String, String)>,
+    Path((user, reference)): Path<(String, String)>,
     Query(params): Query<HistoryParams>,
 ) -> Result<Response, AppError> {
-    api_history(State(state), headers, Path(reference), Query(params)).await
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let max_visibility = machine_auth::require_read(&headers, pool, &user).await?;
+    let resource = db::get_resource_by_ref_in_space(pool, &user, &reference)
+        .await?
+        .filter(|resource| resource.visibility <= max_visibility)
+        .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let settings = db::get_settings_in_space(pool, &user).await?;
+    let page = db::list_resource_snapshots(
+        pool,
+        &resource.id,
+        max_visibility,
+        params.limit.unwrap_or(settings.search_results_per_page),
+        &ListDirection::resolve(params.direction.as_deref(), params.cursor.as_deref()),
+        params.cursor.as_deref(),
+    )
+    .await?;
+    Ok(http::json_status(StatusCode::OK, page))
 }
 
 pub async fn previous(
@@ -129,14 +148,19 @@ async fn nav_response(
     older: bool,
 ) -> Result<Response, AppError> {
     validate_id(&id)?;
-    let is_admin = session::check_session(headers, pool).await?;
     let resource = db::get_resource(pool, &id).await?;
+    let max_visibility = match resource.as_ref() {
+        Some(resource) => space_access::space_access(headers, pool, Some(&resource.space_slug))
+            .await?
+            .max_visibility(),
+        None => Visibility::Public,
+    };
     match resource {
-        Some(resource) if is_admin || !resource.is_private => {
+        Some(resource) if resource.visibility <= max_visibility => {
             let neighbor = if older {
-                db::get_previous_resource(pool, &id, is_admin).await?
+                db::get_previous_resource(pool, &id, max_visibility).await?
             } else {
-                db::get_next_resource(pool, &id, is_admin).await?
+                db::get_next_resource(pool, &id, max_visibility).await?
             };
             Ok(http::json_status(
                 StatusCode::OK,
@@ -148,3 +172,20 @@ async fn nav_response(
         _ => Err(AppError::NotFound(format!("resource '{id}' not found"))),
     }
 }
+
+/// Snapshot lists are member-only in the resource's own space.
+async fn require_viewer(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    resource: &db::Resource,
+) -> Result<Visibility, AppError> {
+    let access = space_access::space_access(headers, pool, Some(&resource.space_slug)).await?;
+    access.require(SpaceRole::Viewer)?;
+    if !access.can_read(resource.visibility) {
+        return Err(AppError::NotFound(format!(
+            "resource '{}' not found",
+            resource.id
+        )));
+    }
+    Ok(access.max_visibility())
+}
@@ -0,0 +1,63 @@
+//! Resource link graph JSON handler
+
+use super::{http, machine_auth};
+use crate::error::AppError;
+use crate::web::db::{self, ResourceLink, Visibility};
+use crate::web::routes::AppState;
+use crate::web::view;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct LinkedResource {
+    id: String,
+    alias: Option<String>,
+    title: String,
+    href: String,
+    visibility: Visibility,
+}
+
+#[derive(Serialize)]
+struct LinksResponse {
+    resource_id: String,
+    outgoing: Vec<ResourceLink>,
+    incoming: Vec<LinkedResource>,
+    broken_count: usize,
+}
+
+pub async fn api_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, reference)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let max_visibility = machine_auth::require_read(&headers, pool, &user).await?;
+    let resource = db::get_resource_by_ref_in_space(pool, &user, &reference)
+        .await?
+        .filter(|resource| resource.visibility <= max_visibility)
+        .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let outgoing = db::list_outgoing_links(pool, &resource, max_visibility).await?;
+    let incoming = db::list_backlinks(pool, &resource, max_visibility)
+        .await?
+        .into_iter()
+        .map(|source| LinkedResource {
+            href: view::resource_href(&source),
+            id: source.id,
+            alias: source.alias,
+            title: source.title,
+            visibility: source.visibility,
+        })
+        .collect();
+    Ok(http::json_status(
+        StatusCode::OK,
+        LinksResponse {
+            resource_id: resource.id,
+            broken_count: outgoing.iter().filter(|link| link.broken).count(),
+            outgoing,
+            incoming,
+        },
+    ))
+}
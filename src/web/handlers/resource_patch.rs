@@ -0,0 +1,44 @@
+//! Partial note updates for the machine API
+
+use super::{machine_auth, resource_etag, resources_scoped::json_resource};
+use crate::error::AppError;
+use crate::web::db::{self, PatchOperation};
+use crate::web::routes::AppState;
+use axum::extract::{Json, Path, State};
+use axum::http::HeaderMap;
+use axum::response::Response;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct PatchInput {
+    pub operations: Vec<PatchOperation>,
+    #[serde(default)]
+    pub base_snapshot: Option<i32>,
+}
+
+pub async fn api_patch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, reference)): Path<(String, String)>,
+    Json(body): Json<PatchInput>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
+    let resource = db::get_resource_by_ref_in_space(pool, &user, &reference)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let patched = db::patch_resource_in_space(
+        pool,
+        &db::ResourcePatch {
+            space_slug: &user,
+            id: &resource.id,
+            operations: &body.operations,
+            actor: &actor,
+            base_snapshot: resource_etag::base_snapshot(&headers, body.base_snapshot)?,
+        },
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    json_resource(pool, patched).await
+}
@@ -1,4 +1,5 @@
-use crate::web::db::Resource;
+use crate::error::AppError;
+use crate::web::db::{self, DbPool, Resource, ResourceJob, Visibility};
 use crate::web::view;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -20,13 +21,22 @@ pub struct ResourcePayload {
     height: Option<i32>,
     duration_ms: Option<i64>,
     media_variants: Option<crate::media::MediaVariants>,
+    media_details: Option<crate::media::MediaDetails>,
     owner_note_id: Option<String>,
+    /// Front matter of the body: `tags` plus typed custom fields.
+    metadata: serde_json::Value,
     is_favorite: bool,
     favorite_position: Option<i64>,
     is_private: bool,
-    visibility: &'static str,
+    visibility: Visibility,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    /// Newest background job of each kind; omitted from listings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    jobs: Vec<ResourceJob>,
+    /// Existing live media in the space with the same content; upload responses only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicate_of: Option<String>,
 }
 
 impl ResourcePayload {
@@ -34,6 +44,9 @@ impl ResourcePayload {
         let file_href = (resource.kind == crate::web::db::ResourceKind::Media)
             .then(|| view::file_href(&resource));
         let href = view::resource_href(&resource);
+        let metadata = crate::core::parse_front_matter(&resource.body)
+            .map(|front| front.to_json())
+            .unwrap_or_else(|| serde_json::json!({}));
         Self {
             id: resource.id,
             kind: resource.kind,
@@ -50,17 +63,33 @@ impl ResourcePayload {
             height: resource.height,
             duration_ms: resource.duration_ms,
             media_variants: resource.media_variants,
+            media_details: resource.media_details,
             owner_note_id: resource.owner_note_id,
+            metadata,
             is_favorite: resource.is_favorite,
             favorite_position: resource.favorite_position,
-            is_private: resource.is_private,
-            visibility: if resource.is_private {
-                "private"
-            } else {
-                "public"
-            },
+            is_private: resource.visibility == Visibility::Private,
+            visibility: resource.visibility,
             created_at: resource.created_at,
             updated_at: resource.updated_at,
+            jobs: Vec::new(),
+            duplicate_of: None,
+        }
+    }
+
+    /// Single-resource responses also report queued, running, or finished jobs.
+    pub async fn with_jobs(pool: &DbPool, resource: Resource) -> Result<Self, AppError> {
+        let jobs = db::list_resource_jobs(pool, &resource.id).await?;
+        Ok(Self {
+            jobs,
+            ..Self::from_resource(resource)
+        })
+    }
+
+    pub fn with_duplicate_of(self, duplicate_of: Option<String>) -> Self {
+        Self {
+            duplicate_of,
+            ..self
         }
     }
 }
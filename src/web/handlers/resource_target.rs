@@ -0,0 +1,57 @@
+//! Live resource and saved snapshot lookup for resource pages
+
+use crate::core::looks_like_id;
+use crate::error::AppError;
+use crate::web::db::{self, DbPool};
+
+pub(super) enum RootResource {
+    Current(Box<db::Resource>),
+    Snapshot(Box<db::SnapshotTarget>),
+}
+
+impl RootResource {
+    /// Slug of the space that owns the live resource.
+    pub(super) fn space_slug(&self) -> &str {
+        match self {
+            Self::Current(resource) => &resource.space_slug,
+            Self::Snapshot(target) => &target.resource.space_slug,
+        }
+    }
+}
+
+pub(super) async fn resolve_root_resource(
+    pool: &DbPool,
+    reference: &str,
+) -> Result<Option<RootResource>, AppError> {
+    if !looks_like_id(reference) {
+        return Ok(db::get_resource_by_alias(pool, reference)
+            .await?
+            .map(Box::new)
+            .map(RootResource::Current));
+    }
+    if let Some(resource) = db::get_resource(pool, reference).await? {
+        return Ok(Some(RootResource::Current(Box::new(resource))));
+    }
+    Ok(db::get_snapshot_target(pool, reference)
+        .await?
+        .map(Box::new)
+        .map(RootResource::Snapshot))
+}
+
+pub(super) async fn resolve_space_resource(
+    pool: &DbPool,
+    space_slug: &str,
+    reference: &str,
+) -> Result<Option<RootResource>, AppError> {
+    if let Some(resource) = db::get_resource_by_ref_in_space(pool, space_slug, reference).await? {
+        return Ok(Some(RootResource::Current(Box::new(resource))));
+    }
+    if looks_like_id(reference) {
+        return Ok(db::get_snapshot_target(pool, reference)
+            .await?
+            .filter(|target| target.resource.space_slug == space_slug)
+            .map(Box::new)
+            .map(RootResource::Snapshot));
+    }
+    Ok(None)
+}
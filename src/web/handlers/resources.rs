@@ -2,9 +2,9 @@
 
 use crate::core::{normalize_alias, validate_id};
 use crate::error::AppError;
-use crate::web::db;
+use crate::web::db::{self, SpaceRole, Visibility};
 use crate::web::handlers::http;
-use crate::web::handlers::{resource_payload::ResourcePayload, session};
+use crate::web::handlers::{resource_payload::ResourcePayload, space_access};
 use crate::web::routes::AppState;
 use axum::extract::{Json, Path, State};
 use axum::http::{HeaderMap, StatusCode};
@@ -16,15 +16,42 @@ pub struct CreateInput {
     pub body: Option<String>,
     pub alias: Option<String>,
     pub is_favorite: Option<bool>,
+    pub visibility: Option<Visibility>,
+    /// Legacy flag; `visibility` wins when both are sent.
     pub is_private: Option<bool>,
 }
 
+impl CreateInput {
+    pub fn visibility_or(&self, default: Visibility) -> Visibility {
+        requested_visibility(self.visibility, self.is_private).unwrap_or(default)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct UpdateInput {
     pub body: String,
     pub alias: Option<String>,
     pub is_favorite: bool,
-    pub is_private: bool,
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+    #[serde(default)]
+    pub is_private: Option<bool>,
+    #[serde(default)]
+    pub base_snapshot: Option<i32>,
+}
+
+impl UpdateInput {
+    pub fn visibility(&self) -> Result<Visibility, AppError> {
+        requested_visibility(self.visibility, self.is_private)
+            .ok_or_else(|| AppError::InvalidRequest("visibility is required".to_string()))
+    }
+}
+
+fn requested_visibility(
+    visibility: Option<Visibility>,
+    is_private: Option<bool>,
+) -> Option<Visibility> {
+    visibility.or(is_private.map(Visibility::from_is_private))
 }
 
 pub async fn create(
@@ -33,7 +60,7 @@ pub async fn create(
     Json(body): Json<CreateInput>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    session::require_session(&headers, pool).await?;
+    space_access::require_role(&headers, pool, None, SpaceRole::Editor).await?;
     let Some(content) = body.body.clone() else {
         return Err(AppError::InvalidRequest("body is required".to_string()));
     };
@@ -43,17 +70,17 @@ pub async fn create(
         normalize_alias(body.alias.as_deref())?.as_deref(),
         &content,
         body.is_favorite.unwrap_or(false),
-        body.is_private.unwrap_or(
+        body.visibility_or(
             db::get_settings(pool)
                 .await?
-                .default_new_resource_is_private,
+                .default_new_resource_visibility,
         ),
     )
     .await?;
-    refresh_resource_embeds(pool, &resource.body).await?;
+    queue_resource_embeds(pool, &resource).await?;
     Ok(http::json_status(
         StatusCode::CREATED,
-        ResourcePayload::from_resource(resource),
+        ResourcePayload::with_jobs(pool, resource).await?,
     ))
 }
 
@@ -64,7 +91,7 @@ pub async fn update(
     Json(body): Json<UpdateInput>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    session::require_session(&headers, pool).await?;
+    space_access::require_role(&headers, pool, None, SpaceRole::Editor).await?;
     validate_id(&id)?;
     match db::update_resource(
         pool,
@@ -72,15 +99,15 @@ pub async fn update(
         normalize_alias(body.alias.as_deref())?.as_deref(),
         &body.body,
         body.is_favorite,
-        body.is_private,
+        body.visibility()?,
     )
     .await?
     {
         Some(resource) => {
-            refresh_resource_embeds(pool, &resource.body).await?;
+            queue_resource_embeds(pool, &resource).await?;
             Ok(http::json_status(
                 StatusCode::OK,
-                ResourcePayload::from_resource(resource),
+                ResourcePayload::with_jobs(pool, resource).await?,
             ))
         }
         None => Err(AppError::NotFound(format!("resource '{id}' not found"))),
@@ -94,7 +121,7 @@ pub async fn api_update(
     Json(body): Json<UpdateInput>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    session::require_session(&headers, pool).await?;
+    space_access::require_role(&headers, pool, None, SpaceRole::Editor).await?;
     let resource = db::get_resource_by_ref(pool, &reference)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
@@ -104,15 +131,15 @@ pub async fn api_update(
         normalize_alias(body.alias.as_deref())?.as_deref(),
         &body.body,
         body.is_favorite,
-        body.is_private,
+        body.visibility()?,
     )
     .await?
     {
         Some(resource) => {
-            refresh_resource_embeds(pool, &resource.body).await?;
+            queue_resource_embeds(pool, &resource).await?;
             Ok(http::json_status(
                 StatusCode::OK,
-                ResourcePayload::from_resource(resource),
+                ResourcePayload::with_jobs(pool, resource).await?,
             ))
         }
         None => Err(AppError::NotFound(format!(
@@ -121,10 +148,19 @@ pub async fn api_update(
     }
 }
 
-pub(super) async fn refresh_resource_embeds(pool: &db::DbPool, body: &str) -> Result<(), AppError> {
+pub(super) async fn queue_resource_embeds(
+    pool: &db::DbPool,
+    resource: &db::Resource,
+) -> Result<(), AppError> {
     let settings = db::get_settings(pool).await?;
     let site = crate::web::site::SiteContext::from_settings(&settings);
-    crate::web::embed_unfurl::refresh_body_embeds(pool, body, site.public_base_url.as_deref()).await
+    crate::web::embed_unfurl::queue_body_embeds(
+        pool,
+        Some(&resource.id),
+        &resource.body,
+        site.public_base_url.as_deref(),
+    )
+    .await
 }
 
 pub async fn remove(
@@ -133,7 +169,7 @@ pub async fn remove(
     Path(id): Path<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
-    session::require_session(&headers, pool).await?;
+    space_access::require_role(&headers, pool, None, SpaceRole::Editor).await?;
     validate_id(&id)?;
     if db::delete_resource(pool, &id).await? {
         Ok(http::empty(StatusCode::NO_CONTENT))
@@ -1,5 +1,5 @@
 use super::resources::{CreateInput, UpdateInput};
-use super::{http, resource_payload::ResourcePayload, resources, session};
+use super::{http, machine_auth, resource_etag, resource_payload::ResourcePayload, resources};
 use crate::core::{normalize_alias, validate_id};
 use crate::error::AppError;
 use crate::web::db;
@@ -16,28 +16,32 @@ pub async fn create(
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     db::require_space(pool, &user).await?;
-    session::require_session(&headers, pool).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
     let Some(content) = body.body.clone() else {
         return Err(AppError::InvalidRequest("body is required".to_string()));
     };
     let resource = db::create_resource_in_space(
         pool,
-        &user,
-        &db::generate_resource_id(pool).await?,
-        normalize_alias(body.alias.as_deref())?.as_deref(),
-        &content,
-        body.is_favorite.unwrap_or(false),
-        body.is_private.unwrap_or(
-            db::get_settings(pool)
-                .await?
-                .default_new_resource_is_private,
-        ),
+        &db::ScopedResourceWrite {
+            space_slug: &user,
+            id: &db::generate_resource_id(pool).await?,
+            alias: normalize_alias(body.alias.as_deref())?.as_deref(),
+            body: &content,
+            is_favorite: body.is_favorite.unwrap_or(false),
+            visibility: body.visibility_or(
+                db::get_settings(pool)
+                    .await?
+                    .default_new_resource_visibility,
+            ),
+            actor: &actor,
+            base_snapshot: None,
+        },
     )
     .await?;
-    resources::refresh_resource_embeds(pool, &resource.body).await?;
+    resources::queue_resource_embeds(pool, &resource).await?;
     Ok(http::json_status(
         StatusCode::CREATED,
-        ResourcePayload::from_resource(resource),
+        ResourcePayload::with_jobs(pool, resource).await?,
     ))
 }
 
@@ -49,17 +53,22 @@ pub async fn update(
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     db::require_space(pool, &user).await?;
-    session::require_session(&headers, pool).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
     validate_id(&id)?;
+    let base_snapshot = resource_etag::base_snapshot(&headers, body.base_snapshot)?;
     let alias = normalize_alias(body.alias.as_deref())?;
     match db::update_resource_in_space(
         pool,
-        &user,
-        &id,
-        alias.as_deref(),
-        &body.body,
-        body.is_favorite,
-        body.is_private,
+        &db::ScopedResourceWrite {
+            space_slug: &user,
+            id: &id,
+            alias: alias.as_deref(),
+            body: &body.body,
+            is_favorite: body.is_favorite,
+            visibility: body.visibility()?,
+            actor: &actor,
+            base_snapshot,
+        },
     )
     .await?
     {
@@ -76,19 +85,24 @@ pub async fn api_update(
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     db::require_space(pool, &user).await?;
-    session::require_session(&headers, pool).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
     let resource = db::get_resource_by_ref_in_space(pool, &user, &reference)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let base_snapshot = resource_etag::base_snapshot(&headers, body.base_snapshot)?;
     let alias = normalize_alias(body.alias.as_deref())?;
     match db::update_resource_in_space(
         pool,
-        &user,
-        &resource.id,
-        alias.as_deref(),
-        &body.body,
-        body.is_favorite,
-        body.is_private,
+        &db::ScopedResourceWrite {
+            space_slug: &user,
+            id: &resource.id,
+            alias: alias.as_deref(),
+            body: &body.body,
+            is_favorite: body.is_favorite,
+            visibility: body.visibility()?,
+            actor: &actor,
+            base_snapshot,
+        },
     )
     .await?
     {
@@ -106,19 +120,52 @@ pub async fn remove(
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     db::require_space(pool, &user).await?;
-    session::require_session(&headers, pool).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
     validate_id(&id)?;
-    if db::delete_resource_in_space(pool, &user, &id).await? {
+    if db::delete_resource_in_space(pool, &user, &id, &actor).await? {
         Ok(http::empty(StatusCode::NO_CONTENT))
     } else {
         Err(AppError::NotFound(format!("resource '{id}' not found")))
     }
 }
 
-async fn json_resource(pool: &db::DbPool, resource: db::Resource) -> Result<Response, AppError> {
-    resources::refresh_resource_embeds(pool, &resource.body).await?;
-    Ok(http::json_status(
-        StatusCode::OK,
-        ResourcePayload::from_resource(resource),
+pub async fn api_restore_snapshot(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, reference, snapshot)): Path<(String, String, i32)>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
+    let resource = db::get_resource_by_ref_in_space(pool, &user, &reference)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let restored = db::restore_snapshot_in_space(
+        pool,
+        &db::SnapshotRestore {
+            space_slug: &user,
+            resource_id: &resource.id,
+            snapshot_number: snapshot,
+            actor: &actor,
+            base_snapshot: resource_etag::base_snapshot(&headers, None)?,
+        },
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    json_resource(pool, restored).await
+}
+
+pub(super) async fn json_resource(
+    pool: &db::DbPool,
+    resource: db::Resource,
+) -> Result<Response, AppError> {
+    resources::queue_resource_embeds(pool, &resource).await?;
+    let snapshot = db::current_snapshot_number(pool, &resource.id).await?;
+    Ok(resource_etag::with_etag(
+        http::json_status(
+            StatusCode::OK,
+            ResourcePayload::with_jobs(pool, resource).await?,
+        ),
+        snapshot,
     ))
 }
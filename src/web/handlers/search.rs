@@ -1,12 +1,14 @@
 //! Search HTML handler
 
+use crate::core::{parse_search_query, SearchQuery};
 use crate::error::AppError;
 use crate::web::db::{
-    self, ListDirection, ListKind, ListRequest, ListScope, ListSort, PopularWindow,
+    self, ListDirection, ListKind, ListRequest, ListScope, ListSort, PopularWindow, QueryEmbedding,
 };
 use crate::web::handlers::http;
-use crate::web::handlers::session;
+use crate::web::handlers::{session, space_access};
 use crate::web::routes::AppState;
+use crate::web::search_embedding::embed_query;
 use crate::web::site::SiteContext;
 use crate::web::templates;
 use crate::web::view;
@@ -24,6 +26,44 @@ pub struct SearchParams {
     pub scope: Option<String>,
     pub cursor: Option<String>,
     pub limit: Option<i64>,
+    pub tag: Option<String>,
+    pub mode: Option<String>,
+}
+
+impl SearchParams {
+    /// `q` split into free text and `field:value` filters.
+    pub(crate) fn parsed_query(&self) -> SearchQuery {
+        parse_search_query(self.q.as_deref().unwrap_or(""))
+    }
+
+    /// Requested sort; `mode=hybrid` asks for semantic ranking when no
+    /// explicit sort is given.
+    pub(crate) fn sort(&self, query_present: bool, scope: &ListScope) -> ListSort {
+        let hybrid = (self.mode.as_deref() == Some("hybrid")).then_some("semantic");
+        ListSort::resolve(self.sort.as_deref().or(hybrid), query_present, scope)
+    }
+}
+
+/// Query vector when the sort ranks by meaning; `None` otherwise.
+pub(crate) async fn query_embedding(
+    state: &AppState,
+    sort: &ListSort,
+    query: Option<&str>,
+) -> Option<QueryEmbedding> {
+    match (sort, query) {
+        (ListSort::Semantic, Some(query)) => embed_query(state.embedder.as_ref(), query).await,
+        _ => None,
+    }
+}
+
+/// `meta.<key>=<value>` pairs of the query string, in order.
+pub(crate) fn meta_filters(uri: &Uri) -> Vec<(String, String)> {
+    url::form_urlencoded::parse(uri.query().unwrap_or("").as_bytes())
+        .filter_map(|(key, value)| {
+            let key = key.strip_prefix("meta.")?.trim().to_string();
+            (!key.is_empty() && !value.trim().is_empty()).then(|| (key, value.into_owned()))
+        })
+        .collect()
 }
 
 pub async fn search_page(
@@ -57,26 +97,31 @@ async fn search_page_inner(
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    let is_admin = session::check_session(&headers, pool).await?;
+    let access = space_access::space_access(&headers, pool, space_slug.as_deref()).await?;
+    let max_visibility = access.max_visibility();
+    let is_member = access.role.is_some();
     let settings = db::get_settings(pool).await?;
     let site = SiteContext::from_settings(&settings);
-    let query = params
-        .q
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string);
+    let parsed = params.parsed_query();
+    let query = (!parsed.text.is_empty()).then(|| parsed.text.clone());
     let limit = params.limit.unwrap_or(settings.search_results_per_page);
     let direction = ListDirection::resolve(params.direction.as_deref(), params.cursor.as_deref());
     let kind = ListKind::resolve(params.kind.as_deref());
     let scope = ListScope::resolve(params.scope.as_deref());
-    let sort = ListSort::resolve(params.sort.as_deref(), query.is_some(), &scope);
+    let sort = params.sort(query.is_some(), &scope);
+    let query_embedding = query_embedding(&state, &sort, query.as_deref()).await;
     let popular_window = sort.popular_window().unwrap_or(PopularWindow::Days30);
+    let meta = meta_filters(&uri);
+    let feed_space = match (&params.tag, &space_slug) {
+        (None, _) => None,
+        (Some(_), Some(slug)) => Some(slug.clone()),
+        (Some(_), None) => Some(db::default_space_slug(pool).await?),
+    };
     let page = db::list_resources(
         pool,
         &ListRequest {
             space_slug,
-            include_private: is_admin,
+            max_visibility,
             limit,
             query: query.clone(),
             direction,
@@ -85,6 +130,10 @@ async fn search_page_inner(
             sort: sort.clone(),
             popular_window,
             cursor: params.cursor,
+            tag: params.tag.clone(),
+            meta: meta.clone(),
+            filters: parsed.filters.clone(),
+            query_embedding,
         },
     )
     .await?;
@@ -92,17 +141,20 @@ async fn search_page_inner(
         notes: &page
             .resources
             .iter()
-            .map(|resource| view::index_item(resource, is_admin))
+            .map(|resource| view::index_item(resource, is_member))
             .collect::<Vec<_>>(),
         previous_cursor: page.previous_cursor.as_deref(),
         next_cursor: page.next_cursor.as_deref(),
         kind: kind.as_str(),
-        query: query.as_deref(),
+        query: Some(&parsed),
         limit,
         scope: scope.as_str(),
         sort: sort.as_str(),
-        is_admin,
+        tag: params.tag.as_deref(),
+        meta: &meta,
+        is_admin: access.can_write(),
         guest_login_href: session::login_url(&uri),
         site: &site,
+        feed_space: feed_space.as_deref(),
     })))
 }
@@ -0,0 +1,171 @@
+//! Service account and API token management handlers
+
+use super::http;
+use super::space_access::require_manager;
+use crate::error::AppError;
+use crate::web::db::{self, ApiScope, MAX_TOKEN_LIFETIME_DAYS};
+use crate::web::routes::AppState;
+use axum::extract::{Json, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_NAME_LEN: usize = 80;
+
+#[derive(Deserialize)]
+pub struct AccountInput {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct AccountUpdate {
+    pub disabled: bool,
+}
+
+#[derive(Deserialize)]
+pub struct TokenInput {
+    pub scopes: Vec<String>,
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct AccountList {
+    service_accounts: Vec<db::ServiceAccount>,
+}
+
+#[derive(Serialize)]
+struct IssuedToken {
+    service_account_id: Uuid,
+    scopes: Vec<&'static str>,
+    token: String,
+}
+
+pub async fn list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    require_manager(&headers, &state.pool, &user).await?;
+    let service_accounts = db::list_service_accounts(&state.pool, &user).await?;
+    Ok(http::json_status(
+        StatusCode::OK,
+        AccountList { service_accounts },
+    ))
+}
+
+pub async fn create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+    Json(input): Json<AccountInput>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    let name = input.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::InvalidRequest(format!(
+            "name must be 1 to {MAX_NAME_LEN} characters"
+        )));
+    }
+    let id = db::create_service_account(&state.pool, &user, name, &actor).await?;
+    let account = db::list_service_accounts(&state.pool, &user)
+        .await?
+        .into_iter()
+        .find(|account| account.id == id);
+    Ok(http::json_status(StatusCode::CREATED, account))
+}
+
+pub async fn update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, Uuid)>,
+    Json(input): Json<AccountUpdate>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    if db::set_service_account_disabled(&state.pool, &user, id, input.disabled, &actor).await? {
+        Ok(http::empty(StatusCode::NO_CONTENT))
+    } else {
+        Err(AppError::NotFound("service account not found".to_string()))
+    }
+}
+
+pub async fn issue(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, Uuid)>,
+    Json(input): Json<TokenInput>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    let scopes = parse_scopes(&input.scopes)?;
+    let expires_at = match input.expires_in_days {
+        Some(days) if !(1..=MAX_TOKEN_LIFETIME_DAYS).contains(&days) => {
+            return Err(AppError::InvalidRequest(format!(
+                "expires_in_days must be 1 to {MAX_TOKEN_LIFETIME_DAYS}"
+            )))
+        }
+        Some(days) => Some(Utc::now() + Duration::days(days)),
+        None => None,
+    };
+    let token = db::issue_api_token(&state.pool, &user, id, &scopes, expires_at, &actor).await?;
+    Ok(http::json_status(
+        StatusCode::CREATED,
+        IssuedToken {
+            service_account_id: id,
+            scopes: scopes.into_iter().map(ApiScope::as_str).collect(),
+            token,
+        },
+    ))
+}
+
+pub async fn revoke(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, token_id)): Path<(String, Uuid)>,
+) -> Result<Response, AppError> {
+    let actor = require_manager(&headers, &state.pool, &user).await?;
+    if db::revoke_api_token(&state.pool, &user, token_id, &actor).await? {
+        Ok(http::empty(StatusCode::NO_CONTENT))
+    } else {
+        Err(AppError::NotFound("API token not found".to_string()))
+    }
+}
+
+fn parse_scopes(values: &[String]) -> Result<Vec<ApiScope>, AppError> {
+    let mut scopes = Vec::new();
+    for value in values {
+        let scope = ApiScope::parse(value)
+            .ok_or_else(|| AppError::InvalidRequest(format!("unknown scope '{value}'")))?;
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    if scopes.is_empty() {
+        return Err(AppError::InvalidRequest(
+            "at least one scope is required".to_string(),
+        ));
+    }
+    Ok(scopes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_scopes;
+    use crate::web::db::ApiScope;
+
+    #[test]
+    fn parse_scopes_dedupes_and_rejects_unknown() {
+        let scopes = parse_scopes(&[
+            "resource:write".to_string(),
+            "resource:write".to_string(),
+            "resource:read".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            scopes,
+            vec![ApiScope::ResourceWrite, ApiScope::ResourceRead]
+        );
+        assert!(parse_scopes(&["settings:write".to_string()]).is_err());
+        assert!(parse_scopes(&[]).is_err());
+    }
+}
@@ -1,10 +1,10 @@
 //! Admin settings handler
 
 use crate::error::AppError;
-use crate::web::db;
+use crate::web::db::{self, SettingsChange, SpaceRole, Visibility, WriteActor};
 use crate::web::handlers::http;
-use crate::web::handlers::session;
 use crate::web::handlers::settings_input::{validate_settings_form, SettingsForm};
+use crate::web::handlers::{session, space_access};
 use crate::web::routes::AppState;
 use crate::web::site::SiteContext;
 use crate::web::{templates, view};
@@ -48,14 +48,24 @@ async fn settings_page_inner(
     if !db::is_setup(pool).await? {
         return Ok(http::redirect("/setup"));
     }
-    if !session::check_session(&headers, pool).await? {
+    let access = space_access::space_access(&headers, pool, space_slug.as_deref()).await?;
+    if access.user_id.is_none() {
         return Ok(http::redirect(&session::login_url(&uri)));
     }
+    access.require(SpaceRole::Admin)?;
     let settings = match space_slug.as_deref() {
         Some(slug) => db::get_settings_in_space(pool, slug).await?,
         None => db::get_settings(pool).await?,
     };
-    let favorites = db::list_all_favorite_resources(pool, space_slug.as_deref(), true).await?;
+    let favorites =
+        db::list_all_favorite_resources(pool, space_slug.as_deref(), Visibility::Private).await?;
+    let (members, service_accounts) = match space_slug.as_deref() {
+        Some(slug) => (
+            db::list_space_members(pool, slug).await?,
+            db::list_service_accounts(pool, slug).await?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
     let site = SiteContext::from_settings(&settings);
     Ok(http::html(templates::settings_page(
         &settings,
@@ -63,6 +73,11 @@ async fn settings_page_inner(
             .iter()
             .map(|resource| view::index_item(resource, true))
             .collect::<Vec<_>>(),
+        space_slug.as_deref().map(|slug| templates::SpaceSettings {
+            slug,
+            members: &members,
+            service_accounts: &service_accounts,
+        }),
         &site,
     )))
 }
@@ -91,20 +106,27 @@ async fn settings_submit_inner(
     Form(form): Form<SettingsForm>,
     space_slug: Option<String>,
 ) -> Result<Response, AppError> {
-    session::require_session(&headers, &state.pool).await?;
     let pool = &state.pool;
+    let actor = WriteActor::user(
+        space_access::require_role(&headers, pool, space_slug.as_deref(), SpaceRole::Admin).await?,
+    );
     let current = match space_slug.as_deref() {
         Some(slug) => db::get_settings_in_space(pool, slug).await?,
         None => db::get_settings(pool).await?,
     };
     let next = validate_settings_form(&form, &current)?;
+    let change = SettingsChange {
+        actor: &actor,
+        changed: &current.changed_fields(&next),
+    };
     match space_slug.as_deref() {
-        Some(slug) => db::update_settings_in_space(pool, slug, &next).await?,
-        None => db::update_settings(pool, &next).await?,
+        Some(slug) => db::update_settings_in_space(pool, slug, &next, &change).await?,
+        None => db::update_settings(pool, &next, &change).await?,
     }
     let site = SiteContext::from_settings(&next);
-    crate::web::embed_unfurl::refresh_body_embeds(
+    crate::web::embed_unfurl::queue_body_embeds(
         pool,
+        None,
         &next.home_intro_markdown,
         site.public_base_url.as_deref(),
     )
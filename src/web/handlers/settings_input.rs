@@ -4,8 +4,9 @@ use crate::core::live_settings::{
     normalize_live_camera_facing, normalize_live_source, validate_live_fps, validate_live_height,
 };
 use crate::core::nostr::{normalize_names_json, normalize_relays_json};
+use crate::core::search_language::normalize_search_language;
 use crate::error::AppError;
-use crate::web::db::AppSettings;
+use crate::web::db::{AppSettings, Visibility};
 use crate::web::site::normalize_public_base_url;
 use serde::Deserialize;
 use std::collections::HashSet;
@@ -36,7 +37,12 @@ pub struct SettingsForm {
     pub search_results_per_page: i64,
     pub session_timeout_minutes: i64,
     pub media_webp_quality: i64,
-    pub default_new_resource_is_private: Option<String>,
+    pub media_strip_location: Option<String>,
+    #[serde(default)]
+    pub search_language: Option<String>,
+    pub search_cjk: Option<String>,
+    #[serde(default)]
+    pub default_new_resource_visibility: Option<String>,
 }
 
 pub fn validate_settings_form(
@@ -55,6 +61,10 @@ pub fn validate_settings_form(
     let live_default_height =
         validate_live_height(form.live_default_height).map_err(|e| invalid(&e))?;
     let live_default_fps = validate_live_fps(form.live_default_fps).map_err(|e| invalid(&e))?;
+    let search_language = match form.search_language.as_deref() {
+        Some(value) => normalize_search_language(value).map_err(|e| invalid(&e))?,
+        None => current.search_language.clone(),
+    };
     let google_maps_embed_api_key = validate_maps_key(&form.google_maps_embed_api_key)?;
     if site_name.is_empty() || site_name.len() > 80 {
         return Err(invalid("site name must be between 1 and 80 characters"));
@@ -104,14 +114,28 @@ pub fn validate_settings_form(
         home_favorite_position: form.home_favorite_position,
         home_popular_position: form.home_popular_position,
         search_results_per_page: form.search_results_per_page,
+        search_language,
+        search_cjk: form.search_cjk.is_some(),
         session_timeout_minutes: form.session_timeout_minutes,
         media_webp_quality: form.media_webp_quality,
-        default_new_resource_is_private: form.default_new_resource_is_private.is_some(),
+        media_strip_location: form.media_strip_location.is_some(),
+        default_new_resource_visibility: new_resource_visibility(form, current)?,
         site_icon_key: current.site_icon_key.clone(),
         site_icon_content_type: current.site_icon_content_type.clone(),
     })
 }
 
+fn new_resource_visibility(
+    form: &SettingsForm,
+    current: &AppSettings,
+) -> Result<Visibility, AppError> {
+    match form.default_new_resource_visibility.as_deref() {
+        None => Ok(current.default_new_resource_visibility),
+        Some(value) => Visibility::parse(value)
+            .ok_or_else(|| invalid("new resource visibility must be public, space, or private")),
+    }
+}
+
 fn validate_public_base_url(value: &str) -> Result<String, AppError> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
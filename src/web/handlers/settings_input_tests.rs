@@ -27,7 +27,10 @@ fn sample_form() -> SettingsForm {
         search_results_per_page: 20,
         session_timeout_minutes: 1440,
         media_webp_quality: 82,
-        default_new_resource_is_private: None,
+        media_strip_location: Some("on".to_string()),
+        search_language: Some("English".to_string()),
+        search_cjk: None,
+        default_new_resource_visibility: None,
     }
 }
 
@@ -103,3 +106,18 @@ fn validate_rejects_invalid_google_maps_key() {
     form.google_maps_embed_api_key = "maps\0key".to_string();
     assert!(validate_settings_form(&form, &AppSettings::default()).is_err());
 }
+
+#[test]
+fn validate_normalizes_search_language() {
+    let mut form = sample_form();
+    let settings = validate_settings_form(&form, &AppSettings::default()).unwrap();
+    assert_eq!(settings.search_language, "english");
+    assert!(!settings.search_cjk);
+    form.search_language = None;
+    form.search_cjk = Some("on".to_string());
+    let settings = validate_settings_form(&form, &AppSettings::default()).unwrap();
+    assert_eq!(settings.search_language, "simple");
+    assert!(settings.search_cjk);
+    form.search_language = Some("klingon".to_string());
+    assert!(validate_settings_form(&form, &AppSettings::default()).is_err());
+}
@@ -1,15 +1,14 @@
-use super::session;
+use super::space_access::require_manager;
 use crate::error::AppError;
-use crate::web::db;
+use crate::web::db::{self, SettingsChange, WriteActor};
 use crate::web::handlers::http;
 use crate::web::handlers::media_input::{discard_field, field_bytes_limited};
 use crate::web::routes::AppState;
 use axum::extract::multipart::Field;
-use axum::extract::{Multipart, State};
+use axum::extract::{Multipart, Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
 use serde::Serialize;
-use std::path::Path;
 use uuid::Uuid;
 
 struct IconUpload {
@@ -28,11 +27,12 @@ struct IconState {
 pub async fn upload(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Path(user): Path<String>,
     payload: Multipart,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     let storage = &state.storage;
-    session::require_session(&headers, pool).await?;
+    let actor = require_manager(&headers, pool, &user).await?;
     let upload = parse_icon(payload, state.site_icon_upload_max_bytes).await?;
     validate_icon(&upload)?;
     let key = format!(
@@ -46,7 +46,7 @@ pub async fn upload(
     let mut settings = db::get_settings(pool).await?;
     let old_key = settings.site_icon_key.replace(key.clone());
     settings.site_icon_content_type = Some(upload.content_type);
-    let result = db::update_settings(pool, &settings).await;
+    let result = save_icon(pool, &settings, &actor).await;
     if result.is_err() {
         let _ = storage.delete_object(&key).await;
     } else if let Some(old_key) = old_key.filter(|old_key| old_key != &key) {
@@ -59,20 +59,41 @@ pub async fn upload(
 pub async fn reset(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Path(user): Path<String>,
 ) -> Result<Response, AppError> {
     let pool = &state.pool;
     let storage = &state.storage;
-    session::require_session(&headers, pool).await?;
+    let actor = require_manager(&headers, pool, &user).await?;
     let mut settings = db::get_settings(pool).await?;
     let old_key = settings.site_icon_key.take();
     settings.site_icon_content_type = None;
-    db::update_settings(pool, &settings).await?;
+    save_icon(pool, &settings, &actor).await?;
     if let Some(old_key) = old_key {
         let _ = storage.delete_object(&old_key).await;
     }
     Ok(http::json_status(StatusCode::OK, icon_state(&settings)))
 }
 
+async fn save_icon(
+    pool: &db::DbPool,
+    settings: &db::AppSettings,
+    actor: &WriteActor,
+) -> Result<(), AppError> {
+    let changed = [
+        "site_icon_key".to_string(),
+        "site_icon_content_type".to_string(),
+    ];
+    db::update_settings(
+        pool,
+        settings,
+        &SettingsChange {
+            actor,
+            changed: &changed,
+        },
+    )
+    .await
+}
+
 async fn parse_icon(mut payload: Multipart, max_bytes: usize) -> Result<IconUpload, AppError> {
     let mut icon = None;
     while let Some(field) = payload
@@ -135,7 +156,7 @@ fn safe_name(filename: &str) -> String {
 }
 
 fn extension(filename: &str) -> Option<&str> {
-    Path::new(filename)
+    std::path::Path::new(filename)
         .extension()
         .and_then(|value| value.to_str())
 }
@@ -0,0 +1,153 @@
+//! Saved snapshot diff JSON and compare page handlers
+
+use super::{http, machine_auth, session, space_access};
+use crate::core::text_diff::{diff_bodies, split_rows, BodyDiff, SplitRow, DEFAULT_CONTEXT};
+use crate::error::AppError;
+use crate::web::db::{self, DbPool, ResourceSnapshot, Visibility};
+use crate::web::routes::AppState;
+use crate::web::site::SiteContext;
+use crate::web::{templates, view_chrome};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const MAX_CONTEXT: usize = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct DiffParams {
+    pub context: Option<usize>,
+    pub view: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DiffSide {
+    id: String,
+    snapshot_number: i32,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct DiffResponse {
+    resource_id: String,
+    from: DiffSide,
+    to: DiffSide,
+    #[serde(flatten)]
+    diff: BodyDiff,
+    side_by_side: Vec<Vec<SplitRow>>,
+}
+
+pub async fn api_diff(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, reference, from, to)): Path<(String, String, i32, i32)>,
+    Query(params): Query<DiffParams>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let max_visibility = machine_auth::require_read(&headers, pool, &user).await?;
+    let resource = db::get_resource_by_ref_in_space(pool, &user, &reference)
+        .await?
+        .filter(|resource| resource.visibility <= max_visibility)
+        .ok_or_else(|| AppError::NotFound(format!("resource '{reference}' not found")))?;
+    let (old, new) = snapshot_pair(pool, &resource.id, (from, to), max_visibility).await?;
+    let diff = diff_snapshots(&old, &new, params.context);
+    Ok(http::json_status(
+        StatusCode::OK,
+        DiffResponse {
+            resource_id: resource.id,
+            side_by_side: diff.hunks.iter().map(split_rows).collect(),
+            from: side(old),
+            to: side(new),
+            diff,
+        },
+    ))
+}
+
+pub async fn diff_page(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    Path((user, reference, from, to)): Path<(String, String, i32, i32)>,
+    Query(params): Query<DiffParams>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    if !session::check_session(&headers, pool).await? {
+        return Ok(http::redirect(&session::login_url(&uri)));
+    }
+    let settings = db::get_settings_in_space(pool, &user).await?;
+    let site = SiteContext::from_settings(&settings);
+    let access = space_access::space_access(&headers, pool, Some(&user)).await?;
+    let resource = match db::get_resource_by_ref_in_space(pool, &user, &reference).await? {
+        Some(resource) if access.role.is_some() && access.can_read(resource.visibility) => resource,
+        _ => return Ok(not_found(&site)),
+    };
+    let max_visibility = access.max_visibility();
+    let Ok((old, new)) = snapshot_pair(pool, &resource.id, (from, to), max_visibility).await else {
+        return Ok(not_found(&site));
+    };
+    let chrome = view_chrome::resource_chrome(pool, &resource, max_visibility).await?;
+    let diff = diff_snapshots(&old, &new, params.context);
+    Ok(http::html(templates::snapshot_diff_page(
+        &chrome,
+        &templates::SnapshotDiffView {
+            from: &old,
+            to: &new,
+            diff: &diff,
+            split: params.view.as_deref() == Some("split"),
+        },
+        access.can_write(),
+        &site,
+    )))
+}
+
+async fn snapshot_pair(
+    pool: &DbPool,
+    resource_id: &str,
+    (from, to): (i32, i32),
+    max_visibility: Visibility,
+) -> Result<(ResourceSnapshot, ResourceSnapshot), AppError> {
+    let load = |number: i32| async move {
+        db::get_resource_snapshot(pool, resource_id, number, max_visibility)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("snapshot {number} not found")))
+    };
+    Ok((load(from).await?, load(to).await?))
+}
+
+fn diff_snapshots(
+    old: &ResourceSnapshot,
+    new: &ResourceSnapshot,
+    context: Option<usize>,
+) -> BodyDiff {
+    diff_bodies(
+        &old.body,
+        &new.body,
+        (
+            &format!("snapshot {}", old.snapshot_number),
+            &format!("snapshot {}", new.snapshot_number),
+        ),
+        context.unwrap_or(DEFAULT_CONTEXT).min(MAX_CONTEXT),
+    )
+}
+
+fn side(snapshot: ResourceSnapshot) -> DiffSide {
+    DiffSide {
+        id: snapshot.id,
+        snapshot_number: snapshot.snapshot_number,
+        created_at: snapshot.created_at,
+    }
+}
+
+fn not_found(site: &SiteContext) -> Response {
+    http::html_status(
+        StatusCode::NOT_FOUND,
+        templates::not_found_page(&site.page_meta(
+            "Not Found",
+            "The requested resource could not be found.",
+            false,
+            None,
+        )),
+    )
+}
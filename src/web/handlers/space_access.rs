@@ -0,0 +1,136 @@
+//! Per-space authorization decisions for browser sessions
+
+use super::{machine_auth, session};
+use crate::error::AppError;
+use crate::web::db::{self, DbPool, SpaceRole, Visibility, WriteActor};
+use axum::http::HeaderMap;
+use uuid::Uuid;
+
+/// Session caller and their role in one space.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpaceAccess {
+    pub user_id: Option<Uuid>,
+    pub role: Option<SpaceRole>,
+}
+
+impl SpaceAccess {
+    /// Widest visibility the caller may read: viewers stop at `space`, and
+    /// only members who can write read `private` resources.
+    pub fn max_visibility(self) -> Visibility {
+        match self.role {
+            None => Visibility::Public,
+            Some(SpaceRole::Viewer) => Visibility::Space,
+            Some(_) => Visibility::Private,
+        }
+    }
+
+    pub fn can_read(self, visibility: Visibility) -> bool {
+        visibility <= self.max_visibility()
+    }
+
+    /// Editors and above get the editor and write routes.
+    pub fn can_write(self) -> bool {
+        self.role.is_some_and(SpaceRole::can_write)
+    }
+
+    /// Reject callers without a session or below `minimum`.
+    pub fn require(self, minimum: SpaceRole) -> Result<Uuid, AppError> {
+        let Some(user_id) = self.user_id else {
+            return Err(AppError::Unauthorized("Session required".to_string()));
+        };
+        match self.role {
+            Some(role) if role >= minimum => Ok(user_id),
+            _ => Err(AppError::Forbidden(format!(
+                "{} role required in this space",
+                minimum.as_str()
+            ))),
+        }
+    }
+}
+
+/// Resolve the session caller's role; `None` slug means the default space.
+pub async fn space_access(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    space_slug: Option<&str>,
+) -> Result<SpaceAccess, AppError> {
+    let Some(user_id) = session::session_user(headers, pool).await? else {
+        return Ok(SpaceAccess::default());
+    };
+    Ok(SpaceAccess {
+        user_id: Some(user_id),
+        role: db::space_role(pool, space_slug, user_id).await?,
+    })
+}
+
+/// Require a session whose role in the space is at least `minimum`.
+pub async fn require_role(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    space_slug: Option<&str>,
+    minimum: SpaceRole,
+) -> Result<Uuid, AppError> {
+    space_access(headers, pool, space_slug)
+        .await?
+        .require(minimum)
+}
+
+/// Require an admin browser session for settings routes tokens never reach.
+pub async fn require_manager(
+    headers: &HeaderMap,
+    pool: &DbPool,
+    space_slug: &str,
+) -> Result<WriteActor, AppError> {
+    db::require_space(pool, space_slug).await?;
+    machine_auth::reject_bearer(headers)?;
+    require_role(headers, pool, Some(space_slug), SpaceRole::Admin)
+        .await
+        .map(WriteActor::user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpaceAccess;
+    use crate::error::AppError;
+    use crate::web::db::{SpaceRole, Visibility};
+    use uuid::Uuid;
+
+    fn access(role: Option<SpaceRole>) -> SpaceAccess {
+        SpaceAccess {
+            user_id: Some(Uuid::nil()),
+            role,
+        }
+    }
+
+    #[test]
+    fn viewers_read_space_resources_but_not_private_ones() {
+        let viewer = access(Some(SpaceRole::Viewer));
+        assert!(viewer.can_read(Visibility::Space) && !viewer.can_read(Visibility::Private));
+        assert_eq!(viewer.max_visibility(), Visibility::Space);
+        assert!(!viewer.can_write());
+        assert_eq!(
+            access(Some(SpaceRole::Editor)).max_visibility(),
+            Visibility::Private
+        );
+        assert!(matches!(
+            viewer.require(SpaceRole::Editor),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(access(Some(SpaceRole::Owner))
+            .require(SpaceRole::Admin)
+            .is_ok());
+    }
+
+    #[test]
+    fn missing_session_or_membership_is_rejected() {
+        assert!(matches!(
+            SpaceAccess::default().require(SpaceRole::Viewer),
+            Err(AppError::Unauthorized(_))
+        ));
+        assert_eq!(access(None).max_visibility(), Visibility::Public);
+        assert!(matches!(
+            access(None).require(SpaceRole::Viewer),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+}
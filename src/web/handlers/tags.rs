@@ -0,0 +1,31 @@
+//! Space tag index HTML handler
+
+use crate::error::AppError;
+use crate::web::db;
+use crate::web::handlers::{http, session, space_access};
+use crate::web::routes::AppState;
+use crate::web::site::SiteContext;
+use crate::web::templates;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, Uri};
+use axum::response::Response;
+
+pub async fn tags_page(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let access = space_access::space_access(&headers, pool, Some(&user)).await?;
+    let tags = db::list_space_tags(pool, &user, access.max_visibility()).await?;
+    let settings = db::get_settings(pool).await?;
+    Ok(http::html(templates::tags_page(
+        &user,
+        &tags,
+        access.can_write(),
+        &session::login_url(&uri),
+        &SiteContext::from_settings(&settings),
+    )))
+}
@@ -0,0 +1,99 @@
+//! Trash listing, restore, and permanent purge handlers
+
+use super::resource_payload::ResourcePayload;
+use super::{http, machine_auth, session, space_access};
+use crate::core::validate_id;
+use crate::error::AppError;
+use crate::web::db::{self, SpaceRole};
+use crate::web::routes::AppState;
+use crate::web::site::SiteContext;
+use crate::web::{templates, trash_sweep};
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::Response;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct TrashList {
+    resources: Vec<db::TrashedResource>,
+    retention_days: usize,
+}
+
+pub async fn api_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    machine_auth::require_write(&headers, pool, &user).await?;
+    Ok(http::json_status(
+        StatusCode::OK,
+        TrashList {
+            resources: db::list_trashed_resources(pool, &user).await?,
+            retention_days: state.trash_retention_days,
+        },
+    ))
+}
+
+pub async fn restore(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let actor = machine_auth::require_write(&headers, pool, &user).await?;
+    validate_id(&id)?;
+    match db::restore_resource_in_space(pool, &user, &id, &actor).await? {
+        Some(resource) => Ok(http::json_status(
+            StatusCode::OK,
+            ResourcePayload::from_resource(resource),
+        )),
+        None => Err(AppError::NotFound(format!(
+            "resource '{id}' not found in trash"
+        ))),
+    }
+}
+
+pub async fn purge(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let actor = space_access::require_manager(&headers, &state.pool, &user).await?;
+    validate_id(&id)?;
+    match db::purge_resource_in_space(&state.pool, &user, &id, &actor).await? {
+        Some(purged) => {
+            trash_sweep::delete_orphaned_objects(&state.pool, &state.storage, &purged).await;
+            Ok(http::empty(StatusCode::NO_CONTENT))
+        }
+        None => Err(AppError::NotFound(format!(
+            "resource '{id}' not found in trash"
+        ))),
+    }
+}
+
+pub async fn trash_page(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    Path(user): Path<String>,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+    db::require_space(pool, &user).await?;
+    let access = space_access::space_access(&headers, pool, Some(&user)).await?;
+    if access.user_id.is_none() {
+        return Ok(http::redirect(&session::login_url(&uri)));
+    }
+    access.require(SpaceRole::Editor)?;
+    let resources = db::list_trashed_resources(pool, &user).await?;
+    let settings = db::get_settings_in_space(pool, &user).await?;
+    Ok(http::html(templates::trash_page(
+        &user,
+        &resources,
+        access.require(SpaceRole::Admin).is_ok(),
+        state.trash_retention_days,
+        &SiteContext::from_settings(&settings),
+    )))
+}
@@ -0,0 +1,99 @@
+//! Finalizing resumable upload sessions into media create requests
+
+use super::media_input::UploadPart;
+use super::uploaded_file::UploadedFile;
+use crate::core::validate_id;
+use crate::error::AppError;
+use crate::web::db::{self, UploadSession, WriteActor};
+use crate::web::routes::AppState;
+
+/// Turn form parts into files, locking each session `actor` opened until
+/// `settle_uploads`.
+pub(super) async fn resolve_uploads(
+    state: &AppState,
+    space_slug: Option<&str>,
+    actor: &WriteActor,
+    parts: Vec<UploadPart>,
+) -> Result<(Vec<UploadedFile>, Vec<UploadSession>), AppError> {
+    let mut files = Vec::with_capacity(parts.len());
+    let mut sessions = Vec::new();
+    for part in parts {
+        let id = match part {
+            UploadPart::File(file) => {
+                files.push(file);
+                continue;
+            }
+            UploadPart::Session(id) => id,
+        };
+        match checkout(state, space_slug, actor, &id).await {
+            Ok((file, session)) => {
+                files.push(file);
+                sessions.push(session);
+            }
+            Err(error) => {
+                settle_uploads(state, &sessions, false).await;
+                return Err(error);
+            }
+        }
+    }
+    Ok((files, sessions))
+}
+
+/// Drop sessions whose resource committed, or unlock them so the client may retry.
+pub(super) async fn settle_uploads(state: &AppState, sessions: &[UploadSession], committed: bool) {
+    for session in sessions {
+        if committed {
+            let _ = db::delete_upload_session(&state.pool, &session.id).await;
+            state.upload_spool.remove(&session.id).await;
+        } else {
+            let _ = db::release_upload_session(&state.pool, session, session.byte_size).await;
+        }
+    }
+}
+
+async fn checkout(
+    state: &AppState,
+    space_slug: Option<&str>,
+    actor: &WriteActor,
+    id: &str,
+) -> Result<(UploadedFile, UploadSession), AppError> {
+    validate_id(id).map_err(|_| AppError::InvalidRequest(format!("upload '{id}' is invalid")))?;
+    let session = db::get_upload_session(&state.pool, space_slug, id, actor)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("upload '{id}' not found")))?;
+    if !session.is_complete() {
+        return Err(AppError::InvalidRequest(format!(
+            "upload '{id}' has {} of {} bytes",
+            session.received_bytes, session.byte_size
+        )));
+    }
+    let session = db::claim_upload_session(&state.pool, id, session.byte_size)
+        .await?
+        .ok_or_else(|| AppError::InvalidRequest(format!("upload '{id}' is already in use")))?;
+    let checked_out = state.upload_spool.checkout(id).await;
+    let (temp_file, sha256_hex) = match checked_out {
+        Ok(checked_out) => checked_out,
+        Err(error) => {
+            settle_uploads(state, std::slice::from_ref(&session), false).await;
+            return Err(error);
+        }
+    };
+    if session
+        .sha256_hex
+        .as_ref()
+        .is_some_and(|declared| *declared != sha256_hex)
+    {
+        settle_uploads(state, std::slice::from_ref(&session), true).await;
+        return Err(AppError::InvalidRequest(format!(
+            "upload '{id}' does not match its sha256_hex and was discarded"
+        )));
+    }
+    let file = UploadedFile::from_temp_file(
+        temp_file,
+        session.byte_size,
+        sha256_hex,
+        session.original_filename.clone(),
+        session.content_type.clone(),
+    );
+    Ok((file, session))
+}
@@ -0,0 +1,188 @@
+//! Resumable upload session handlers: create, chunk, progress, and cancel
+
+use super::http;
+use super::machine_auth::require_write;
+use super::media_support::detect_media_family;
+use crate::core::{generate_id, validate_id};
+use crate::error::AppError;
+use crate::web::db::{self, UploadSession, UploadSessionCreate};
+use crate::web::routes::AppState;
+use axum::body::Body;
+use axum::extract::{Json, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Largest body one chunk request may carry.
+pub const UPLOAD_CHUNK_MAX_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_FILENAME_LEN: usize = 255;
+
+#[derive(Deserialize)]
+pub struct SessionInput {
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub byte_size: i64,
+    pub sha256_hex: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ChunkQuery {
+    pub offset: i64,
+}
+
+#[derive(Serialize)]
+struct SessionPayload {
+    id: String,
+    filename: String,
+    content_type: String,
+    byte_size: i64,
+    offset: i64,
+    complete: bool,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct OffsetConflict {
+    error: &'static str,
+    message: String,
+    offset: i64,
+}
+
+pub async fn create_scoped(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user): Path<String>,
+    Json(input): Json<SessionInput>,
+) -> Result<Response, AppError> {
+    db::require_space(&state.pool, &user).await?;
+    let actor = require_write(&headers, &state.pool, &user).await?;
+    if input.byte_size <= 0 {
+        return Err(invalid("byte_size must be positive"));
+    }
+    if input.byte_size as u64 > state.upload_session_max_bytes as u64 {
+        return Err(AppError::PayloadTooLarge(
+            "file exceeds upload session limit".to_string(),
+        ));
+    }
+    let filename = input.filename.trim();
+    if filename.is_empty() || filename.len() > MAX_FILENAME_LEN {
+        return Err(invalid("filename must be 1 to 255 bytes"));
+    }
+    let sha256_hex = input.sha256_hex.map(|value| value.to_ascii_lowercase());
+    if let Some(value) = sha256_hex.as_deref() {
+        if value.len() != 64 || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid("sha256_hex must be 64 hex characters"));
+        }
+    }
+    let content_type = input
+        .content_type
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    detect_media_family(content_type.trim(), filename)?;
+    let id = generate_id();
+    let session = db::create_upload_session(
+        &state.pool,
+        &UploadSessionCreate {
+            id: &id,
+            space_slug: Some(&user),
+            original_filename: filename,
+            content_type: content_type.trim(),
+            byte_size: input.byte_size,
+            sha256_hex: sha256_hex.as_deref(),
+            actor: &actor,
+            space_byte_limit: (state.upload_session_max_bytes as i64).saturating_mul(2),
+        },
+    )
+    .await?;
+    Ok(http::json_status(StatusCode::CREATED, payload(session)))
+}
+
+pub async fn status_scoped(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let session = session_for_write(&state, &headers, &user, &id).await?;
+    Ok(http::json_status(StatusCode::OK, payload(session)))
+}
+
+/// Append one chunk at `?offset=`; a stale offset answers `409` with the
+/// offset to resume from.
+pub async fn put_chunk_scoped(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, String)>,
+    Query(query): Query<ChunkQuery>,
+    body: Body,
+) -> Result<Response, AppError> {
+    let session = session_for_write(&state, &headers, &user, &id).await?;
+    let Some(claim) = db::claim_upload_session(&state.pool, &id, query.offset).await? else {
+        let message = if session.received_bytes == query.offset {
+            "another chunk for this upload is still in flight".to_string()
+        } else {
+            format!("upload continues at offset {}", session.received_bytes)
+        };
+        return Ok(http::json_status(
+            StatusCode::CONFLICT,
+            OffsetConflict {
+                error: "conflict",
+                message,
+                offset: session.received_bytes,
+            },
+        ));
+    };
+    let remaining = (claim.byte_size - claim.received_bytes) as u64;
+    let limit = remaining.min(UPLOAD_CHUNK_MAX_BYTES);
+    let (written, result) = state
+        .upload_spool
+        .write_chunk(&id, claim.received_bytes as u64, limit, body)
+        .await;
+    let received = claim.received_bytes + written as i64;
+    let session = db::release_upload_session(&state.pool, &claim, received)
+        .await?
+        .ok_or_else(|| invalid("upload chunk was superseded by a newer request"))?;
+    result?;
+    Ok(http::json_status(StatusCode::OK, payload(session)))
+}
+
+pub async fn cancel_scoped(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user, id)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    session_for_write(&state, &headers, &user, &id).await?;
+    db::delete_upload_session(&state.pool, &id).await?;
+    state.upload_spool.remove(&id).await;
+    Ok(http::empty(StatusCode::NO_CONTENT))
+}
+
+async fn session_for_write(
+    state: &AppState,
+    headers: &HeaderMap,
+    user: &str,
+    id: &str,
+) -> Result<UploadSession, AppError> {
+    db::require_space(&state.pool, user).await?;
+    let actor = require_write(headers, &state.pool, user).await?;
+    validate_id(id).map_err(|_| AppError::NotFound(format!("upload '{id}' not found")))?;
+    db::get_upload_session(&state.pool, Some(user), id, &actor)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("upload '{id}' not found")))
+}
+
+fn payload(session: UploadSession) -> SessionPayload {
+    SessionPayload {
+        complete: session.is_complete(),
+        id: session.id,
+        filename: session.original_filename,
+        content_type: session.content_type,
+        byte_size: session.byte_size,
+        offset: session.received_bytes,
+        expires_at: session.expires_at,
+    }
+}
+
+fn invalid(message: &str) -> AppError {
+    AppError::InvalidRequest(message.to_string())
+}
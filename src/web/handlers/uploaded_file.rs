@@ -0,0 +1,90 @@
+//! Spooled upload bytes with their size and checksum
+
+use super::media_input::invalid;
+use crate::error::AppError;
+use axum::extract::multipart::Field;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+pub struct UploadedFile {
+    temp_file: NamedTempFile,
+    pub byte_size: i64,
+    pub sha256_hex: String,
+    pub original_filename: String,
+    pub content_type: String,
+}
+
+impl UploadedFile {
+    pub fn path(&self) -> &Path {
+        self.temp_file.path()
+    }
+
+    /// Wrap bytes assembled outside multipart, such as a finished upload session.
+    pub(super) fn from_temp_file(
+        temp_file: NamedTempFile,
+        byte_size: i64,
+        sha256_hex: String,
+        original_filename: String,
+        content_type: String,
+    ) -> Self {
+        Self {
+            temp_file,
+            byte_size,
+            sha256_hex,
+            original_filename,
+            content_type,
+        }
+    }
+}
+
+pub(super) async fn read_uploaded_file(
+    mut field: Field<'_>,
+    max_file_bytes: usize,
+) -> Result<UploadedFile, AppError> {
+    let original_filename = field
+        .file_name()
+        .map(str::to_string)
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "upload.bin".to_string());
+    let content_type = field
+        .content_type()
+        .map(str::to_string)
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let mut temp_file = NamedTempFile::new()
+        .map_err(|e| AppError::StorageError(format!("upload temp file create failed: {e}")))?;
+    let mut hasher = Sha256::new();
+    let mut byte_size = 0usize;
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|e| invalid(&format!("could not read upload: {e}")))?
+    {
+        byte_size = byte_size
+            .checked_add(chunk.len())
+            .ok_or_else(|| AppError::PayloadTooLarge("file exceeds upload limit".to_string()))?;
+        if byte_size > max_file_bytes {
+            return Err(AppError::PayloadTooLarge(
+                "file exceeds upload limit".to_string(),
+            ));
+        }
+        hasher.update(&chunk);
+        temp_file
+            .write_all(&chunk)
+            .map_err(|e| AppError::StorageError(format!("upload temp file write failed: {e}")))?;
+    }
+    temp_file
+        .flush()
+        .map_err(|e| AppError::StorageError(format!("upload temp file flush failed: {e}")))?;
+    if byte_size == 0 {
+        return Err(invalid("file is required"));
+    }
+    Ok(UploadedFile {
+        temp_file,
+        byte_size: byte_size as i64,
+        sha256_hex: format!("{:x}", hasher.finalize()),
+        original_filename,
+        content_type,
+    })
+}
@@ -1,47 +1,36 @@
-//! Changed-text summaries for history cards
+//! Changed-line summaries for history cards
+
+use crate::core::text_diff::{line_stats, DiffStats};
+use std::time::Duration;
+
+/// Bodies above this combined size show the fallback instead of line counts.
+const SUMMARY_MAX_BYTES: usize = 256 * 1024;
+/// Per-card diff budget; a history page renders many cards.
+const SUMMARY_TIMEOUT: Duration = Duration::from_millis(50);
 
 pub fn changed_summary(before: &str, after: &str, fallback: &str) -> String {
-    if before == after {
+    if before.len() + after.len() > SUMMARY_MAX_BYTES {
         return fallback.to_string();
     }
-    let before_lines = before.lines().collect::<Vec<_>>();
-    let after_lines = after.lines().collect::<Vec<_>>();
-    let max = before_lines.len().max(after_lines.len());
-    for index in 0..max {
-        match (before_lines.get(index), after_lines.get(index)) {
-            (Some(old), Some(new)) if old != new => return changed_line("Changed", new, fallback),
-            (None, Some(new)) => return changed_line("Added", new, fallback),
-            (Some(old), None) => return changed_line("Removed", old, fallback),
-            _ => {}
-        }
+    let stats = line_stats(before, after, SUMMARY_TIMEOUT);
+    if stats == DiffStats::default() {
+        return fallback.to_string();
     }
-    fallback.to_string()
+    format!("+{}/\u{2212}{} lines", stats.added, stats.removed)
 }
 
-fn changed_line(label: &str, line: &str, fallback: &str) -> String {
-    let preview = plain_preview(line).unwrap_or_else(|| fallback.to_string());
-    format!("{label}: {preview}")
-}
-
-fn plain_preview(line: &str) -> Option<String> {
-    let trimmed = line
-        .trim()
-        .trim_start_matches('#')
-        .trim_start_matches(['-', '*', '>', ' '])
-        .trim();
-    if trimmed.is_empty() {
-        None
-    } else {
-        Some(limit_chars(trimmed, 160))
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::changed_summary;
 
-fn limit_chars(value: &str, limit: usize) -> String {
-    let mut chars = value.chars();
-    let preview = chars.by_ref().take(limit).collect::<String>();
-    if chars.next().is_some() {
-        format!("{preview}...")
-    } else {
-        preview
+    #[test]
+    fn changed_summary_reports_line_counts_or_fallback() {
+        assert_eq!(
+            changed_summary("# A\nold\n", "# A\nnew\nmore\n", "Body"),
+            "+2/\u{2212}1 lines"
+        );
+        assert_eq!(changed_summary("same", "same", "Body"), "Body");
+        let large = "line\n".repeat(60_000);
+        assert_eq!(changed_summary(&large, "", "Body"), "Body");
     }
 }
@@ -0,0 +1,85 @@
+//! Concurrency limits for on-demand image transforms
+
+use crate::error::AppError;
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::Semaphore;
+
+/// Transforms fetching, decoding, or encoding at once; later misses wait.
+const TRANSFORM_SLOTS: usize = 2;
+
+type Encode = Shared<BoxFuture<'static, Result<Vec<u8>, AppError>>>;
+
+/// Runs cache-miss transforms: at most `TRANSFORM_SLOTS` at a time, and one
+/// run per cache key however many requests miss it together.
+#[derive(Clone)]
+pub struct ImageTransforms {
+    slots: Arc<Semaphore>,
+    in_flight: Arc<Mutex<HashMap<String, Encode>>>,
+}
+
+impl Default for ImageTransforms {
+    fn default() -> Self {
+        Self::with_slots(TRANSFORM_SLOTS)
+    }
+}
+
+impl ImageTransforms {
+    pub(crate) fn with_slots(slots: usize) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(slots)),
+            in_flight: Arc::default(),
+        }
+    }
+
+    /// Await the run for `key`, starting `encode` only when none is in
+    /// flight. The run is its own task, so it finishes and fills the cache
+    /// even if every waiting request goes away.
+    pub async fn run<F>(&self, key: &str, encode: F) -> Result<Vec<u8>, AppError>
+    where
+        F: Future<Output = Result<Vec<u8>, AppError>> + Send + 'static,
+    {
+        let shared = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            in_flight
+                .entry(key.to_string())
+                .or_insert_with(|| self.start(key.to_string(), encode))
+                .clone()
+        };
+        shared.await
+    }
+
+    fn start<F>(&self, key: String, encode: F) -> Encode
+    where
+        F: Future<Output = Result<Vec<u8>, AppError>> + Send + 'static,
+    {
+        let (slots, in_flight) = (self.slots.clone(), self.in_flight.clone());
+        let task = tokio::spawn(async move {
+            let result = match slots.acquire_owned().await {
+                Ok(_permit) => encode.await,
+                Err(_) => Err(AppError::StorageError(
+                    "image transforms stopped".to_string(),
+                )),
+            };
+            in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&key);
+            result
+        });
+        async move {
+            task.await.unwrap_or_else(|error| {
+                Err(AppError::StorageError(format!(
+                    "image transform task failed: {error}"
+                )))
+            })
+        }
+        .boxed()
+        .shared()
+    }
+}
@@ -0,0 +1,27 @@
+use super::image_transforms::ImageTransforms;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn concurrent_misses_for_one_key_share_a_single_run() {
+    let transforms = ImageTransforms::with_slots(1);
+    let runs = Arc::new(AtomicUsize::new(0));
+    let encode = |runs: Arc<AtomicUsize>| async move {
+        runs.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok(b"webp".to_vec())
+    };
+    let (first, second) = tokio::join!(
+        transforms.run("a.webp", encode(runs.clone())),
+        transforms.run("a.webp", encode(runs.clone())),
+    );
+    assert_eq!(first.unwrap(), b"webp");
+    assert_eq!(second.unwrap(), b"webp");
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    transforms
+        .run("a.webp", encode(runs.clone()))
+        .await
+        .unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
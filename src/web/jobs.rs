@@ -0,0 +1,149 @@
+//! In-process workers for the background job queue
+
+use crate::error::AppError;
+use crate::media_video::TRANSCODE_BUDGET;
+use crate::storage::Storage;
+use crate::web::db::{self, ClaimedJob, DbPool, JobKind};
+use crate::web::search_embedding::SharedEmbedder;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, interval_at, sleep, Instant};
+use tracing::warn;
+
+mod media_derivatives;
+mod search_embedding;
+
+const WORKERS: usize = 2;
+const IDLE_POLL: Duration = Duration::from_secs(2);
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// How often a running job refreshes its lock.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+/// Hard limit for one run, below the queue's 15 minute stale-lock threshold.
+const JOB_TIME_LIMIT: Duration = Duration::from_secs(10 * 60);
+const _: () = assert!(TRANSCODE_BUDGET.as_secs() + 2 * 60 <= JOB_TIME_LIMIT.as_secs());
+
+/// Start the job workers and the hourly prune of finished jobs.
+pub fn spawn(pool: DbPool, storage: Storage, embedder: SharedEmbedder) {
+    for _ in 0..WORKERS {
+        let (pool, storage, embedder) = (pool.clone(), storage.clone(), embedder.clone());
+        let worker = crate::core::generate_id();
+        tokio::spawn(async move {
+            loop {
+                match db::claim_job(&pool, &worker).await {
+                    Ok(Some(job)) => run(&pool, &storage, &embedder, job).await,
+                    Ok(None) => sleep(IDLE_POLL).await,
+                    Err(error) => {
+                        warn!(error = %error, "job claim failed");
+                        sleep(IDLE_POLL).await;
+                    }
+                }
+            }
+        });
+    }
+    tokio::spawn(async move {
+        let mut ticker = interval(PRUNE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(error) = db::prune_finished_jobs(&pool).await {
+                warn!(error = %error, "finished job prune failed");
+            }
+        }
+    });
+}
+
+/// Run one claimed job on its own task so a panic fails the job, not the worker.
+async fn run(pool: &DbPool, storage: &Storage, embedder: &SharedEmbedder, job: ClaimedJob) {
+    let mut task = tokio::spawn(execute(
+        pool.clone(),
+        storage.clone(),
+        embedder.clone(),
+        job.clone(),
+    ));
+    let result = supervise(pool, &job, &mut task).await;
+    let update = match result {
+        Ok(()) => db::complete_job(pool, &job).await,
+        Err(error) => {
+            warn!(
+                job = job.id,
+                kind = job.kind.as_str(),
+                attempt = job.attempts,
+                max_attempts = job.max_attempts,
+                error = %error,
+                "background job failed"
+            );
+            db::fail_job(pool, &job, &error, retry_delay(job.attempts)).await
+        }
+    };
+    match update {
+        Ok(true) => {}
+        Ok(false) => warn!(job = job.id, "job lock lost before its status update"),
+        Err(error) => warn!(job = job.id, error = %error, "job status update failed"),
+    }
+}
+
+/// Wait for the job task while refreshing its lock; abort it once the time
+/// limit passes or another worker has taken the job over. Aborting drops the
+/// task, which kills any child processes it spawned.
+async fn supervise(
+    pool: &DbPool,
+    job: &ClaimedJob,
+    task: &mut JoinHandle<Result<(), AppError>>,
+) -> Result<(), String> {
+    let deadline = sleep(JOB_TIME_LIMIT);
+    tokio::pin!(deadline);
+    let mut heartbeat = interval_at(Instant::now() + HEARTBEAT_INTERVAL, HEARTBEAT_INTERVAL);
+    loop {
+        tokio::select! {
+            joined = &mut *task => {
+                return match joined {
+                    Ok(result) => result.map_err(|error| error.to_string()),
+                    Err(error) => Err(format!("job task aborted: {error}")),
+                };
+            }
+            () = &mut deadline => {
+                task.abort();
+                return Err(format!(
+                    "job exceeded its {} minute time limit",
+                    JOB_TIME_LIMIT.as_secs() / 60
+                ));
+            }
+            _ = heartbeat.tick() => match db::heartbeat_job(pool, job).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    task.abort();
+                    return Err("job lock taken by another worker".to_string());
+                }
+                Err(error) => warn!(job = job.id, error = %error, "job heartbeat failed"),
+            },
+        }
+    }
+}
+
+async fn execute(
+    pool: DbPool,
+    storage: Storage,
+    embedder: SharedEmbedder,
+    job: ClaimedJob,
+) -> Result<(), AppError> {
+    match job.kind {
+        JobKind::MediaDerivatives => media_derivatives::generate(&pool, &storage, &job).await,
+        JobKind::SearchEmbedding => {
+            search_embedding::generate(&pool, embedder.as_ref(), &job).await
+        }
+        JobKind::EmbedUnfurl => {
+            let urls: Vec<String> = job.payload["urls"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|url| url.as_str().map(str::to_string))
+                .collect();
+            crate::web::embed_unfurl::unfurl_urls(&pool, &urls).await
+        }
+    }
+}
+
+/// Exponential backoff: 30 seconds doubling per attempt, capped at one hour.
+pub(crate) fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 7) as u32;
+    Duration::from_secs((30 * 2u64.pow(exponent)).min(3600))
+}
@@ -0,0 +1,83 @@
+use crate::error::AppError;
+use crate::media::{probe_image, MediaVariants, VideoTranscoder};
+use crate::storage::Storage;
+use crate::web::db::{self, ClaimedJob, DbPool, MediaFamily};
+use crate::web::handlers::media_derivatives::{build_variants, store_variants};
+use std::path::Path;
+
+/// Rebuild derivatives from the stored original and point the resource at them.
+///
+/// Images also refresh their probed details, backfilling capture metadata;
+/// videos also get MP4/WebM renditions and an HLS package. Each stored object
+/// is recorded before the next step starts, so an aborted run leaves nothing
+/// unreferenced. Trashed or purged media finish without work; a partial
+/// upload fails the job so the retry regenerates the full set.
+pub(super) async fn generate(
+    pool: &DbPool,
+    storage: &Storage,
+    job: &ClaimedJob,
+) -> Result<(), AppError> {
+    let Some(id) = job.resource_id.as_deref() else {
+        return Ok(());
+    };
+    let Some(resource) = db::get_resource(pool, id).await? else {
+        return Ok(());
+    };
+    let (Some(family), Some(file_key)) = (resource.media_family, resource.file_key.as_deref())
+    else {
+        return Ok(());
+    };
+    let suffix = resource
+        .original_filename
+        .as_deref()
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{ext}"))
+        .unwrap_or_default();
+    let temp_file = tempfile::Builder::new()
+        .prefix("kjxlkj-derivative-")
+        .suffix(&suffix)
+        .tempfile()
+        .map_err(|e| AppError::StorageError(format!("temp file failed: {e}")))?;
+    storage.get_file(file_key, temp_file.path()).await?;
+    if family == MediaFamily::Image {
+        let details = probe_image(temp_file.path()).await.details;
+        if details != resource.media_details {
+            db::update_media_details(pool, &resource.id, file_key, &details).await?;
+        }
+    }
+    let quality = db::get_settings_in_space(pool, &resource.space_slug)
+        .await?
+        .media_webp_quality;
+    let generated = build_variants(&resource.id, family, temp_file.path(), quality).await;
+    let (stored, stored_keys) = store_variants(storage, &generated).await;
+    let mut variants = resource.media_variants.clone().unwrap_or_default();
+    if let Some(stored) = stored {
+        variants.merge(stored);
+        db::update_media_variants(pool, &resource.id, file_key, &variants).await?;
+    }
+    if family == MediaFamily::Video {
+        let dir = tempfile::tempdir()
+            .map_err(|e| AppError::StorageError(format!("temp dir failed: {e}")))?;
+        let mut transcoder = VideoTranscoder::new(&resource.id, temp_file.path(), dir.path()).await;
+        while let Some(step) = transcoder.next_rendition().await {
+            for object in &step.staged {
+                storage
+                    .put_file(&object.key, &object.path, &object.content_type)
+                    .await?;
+            }
+            variants.merge(MediaVariants {
+                renditions: step.renditions,
+                hls: step.hls,
+                ..MediaVariants::default()
+            });
+            db::update_media_variants(pool, &resource.id, file_key, &variants).await?;
+        }
+    }
+    if stored_keys.len() < generated.len() {
+        return Err(AppError::StorageError(
+            "some media derivatives failed to upload".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::error::AppError;
+use crate::web::db::{self, ClaimedJob, DbPool};
+use crate::web::search_embedding::{embed_resource, EmbeddingProvider};
+
+/// Embed the live resource text and store the vector with its model name.
+/// Trashed or purged resources finish without work.
+pub(super) async fn generate(
+    pool: &DbPool,
+    embedder: &dyn EmbeddingProvider,
+    job: &ClaimedJob,
+) -> Result<(), AppError> {
+    let Some(id) = job.resource_id.as_deref() else {
+        return Ok(());
+    };
+    let Some(resource) = db::get_resource(pool, id).await? else {
+        return Ok(());
+    };
+    let vector = embed_resource(embedder, &resource)
+        .await
+        .map_err(|error| AppError::StorageError(format!("embedding failed: {error}")))?;
+    db::store_search_embedding(pool, &resource.id, embedder.model(), &vector).await
+}
@@ -0,0 +1,16 @@
+use super::jobs::retry_delay;
+use std::time::Duration;
+
+#[test]
+fn retry_delay_doubles_from_thirty_seconds() {
+    assert_eq!(retry_delay(1), Duration::from_secs(30));
+    assert_eq!(retry_delay(2), Duration::from_secs(60));
+    assert_eq!(retry_delay(4), Duration::from_secs(240));
+}
+
+#[test]
+fn retry_delay_caps_at_one_hour() {
+    assert_eq!(retry_delay(0), Duration::from_secs(30));
+    assert_eq!(retry_delay(8), Duration::from_secs(3600));
+    assert_eq!(retry_delay(i32::MAX), Duration::from_secs(3600));
+}
@@ -1,19 +1,19 @@
 use crate::core::looks_like_id;
 use crate::error::AppError;
-use crate::web::db::{self, DbPool, MediaFamily, Resource, ResourceSnapshot};
+use crate::web::db::{self, DbPool, MediaFamily, Resource, ResourceSnapshot, Visibility};
 use crate::web::{templates, view, view_media};
 use std::collections::HashMap;
 
 pub async fn decorate_local_cards(
     pool: &DbPool,
     html: &str,
-    is_admin: bool,
+    max_visibility: Visibility,
 ) -> Result<String, AppError> {
     let mut targets = HashMap::new();
     for href in local_card_hrefs(html) {
         if let std::collections::hash_map::Entry::Vacant(entry) = targets.entry(href) {
             let key = entry.key().to_string();
-            entry.insert(resolve_card(pool, &key, is_admin).await?);
+            entry.insert(resolve_card(pool, &key, max_visibility).await?);
         }
     }
     Ok(apply_cards(html, &targets))
@@ -38,7 +38,7 @@ fn local_card_hrefs(html: &str) -> Vec<String> {
 async fn resolve_card(
     pool: &DbPool,
     href: &str,
-    is_admin: bool,
+    max_visibility: Visibility,
 ) -> Result<Option<String>, AppError> {
     let Some((reference, _is_file)) = local_reference(href) else {
         return Ok(None);
@@ -46,15 +46,15 @@ async fn resolve_card(
     if !looks_like_id(reference) {
         return Ok(db::get_resource_by_ref(pool, reference)
             .await?
-            .filter(|resource| is_admin || !resource.is_private)
+            .filter(|resource| resource.visibility <= max_visibility)
             .map(|resource| live_card(&resource)));
     }
     if let Some(resource) = db::get_resource(pool, reference).await? {
-        return Ok((is_admin || !resource.is_private).then(|| live_card(&resource)));
+        return Ok((resource.visibility <= max_visibility).then(|| live_card(&resource)));
     }
     Ok(db::get_snapshot_target(pool, reference)
         .await?
-        .filter(|target| is_admin || !target.snapshot.is_private)
+        .filter(|target| target.snapshot.visibility <= max_visibility)
         .map(|target| snapshot_card(&target.snapshot)))
 }
 
@@ -81,7 +81,7 @@ fn apply_cards(html: &str, cards: &HashMap<String, Option<String>>) -> String {
     output
 }
 
-fn live_card(resource: &Resource) -> String {
+pub(crate) fn live_card(resource: &Resource) -> String {
     resource_card(CardView {
         href: view::resource_href(resource),
         id: resource.id.clone(),
@@ -105,7 +105,7 @@ fn snapshot_card(snapshot: &ResourceSnapshot) -> String {
         title: format!("Saved snapshot {}", snapshot.snapshot_number),
         summary: snapshot.summary.clone(),
         created: templates::render_time(&snapshot.created_at),
-        updated: view::visibility_label(snapshot.is_private).to_string(),
+        updated: snapshot.visibility.label().to_string(),
         favorite: false,
         media_href: media_card_href(snapshot.media_family, || {
             format!("/{}/file?variant=card", snapshot.id)
@@ -153,7 +153,11 @@ fn resource_card(card: CardView<'_>) -> String {
 }
 
 fn media_card_href<F: FnOnce() -> String>(family: Option<MediaFamily>, build: F) -> Option<String> {
-    matches!(family, Some(MediaFamily::Image | MediaFamily::Video)).then(build)
+    matches!(
+        family,
+        Some(MediaFamily::Image | MediaFamily::Video | MediaFamily::Audio)
+    )
+    .then(build)
 }
 
 fn pill(label: &str) -> String {
@@ -184,7 +188,7 @@ fn local_reference(href: &str) -> Option<(&str, bool)> {
     }
 }
 
-fn esc(value: &str) -> String {
+pub(crate) fn esc(value: &str) -> String {
     value
         .replace('&', "&amp;")
         .replace('<', "&lt;")
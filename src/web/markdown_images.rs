@@ -0,0 +1,195 @@
+//! Owner-note targets and stored dimensions for local media in rendered Markdown
+
+use crate::core::looks_like_id;
+use crate::error::AppError;
+use crate::web::db::{self, DbPool, Resource, Visibility};
+use crate::web::view;
+use std::collections::HashMap;
+
+/// What a local media `src` resolves to for one reader.
+#[derive(Default)]
+struct MediaTarget {
+    /// Page a clicked image opens; images only.
+    href: Option<String>,
+    /// Stored display dimensions, emitted to reserve layout space.
+    size: Option<(i32, i32)>,
+}
+
+pub(crate) async fn decorate_local_images(
+    pool: &DbPool,
+    html: &str,
+    current_resource_id: Option<&str>,
+    max_visibility: Visibility,
+) -> Result<String, AppError> {
+    let mut targets = HashMap::new();
+    for src in local_media_sources(html) {
+        if let std::collections::hash_map::Entry::Vacant(entry) = targets.entry(src) {
+            let target =
+                resolve_media_target(pool, entry.key(), current_resource_id, max_visibility)
+                    .await?;
+            entry.insert(target);
+        }
+    }
+    let html = rewrite_tags(html, "<img", |tag| apply_target(tag, &targets, true));
+    Ok(rewrite_tags(&html, "<video", |tag| {
+        apply_target(tag, &targets, false)
+    }))
+}
+
+fn local_media_sources(html: &str) -> Vec<String> {
+    let mut sources = Vec::new();
+    for marker in ["<img", "<video"] {
+        rewrite_tags(html, marker, |tag| {
+            if let Some(src) = media_src(tag).filter(|src| is_local_file_href(src)) {
+                sources.push(src.to_string());
+            }
+            tag.to_string()
+        });
+    }
+    sources
+}
+
+async fn resolve_media_target(
+    pool: &DbPool,
+    src: &str,
+    current_resource_id: Option<&str>,
+    max_visibility: Visibility,
+) -> Result<Option<MediaTarget>, AppError> {
+    let Some(reference) = local_file_reference(src) else {
+        return Ok(None);
+    };
+    let (resource, size) = if !looks_like_id(reference) {
+        let Some(resource) = db::get_resource_by_ref(pool, reference).await? else {
+            return Ok(None);
+        };
+        let size = resource_size(&resource, max_visibility);
+        (resource, size)
+    } else if let Some(resource) = db::get_resource(pool, reference).await? {
+        let size = resource_size(&resource, max_visibility);
+        (resource, size)
+    } else if let Some(target) = db::get_snapshot_target(pool, reference).await? {
+        let snapshot = &target.snapshot;
+        let size = (snapshot.visibility <= max_visibility)
+            .then_some(snapshot.width.zip(snapshot.height))
+            .flatten();
+        (target.resource, size)
+    } else {
+        return Ok(None);
+    };
+    let href =
+        resolve_resource_target(pool, &resource, current_resource_id, max_visibility).await?;
+    Ok(Some(MediaTarget {
+        href: Some(href),
+        size,
+    }))
+}
+
+fn resource_size(resource: &Resource, max_visibility: Visibility) -> Option<(i32, i32)> {
+    (resource.visibility <= max_visibility)
+        .then_some(resource.width.zip(resource.height))
+        .flatten()
+}
+
+async fn resolve_resource_target(
+    pool: &DbPool,
+    resource: &Resource,
+    current_resource_id: Option<&str>,
+    max_visibility: Visibility,
+) -> Result<String, AppError> {
+    if let Some(owner_id) = resource.owner_note_id.as_deref() {
+        if Some(owner_id) != current_resource_id {
+            if let Some(owner) = db::get_resource(pool, owner_id)
+                .await?
+                .filter(|owner| owner.visibility <= max_visibility)
+            {
+                return Ok(view::resource_href(&owner));
+            }
+        }
+    }
+    Ok(view::resource_href(resource))
+}
+
+fn apply_target(tag: &str, targets: &HashMap<String, Option<MediaTarget>>, image: bool) -> String {
+    let Some(target) = media_src(tag)
+        .and_then(|src| targets.get(src))
+        .and_then(Option::as_ref)
+    else {
+        return tag.to_string();
+    };
+    let mut tag = tag.to_string();
+    if let Some(href) = target.href.as_deref().filter(|_| image) {
+        tag = add_attribute(&tag, "data-resource-image-href", href);
+    }
+    if let Some((width, height)) = target.size.filter(|_| !tag.contains(" width=\"")) {
+        tag = add_attribute(&tag, "width", &width.to_string());
+        tag = add_attribute(&tag, "height", &height.to_string());
+    }
+    tag
+}
+
+/// The file a tag shows; decorated videos move it into `<source>` children
+/// and keep it in the poster href.
+fn media_src(tag: &str) -> Option<&str> {
+    attribute_value(tag, "src").or_else(|| {
+        let poster = attribute_value(tag, "poster")?;
+        poster
+            .strip_suffix("?variant=poster")
+            .or_else(|| poster.strip_suffix("&amp;variant=poster"))
+    })
+}
+
+/// Rebuild `html`, passing each opening tag that starts with `marker` through `rewrite`.
+fn rewrite_tags<F: FnMut(&str) -> String>(html: &str, marker: &str, mut rewrite: F) -> String {
+    let mut rest = html;
+    let mut output = String::new();
+    while let Some(start) = rest.find(marker) {
+        output.push_str(&rest[..start]);
+        let after_marker = &rest[start..];
+        let Some(end) = after_marker.find('>') else {
+            output.push_str(after_marker);
+            return output;
+        };
+        output.push_str(&rewrite(&after_marker[..=end]));
+        rest = &after_marker[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn local_file_reference(href: &str) -> Option<&str> {
+    let path = href.split('?').next()?.trim_start_matches('/');
+    let mut segments = path.split('/');
+    let reference = segments.next()?;
+    matches!(segments.next(), Some("file")).then_some(reference)
+}
+
+fn is_local_file_href(href: &str) -> bool {
+    href.starts_with('/') && !href.starts_with("//") && local_file_reference(href).is_some()
+}
+
+fn attribute_value<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let marker = format!(r#"{name}=""#);
+    let start = tag.find(&marker)? + marker.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+fn add_attribute(tag: &str, name: &str, value: &str) -> String {
+    if tag.contains(&format!(r#"{name}=""#)) {
+        return tag.to_string();
+    }
+    format!(
+        "{} {}=\"{}\">",
+        &tag[..tag.len().saturating_sub(1)],
+        name,
+        escape_attr(value),
+    )
+}
+
+fn escape_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
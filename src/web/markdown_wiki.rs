@@ -0,0 +1,100 @@
+//! Resolve `[[ref]]` wiki links and `![[ref]]` transclusions for one reader
+
+use crate::core::{wiki_links, WikiLink, WikiTarget};
+use crate::error::AppError;
+use crate::web::db::{self, DbPool, Resource, ResourceKind};
+use crate::web::markdown::{render_body, PageScope};
+use crate::web::markdown_cards::{esc, live_card};
+use crate::web::view;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+/// Nested transclusions deeper than this render a stop notice.
+const MAX_TRANSCLUSION_DEPTH: usize = 3;
+
+/// Transclusions one page render may expand in total, across all levels.
+pub(crate) const MAX_TRANSCLUSIONS: usize = 32;
+
+/// Look up each wiki reference in the page's space, keeping only targets the
+/// reader may see; `trail` holds the resources already being rendered.
+pub(crate) async fn resolve_wiki_targets(
+    pool: &DbPool,
+    body: &str,
+    space_slug: &str,
+    page: &PageScope<'_>,
+    trail: &[String],
+) -> Result<HashMap<String, WikiTarget>, AppError> {
+    let mut links: Vec<WikiLink> = Vec::new();
+    for link in wiki_links(body) {
+        match links
+            .iter_mut()
+            .find(|seen| seen.reference == link.reference)
+        {
+            Some(seen) => seen.transclude |= link.transclude,
+            None => links.push(link),
+        }
+    }
+    let references: Vec<String> = links.iter().filter_map(WikiLink::resource_ref).collect();
+    let mut found = db::get_resources_by_refs_in_space(pool, space_slug, &references).await?;
+    let mut targets = HashMap::new();
+    for link in links {
+        let Some(resource) = link
+            .resource_ref()
+            .and_then(|reference| found.remove(&reference))
+            .filter(|resource| resource.visibility <= page.max_visibility)
+        else {
+            continue;
+        };
+        let transclusion_html = if link.transclude && resource.kind == ResourceKind::Note {
+            Some(transclusion(pool, &resource, page, trail).await?)
+        } else {
+            None
+        };
+        targets.insert(
+            link.reference,
+            WikiTarget {
+                href: view::resource_href(&resource),
+                title: resource.title.clone(),
+                card_html: live_card(&resource),
+                transclusion_html,
+            },
+        );
+    }
+    Ok(targets)
+}
+
+async fn transclusion(
+    pool: &DbPool,
+    resource: &Resource,
+    page: &PageScope<'_>,
+    trail: &[String],
+) -> Result<String, AppError> {
+    let href = view::resource_href(resource);
+    let head = format!(
+        r#"<header class="transclusion-head"><a href="{}">{}</a></header>"#,
+        esc(&href),
+        esc(&resource.title),
+    );
+    if trail.contains(&resource.id) || trail.len() > MAX_TRANSCLUSION_DEPTH {
+        return Ok(format!(
+            r#"<section class="transclusion transclusion-stopped">{head}<p>Not repeated here to avoid an endless loop.</p></section>"#
+        ));
+    }
+    let spent =
+        page.transclusions_left
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                left.checked_sub(1)
+            });
+    if spent.is_err() {
+        return Ok(format!(
+            r#"<section class="transclusion transclusion-stopped">{head}<p>Not shown here because this page already embeds too many notes.</p></section>"#
+        ));
+    }
+    let mut nested = trail.to_vec();
+    nested.push(resource.id.clone());
+    let html = render_body(pool, &resource.body, page, nested).await?;
+    Ok(format!(
+        r#"<section class="transclusion" data-transclusion-id="{}">{head}<div class="transclusion-body prose">{html}</div></section>"#,
+        resource.id
+    ))
+}
@@ -2,20 +2,44 @@
 
 pub(crate) mod db;
 pub(crate) mod embed_unfurl;
+mod feed;
+#[cfg(test)]
+mod feed_tests;
+mod feed_xml;
 pub mod handlers;
 mod history_summary;
+mod image_transforms;
+#[cfg(test)]
+mod image_transforms_tests;
+mod jobs;
+#[cfg(test)]
+mod jobs_tests;
 mod live;
 #[cfg(test)]
 mod live_tests;
 mod markdown;
 mod markdown_cards;
 mod markdown_external;
+mod markdown_images;
+mod markdown_wiki;
 mod routes;
+mod routes_feeds;
+mod routes_history;
+mod routes_media;
+mod routes_settings;
+pub(crate) mod search_embedding;
+#[cfg(test)]
+mod search_embedding_tests;
 mod site;
 #[cfg(test)]
 mod site_tests;
 mod templates;
+mod trash_sweep;
+mod upload_spool;
+#[cfg(test)]
+mod upload_spool_tests;
 mod view;
+mod view_chrome;
 mod view_media;
 
 pub use routes::run_server;
@@ -5,12 +5,19 @@ use crate::error::AppError;
 use crate::storage::Storage;
 use crate::web::db;
 use crate::web::handlers::{
-    admin, assets, discoverability, favorites, health, history, home, live, login, logout, media,
-    media_attachments, password_reset, popular_sections, preview, resource, resource_api,
-    resource_file, resource_history, resources_scoped, search, settings, setup, site_icon,
+    admin, assets, discoverability, favorites, health, home, live, login, logout, password_reset,
+    popular_sections, preview, resource, resource_api, resource_patch, resources_scoped, search,
+    settings, setup, tags,
 };
+use crate::web::image_transforms::ImageTransforms;
 use crate::web::live::LiveHub;
-use axum::extract::DefaultBodyLimit;
+use crate::web::routes_feeds::feed_routes;
+use crate::web::routes_history::history_routes;
+use crate::web::routes_media::media_routes;
+use crate::web::routes_settings::settings_routes;
+use crate::web::search_embedding::{self, SharedEmbedder};
+use crate::web::upload_spool::{self, UploadSpool};
+use crate::web::{jobs, trash_sweep};
 use axum::routing::{get, post, put};
 use axum::Router;
 use std::net::SocketAddr;
@@ -26,7 +33,12 @@ pub struct AppState {
     pub live_hub: LiveHub,
     pub live_trusted_proxy_ips: Vec<std::net::IpAddr>,
     pub media_upload_max_bytes: usize,
+    pub upload_session_max_bytes: usize,
+    pub upload_spool: UploadSpool,
+    pub image_transforms: ImageTransforms,
     pub site_icon_upload_max_bytes: usize,
+    pub trash_retention_days: usize,
+    pub embedder: SharedEmbedder,
 }
 
 pub async fn run_server(config: Config) -> Result<(), AppError> {
@@ -49,6 +61,12 @@ pub async fn run_server(config: Config) -> Result<(), AppError> {
     )
     .await
     .map_err(AppError::StorageError)?;
+    trash_sweep::spawn(pool.clone(), storage.clone(), config.trash_retention_days);
+    let embedder = search_embedding::from_config(&config);
+    db::requeue_search_embeddings(&pool, embedder.model()).await?;
+    jobs::spawn(pool.clone(), storage.clone(), embedder.clone());
+    let upload_spool = UploadSpool::open(config.upload_session_dir.clone()).await?;
+    upload_spool::spawn_sweep(pool.clone(), upload_spool.clone());
     let state = AppState {
         pool,
         storage,
@@ -56,7 +74,12 @@ pub async fn run_server(config: Config) -> Result<(), AppError> {
         live_hub,
         live_trusted_proxy_ips: config.live_trusted_proxy_ips.clone(),
         media_upload_max_bytes: config.media_upload_max_bytes,
+        upload_session_max_bytes: config.upload_session_max_bytes,
+        upload_spool,
+        image_transforms: ImageTransforms::default(),
         site_icon_upload_max_bytes: config.site_icon_upload_max_bytes,
+        trash_retention_days: config.trash_retention_days,
+        embedder,
     };
 
     info!("Starting HTTP server on {}", bind_addr);
@@ -101,21 +124,17 @@ pub fn router(state: AppState) -> Router {
             "/_/popular-resources/{surface}/{window}",
             get(popular_sections::popular_resources_section),
         )
-        .route(
-            "/{user}/settings",
-            get(settings::settings_page_scoped).post(settings::settings_submit_scoped),
-        )
+        .merge(settings_routes(&state))
+        .merge(media_routes(&state))
+        .merge(feed_routes())
+        .merge(history_routes())
         .route("/account/password", post(settings::password_submit))
-        .route(
-            "/{user}/settings/site-icon",
-            post(site_icon::upload).layer(DefaultBodyLimit::max(state.site_icon_upload_max_bytes)),
-        )
-        .route("/{user}/settings/site-icon/reset", post(site_icon::reset))
         .route(
             "/{user}/markdown-preview",
             post(preview::render_markdown_preview),
         )
         .route("/{user}/search", get(search::search_page_scoped))
+        .route("/{user}/tags", get(tags::tags_page))
         .route(
             "/api/users/{user}/resources/search",
             get(resource_api::search_scoped),
@@ -126,56 +145,23 @@ pub fn router(state: AppState) -> Router {
         )
         .route("/{user}/live", get(live::live_page))
         .route("/{user}/live/ws", get(live::live_ws))
-        .route(
-            "/{user}/{reference}/file",
-            get(resource_file::current_file_scoped),
-        )
-        .route("/{user}/{id}/history", get(history::history_page_scoped))
-        .route(
-            "/api/users/{user}/resources/media",
-            post(media::create_scoped).layer(DefaultBodyLimit::max(state.media_upload_max_bytes)),
-        )
         .route(
             "/api/users/{user}/resources/notes",
             post(resources_scoped::create),
         )
-        .route(
-            "/api/users/{user}/resources/{reference}/history",
-            get(resource_history::api_history_scoped),
-        )
         .route(
             "/api/users/{user}/resources/{reference}",
             get(resource_api::fetch_scoped)
                 .put(resources_scoped::api_update)
+                .patch(resource_patch::api_patch)
                 .delete(resources_scoped::remove),
         )
-        .route(
-            "/{user}/resources/media",
-            post(media::create_scoped).layer(DefaultBodyLimit::max(state.media_upload_max_bytes)),
-        )
-        .route(
-            "/{user}/resources/{id}/media-attachments",
-            post(media_attachments::attach_media_scoped)
-                .layer(DefaultBodyLimit::max(state.media_upload_max_bytes)),
-        )
         .route("/{user}/resources/notes", post(resources_scoped::create))
         .route(
             "/{user}/resources/{id}",
             put(resources_scoped::update).delete(resources_scoped::remove),
         )
         .route("/{user}/favorites/order", put(favorites::reorder))
-        .route(
-            "/{user}/resources/{id}/history",
-            get(resource_history::history_scoped),
-        )
-        .route(
-            "/{user}/resources/{id}/prev",
-            get(resource_history::previous_scoped),
-        )
-        .route(
-            "/{user}/resources/{id}/next",
-            get(resource_history::next_scoped),
-        )
         .route("/{user}", get(home::home_page_scoped))
         .route("/{user}/{reference}", get(resource::resource_page_scoped))
         .layer(CompressionLayer::new())
@@ -0,0 +1,16 @@
+//! Space and tag feed routes
+
+use crate::web::handlers::feeds;
+use crate::web::routes::AppState;
+use axum::routing::get;
+use axum::Router;
+
+pub(super) fn feed_routes() -> Router<AppState> {
+    Router::new()
+        .route("/{user}/feed.atom", get(feeds::space_atom))
+        .route("/{user}/feed.rss", get(feeds::space_rss))
+        .route("/{user}/feed.json", get(feeds::space_json))
+        .route("/{user}/tags/{tag}/feed.atom", get(feeds::tag_atom))
+        .route("/{user}/tags/{tag}/feed.rss", get(feeds::tag_rss))
+        .route("/{user}/tags/{tag}/feed.json", get(feeds::tag_json))
+}
@@ -0,0 +1,45 @@
+//! Snapshot history, diff, restore, and link routes
+
+use crate::web::handlers::{
+    history, resource_history, resource_links, resources_scoped, snapshot_diff,
+};
+use crate::web::routes::AppState;
+use axum::routing::{get, post};
+use axum::Router;
+
+pub(super) fn history_routes() -> Router<AppState> {
+    Router::new()
+        .route("/{user}/{id}/history", get(history::history_page_scoped))
+        .route(
+            "/{user}/{reference}/history/{from}/diff/{to}",
+            get(snapshot_diff::diff_page),
+        )
+        .route(
+            "/api/users/{user}/resources/{reference}/history",
+            get(resource_history::api_history_scoped),
+        )
+        .route(
+            "/api/users/{user}/resources/{reference}/links",
+            get(resource_links::api_links),
+        )
+        .route(
+            "/api/users/{user}/resources/{reference}/history/{from}/diff/{to}",
+            get(snapshot_diff::api_diff),
+        )
+        .route(
+            "/api/users/{user}/resources/{reference}/history/{snapshot}/restore",
+            post(resources_scoped::api_restore_snapshot),
+        )
+        .route(
+            "/{user}/resources/{id}/history",
+            get(resource_history::history_scoped),
+        )
+        .route(
+            "/{user}/resources/{id}/prev",
+            get(resource_history::previous_scoped),
+        )
+        .route(
+            "/{user}/resources/{id}/next",
+            get(resource_history::next_scoped),
+        )
+}
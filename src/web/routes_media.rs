@@ -0,0 +1,45 @@
+//! Media upload and stored file routes
+
+use crate::web::handlers::{media, media_attachments, resource_file, upload_sessions};
+use crate::web::routes::AppState;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{get, post};
+use axum::Router;
+
+pub(super) fn media_routes(state: &AppState) -> Router<AppState> {
+    let upload_limit = DefaultBodyLimit::max(state.media_upload_max_bytes);
+    Router::new()
+        .route(
+            "/{user}/{reference}/file",
+            get(resource_file::current_file_scoped),
+        )
+        .route(
+            "/{user}/{reference}/file/hls/{name}",
+            get(resource_file::hls_file_scoped),
+        )
+        .route(
+            "/api/users/{user}/resources/media",
+            post(media::create_scoped).layer(upload_limit),
+        )
+        .route(
+            "/{user}/resources/media",
+            post(media::create_scoped).layer(upload_limit),
+        )
+        .route(
+            "/{user}/resources/{id}/media-attachments",
+            post(media_attachments::attach_media_scoped).layer(upload_limit),
+        )
+        .merge(upload_session_routes("/api/users/{user}/uploads"))
+        .merge(upload_session_routes("/{user}/uploads"))
+}
+
+fn upload_session_routes(base: &str) -> Router<AppState> {
+    Router::new()
+        .route(base, post(upload_sessions::create_scoped))
+        .route(
+            &format!("{base}/{{id}}"),
+            get(upload_sessions::status_scoped)
+                .put(upload_sessions::put_chunk_scoped)
+                .delete(upload_sessions::cancel_scoped),
+        )
+}
@@ -0,0 +1,50 @@
+//! Settings, member, service account, audit, and trash routes
+
+use crate::web::handlers::{audit, members, service_accounts, settings, site_icon, trash};
+use crate::web::routes::AppState;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{delete, get, post, put};
+use axum::Router;
+
+pub(super) fn settings_routes(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{user}/settings",
+            get(settings::settings_page_scoped).post(settings::settings_submit_scoped),
+        )
+        .route(
+            "/{user}/settings/site-icon",
+            post(site_icon::upload).layer(DefaultBodyLimit::max(state.site_icon_upload_max_bytes)),
+        )
+        .route("/{user}/settings/site-icon/reset", post(site_icon::reset))
+        .route(
+            "/{user}/settings/service-accounts",
+            get(service_accounts::list).post(service_accounts::create),
+        )
+        .route(
+            "/{user}/settings/service-accounts/{id}",
+            put(service_accounts::update),
+        )
+        .route(
+            "/{user}/settings/service-accounts/{id}/tokens",
+            post(service_accounts::issue),
+        )
+        .route(
+            "/{user}/settings/api-tokens/{id}/revoke",
+            post(service_accounts::revoke),
+        )
+        .route(
+            "/{user}/settings/members",
+            get(members::list).post(members::invite),
+        )
+        .route(
+            "/{user}/settings/members/{id}",
+            put(members::update).delete(members::remove),
+        )
+        .route("/{user}/admin/audit", get(audit::audit_page))
+        .route("/api/users/{user}/audit", get(audit::api_feed))
+        .route("/{user}/admin/trash", get(trash::trash_page))
+        .route("/api/users/{user}/trash", get(trash::api_list))
+        .route("/api/users/{user}/trash/{id}", delete(trash::purge))
+        .route("/api/users/{user}/trash/{id}/restore", post(trash::restore))
+}
@@ -0,0 +1,109 @@
+//! Embedding providers and vectors for semantic search
+
+use crate::config::Config;
+use crate::core::{parse_front_matter, strip_front_matter};
+use crate::web::db::{QueryEmbedding, Resource};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+mod hash;
+mod http;
+
+pub use hash::HashEmbedding;
+#[cfg(test)]
+pub(crate) use http::parse_vectors;
+pub use http::HttpEmbedding;
+
+/// Longest resource text handed to a provider; only the start is embedded.
+const MAX_INPUT_CHARS: usize = 8000;
+
+/// Turns texts into vectors. `model` names the vector space: stored vectors
+/// from another model are ignored and re-embedded.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    fn model(&self) -> &str;
+
+    /// Cosine similarity above which a resource matches a query on meaning
+    /// alone; typical sentence-embedding models put unrelated text below 0.5.
+    fn min_similarity(&self) -> f64 {
+        0.5
+    }
+
+    /// One vector per input text, in input order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String>;
+}
+
+pub type SharedEmbedder = Arc<dyn EmbeddingProvider>;
+
+/// HTTP provider when `EMBEDDING_URL` is set, otherwise the local hash provider.
+pub fn from_config(config: &Config) -> SharedEmbedder {
+    match &config.embedding_url {
+        Some(url) => Arc::new(HttpEmbedding::new(url, &config.embedding_model)),
+        None => Arc::new(HashEmbedding::default()),
+    }
+}
+
+/// Vector for one resource, unit length so cosine similarity is a dot product.
+pub async fn embed_resource(
+    embedder: &dyn EmbeddingProvider,
+    resource: &Resource,
+) -> Result<Vec<f32>, String> {
+    embed_one(embedder, resource_text(resource)).await
+}
+
+/// Query vector for semantic ranking; provider failures degrade to lexical
+/// ranking instead of failing the search.
+pub async fn embed_query(embedder: &dyn EmbeddingProvider, query: &str) -> Option<QueryEmbedding> {
+    match embed_one(embedder, query.trim().to_string()).await {
+        Ok(vector) => Some(QueryEmbedding {
+            model: embedder.model().to_string(),
+            vector,
+            min_similarity: embedder.min_similarity(),
+        }),
+        Err(error) => {
+            warn!(model = embedder.model(), error = %error, "query embedding failed");
+            None
+        }
+    }
+}
+
+/// Title, summary, tags, filename, and body without front matter.
+pub fn resource_text(resource: &Resource) -> String {
+    let tags = parse_front_matter(&resource.body)
+        .map(|front| front.tags.join(" "))
+        .unwrap_or_default();
+    [
+        resource.title.as_str(),
+        resource.summary.as_str(),
+        tags.as_str(),
+        resource.original_filename.as_deref().unwrap_or_default(),
+        strip_front_matter(&resource.body),
+    ]
+    .into_iter()
+    .filter(|part| !part.trim().is_empty())
+    .collect::<Vec<_>>()
+    .join("\n")
+    .chars()
+    .take(MAX_INPUT_CHARS)
+    .collect()
+}
+
+pub fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 && norm.is_finite() {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+async fn embed_one(embedder: &dyn EmbeddingProvider, text: String) -> Result<Vec<f32>, String> {
+    let vector = embedder
+        .embed(&[text])
+        .await?
+        .into_iter()
+        .next()
+        .filter(|vector| !vector.is_empty())
+        .ok_or_else(|| "provider returned no vector".to_string())?;
+    Ok(normalize(vector))
+}
@@ -0,0 +1,71 @@
+use super::{normalize, EmbeddingProvider};
+use async_trait::async_trait;
+
+const DIMENSIONS: usize = 256;
+const TRIGRAM_WEIGHT: f32 = 0.5;
+/// Hashed vectors of unrelated texts sit near zero, so a low floor suffices.
+const MIN_SIMILARITY: f64 = 0.3;
+
+/// Deterministic feature-hashing embedder that needs no model server.
+///
+/// Words and their character trigrams land in signed buckets, so texts
+/// sharing vocabulary or word stems score close; it does not know synonyms.
+pub struct HashEmbedding {
+    model: String,
+}
+
+impl Default for HashEmbedding {
+    fn default() -> Self {
+        Self {
+            model: format!("hash-{DIMENSIONS}"),
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for HashEmbedding {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn min_similarity(&self) -> f64 {
+        MIN_SIMILARITY
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        Ok(texts.iter().map(|text| hash_vector(text)).collect())
+    }
+}
+
+fn hash_vector(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; DIMENSIONS];
+    let lower = text.to_lowercase();
+    for word in lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        add(&mut vector, word.as_bytes(), 1.0);
+        let padded: Vec<char> = format!("#{word}#").chars().collect();
+        for trigram in padded.windows(3) {
+            add(
+                &mut vector,
+                trigram.iter().collect::<String>().as_bytes(),
+                TRIGRAM_WEIGHT,
+            );
+        }
+    }
+    normalize(vector)
+}
+
+fn add(vector: &mut [f32], feature: &[u8], weight: f32) {
+    let hash = fnv1a(feature);
+    let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+    vector[(hash % DIMENSIONS as u64) as usize] += sign * weight;
+}
+
+/// FNV-1a, stable across builds unlike the std hasher.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,94 @@
+use super::EmbeddingProvider;
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Embedder backed by a local model server.
+///
+/// Posts `{"model", "input"}` and accepts either the OpenAI-style
+/// `data[].embedding` response (llama.cpp, vLLM, LM Studio) or the
+/// `embeddings` array returned by Ollama's `/api/embed`.
+pub struct HttpEmbedding {
+    url: String,
+    model: String,
+    client: Client,
+}
+
+impl HttpEmbedding {
+    pub fn new(url: &str, model: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            model: model.to_string(),
+            client: Client::builder()
+                .timeout(TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for HttpEmbedding {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(json!({ "model": self.model, "input": texts }).to_string())
+            .send()
+            .await
+            .map_err(|error| error.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("model server answered {status}"));
+        }
+        let bytes = response.bytes().await.map_err(|error| error.to_string())?;
+        let body: Value = serde_json::from_slice(&bytes).map_err(|error| error.to_string())?;
+        let vectors = parse_vectors(&body)?;
+        if vectors.len() != texts.len() {
+            return Err(format!(
+                "expected {} vectors, got {}",
+                texts.len(),
+                vectors.len()
+            ));
+        }
+        Ok(vectors)
+    }
+}
+
+/// Vectors of an OpenAI-style or Ollama-style embedding response.
+pub fn parse_vectors(body: &Value) -> Result<Vec<Vec<f32>>, String> {
+    let rows: Vec<&Value> = match (body.get("data"), body.get("embeddings")) {
+        (Some(Value::Array(data)), _) => {
+            let mut data: Vec<&Value> = data.iter().collect();
+            data.sort_by_key(|item| item.get("index").and_then(Value::as_u64).unwrap_or(0));
+            data.into_iter()
+                .map(|item| item.get("embedding").unwrap_or(&Value::Null))
+                .collect()
+        }
+        (_, Some(Value::Array(embeddings))) => embeddings.iter().collect(),
+        _ => return Err("response has neither data nor embeddings".to_string()),
+    };
+    rows.into_iter()
+        .map(|row| {
+            row.as_array()
+                .ok_or_else(|| "embedding is not an array".to_string())?
+                .iter()
+                .map(|value| {
+                    value
+                        .as_f64()
+                        .map(|value| value as f32)
+                        .ok_or_else(|| "embedding holds a non-number".to_string())
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use super::search_embedding::{parse_vectors, resource_text, EmbeddingProvider, HashEmbedding};
+use crate::web::db::{Resource, ResourceKind, Visibility};
+use chrono::Utc;
+use serde_json::json;
+
+fn note(body: &str) -> Resource {
+    Resource {
+        id: "abcdefghijklmnopqrstuvwx26".to_string(),
+        space_slug: "alice".to_string(),
+        kind: ResourceKind::Note,
+        alias: None,
+        title: "Trip Log".to_string(),
+        summary: "First day".to_string(),
+        body: body.to_string(),
+        media_family: None,
+        file_key: None,
+        content_type: None,
+        byte_size: None,
+        sha256_hex: None,
+        original_filename: None,
+        width: None,
+        height: None,
+        duration_ms: None,
+        media_variants: None,
+        media_details: None,
+        owner_note_id: None,
+        is_favorite: false,
+        favorite_position: None,
+        visibility: Visibility::Private,
+        view_count_total: 0,
+        last_viewed_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn dot(left: &[f32], right: &[f32]) -> f32 {
+    left.iter().zip(right).map(|(a, b)| a * b).sum()
+}
+
+async fn embed(texts: &[&str]) -> Vec<Vec<f32>> {
+    let texts: Vec<String> = texts.iter().map(|text| text.to_string()).collect();
+    HashEmbedding::default().embed(&texts).await.unwrap()
+}
+
+#[tokio::test]
+async fn hash_vectors_are_deterministic_unit_vectors() {
+    let vectors = embed(&["Deploying the web server", "Deploying the web server"]).await;
+    assert_eq!(vectors[0], vectors[1]);
+    assert_eq!(vectors[0].len(), 256);
+    assert!((dot(&vectors[0], &vectors[0]) - 1.0).abs() < 1e-4);
+    assert_eq!(HashEmbedding::default().model(), "hash-256");
+}
+
+#[tokio::test]
+async fn hash_vectors_rank_shared_vocabulary_higher() {
+    let vectors = embed(&[
+        "how to deploy the server",
+        "Server deployment checklist and deploy steps",
+        "Banana bread recipe with walnuts",
+    ])
+    .await;
+    assert!(dot(&vectors[0], &vectors[1]) > dot(&vectors[0], &vectors[2]));
+}
+
+#[test]
+fn parse_vectors_accepts_openai_and_ollama_shapes() {
+    let openai = json!({"data": [
+        {"index": 1, "embedding": [0.5, 0.5]},
+        {"index": 0, "embedding": [1.0, 0.0]},
+    ]});
+    assert_eq!(
+        parse_vectors(&openai).unwrap(),
+        vec![vec![1.0, 0.0], vec![0.5, 0.5]]
+    );
+    let ollama = json!({"embeddings": [[0.25, 0.75]]});
+    assert_eq!(parse_vectors(&ollama).unwrap(), vec![vec![0.25, 0.75]]);
+    assert!(parse_vectors(&json!({"data": [{"embedding": ["x"]}]})).is_err());
+    assert!(parse_vectors(&json!({"error": "no model"})).is_err());
+}
+
+#[test]
+fn resource_text_joins_fields_without_front_matter() {
+    let text = resource_text(&note(
+        "---\ntags: [Travel]\nrating: 4\n---\nWe drove north.",
+    ));
+    assert_eq!(text, "Trip Log\nFirst day\ntravel\nWe drove north.");
+}
@@ -56,6 +56,7 @@ impl SiteContext {
                 None
             },
             social_card: None,
+            feed_links: String::new(),
         }
     }
 }
@@ -90,6 +91,7 @@ pub struct PageMeta {
     robots_content: &'static str,
     canonical_url: Option<String>,
     social_card: Option<SocialCardMeta>,
+    feed_links: String,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -111,6 +113,16 @@ impl PageMeta {
         self
     }
 
+    /// Attach `<link rel="alternate">` feed tags for the page's space.
+    pub fn with_feed_links(mut self, feed_links: String) -> Self {
+        self.feed_links = feed_links;
+        self
+    }
+
+    pub fn feed_links(&self) -> &str {
+        &self.feed_links
+    }
+
     pub fn head_tags(&self) -> String {
         let canonical = self.canonical_url.as_ref().map_or_else(String::new, |url| {
             format!(r#"<link rel="canonical" href="{}">"#, escape_html_attr(url))
@@ -0,0 +1,83 @@
+//! Admin audit log template
+
+use super::index::{admin_create_actions, list_rail, pager};
+use super::layout::{base, html_escape, render_time, shell_page};
+use super::sections::page_header;
+use crate::web::db::{AuditEntry, AuditPage};
+use crate::web::site::SiteContext;
+
+const ACTIONS_JS: &str = include_str!("resource_actions.js");
+
+pub fn audit_page(space_slug: &str, page: &AuditPage, limit: i64, site: &SiteContext) -> String {
+    let slug = html_escape(space_slug);
+    let rows = if page.events.is_empty() {
+        r#"<p class="surface-empty">No audit events yet.</p>"#.to_string()
+    } else {
+        format!(
+            r#"<table class="audit-table"><thead><tr><th>When</th><th>Event</th><th>Entity</th><th>Actor</th><th>Detail</th></tr></thead><tbody>{}</tbody></table>"#,
+            page.events
+                .iter()
+                .map(|event| audit_row(&slug, event))
+                .collect::<Vec<_>>()
+                .join("")
+        )
+    };
+    let content = format!(
+        r#"{}<section class="section-block audit-section">{rows}{}</section>"#,
+        page_header(
+            "Audit log",
+            Some(&format!(
+                r#"<a href="/{slug}/admin/trash" class="btn">Trash</a><a href="/api/users/{slug}/audit" class="btn">JSON feed</a>"#
+            )),
+            "audit-head",
+        ),
+        pager(
+            &format!("/{slug}/admin/audit"),
+            None,
+            page.next_cursor.as_deref(),
+            &[("limit", &limit.to_string())],
+        ),
+    );
+    base(
+        &site.page_meta(
+            "Audit log",
+            format!("Audit log for {}.", site.site_name),
+            false,
+            None,
+        ),
+        &shell_page(
+            "Admin",
+            &list_rail(
+                "admin",
+                &admin_create_actions(),
+                r#"<form method="POST" action="/logout"><button type="submit" class="btn">Logout</button></form>"#,
+                true,
+            ),
+            &content,
+            "audit-page",
+            &site.site_name,
+        ),
+        "",
+        &format!(r#"<script>{ACTIONS_JS}</script>"#),
+    )
+}
+
+fn audit_row(slug: &str, event: &AuditEntry) -> String {
+    let entity_id = html_escape(&event.entity_id);
+    let entity = if event.entity_type == "resource" {
+        format!(r#"<a href="/{slug}/{entity_id}">{entity_id}</a>"#)
+    } else {
+        format!("{} {entity_id}", html_escape(&event.entity_type))
+    };
+    let actor = match (&event.actor_name, event.actor_service_account_id) {
+        (Some(name), Some(_)) => format!("{} (service)", html_escape(name)),
+        (Some(name), None) => html_escape(name),
+        (None, _) => "operator".to_string(),
+    };
+    format!(
+        "<tr><td>{}</td><td><code>{}</code></td><td>{entity}</td><td>{actor}</td><td><code>{}</code></td></tr>",
+        render_time(&event.created_at),
+        html_escape(&event.event_type),
+        html_escape(&event.payload.to_string()),
+    )
+}
@@ -65,10 +65,11 @@ pub fn admin_page(
 fn stats_grid(stats: &ResourceStats) -> String {
     format!(
         r#"<section class="stats-grid">
-{}{}{}{}{}{}{}{}{}{}{}
+{}{}{}{}{}{}{}{}{}{}{}{}
 </section>"#,
         stat_card("Resources", stats.total),
         stat_card("Public", stats.public_count),
+        stat_card("Space", stats.space_count),
         stat_card("Private", stats.private_count),
         stat_card("Favorites", stats.favorite_count),
         stat_card("Updated this month", stats.updated_this_month),
@@ -93,17 +94,13 @@ fn settings_panel(settings: &AppSettings) -> String {
             r#"<div class="settings-summary-grid">
 <article class="surface settings-summary-card"><small>Home hero</small><strong>{hero_state}</strong></article>
 <article class="surface settings-summary-card"><small>Session timeout</small><strong>{}</strong></article>
-<article class="surface settings-summary-card"><small>New resources</small><strong>{}</strong></article>
+<article class="surface settings-summary-card"><small>New resources</small><strong>{} by default</strong></article>
 <article class="surface settings-summary-card"><small>Search page size</small><strong>{}</strong></article>
 <article class="surface settings-summary-card"><small>Home order</small><strong>{}</strong></article>
 </div>
 <a href="/admin/settings" class="btn btn-primary">Open settings</a>"#,
             session_timeout_label(settings.session_timeout_minutes),
-            if settings.default_new_resource_is_private {
-                "Private by default"
-            } else {
-                "Public by default"
-            },
+            settings.default_new_resource_visibility.label(),
             settings.search_results_per_page,
             home_order(settings),
         ),
@@ -1,7 +1,7 @@
 var editorState = {
     bodyField: null,
     aliasField: null,
-    publicToggle: null,
+    visibilitySelect: null,
     favoriteToggle: null,
     uploadButton: null,
     uploadInput: null,
@@ -18,10 +18,11 @@ var editorState = {
     lastSavedBody: '',
     lastSavedAlias: null,
     lastSavedFavorite: false,
-    lastSavedPrivate: null,
+    lastSavedVisibility: null,
     saveInFlight: false,
     savePromise: null,
     pendingSave: false,
+    conflict: false,
     composing: false,
     uploading: false,
     lastPreviewBody: null,
@@ -41,7 +42,7 @@ function initEditor() {
     editorState.lastSavedBody = editorState.bodyField.value;
     editorState.lastSavedAlias = currentAlias;
     editorState.lastSavedFavorite = isFavorite;
-    editorState.lastSavedPrivate = isPrivate;
+    editorState.lastSavedVisibility = currentVisibility;
     syncResourceChrome();
     syncPreviewMode();
     registerEditorCleanup();
@@ -51,7 +52,7 @@ function initEditor() {
 function cacheEditorNodes() {
     editorState.bodyField = document.getElementById('editor-body');
     editorState.aliasField = document.getElementById('alias-input');
-    editorState.publicToggle = document.getElementById('public-toggle');
+    editorState.visibilitySelect = document.getElementById('visibility-select');
     editorState.favoriteToggle = document.getElementById('favorite-toggle');
     editorState.uploadButton = document.getElementById('upload-media-trigger');
     editorState.uploadInput = document.getElementById('upload-media-input');
@@ -67,7 +68,7 @@ function bindEditorInputs() {
     editorState.bodyField.addEventListener('compositionstart', onCompositionStart);
     editorState.bodyField.addEventListener('compositionend', onCompositionEnd);
     editorState.aliasField.addEventListener('input', onAliasInput);
-    editorState.publicToggle.addEventListener('change', onPublicToggle);
+    editorState.visibilitySelect.addEventListener('change', onVisibilityChange);
     editorState.favoriteToggle.addEventListener('change', onFavoriteToggle);
 }
 
@@ -92,8 +93,8 @@ function onAliasInput() {
     queueSave();
 }
 
-function onPublicToggle() {
-    isPrivate = !editorState.publicToggle.checked;
+function onVisibilityChange() {
+    currentVisibility = editorState.visibilitySelect.value;
     syncResourceChrome();
     queueSave();
 }
@@ -119,7 +120,7 @@ function draftSnapshot() {
         body: currentBody(),
         alias: draftAliasValue(),
         isFavorite: isFavorite,
-        isPrivate: isPrivate,
+        visibility: currentVisibility,
         selection: currentSelection()
     };
 }
@@ -134,7 +135,7 @@ function registerEditorCleanup() {
             editorState.bodyField.removeEventListener('compositionend', onCompositionEnd);
         }
         if (editorState.aliasField) editorState.aliasField.removeEventListener('input', onAliasInput);
-        if (editorState.publicToggle) editorState.publicToggle.removeEventListener('change', onPublicToggle);
+        if (editorState.visibilitySelect) editorState.visibilitySelect.removeEventListener('change', onVisibilityChange);
         if (editorState.favoriteToggle) editorState.favoriteToggle.removeEventListener('change', onFavoriteToggle);
         if (typeof unbindPreviewEvents === 'function') unbindPreviewEvents();
         if (window.kjxlkj) delete window.kjxlkj.beforeNavigate;
@@ -155,3 +156,19 @@ async function beforeEditorNavigate() {
     }
     return flushPendingSave();
 }
+
+function trackSnapshot(response) {
+    var tag = (response.headers.get('ETag') || '').replace(/^W\//, '').replace(/"/g, '');
+    if (/^\d+$/.test(tag)) currentSnapshot = Number(tag);
+}
+
+function rejectConflict(payload) {
+    editorState.conflict = true;
+    clearTimeout(editorState.saveTimer);
+    throw new Error(conflictMessage(payload && payload.current_snapshot));
+}
+
+function conflictMessage(snapshot) {
+    var where = snapshot ? ' (snapshot ' + snapshot + ')' : '';
+    return 'Newer changes were saved elsewhere' + where + '. Copy your edits and reload.';
+}
@@ -1,6 +1,6 @@
 function queueSave(delay) {
     clearTimeout(editorState.saveTimer);
-    if (editorState.composing || !isDirty(currentBody(), draftAliasValue(), isFavorite, isPrivate)) return;
+    if (editorState.conflict || editorState.composing || !isDirty(currentBody(), draftAliasValue(), isFavorite, currentVisibility)) return;
     editorState.pendingSave = true;
     if (editorState.saveInFlight) return;
     editorState.saveTimer = setTimeout(function () {
@@ -8,17 +8,18 @@ function queueSave(delay) {
     }, typeof delay === 'number' ? delay : 500);
 }
 
-function isDirty(body, alias, favorite, nextPrivate) {
+function isDirty(body, alias, favorite, nextVisibility) {
     return body !== editorState.lastSavedBody ||
         alias !== editorState.lastSavedAlias ||
         favorite !== editorState.lastSavedFavorite ||
-        nextPrivate !== editorState.lastSavedPrivate;
+        nextVisibility !== editorState.lastSavedVisibility;
 }
 
 function saveNote() {
     if (!editorState.bodyField || typeof currentId === 'undefined') return Promise.resolve(null);
     if (editorState.saveInFlight) return editorState.savePromise || Promise.resolve(null);
-    if (editorState.composing || !isDirty(currentBody(), draftAliasValue(), isFavorite, isPrivate)) {
+    if (editorState.conflict) return Promise.reject(new Error(conflictMessage(currentSnapshot)));
+    if (editorState.composing || !isDirty(currentBody(), draftAliasValue(), isFavorite, currentVisibility)) {
         return Promise.resolve(null);
     }
     var request = draftSnapshot();
@@ -31,7 +32,8 @@ function saveNote() {
             body: request.body,
             alias: request.alias,
             is_favorite: request.isFavorite,
-            is_private: request.isPrivate
+            visibility: request.visibility,
+            base_snapshot: currentSnapshot
         })
     })
         .then(readSaveResponse)
@@ -47,8 +49,9 @@ function saveNote() {
         .finally(function () {
             editorState.saveInFlight = false;
             editorState.savePromise = null;
+            if (editorState.conflict) return;
             if (editorState.pendingSave || (!editorState.composing &&
-                isDirty(currentBody(), draftAliasValue(), isFavorite, isPrivate))) {
+                isDirty(currentBody(), draftAliasValue(), isFavorite, currentVisibility))) {
                 queueSave(0);
             }
         });
@@ -68,7 +71,7 @@ async function flushPendingSave() {
             continue;
         }
         if (editorState.composing) return false;
-        if (!isDirty(currentBody(), draftAliasValue(), isFavorite, isPrivate)) return true;
+        if (!isDirty(currentBody(), draftAliasValue(), isFavorite, currentVisibility)) return true;
         try {
             await saveNote();
         } catch {
@@ -78,7 +81,11 @@ async function flushPendingSave() {
 }
 
 function readSaveResponse(response) {
-    if (response.ok) return response.json();
+    if (response.ok) {
+        trackSnapshot(response);
+        return response.json();
+    }
+    if (response.status === 409) return response.json().then(rejectConflict, rejectConflict);
     return response.json()
         .then(
             function (payload) {
@@ -102,11 +109,11 @@ function applySavedResource(note, request, selection) {
     editorState.lastSavedBody = note.body;
     editorState.lastSavedAlias = currentAlias;
     editorState.lastSavedFavorite = !!note.is_favorite;
-    editorState.lastSavedPrivate = !!note.is_private;
+    editorState.lastSavedVisibility = note.visibility;
     var bodyStale = currentBody() !== request.body;
     var aliasStale = draftAliasValue() !== request.alias;
     var favoriteStale = isFavorite !== request.isFavorite;
-    var privateStale = isPrivate !== request.isPrivate;
+    var visibilityStale = currentVisibility !== request.visibility;
     if (!bodyStale && !editorState.composing && editorState.bodyField && editorState.bodyField.value !== note.body) {
         editorState.bodyField.value = note.body;
     }
@@ -115,9 +122,9 @@ function applySavedResource(note, request, selection) {
         isFavorite = !!note.is_favorite;
         if (editorState.favoriteToggle) editorState.favoriteToggle.checked = isFavorite;
     }
-    if (!privateStale) {
-        isPrivate = !!note.is_private;
-        if (editorState.publicToggle) editorState.publicToggle.checked = !isPrivate;
+    if (!visibilityStale) {
+        currentVisibility = note.visibility;
+        if (editorState.visibilitySelect) editorState.visibilitySelect.value = currentVisibility;
     }
     syncResourceChrome();
     if (!bodyStale) restoreSelection(selection || request.selection);
@@ -125,7 +132,7 @@ function applySavedResource(note, request, selection) {
 
 function syncResourceChrome() {
     var title = deriveTitle(currentBody());
-    var visibility = isPrivate ? 'Private' : 'Public';
+    var visibility = currentVisibility.charAt(0).toUpperCase() + currentVisibility.slice(1);
     updateLiveText('[data-live-title]', title, 'renderedTitle');
     updateLiveText('[data-live-visibility]', visibility, 'renderedVisibility');
     updateLiveText('[data-live-alias]', currentAlias || 'None', 'renderedAlias');
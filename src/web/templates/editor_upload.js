@@ -46,14 +46,14 @@ async function uploadSelectedMedia(files) {
     var request = draftSnapshot();
     var body = request.body;
     var formData = new FormData();
-    for (const file of files) formData.append('file', file);
-    formData.append('body', body);
-    formData.append('is_favorite', request.isFavorite ? 'true' : 'false');
-    formData.append('is_private', request.isPrivate ? 'true' : 'false');
-    formData.append('insert_start', String(utf8Offset(body, selection.start)));
-    formData.append('insert_end', String(utf8Offset(body, selection.end)));
-    if (request.alias) formData.append('alias', request.alias);
     try {
+        await appendUploadParts(formData, files);
+        formData.append('body', body);
+        formData.append('is_favorite', request.isFavorite ? 'true' : 'false');
+        formData.append('visibility', request.visibility);
+        formData.append('insert_start', String(utf8Offset(body, selection.start)));
+        formData.append('insert_end', String(utf8Offset(body, selection.end)));
+        if (request.alias) formData.append('alias', request.alias);
         var response = await fetch('/resources/' + currentId + '/media-attachments', {
             method: 'POST',
             body: formData
@@ -146,7 +146,7 @@ function setUploadBusy(busy) {
     if (editorState.uploadButton) editorState.uploadButton.disabled = busy;
     if (editorState.bodyField) editorState.bodyField.readOnly = busy;
     if (editorState.aliasField) editorState.aliasField.readOnly = busy;
-    if (editorState.publicToggle) editorState.publicToggle.disabled = busy;
+    if (editorState.visibilitySelect) editorState.visibilitySelect.disabled = busy;
     if (editorState.favoriteToggle) editorState.favoriteToggle.disabled = busy;
 }
 
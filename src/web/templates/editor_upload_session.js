@@ -0,0 +1,98 @@
+var UPLOAD_SESSION_MIN_BYTES = 16 * 1024 * 1024;
+var UPLOAD_CHUNK_BYTES = 8 * 1024 * 1024;
+var UPLOAD_HASH_MAX_BYTES = 256 * 1024 * 1024;
+var UPLOAD_CHUNK_RETRIES = 5;
+
+async function appendUploadParts(formData, files) {
+    for (const file of files) {
+        if (file.size < UPLOAD_SESSION_MIN_BYTES) {
+            formData.append('file', file);
+        } else {
+            formData.append('upload', await uploadInChunks(file));
+        }
+    }
+}
+
+async function uploadInChunks(file) {
+    setUploadStatus('Preparing ' + uploadLabel(file) + '...', '');
+    var response = await fetch('/uploads', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({
+            filename: file.name || 'upload.bin',
+            content_type: file.type || null,
+            byte_size: file.size,
+            sha256_hex: await fileSha256(file)
+        })
+    });
+    var session = await readUploadResponse(response);
+    if (!response.ok) throw new Error(session.message || 'Media upload failed.');
+    try {
+        await sendUploadChunks(file, session.id);
+    } catch (error) {
+        fetch('/uploads/' + session.id, { method: 'DELETE' }).catch(function () {});
+        throw error;
+    }
+    return session.id;
+}
+
+async function sendUploadChunks(file, id) {
+    var offset = 0;
+    var failures = 0;
+    while (offset < file.size) {
+        setUploadStatus(
+            'Uploading ' + uploadLabel(file) + ': ' + Math.floor(offset * 100 / file.size) + '%',
+            ''
+        );
+        var result = await putUploadChunk(id, file, offset);
+        if (result.offset !== null && result.offset > offset) {
+            offset = result.offset;
+            failures = 0;
+            continue;
+        }
+        failures += 1;
+        if (result.fatal || failures > UPLOAD_CHUNK_RETRIES) {
+            throw new Error(result.message || 'Media upload failed.');
+        }
+        await new Promise(function (resolve) { setTimeout(resolve, 1000 * failures); });
+        offset = await uploadSessionOffset(id, offset);
+    }
+}
+
+async function putUploadChunk(id, file, offset) {
+    try {
+        var response = await fetch('/uploads/' + id + '?offset=' + offset, {
+            method: 'PUT',
+            body: file.slice(offset, offset + UPLOAD_CHUNK_BYTES)
+        });
+        var payload = await readUploadResponse(response);
+        if ((response.ok || response.status === 409) && typeof payload.offset === 'number') {
+            return { offset: payload.offset };
+        }
+        return { offset: null, fatal: response.status < 500, message: payload.message };
+    } catch (error) {
+        return { offset: null, fatal: false, message: error.message };
+    }
+}
+
+async function uploadSessionOffset(id, fallback) {
+    try {
+        var response = await fetch('/uploads/' + id);
+        var payload = await readUploadResponse(response);
+        return response.ok && typeof payload.offset === 'number' ? payload.offset : fallback;
+    } catch (_) {
+        return fallback;
+    }
+}
+
+async function fileSha256(file) {
+    if (file.size > UPLOAD_HASH_MAX_BYTES || !window.crypto?.subtle) return null;
+    var digest = await window.crypto.subtle.digest('SHA-256', await file.arrayBuffer());
+    return Array.from(new Uint8Array(digest))
+        .map(function (byte) { return byte.toString(16).padStart(2, '0'); })
+        .join('');
+}
+
+function uploadLabel(file) {
+    return file.name || 'media';
+}
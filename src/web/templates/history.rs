@@ -10,9 +10,12 @@ use super::resource_media::snapshot_media_block;
 use super::resource_shell::resource_rail;
 use super::resource_words::{live_label, open_live_label};
 use super::sections::page_header;
+use super::snapshot_actions::{compare_link, restore_button};
 use crate::web::db::{Resource, ResourceKind, ResourceSnapshot};
 use crate::web::site::SiteContext;
 
+const RESTORE_JS: &str = include_str!("snapshot_restore.js");
+
 pub struct HistoryPage<'a> {
     pub history: &'a [HistoryLink],
     pub previous_cursor: Option<&'a str>,
@@ -41,7 +44,13 @@ pub fn history_page(
         page_header(
             &format!("History: {}", chrome.title),
             Some(&format!(
-                r#"<a href="{}" class="btn">{}</a>"#,
+                r#"{}<a href="{}" class="btn">{}</a>"#,
+                compare_link(
+                    chrome,
+                    chrome.snapshot_number - 1,
+                    chrome.snapshot_number,
+                    "Compare latest changes"
+                ),
                 chrome.current_href,
                 open_live_label(chrome.kind),
             )),
@@ -74,14 +83,23 @@ pub fn snapshot_page(
     is_admin: bool,
     site: &SiteContext,
 ) -> String {
+    let number = snapshot.snapshot_number;
     let history_link = if is_admin {
         format!(
-            r#"<a href="{}" class="btn">Back to history</a>"#,
+            r#"{}{}<a href="{}" class="btn">Back to history</a>"#,
+            compare_link(chrome, number - 1, number, "Compare with previous"),
+            compare_link(
+                chrome,
+                number,
+                chrome.snapshot_number,
+                "Compare with latest"
+            ),
             chrome.history_href
         )
     } else {
         String::new()
     };
+    let restore_button = restore_button(chrome, snapshot, is_admin);
     let content = format!(
         r#"<header class="page-head">
 <div class="page-title-stack">
@@ -91,6 +109,7 @@ pub fn snapshot_page(
 <div class="page-actions">
 <span class="status-pill">{}</span>
 {}
+{}
 <a href="{}" class="btn">{}</a>
 </div>
 </header>
@@ -99,12 +118,9 @@ pub fn snapshot_page(
         snapshot.snapshot_number,
         html_escape(&snapshot.title),
         super::render_time(&snapshot.created_at),
-        if snapshot.is_private {
-            "Private"
-        } else {
-            "Public"
-        },
+        snapshot.visibility.label(),
         history_link,
+        restore_button,
         chrome.current_href,
         open_live_label(chrome.kind),
         if snapshot.kind == ResourceKind::Media {
@@ -150,7 +166,7 @@ fn shell(
             &site.site_name,
         ),
         "",
-        "",
+        &format!("<script>{RESTORE_JS}</script>"),
     )
 }
 
@@ -1,8 +1,10 @@
 use super::{
-    history::{history_page, HistoryPage},
+    history::{history_page, snapshot_page, HistoryPage},
+    snapshot_diff::{snapshot_diff_page, SnapshotDiffView},
     HistoryLink, ResourceChrome,
 };
-use crate::web::db::{Resource, ResourceKind};
+use crate::core::text_diff::{diff_bodies, DEFAULT_CONTEXT};
+use crate::web::db::{Resource, ResourceKind, ResourceSnapshot, Visibility};
 use crate::web::site::SiteContext;
 use chrono::Utc;
 
@@ -25,10 +27,11 @@ fn sample_resource() -> Resource {
         height: None,
         duration_ms: None,
         media_variants: None,
+        media_details: None,
         owner_note_id: None,
         is_favorite: true,
         favorite_position: Some(1),
-        is_private: false,
+        visibility: Visibility::Public,
         view_count_total: 3,
         last_viewed_at: None,
         created_at: Utc::now(),
@@ -51,6 +54,9 @@ fn sample_chrome() -> ResourceChrome {
         previous: None,
         next: None,
         history_href: "/demo-note/history".to_string(),
+        snapshot_number: 1,
+        backlinks: Vec::new(),
+        broken_links: Vec::new(),
     }
 }
 
@@ -95,3 +101,80 @@ fn history_page_lists_live_note_and_saved_snapshots() {
     assert!(html.contains("<title>History: Demo | Launchpad</title>"));
     assert!(html.contains("content=\"noindex,nofollow\""));
 }
+
+fn sample_snapshot(snapshot_number: i32) -> ResourceSnapshot {
+    let resource = sample_resource();
+    ResourceSnapshot {
+        id: "zyxwvutsrqponmlkjihgfedcba".to_string(),
+        space_slug: resource.space_slug,
+        kind: resource.kind,
+        snapshot_number,
+        alias: resource.alias,
+        title: resource.title,
+        summary: resource.summary,
+        body: resource.body,
+        media_family: None,
+        file_key: None,
+        content_type: None,
+        byte_size: None,
+        sha256_hex: None,
+        original_filename: None,
+        width: None,
+        height: None,
+        duration_ms: None,
+        media_variants: None,
+        media_details: None,
+        owner_note_id: None,
+        visibility: Visibility::Public,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn snapshot_page_offers_restore_only_for_older_snapshots_to_writers() {
+    let chrome = ResourceChrome {
+        snapshot_number: 3,
+        ..sample_chrome()
+    };
+    let restore = "/api/users/alice/resources/abcdefghijklmnopqrstuvwx26/history/2/restore";
+    let older = snapshot_page(&chrome, &sample_snapshot(2), "", true, &sample_site());
+    assert!(older.contains(restore));
+    assert!(older.contains(r#"data-snapshot-base="3""#));
+    let reader = snapshot_page(&chrome, &sample_snapshot(2), "", false, &sample_site());
+    assert!(!reader.contains("data-snapshot-restore="));
+    let latest = snapshot_page(&chrome, &sample_snapshot(3), "", true, &sample_site());
+    assert!(!latest.contains("data-snapshot-restore="));
+}
+
+#[test]
+fn snapshot_diff_page_marks_changed_words_in_both_views() {
+    let (from, to) = (sample_snapshot(1), sample_snapshot(2));
+    let to = ResourceSnapshot {
+        body: "# Demo\n\nBody changed".to_string(),
+        visibility: Visibility::Private,
+        ..to
+    };
+    let diff = diff_bodies(&from.body, &to.body, ("1", "2"), DEFAULT_CONTEXT);
+    let render = |split| {
+        snapshot_diff_page(
+            &sample_chrome(),
+            &SnapshotDiffView {
+                from: &from,
+                to: &to,
+                diff: &diff,
+                split,
+            },
+            true,
+            &sample_site(),
+        )
+    };
+    let unified = render(false);
+    assert!(unified.contains("+1 / &minus;1 lines"));
+    assert!(unified.contains("changed</mark>"));
+    assert!(unified.contains(r#"<code>Public</code> &rarr; <code>Private</code>"#));
+    assert!(unified.contains(r#"href="/demo-note/history/1/diff/2?view=split""#));
+    let split = render(true);
+    assert!(split.contains(r#"<td class="diff-delete">"#));
+    assert!(split.contains(r#"<td class="diff-insert">"#));
+    assert!(split.contains("changed</mark>"));
+}
@@ -8,6 +8,7 @@ use super::list_sections::{
 use super::model::IndexItem;
 use super::popular_sections::home_popular_section;
 use crate::web::db::{AppSettings, PopularWindow};
+use crate::web::feed::alternate_links;
 use crate::web::site::SiteContext;
 
 const ACTIONS_JS: &str = include_str!("resource_actions.js");
@@ -23,6 +24,8 @@ pub struct HomeView<'a> {
     pub is_admin: bool,
     pub guest_login_href: &'a str,
     pub site: &'a SiteContext,
+    /// Space whose feeds the page advertises.
+    pub feed_space: &'a str,
 }
 
 pub fn home_page(view: HomeView<'_>) -> String {
@@ -46,12 +49,15 @@ pub fn home_page(view: HomeView<'_>) -> String {
     );
     let admin_actions = view.is_admin.then(admin_create_actions);
     base(
-        &view.site.page_meta(
-            "Home",
-            view.site.site_description.clone(),
-            !view.is_admin,
-            Some("/"),
-        ),
+        &view
+            .site
+            .page_meta(
+                "Home",
+                view.site.site_description.clone(),
+                !view.is_admin,
+                Some("/"),
+            )
+            .with_feed_links(alternate_links(view.site, view.feed_space, None)),
         &shell_page(
             if view.is_admin { "Admin" } else { "Guest" },
             &list_rail(
@@ -10,7 +10,7 @@ use crate::web::db::MediaFamily;
 pub(crate) fn note_row(note: &IndexItem) -> String {
     let has_visual_cover = matches!(
         note.media_family,
-        Some(MediaFamily::Image | MediaFamily::Video)
+        Some(MediaFamily::Image | MediaFamily::Video | MediaFamily::Audio)
     );
     linked_card(
         &note.href,
@@ -156,7 +156,7 @@ fn card_cover(note: &IndexItem) -> String {
             r#"<div class="card-cover"><img class="card-cover-media" src="{}" alt=""></div>"#,
             html_escape(href),
         ),
-        (Some(MediaFamily::Video), Some(href)) => format!(
+        (Some(MediaFamily::Video | MediaFamily::Audio), Some(href)) => format!(
             r#"<div class="card-cover"><img class="card-cover-media" src="{}" alt=""></div>"#,
             html_escape(href),
         ),
@@ -18,7 +18,7 @@ pub fn base(meta: &PageMeta, content: &str, extra_head: &str, extra_script: &str
 <script>document.documentElement.classList.add('js');</script>
 <title>{}</title>
 <link rel="icon" href="/assets/site-icon" sizes="any">
-{}
+{}{}
 {extra_head}
 <style>{}</style>
 </head>
@@ -26,6 +26,7 @@ pub fn base(meta: &PageMeta, content: &str, extra_head: &str, extra_script: &str
 </html>"#,
         html_escape(&meta.full_title()),
         meta.head_tags(),
+        meta.feed_links(),
         stylesheet(),
     )
 }
@@ -1,5 +1,6 @@
 //! HTML templates
 
+mod audit;
 mod auth;
 mod card_frame;
 mod dashboard;
@@ -23,7 +24,10 @@ mod popular_sections_tests;
 mod resource;
 mod resource_editor;
 mod resource_focus;
+mod resource_links;
 mod resource_media;
+#[cfg(test)]
+mod resource_media_tests;
 mod resource_shell;
 #[cfg(test)]
 mod resource_shell_tests;
@@ -39,11 +43,20 @@ mod settings_core;
 mod settings_home;
 mod settings_icon;
 mod settings_live;
+mod settings_members;
 mod settings_page;
 mod settings_panel;
 mod settings_security;
+mod settings_tokens;
+mod snapshot_actions;
+mod snapshot_diff;
 mod style_bundle;
+mod tags;
+#[cfg(test)]
+mod tags_tests;
+mod trash;
 
+pub use audit::audit_page;
 pub use auth::{login_page, password_reset_page, setup_page};
 pub use dashboard::admin_page;
 pub use history::{history_page, snapshot_page, HistoryPage};
@@ -54,4 +67,7 @@ pub use model::{HistoryLink, IndexItem, IndexMetric, NavLink, ResourceAnalytics,
 pub use popular_sections::{admin_popular_section, home_popular_section};
 pub use resource::resource_page;
 pub use search::{search_page, SearchView};
-pub use settings_page::settings_page;
+pub use settings_page::{settings_page, SpaceSettings};
+pub use snapshot_diff::{snapshot_diff_page, SnapshotDiffView};
+pub use tags::tags_page;
+pub use trash::trash_page;
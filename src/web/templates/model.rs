@@ -37,6 +37,11 @@ pub struct ResourceChrome {
     pub previous: Option<NavLink>,
     pub next: Option<NavLink>,
     pub history_href: String,
+    pub snapshot_number: i32,
+    /// Visible live resources whose bodies link here.
+    pub backlinks: Vec<NavLink>,
+    /// Local hrefs in the body that no live resource answers.
+    pub broken_links: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -5,9 +5,11 @@ use super::layout::{base, shell_page};
 use super::model::{ResourceAnalytics, ResourceChrome};
 use super::resource_editor::{editor_script, editor_surface};
 use super::resource_focus::{analytics_block, live_resource_nav_strip};
+use super::resource_links::{resource_link_sections, resource_tag_section};
 use super::resource_media::{admin_media_panel, current_media_block};
 use super::resource_shell::live_resource_rail;
 use crate::web::db::{Resource, ResourceKind};
+use crate::web::feed::alternate_links;
 use crate::web::site::SiteContext;
 use crate::web::{view, view_media};
 
@@ -27,28 +29,31 @@ pub fn resource_page(
 <div class="card-badges page-badges">{}</div>
 </div>
 </header>
-{}{}{}"#,
+{}{}{}{}{}"#,
         chrome.created_at,
         chrome.updated_at,
         resource_badges(resource, chrome),
         live_resource_nav_strip(chrome, is_admin),
         analytics_block(analytics),
         resource_body(resource, chrome, body_html, is_admin),
+        resource_tag_section(resource),
+        resource_link_sections(chrome, is_admin),
     );
     let page_meta = site
         .page_meta(
             &chrome.title,
             resource.summary.clone(),
-            !is_admin && !resource.is_private,
-            (!is_admin && !resource.is_private).then_some(chrome.current_href.as_str()),
+            !is_admin && resource.visibility.is_public(),
+            (!is_admin && resource.visibility.is_public()).then_some(chrome.current_href.as_str()),
         )
         .with_social_card(
-            (!is_admin && !resource.is_private)
+            (!is_admin && resource.visibility.is_public())
                 .then(|| {
                     view_media::social_card_href(resource).and_then(|href| site.absolute_url(&href))
                 })
                 .flatten(),
-        );
+        )
+        .with_feed_links(alternate_links(site, &resource.space_slug, None));
     base(
         &page_meta,
         &shell_page(
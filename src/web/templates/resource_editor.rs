@@ -1,11 +1,12 @@
 use super::layout::html_escape;
 use super::model::ResourceChrome;
-use crate::web::db::{Resource, ResourceKind};
+use crate::web::db::{Resource, ResourceKind, Visibility};
 
 const EDITOR_CORE_JS: &str = include_str!("editor.js");
 const EDITOR_SYNC_JS: &str = include_str!("editor_sync.js");
 const EDITOR_UI_JS: &str = include_str!("editor_ui.js");
 const EDITOR_UPLOAD_JS: &str = include_str!("editor_upload.js");
+const EDITOR_UPLOAD_SESSION_JS: &str = include_str!("editor_upload_session.js");
 const NOTE_ACTIONS_JS: &str = include_str!("resource_actions.js");
 
 pub fn editor_script(
@@ -24,7 +25,9 @@ var currentAlias = {};
 var currentHref = {};
 var currentSiteName = {};
 var isFavorite = {};
-var isPrivate = {};
+var currentVisibility = {};
+var currentSnapshot = {};
+{}
 {}
 {}
 {}
@@ -37,7 +40,8 @@ initEditor();
         serde_json::to_string(&chrome.current_href).unwrap(),
         serde_json::to_string(site_name).unwrap(),
         resource.is_favorite,
-        resource.is_private,
+        serde_json::to_string(&resource.visibility).unwrap(),
+        chrome.snapshot_number,
         NOTE_ACTIONS_JS,
         EDITOR_UI_JS,
         EDITOR_CORE_JS,
@@ -47,6 +51,11 @@ initEditor();
         } else {
             ""
         },
+        if resource.kind == ResourceKind::Note {
+            EDITOR_UPLOAD_SESSION_JS
+        } else {
+            ""
+        },
     )
 }
 
@@ -67,7 +76,7 @@ pub fn editor_surface(resource: &Resource, chrome: &ResourceChrome) -> String {
 <button type="button" id="preview-toggle" class="btn" aria-expanded="false" onclick="togglePreview()">Show preview</button>
 {upload_controls}
 <label class="check-row" for="favorite-toggle"><input type="checkbox" id="favorite-toggle" {favorite_checked}><span>Favorite</span></label>
-<label class="check-row" for="public-toggle"><input type="checkbox" id="public-toggle" {public_checked}><span>Public</span></label>
+<label class="check-row" for="visibility-select"><span>Visibility</span><select id="visibility-select">{visibility_options}</select></label>
 </div>
 <div class="editor-statuses">
 <span id="upload-media-status" class="editor-status" hidden aria-live="polite"></span>
@@ -95,6 +104,24 @@ pub fn editor_surface(resource: &Resource, chrome: &ResourceChrome) -> String {
 </section>"#,
         upload_controls = upload_controls,
         favorite_checked = if chrome.is_favorite { "checked" } else { "" },
-        public_checked = if resource.is_private { "" } else { "checked" },
+        visibility_options = visibility_options(resource.visibility),
     )
 }
+
+pub(super) fn visibility_options(current: Visibility) -> String {
+    Visibility::ALL
+        .into_iter()
+        .map(|visibility| {
+            format!(
+                r#"<option value="{}"{}>{}</option>"#,
+                visibility.as_str(),
+                if visibility == current {
+                    " selected"
+                } else {
+                    ""
+                },
+                visibility.label(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,59 @@
+//! Tag, backlink, and broken link sections for live resource pages
+
+use super::layout::html_escape;
+use super::model::ResourceChrome;
+use crate::core::parse_front_matter;
+use crate::web::db::Resource;
+use url::form_urlencoded::byte_serialize;
+
+/// Front matter tags, each linking to the space search filtered by it.
+pub fn resource_tag_section(resource: &Resource) -> String {
+    let Some(front) = parse_front_matter(&resource.body).filter(|front| !front.tags.is_empty())
+    else {
+        return String::new();
+    };
+    let slug = html_escape(&resource.space_slug);
+    format!(
+        r#"<nav class="tag-list resource-tags" aria-label="Tags">{}</nav>"#,
+        front
+            .tags
+            .iter()
+            .map(|tag| format!(
+                r#"<a class="btn" href="/{slug}/search?tag={}">#{}</a>"#,
+                byte_serialize(tag.as_bytes()).collect::<String>(),
+                html_escape(tag),
+            ))
+            .collect::<String>()
+    )
+}
+
+/// "Linked from" for every reader, plus broken local links for writers.
+pub fn resource_link_sections(chrome: &ResourceChrome, is_admin: bool) -> String {
+    let mut sections = String::new();
+    if !chrome.backlinks.is_empty() {
+        sections.push_str(&format!(
+            r#"<section class="surface resource-links"><h2>Linked from</h2><ul>{}</ul></section>"#,
+            chrome
+                .backlinks
+                .iter()
+                .map(|link| format!(
+                    r#"<li><a href="{}">{}</a><small>{}</small></li>"#,
+                    html_escape(&link.href),
+                    html_escape(&link.title),
+                    html_escape(&link.summary),
+                ))
+                .collect::<String>()
+        ));
+    }
+    if is_admin && !chrome.broken_links.is_empty() {
+        sections.push_str(&format!(
+            r#"<section class="surface resource-links resource-links-broken" data-broken-links><h2>Broken links</h2><p>These local links do not reach a live resource.</p><ul>{}</ul></section>"#,
+            chrome
+                .broken_links
+                .iter()
+                .map(|href| format!("<li><code>{}</code></li>", html_escape(href)))
+                .collect::<String>()
+        ));
+    }
+    sections
+}
@@ -1,7 +1,7 @@
 use super::layout::html_escape;
 use crate::web::db::{MediaFamily, Resource, ResourceSnapshot};
 use crate::web::view;
-use crate::web::view_media;
+use crate::web::view_media::{self, VideoSource};
 
 pub fn current_media_block(resource: &Resource) -> String {
     media_surface(
@@ -14,7 +14,9 @@ pub fn current_media_block(resource: &Resource) -> String {
             content_type: resource.content_type.as_deref(),
             byte_size: resource.byte_size,
             poster_href: view_media::poster_href(resource).as_deref(),
+            video_sources: view_media::video_sources(resource),
             title: &resource.title,
+            size: resource.width.zip(resource.height),
         },
     )
 }
@@ -56,7 +58,9 @@ pub fn snapshot_media_block(snapshot: &ResourceSnapshot) -> String {
             content_type: snapshot.content_type.as_deref(),
             byte_size: snapshot.byte_size,
             poster_href: view_media::snapshot_poster_href(snapshot).as_deref(),
+            video_sources: view_media::snapshot_video_sources(snapshot),
             title: &snapshot.title,
+            size: snapshot.width.zip(snapshot.height),
         },
     )
 }
@@ -69,7 +73,10 @@ struct MediaSurface<'a> {
     content_type: Option<&'a str>,
     byte_size: Option<i64>,
     poster_href: Option<&'a str>,
+    video_sources: Vec<VideoSource>,
     title: &'a str,
+    /// Stored display dimensions used to reserve layout space.
+    size: Option<(i32, i32)>,
 }
 
 fn media_surface(label: Option<&str>, surface: MediaSurface<'_>) -> String {
@@ -88,16 +95,29 @@ fn media_surface(label: Option<&str>, surface: MediaSurface<'_>) -> String {
 fn media_markup(surface: &MediaSurface<'_>) -> String {
     match surface.media_family.unwrap_or(MediaFamily::Image) {
         MediaFamily::Image => format!(
-            r#"<img src="{}" alt="{}" fetchpriority="high" style="width:100%;height:auto;display:block;">"#,
+            r#"<img src="{}" alt="{}"{} fetchpriority="high" style="width:100%;height:auto;display:block;">"#,
             html_escape(surface.href),
             html_escape(surface.title),
+            size_attributes(surface.size),
         ),
         MediaFamily::Video => format!(
-            r#"<video controls preload="metadata"{} src="{}" style="width:100%;height:auto;display:block;"></video>"#,
+            r#"<video controls preload="metadata"{}{} style="width:100%;height:auto;display:block;">{}</video>"#,
             surface
                 .poster_href
                 .map(|href| format!(r#" poster="{}""#, html_escape(href)))
                 .unwrap_or_default(),
+            size_attributes(surface.size),
+            source_elements(&surface.video_sources),
+        ),
+        MediaFamily::Audio => format!(
+            r#"<div class="audio-player">{}<audio controls preload="metadata" src="{}"></audio></div>"#,
+            surface
+                .poster_href
+                .map(|href| format!(
+                    r#"<img class="audio-waveform" src="{}" alt="">"#,
+                    html_escape(href)
+                ))
+                .unwrap_or_default(),
             html_escape(surface.href),
         ),
         MediaFamily::File => format!(
@@ -113,6 +133,28 @@ fn media_markup(surface: &MediaSurface<'_>) -> String {
     }
 }
 
+fn source_elements(sources: &[VideoSource]) -> String {
+    sources
+        .iter()
+        .map(|source| {
+            format!(
+                r#"<source src="{}"{}>"#,
+                html_escape(&source.href),
+                source
+                    .content_type
+                    .as_deref()
+                    .map(|value| format!(r#" type="{}""#, html_escape(value)))
+                    .unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn size_attributes(size: Option<(i32, i32)>) -> String {
+    size.map(|(width, height)| format!(r#" width="{width}" height="{height}""#))
+        .unwrap_or_default()
+}
+
 fn file_summary(content_type: Option<&str>, byte_size: Option<i64>) -> String {
     format!(
         "{} · {}",
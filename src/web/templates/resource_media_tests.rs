@@ -0,0 +1,186 @@
+use super::resource_tests::{sample_chrome, sample_site};
+use super::{resource::resource_page, ResourceAnalytics, ResourceChrome};
+use crate::core::render_markdown;
+use crate::web::db::{MediaFamily, Resource, ResourceKind, Visibility};
+use chrono::Utc;
+
+fn sample_media_resource() -> Resource {
+    Resource {
+        id: "bcdefghijklmnopqrstuvwxy27".to_string(),
+        space_slug: "alice".to_string(),
+        kind: ResourceKind::Media,
+        alias: Some("demo-image".to_string()),
+        title: "Demo file".to_string(),
+        summary: "File body".to_string(),
+        body: "# Demo file\n\nBody".to_string(),
+        media_family: Some(MediaFamily::File),
+        file_key: Some("media/demo/original.heic".to_string()),
+        content_type: Some("image/heic".to_string()),
+        byte_size: Some(1234),
+        sha256_hex: Some("abc".to_string()),
+        original_filename: Some("demo.heic".to_string()),
+        width: None,
+        height: None,
+        duration_ms: None,
+        media_variants: None,
+        media_details: None,
+        owner_note_id: None,
+        is_favorite: false,
+        favorite_position: None,
+        visibility: Visibility::Public,
+        view_count_total: 0,
+        last_viewed_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn guest_media_page_exposes_original_download_and_display_route() {
+    let html = resource_page(
+        &sample_media_resource(),
+        &ResourceChrome {
+            current_href: "/demo-image".to_string(),
+            history_href: "/demo-image/history".to_string(),
+            kind: ResourceKind::Media,
+            ..sample_chrome()
+        },
+        None,
+        &render_markdown(&sample_media_resource().body),
+        false,
+        &sample_site(),
+    );
+    assert!(html.contains("Download original"));
+    assert!(html.contains("href=\"/alice/demo-image/file\""));
+    assert!(html.contains("download=\"demo.heic\""));
+    assert!(html.contains("Open raw file"));
+    assert!(html.contains(r#"class="resource-nav-strip resource-nav-strip-dual""#));
+    assert!(!html.contains(r#"class="summary-card current-resource-card"#));
+    assert!(!html.contains("data-history-link"));
+    assert!(!html.contains("variant=display"));
+}
+
+#[test]
+fn admin_media_page_uses_live_resource_shell() {
+    let html = resource_page(
+        &sample_media_resource(),
+        &ResourceChrome {
+            current_href: "/demo-image".to_string(),
+            history_href: "/demo-image/history".to_string(),
+            kind: ResourceKind::Media,
+            ..sample_chrome()
+        },
+        Some(&ResourceAnalytics {
+            total: 5,
+            views_1d: 1,
+            views_7d: 2,
+            views_30d: 3,
+            views_90d: 4,
+            last_viewed_at: Some("2026-03-26 08:35 UTC".to_string()),
+        }),
+        &render_markdown(&sample_media_resource().body),
+        true,
+        &sample_site(),
+    );
+    assert!(html.contains("resource-nav-strip"));
+    assert!(html.contains("File URL"));
+    assert!(html.contains("File metadata"));
+    assert!(html.contains("Delete media"));
+    assert!(html.contains("Download original"));
+    assert!(html.contains("id=\"editor-body\""));
+    assert!(!html.contains(">Upload media<"));
+    assert!(!html.contains(r#"class="summary-card current-resource-card"#));
+}
+
+#[test]
+fn audio_media_page_renders_player_with_waveform() {
+    let waveform = crate::media::MediaVariant {
+        key: "media/demo/variants/poster.webp".to_string(),
+        content_type: "image/webp".to_string(),
+        byte_size: 10,
+        width: 1280,
+        height: 320,
+    };
+    let resource = Resource {
+        media_family: Some(MediaFamily::Audio),
+        content_type: Some("audio/mpeg".to_string()),
+        original_filename: Some("demo.mp3".to_string()),
+        duration_ms: Some(61_000),
+        media_variants: Some(crate::media::MediaVariants {
+            poster: Some(waveform),
+            ..Default::default()
+        }),
+        ..sample_media_resource()
+    };
+    let html = resource_page(
+        &resource,
+        &ResourceChrome {
+            kind: ResourceKind::Media,
+            ..sample_chrome()
+        },
+        None,
+        &render_markdown(&resource.body),
+        false,
+        &sample_site(),
+    );
+    assert!(html.contains(r#"<audio controls preload="metadata" src="/alice/demo-image/file">"#));
+    assert!(html.contains(r#"class="audio-waveform" src="/alice/demo-image/file?variant=poster""#));
+    assert!(!html.contains("Open raw file"));
+}
+
+#[test]
+fn image_media_page_reserves_stored_dimensions() {
+    let resource = Resource {
+        media_family: Some(MediaFamily::Image),
+        content_type: Some("image/jpeg".to_string()),
+        width: Some(1080),
+        height: Some(1920),
+        ..sample_media_resource()
+    };
+    let html = resource_page(
+        &resource,
+        &ResourceChrome {
+            kind: ResourceKind::Media,
+            ..sample_chrome()
+        },
+        None,
+        &render_markdown(&resource.body),
+        false,
+        &sample_site(),
+    );
+    assert!(html.contains(r#"alt="Demo file" width="1080" height="1920" fetchpriority="high""#));
+}
+
+#[test]
+fn video_media_page_prefers_transcoded_sources() {
+    let hls = crate::media::HlsFile {
+        name: "master.m3u8".to_string(),
+        key: "media/demo/hls/master.m3u8".to_string(),
+        content_type: "application/vnd.apple.mpegurl".to_string(),
+        byte_size: 10,
+    };
+    let resource = Resource {
+        media_family: Some(MediaFamily::Video),
+        content_type: Some("video/x-matroska".to_string()),
+        media_variants: Some(crate::media::MediaVariants {
+            hls: vec![hls],
+            ..Default::default()
+        }),
+        ..sample_media_resource()
+    };
+    let html = resource_page(
+        &resource,
+        &ResourceChrome {
+            kind: ResourceKind::Media,
+            ..sample_chrome()
+        },
+        None,
+        &render_markdown(&resource.body),
+        false,
+        &sample_site(),
+    );
+    assert!(html.contains(
+        r#"<source src="/alice/demo-image/file/hls/master.m3u8" type="application/vnd.apple.mpegurl"><source src="/alice/demo-image/file"></video>"#
+    ));
+    assert!(!html.contains("variant=mp4"));
+}
@@ -16,6 +16,9 @@ fn chrome(previous: Option<NavLink>, next: Option<NavLink>) -> ResourceChrome {
         previous,
         next,
         history_href: "/demo/history".to_string(),
+        snapshot_number: 1,
+        backlinks: Vec::new(),
+        broken_links: Vec::new(),
     }
 }
 
@@ -1,6 +1,6 @@
-use super::{resource::resource_page, ResourceAnalytics, ResourceChrome};
+use super::{resource::resource_page, NavLink, ResourceAnalytics, ResourceChrome};
 use crate::core::render_markdown;
-use crate::web::db::{MediaFamily, Resource, ResourceKind};
+use crate::web::db::{Resource, ResourceKind, Visibility};
 use crate::web::site::SiteContext;
 use chrono::Utc;
 
@@ -23,10 +23,11 @@ fn sample_resource() -> Resource {
         height: None,
         duration_ms: None,
         media_variants: None,
+        media_details: None,
         owner_note_id: None,
         is_favorite: true,
         favorite_position: Some(1),
-        is_private: false,
+        visibility: Visibility::Public,
         view_count_total: 3,
         last_viewed_at: None,
         created_at: Utc::now(),
@@ -34,7 +35,7 @@ fn sample_resource() -> Resource {
     }
 }
 
-fn sample_chrome() -> ResourceChrome {
+pub(super) fn sample_chrome() -> ResourceChrome {
     ResourceChrome {
         id: "abcdefghijklmnopqrstuvwx26".to_string(),
         kind: ResourceKind::Note,
@@ -49,40 +50,13 @@ fn sample_chrome() -> ResourceChrome {
         previous: None,
         next: None,
         history_href: "/demo-note/history".to_string(),
+        snapshot_number: 1,
+        backlinks: Vec::new(),
+        broken_links: Vec::new(),
     }
 }
 #[rustfmt::skip]
-fn sample_site() -> SiteContext { SiteContext { site_name: "Launchpad".to_string(), site_description: "Search-friendly notes.".to_string(), public_base_url: Some("https://example.com".to_string()) } }
-fn sample_media_resource() -> Resource {
-    Resource {
-        id: "bcdefghijklmnopqrstuvwxy27".to_string(),
-        space_slug: "alice".to_string(),
-        kind: ResourceKind::Media,
-        alias: Some("demo-image".to_string()),
-        title: "Demo file".to_string(),
-        summary: "File body".to_string(),
-        body: "# Demo file\n\nBody".to_string(),
-        media_family: Some(MediaFamily::File),
-        file_key: Some("media/demo/original.heic".to_string()),
-        content_type: Some("image/heic".to_string()),
-        byte_size: Some(1234),
-        sha256_hex: Some("abc".to_string()),
-        original_filename: Some("demo.heic".to_string()),
-        width: None,
-        height: None,
-        duration_ms: None,
-        media_variants: None,
-        owner_note_id: None,
-        is_favorite: false,
-        favorite_position: None,
-        is_private: false,
-        view_count_total: 0,
-        last_viewed_at: None,
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
-    }
-}
-
+pub(super) fn sample_site() -> SiteContext { SiteContext { site_name: "Launchpad".to_string(), site_description: "Search-friendly notes.".to_string(), public_base_url: Some("https://example.com".to_string()) } }
 #[test]
 #[rustfmt::skip]
 fn guest_resource_page_hides_editor() {
@@ -143,58 +117,44 @@ fn admin_resource_page_renders_alias_controls_without_markdown_body_label() {
 }
 
 #[test]
-fn guest_media_page_exposes_original_download_and_display_route() {
-    let html = resource_page(
-        &sample_media_resource(),
-        &ResourceChrome {
-            current_href: "/demo-image".to_string(),
-            history_href: "/demo-image/history".to_string(),
-            kind: ResourceKind::Media,
-            ..sample_chrome()
-        },
-        None,
-        &render_markdown(&sample_media_resource().body),
-        false,
-        &sample_site(),
-    );
-    assert!(html.contains("Download original"));
-    assert!(html.contains("href=\"/alice/demo-image/file\""));
-    assert!(html.contains("download=\"demo.heic\""));
-    assert!(html.contains("Open raw file"));
-    assert!(html.contains(r#"class="resource-nav-strip resource-nav-strip-dual""#));
-    assert!(!html.contains(r#"class="summary-card current-resource-card"#));
-    assert!(!html.contains("data-history-link"));
-    assert!(!html.contains("variant=display"));
+fn resource_page_lists_backlinks_and_flags_broken_links_for_writers() {
+    let chrome = ResourceChrome {
+        backlinks: vec![NavLink {
+            href: "/alice/plan".to_string(),
+            title: "Plan <v2>".to_string(),
+            summary: "Roadmap".to_string(),
+            created_at: String::new(),
+            kind_badge: "Note",
+            is_favorite: false,
+            visibility: None,
+        }],
+        broken_links: vec!["/alice/gone".to_string()],
+        ..sample_chrome()
+    };
+    let render = |is_admin| {
+        resource_page(
+            &sample_resource(),
+            &chrome,
+            None,
+            "",
+            is_admin,
+            &sample_site(),
+        )
+    };
+    let guest = render(false);
+    assert!(guest.contains("<h2>Linked from</h2>"));
+    assert!(guest.contains(r#"<a href="/alice/plan">Plan &lt;v2&gt;</a>"#));
+    assert!(!guest.contains("data-broken-links"));
+    assert!(render(true).contains("<code>/alice/gone</code>"));
 }
 
 #[test]
-fn admin_media_page_uses_live_resource_shell() {
-    let html = resource_page(
-        &sample_media_resource(),
-        &ResourceChrome {
-            current_href: "/demo-image".to_string(),
-            history_href: "/demo-image/history".to_string(),
-            kind: ResourceKind::Media,
-            ..sample_chrome()
-        },
-        Some(&ResourceAnalytics {
-            total: 5,
-            views_1d: 1,
-            views_7d: 2,
-            views_30d: 3,
-            views_90d: 4,
-            last_viewed_at: Some("2026-03-26 08:35 UTC".to_string()),
-        }),
-        &render_markdown(&sample_media_resource().body),
-        true,
-        &sample_site(),
-    );
-    assert!(html.contains("resource-nav-strip"));
-    assert!(html.contains("File URL"));
-    assert!(html.contains("File metadata"));
-    assert!(html.contains("Delete media"));
-    assert!(html.contains("Download original"));
-    assert!(html.contains("id=\"editor-body\""));
-    assert!(!html.contains(">Upload media<"));
-    assert!(!html.contains(r#"class="summary-card current-resource-card"#));
+fn resource_page_links_front_matter_tags_to_search() {
+    let resource = Resource {
+        body: "---\ntags: [Road Trip, c++]\n---\nBody".to_string(),
+        ..sample_resource()
+    };
+    let html = resource_page(&resource, &sample_chrome(), None, "", false, &sample_site());
+    assert!(html.contains(r#"href="/alice/search?tag=road-trip">#road-trip</a>"#));
+    assert!(html.contains(r#"href="/alice/search?tag=c%2B%2B">#c++</a>"#));
 }
@@ -5,6 +5,8 @@ use super::layout::{base, html_escape, shell_page};
 use super::model::IndexItem;
 use super::search_form::search_section;
 use super::sections::{page_header, section};
+use crate::core::{normalize_tag, SearchQuery};
+use crate::web::feed::alternate_links;
 use crate::web::site::SiteContext;
 
 const ACTIONS_JS: &str = include_str!("resource_actions.js");
@@ -14,35 +16,42 @@ pub struct SearchView<'a> {
     pub previous_cursor: Option<&'a str>,
     pub next_cursor: Option<&'a str>,
     pub kind: &'a str,
-    pub query: Option<&'a str>,
+    pub query: Option<&'a SearchQuery>,
     pub limit: i64,
     pub scope: &'a str,
     pub sort: &'a str,
+    pub tag: Option<&'a str>,
+    pub meta: &'a [(String, String)],
     pub is_admin: bool,
     pub guest_login_href: String,
     pub site: &'a SiteContext,
+    /// Space whose tag feed the page advertises when `tag` is set.
+    pub feed_space: Option<&'a str>,
 }
 
 pub fn search_page(view: SearchView<'_>) -> String {
-    let query = view.query.unwrap_or("").trim();
+    let canonical = view.query.map(ToString::to_string).unwrap_or_default();
+    let query = canonical.as_str();
     let has_query = !query.is_empty();
     let extra_script = if view.is_admin {
         format!(r#"<script>{ACTIONS_JS}</script>"#)
     } else {
         String::new()
     };
-    let rail_actions = rail_actions(&view);
+    let rail_actions = session_actions(view.is_admin, &view.guest_login_href);
+    let filters = metadata_filters(&view);
     let content = format!(
         "{}{}{}",
         page_header("Search", None, "search-head"),
-        search_section(query, view.kind, view.scope, view.sort, has_query,),
-        results_section(&view, query, has_query),
+        search_section(view.query, view.kind, view.scope, view.sort, &filters),
+        results_section(&view, query, has_query, &filters),
     );
     let admin_actions = view.is_admin.then(admin_create_actions);
     base(
         &view
             .site
-            .page_meta("Search", view.site.site_description.clone(), false, None),
+            .page_meta("Search", view.site.site_description.clone(), false, None)
+            .with_feed_links(tag_feed_links(&view)),
         &shell_page(
             if view.is_admin { "Admin" } else { "Guest" },
             &list_rail(
@@ -62,7 +71,45 @@ pub fn search_page(view: SearchView<'_>) -> String {
     )
 }
 
-fn results_section(view: &SearchView<'_>, query: &str, has_query: bool) -> String {
+fn tag_feed_links(view: &SearchView<'_>) -> String {
+    match (view.feed_space, view.tag.and_then(normalize_tag)) {
+        (Some(space), Some(tag)) => alternate_links(view.site, space, Some(&tag)),
+        _ => String::new(),
+    }
+}
+
+/// Active `tag` and `meta.<key>` filters as query fields.
+fn metadata_filters(view: &SearchView<'_>) -> Vec<(String, String)> {
+    view.tag
+        .map(|tag| ("tag".to_string(), tag.to_string()))
+        .into_iter()
+        .chain(
+            view.meta
+                .iter()
+                .map(|(key, value)| (format!("meta.{key}"), value.clone())),
+        )
+        .collect()
+}
+
+fn results_section(
+    view: &SearchView<'_>,
+    query: &str,
+    has_query: bool,
+    filters: &[(String, String)],
+) -> String {
+    let limit = view.limit.to_string();
+    let mut fields = vec![
+        ("q", query),
+        ("kind", view.kind),
+        ("scope", view.scope),
+        ("sort", view.sort),
+        ("limit", limit.as_str()),
+    ];
+    fields.extend(
+        filters
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str())),
+    );
     let cards = if view.notes.is_empty() {
         format!(
             r#"<p class="surface-empty">{}</p>"#,
@@ -80,18 +127,7 @@ fn results_section(view: &SearchView<'_>, query: &str, has_query: bool) -> Strin
         &format!(
             r#"<div class="resource-list resource-grid">{cards}</div>
 {}"#,
-            pager(
-                "/search",
-                view.previous_cursor,
-                view.next_cursor,
-                &[
-                    ("q", query),
-                    ("kind", view.kind),
-                    ("scope", view.scope),
-                    ("sort", view.sort),
-                    ("limit", &view.limit.to_string()),
-                ],
-            )
+            pager("/search", view.previous_cursor, view.next_cursor, &fields,)
         ),
         "resource-section",
     )
@@ -105,14 +141,15 @@ fn rail_primary_action(is_admin: bool) -> &'static str {
     }
 }
 
-fn rail_actions(view: &SearchView<'_>) -> String {
-    if view.is_admin {
+/// Logout for members, sign-in link for guests.
+pub(super) fn session_actions(is_admin: bool, guest_login_href: &str) -> String {
+    if is_admin {
         r#"<form method="POST" action="/logout"><button type="submit" class="btn">Logout</button></form>"#
             .to_string()
     } else {
         format!(
             r#"<a href="{}" class="btn">Admin sign in</a>"#,
-            html_escape(&view.guest_login_href),
+            html_escape(guest_login_href),
         )
     }
 }
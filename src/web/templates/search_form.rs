@@ -1,23 +1,47 @@
 use super::layout::html_escape;
 use super::sections::section;
+use crate::core::SearchQuery;
 
-pub fn search_section(query: &str, kind: &str, scope: &str, sort: &str, has_query: bool) -> String {
-    let query_card = if has_query {
-        format!(
-            r#"<div class="search-state-card"><small>Query</small><strong>{}</strong></div>"#,
-            html_escape(query)
-        )
+/// Search form echoing the parsed query: the input holds its canonical text
+/// and each recognized `field:value` filter gets its own state card.
+pub fn search_section(
+    query: Option<&SearchQuery>,
+    kind: &str,
+    scope: &str,
+    sort: &str,
+    filters: &[(String, String)],
+) -> String {
+    let canonical = query.map(ToString::to_string).unwrap_or_default();
+    let text = query.map_or("", |query| query.text.as_str());
+    let has_query = !text.is_empty();
+    let mut query_card = if has_query {
+        state_card("Query", text)
     } else {
         String::new()
     };
+    for filter in query.map_or(&[][..], |query| &query.filters) {
+        query_card.push_str(&state_card("Filter", &filter.to_string()));
+    }
+    let mut hidden = String::new();
+    for (name, value) in filters {
+        query_card.push_str(&state_card(
+            name.strip_prefix("meta.").unwrap_or("Tag"),
+            value,
+        ));
+        hidden.push_str(&format!(
+            r#"<input type="hidden" name="{}" value="{}">"#,
+            html_escape(name),
+            html_escape(value)
+        ));
+    }
     section(
         "Search resources",
         &format!(
             r#"{}<form class="search-form" method="GET" action="/search">
 <label for="search-page-input" class="visually-hidden">Search resources</label>
 <div class="search-grid">
-<input id="search-page-input" type="search" name="q" value="{}" placeholder="Search aliases, titles, bodies, and filenames">
-<input type="hidden" name="scope" value="{}">
+<input id="search-page-input" type="search" name="q" value="{}" placeholder="Search text or filters like title:plan is:favorite updated:&lt;7d">
+<input type="hidden" name="scope" value="{}">{}
 <label class="form-group search-sort" for="search-kind">
 <span class="visually-hidden">Kind</span>
 <select id="search-kind" name="kind" aria-label="Kind">{}</select>
@@ -30,8 +54,9 @@ pub fn search_section(query: &str, kind: &str, scope: &str, sort: &str, has_quer
 </div>
 </form>"#,
             query_card,
-            html_escape(query),
+            html_escape(&canonical),
             scope,
+            hidden,
             kind_options(kind),
             sort_options(sort, has_query, scope),
         ),
@@ -39,6 +64,14 @@ pub fn search_section(query: &str, kind: &str, scope: &str, sort: &str, has_quer
     )
 }
 
+fn state_card(label: &str, value: &str) -> String {
+    format!(
+        r#"<div class="search-state-card"><small>{}</small><strong>{}</strong></div>"#,
+        html_escape(label),
+        html_escape(value)
+    )
+}
+
 fn sort_options(selected: &str, has_query: bool, scope: &str) -> String {
     sort_catalog(has_query, scope)
         .into_iter()
@@ -84,6 +117,7 @@ fn sort_catalog(has_query: bool, scope: &str) -> Vec<(&'static str, &'static str
         ("popular_all_desc", "Popular all time"),
     ];
     if has_query {
+        items.insert(0, ("semantic", "Relevance + meaning"));
         items.insert(0, ("relevance", "Relevance"));
     }
     if scope == "favorites" {
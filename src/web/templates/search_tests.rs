@@ -2,6 +2,7 @@ use super::{
     search::{search_page, SearchView},
     IndexItem,
 };
+use crate::core::parse_search_query;
 use crate::web::site::SiteContext;
 
 fn sample_item() -> IndexItem {
@@ -40,11 +41,15 @@ fn search_page_browses_without_query() {
         limit: 20,
         scope: "all",
         sort: "updated_desc",
+        tag: None,
+        meta: &[],
         is_admin: false,
         guest_login_href: "/login?return_to=%2Fsearch".to_string(),
         site: &sample_site(),
+        feed_space: Some("alice"),
     });
     assert!(html.contains(">Resources<"));
+    assert!(!html.contains("rel=\"alternate\""));
     assert!(!html.contains(">Query<"));
     assert!(html.contains("name=\"sort\""));
     assert!(html.contains("aria-label=\"Sort\""));
@@ -65,17 +70,27 @@ fn search_page_keeps_query_and_sort_in_form() {
         previous_cursor: Some("prev"),
         next_cursor: Some("cursor"),
         kind: "all",
-        query: Some("orbit"),
+        query: Some(&parse_search_query("orbit is:favorite")),
         limit: 20,
         scope: "all",
         sort: "relevance",
+        tag: Some("travel"),
+        meta: &[("status".to_string(), "draft".to_string())],
         is_admin: true,
         guest_login_href: "/login?return_to=%2Fsearch".to_string(),
         site: &sample_site(),
+        feed_space: Some("alice"),
     });
-    assert!(html.contains("name=\"q\" value=\"orbit\""));
-    assert!(html.contains(">Query<"));
+    assert!(html.contains(
+        "<link rel=\"alternate\" type=\"application/atom+xml\" title=\"Launchpad #travel (Atom)\" href=\"https://example.com/alice/tags/travel/feed.atom\">"
+    ));
+    assert!(html.contains("name=\"q\" value=\"is:favorite orbit\""));
+    assert!(html.contains("<small>Query</small><strong>orbit</strong>"));
+    assert!(html.contains("<small>Filter</small><strong>is:favorite</strong>"));
     assert!(html.contains("value=\"relevance\" selected"));
+    assert!(html.contains("<option value=\"semantic\">Relevance + meaning</option>"));
     assert!(html.contains("name=\"direction\" value=\"prev\""));
+    assert!(html.contains("<small>Tag</small><strong>travel</strong>"));
+    assert!(html.contains("name=\"meta.status\" value=\"draft\""));
     assert!(html.contains("New note"));
 }
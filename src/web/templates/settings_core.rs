@@ -1,7 +1,9 @@
 //! Scalar and JSON settings rows
 
 use super::layout::html_escape;
+use super::resource_editor::visibility_options;
 use super::settings_panel::settings_row;
+use crate::core::search_language::SEARCH_LANGUAGES;
 use crate::web::db::AppSettings;
 
 pub(super) fn site_name_row(settings: &AppSettings) -> String {
@@ -49,6 +51,32 @@ pub(super) fn search_page_size_row(settings: &AppSettings) -> String {
     )
 }
 
+pub(super) fn search_language_row(settings: &AppSettings) -> String {
+    let options = SEARCH_LANGUAGES
+        .iter()
+        .map(|language| {
+            format!(
+                r#"<option value="{language}"{}>{language}</option>"#,
+                if *language == settings.search_language {
+                    " selected"
+                } else {
+                    ""
+                }
+            )
+        })
+        .collect::<String>();
+    settings_row(
+        "Search/Language",
+        &format!(
+            r#"<label class="form-group" data-settings-item><span>Search/Language</span><select name="search_language">{options}</select></label>
+<label class="check-row check-row-field" data-settings-item><input type="checkbox" name="search_cjk" {}><span>Search/CJK_bigrams</span></label>
+<p class="page-summary" data-settings-item>Changing either reindexes every resource in this space. CJK bigrams make Chinese, Japanese, and Korean text searchable by substrings.</p>"#,
+            if settings.search_cjk { "checked" } else { "" },
+        ),
+        "settings-search-language-row",
+    )
+}
+
 pub(super) fn media_quality_row(settings: &AppSettings) -> String {
     settings_row(
         "Media/WebP_quality",
@@ -61,6 +89,22 @@ pub(super) fn media_quality_row(settings: &AppSettings) -> String {
     )
 }
 
+pub(super) fn media_strip_location_row(settings: &AppSettings) -> String {
+    settings_row(
+        "Media/Strip_location",
+        &format!(
+            r#"<label class="check-row check-row-field" data-settings-item><input type="checkbox" name="media_strip_location" {}><span>Media/Strip_location</span></label>
+<p class="page-summary" data-settings-item>Serves image originals without GPS metadata. Editors can still download the untouched file.</p>"#,
+            if settings.media_strip_location {
+                "checked"
+            } else {
+                ""
+            },
+        ),
+        "settings-media-location-row",
+    )
+}
+
 pub(super) fn google_maps_key_row(settings: &AppSettings) -> String {
     settings_row(
         "Embeds/Google_Maps_API_key",
@@ -73,17 +117,13 @@ pub(super) fn google_maps_key_row(settings: &AppSettings) -> String {
     )
 }
 
-pub(super) fn new_resources_private_row(settings: &AppSettings) -> String {
+pub(super) fn new_resource_visibility_row(settings: &AppSettings) -> String {
+    let options = visibility_options(settings.default_new_resource_visibility);
     settings_row(
-        "Resources/New_resources_start_private",
+        "Resources/New_resource_visibility",
         &format!(
-            r#"<label class="check-row check-row-field" data-settings-item><input type="checkbox" name="default_new_resource_is_private" {}><span>Resources/New_resources_start_private</span></label>
-<p class="page-summary" data-settings-item>Applies to future notes and media only.</p>"#,
-            if settings.default_new_resource_is_private {
-                "checked"
-            } else {
-                ""
-            },
+            r#"<label class="form-group" data-settings-item><span>Resources/New_resource_visibility</span><select name="default_new_resource_visibility">{options}</select></label>
+<p class="page-summary" data-settings-item>Applies to future notes and media only. Space resources are visible to space members.</p>"#,
         ),
         "settings-new-resources-row",
     )
@@ -0,0 +1,68 @@
+(function () {
+    var root = document.querySelector('[data-space-members]');
+    if (!root) return;
+    var base = root.getAttribute('data-members-base');
+    var reveal = root.querySelector('[data-member-invite-reveal]');
+    var value = root.querySelector('[data-member-invite-value]');
+    var status = root.querySelector('[data-members-status]');
+
+    root.addEventListener('submit', function (event) {
+        var form = event.target;
+        if (!form.hasAttribute('data-member-invite')) return;
+        event.preventDefault();
+        send('POST', base, {
+            login: form.elements.login.value,
+            role: form.elements.role.value
+        }, showInvite);
+    });
+
+    root.addEventListener('change', function (event) {
+        var select = event.target;
+        if (!select.hasAttribute('data-member-role')) return;
+        send('PUT', select.getAttribute('data-member-role'), { role: select.value }, reload);
+    });
+
+    root.addEventListener('click', function (event) {
+        var button = event.target.closest('button');
+        if (!button) return;
+        if (button.hasAttribute('data-member-remove')) {
+            send('DELETE', button.getAttribute('data-member-remove'), null, reload);
+        } else if (button.hasAttribute('data-member-invite-done')) {
+            reload();
+        }
+    });
+
+    async function send(method, url, body, done) {
+        setStatus('', false);
+        try {
+            var response = await fetch(url, {
+                method: method,
+                headers: body ? { 'Content-Type': 'application/json' } : {},
+                body: body ? JSON.stringify(body) : undefined
+            });
+            var payload = response.status === 204 ? {} : await response.json().catch(function () { return {}; });
+            if (!response.ok) throw new Error(payload.message || 'Request failed.');
+            done(payload);
+        } catch (error) {
+            setStatus(error.message || 'Request failed.', true);
+        }
+    }
+
+    function showInvite(payload) {
+        if (!payload.invite_token) {
+            reload();
+            return;
+        }
+        value.textContent = payload.invite_token;
+        reveal.hidden = false;
+    }
+
+    function reload() {
+        window.location.reload();
+    }
+
+    function setStatus(message, visible) {
+        status.textContent = message;
+        status.hidden = !visible;
+    }
+})();
@@ -0,0 +1,74 @@
+//! Space member and invite settings section
+
+use super::layout::{format_date, html_escape};
+use super::settings_panel::settings_row;
+use crate::web::db::{SpaceMember, SpaceRole};
+
+pub fn members_section(space_slug: &str, members: &[SpaceMember]) -> String {
+    let base = format!("/{}/settings/members", html_escape(space_slug));
+    let rows = members
+        .iter()
+        .map(|member| member_row(&base, member))
+        .collect::<Vec<_>>()
+        .join("");
+    settings_row(
+        "Access/Members",
+        &format!(
+            r#"<div class="settings-section-grid" data-space-members data-members-base="{base}">
+<p class="settings-wide" data-settings-item>Viewers read private resources, editors also write, and admins manage settings, members, and tokens.</p>
+<form class="settings-submit-row settings-wide" data-member-invite data-settings-item>
+<input type="text" name="login" maxlength="64" placeholder="Username or email" required>
+{select}
+<button type="submit" class="btn">Invite member</button>
+</form>
+<div class="settings-wide service-token-reveal" data-member-invite-reveal hidden>
+<p>Share this invite token. The new member sets a password with it on <a href="/reset-password">/reset-password</a> within 7 days.</p>
+<code data-member-invite-value></code>
+<button type="button" class="btn" data-member-invite-done>Done</button>
+</div>
+<p class="settings-wide" data-members-status hidden></p>
+<table class="settings-wide service-token-table"><thead><tr><th>Member</th><th>Status</th><th>Joined</th><th>Role</th><th></th></tr></thead><tbody>{rows}</tbody></table>
+</div>"#,
+            select = role_select(SpaceRole::Viewer, ""),
+        ),
+        "settings-members-row",
+    )
+}
+
+fn member_row(base: &str, member: &SpaceMember) -> String {
+    let (role, action) = if member.role == SpaceRole::Owner {
+        ("owner".to_string(), String::new())
+    } else {
+        (
+            role_select(
+                member.role,
+                &format!(r#" data-member-role="{base}/{}""#, member.user_id),
+            ),
+            format!(
+                r#"<button type="button" class="btn" data-member-remove="{base}/{}">Remove</button>"#,
+                member.user_id
+            ),
+        )
+    };
+    format!(
+        r#"<tr data-settings-item><td>{name}</td><td>{status}</td><td>{joined}</td><td>{role}</td><td>{action}</td></tr>"#,
+        name = html_escape(&member.username),
+        status = html_escape(&member.status),
+        joined = format_date(&member.created_at),
+    )
+}
+
+fn role_select(selected: SpaceRole, attrs: &str) -> String {
+    let options = SpaceRole::ASSIGNABLE
+        .iter()
+        .map(|role| {
+            format!(
+                r#"<option value="{0}"{1}>{0}</option>"#,
+                role.as_str(),
+                if *role == selected { " selected" } else { "" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("");
+    format!(r#"<select name="role"{attrs}>{options}</select>"#)
+}
@@ -5,9 +5,9 @@ use super::index::{admin_create_actions, list_rail};
 use super::layout::{base, shell_page};
 use super::sections::page_header;
 use super::settings_core::{
-    google_maps_key_row, media_quality_row, new_resources_private_row, nostr_names_row,
-    nostr_relays_row, public_base_url_row, search_page_size_row, session_timeout_row,
-    site_description_row, site_name_row,
+    google_maps_key_row, media_quality_row, media_strip_location_row, new_resource_visibility_row,
+    nostr_names_row, nostr_relays_row, public_base_url_row, search_language_row,
+    search_page_size_row, session_timeout_row, site_description_row, site_name_row,
 };
 use super::settings_home::{home_hero_section, home_sections_section};
 use super::settings_icon::site_icon_section;
@@ -15,21 +15,33 @@ use super::settings_live::{
     live_default_camera_facing_row, live_default_fps_row, live_default_microphone_row,
     live_default_quality_row, live_default_source_row,
 };
+use super::settings_members::members_section;
 use super::settings_panel::settings_row;
 use super::settings_security::security_section;
+use super::settings_tokens::service_accounts_section;
 use super::IndexItem;
-use crate::web::db::AppSettings;
+use crate::web::db::{AppSettings, ServiceAccount, SpaceMember};
 use crate::web::site::SiteContext;
 
 const ACTIONS_JS: &str = include_str!("resource_actions.js");
 const FAVORITE_ORDER_JS: &str = include_str!("favorite_order.js");
 const SETTINGS_ICON_JS: &str = include_str!("settings_icon.js");
+const SETTINGS_MEMBERS_JS: &str = include_str!("settings_members.js");
 const SETTINGS_ORDER_JS: &str = include_str!("settings_order.js");
 const SETTINGS_SEARCH_JS: &str = include_str!("settings_search.js");
+const SETTINGS_TOKENS_JS: &str = include_str!("settings_tokens.js");
+
+/// Sections that exist only on a personal space's settings page.
+pub struct SpaceSettings<'a> {
+    pub slug: &'a str,
+    pub members: &'a [SpaceMember],
+    pub service_accounts: &'a [ServiceAccount],
+}
 
 pub fn settings_page(
     settings: &AppSettings,
     favorites: &[IndexItem],
+    space: Option<SpaceSettings<'_>>,
     site: &SiteContext,
 ) -> String {
     let admin_actions = admin_create_actions();
@@ -50,7 +62,9 @@ pub fn settings_page(
         settings_favorite_order_section(favorites),
         session_timeout_row(settings),
         search_page_size_row(settings),
+        search_language_row(settings),
         media_quality_row(settings),
+        media_strip_location_row(settings),
         google_maps_key_row(settings),
         live_default_source_row(settings),
         live_default_camera_facing_row(settings),
@@ -60,15 +74,21 @@ pub fn settings_page(
         nostr_names_row(settings),
         nostr_relays_row(settings),
         site_icon_section(settings),
-        new_resources_private_row(settings),
+        new_resource_visibility_row(settings),
         save_row,
     ]
     .join("");
     let settings_form = format!(
         r#"<form class="settings-form settings-stack" method="POST" action="/admin/settings">{settings_rows}</form>"#
     );
+    let space_sections = space
+        .map(|space| {
+            members_section(space.slug, space.members)
+                + &service_accounts_section(space.slug, space.service_accounts)
+        })
+        .unwrap_or_default();
     let search_root = format!(
-        r#"<div class="settings-stack" data-settings-search-root>{settings_form}{}</div>"#,
+        r#"<div class="settings-stack" data-settings-search-root>{settings_form}{}{space_sections}</div>"#,
         security_section()
     );
     let content = format!(
@@ -98,7 +118,7 @@ pub fn settings_page(
         ),
         "",
         &format!(
-            r#"<script>{ACTIONS_JS}</script><script>{FAVORITE_ORDER_JS}</script><script>{SETTINGS_ORDER_JS}</script><script>{SETTINGS_ICON_JS}</script><script>{SETTINGS_SEARCH_JS}</script>"#
+            r#"<script>{ACTIONS_JS}</script><script>{FAVORITE_ORDER_JS}</script><script>{SETTINGS_ORDER_JS}</script><script>{SETTINGS_ICON_JS}</script><script>{SETTINGS_SEARCH_JS}</script><script>{SETTINGS_MEMBERS_JS}</script><script>{SETTINGS_TOKENS_JS}</script>"#
         ),
     )
 }
@@ -0,0 +1,70 @@
+(function () {
+    var root = document.querySelector('[data-service-accounts]');
+    if (!root) return;
+    var base = root.getAttribute('data-settings-base');
+    var reveal = root.querySelector('[data-service-token-reveal]');
+    var value = root.querySelector('[data-service-token-value]');
+    var status = root.querySelector('[data-service-accounts-status]');
+
+    root.addEventListener('submit', function (event) {
+        var form = event.target;
+        if (form.hasAttribute('data-service-account-create')) {
+            event.preventDefault();
+            send('POST', base + '/service-accounts', { name: form.elements.name.value }, reload);
+        } else if (form.hasAttribute('data-service-token-issue')) {
+            event.preventDefault();
+            var scopes = Array.prototype.filter
+                .call(form.querySelectorAll('input[name="scopes"]'), function (input) { return input.checked; })
+                .map(function (input) { return input.value; });
+            var days = form.elements.expires_in_days.value;
+            send('POST', form.getAttribute('data-service-token-issue'), {
+                scopes: scopes,
+                expires_in_days: days ? Number(days) : null
+            }, showToken);
+        }
+    });
+
+    root.addEventListener('click', function (event) {
+        var button = event.target.closest('button');
+        if (!button) return;
+        if (button.hasAttribute('data-service-account-toggle')) {
+            send('PUT', button.getAttribute('data-service-account-toggle'), {
+                disabled: button.getAttribute('data-disabled') === 'true'
+            }, reload);
+        } else if (button.hasAttribute('data-service-token-revoke')) {
+            send('POST', button.getAttribute('data-service-token-revoke'), null, reload);
+        } else if (button.hasAttribute('data-service-token-done')) {
+            reload();
+        }
+    });
+
+    async function send(method, url, body, done) {
+        setStatus('', false);
+        try {
+            var response = await fetch(url, {
+                method: method,
+                headers: body ? { 'Content-Type': 'application/json' } : {},
+                body: body ? JSON.stringify(body) : undefined
+            });
+            var payload = response.status === 204 ? {} : await response.json().catch(function () { return {}; });
+            if (!response.ok) throw new Error(payload.message || 'Request failed.');
+            done(payload);
+        } catch (error) {
+            setStatus(error.message || 'Request failed.', true);
+        }
+    }
+
+    function showToken(payload) {
+        value.textContent = payload.token;
+        reveal.hidden = false;
+    }
+
+    function reload() {
+        window.location.reload();
+    }
+
+    function setStatus(message, visible) {
+        status.textContent = message;
+        status.hidden = !visible;
+    }
+})();
@@ -0,0 +1,107 @@
+//! Service account and API token settings section
+
+use super::layout::{format_date, html_escape};
+use super::settings_panel::settings_row;
+use crate::web::db::{ApiScope, ServiceAccount};
+
+pub fn service_accounts_section(space_slug: &str, accounts: &[ServiceAccount]) -> String {
+    let slug = html_escape(space_slug);
+    let base = format!("/{slug}/settings");
+    let cards = if accounts.is_empty() {
+        r#"<p class="surface-empty">No service accounts yet.</p>"#.to_string()
+    } else {
+        accounts
+            .iter()
+            .map(|account| account_card(&base, account))
+            .collect::<Vec<_>>()
+            .join("")
+    };
+    settings_row(
+        "Machine_access/Service_accounts",
+        &format!(
+            r#"<div class="settings-section-grid" data-service-accounts data-settings-base="{base}">
+<p class="settings-wide" data-settings-item>Service accounts let scripts call the machine routes with a bearer token. Tokens never open settings. <a href="/{slug}/admin/audit">Audit log</a></p>
+<form class="settings-submit-row settings-wide" data-service-account-create data-settings-item>
+<input type="text" name="name" maxlength="80" placeholder="Account name" required>
+<button type="submit" class="btn">Create account</button>
+</form>
+<div class="settings-wide service-token-reveal" data-service-token-reveal hidden>
+<p>Copy this token now. It will not be shown again.</p>
+<code data-service-token-value></code>
+<button type="button" class="btn" data-service-token-done>Done</button>
+</div>
+<p class="settings-wide" data-service-accounts-status hidden></p>
+<div class="settings-wide service-account-list">{cards}</div>
+</div>"#
+        ),
+        "settings-service-accounts-row",
+    )
+}
+
+fn account_card(base: &str, account: &ServiceAccount) -> String {
+    let disabled = account.disabled_at.is_some();
+    let state = if disabled { "Disabled" } else { "Active" };
+    let toggle = if disabled { "Enable" } else { "Disable" };
+    let tokens = if account.tokens.is_empty() {
+        r#"<p class="surface-empty">No tokens issued.</p>"#.to_string()
+    } else {
+        format!(
+            r#"<table class="service-token-table"><thead><tr><th>Scopes</th><th>Created</th><th>Expires</th><th>Last used</th><th></th></tr></thead><tbody>{}</tbody></table>"#,
+            account
+                .tokens
+                .iter()
+                .map(|token| {
+                    let action = match token.revoked_at {
+                        Some(at) => format!("Revoked {}", format_date(&at)),
+                        None => format!(
+                            r#"<button type="button" class="btn" data-service-token-revoke="{base}/api-tokens/{}/revoke">Revoke</button>"#,
+                            token.id
+                        ),
+                    };
+                    format!(
+                        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{action}</td></tr>",
+                        token.scopes.join(", "),
+                        format_date(&token.created_at),
+                        token.expires_at.as_ref().map(format_date).unwrap_or_else(|| "Never".to_string()),
+                        token.last_used_at.as_ref().map(format_date).unwrap_or_else(|| "Never".to_string()),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("")
+        )
+    };
+    let scope_inputs = ApiScope::ALL
+        .iter()
+        .map(|scope| {
+            format!(
+                r#"<label><input type="checkbox" name="scopes" value="{0}" checked> {0}</label>"#,
+                scope.as_str()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("");
+    let mint = if disabled {
+        String::new()
+    } else {
+        format!(
+            r#"<form class="settings-submit-row" data-service-token-issue="{base}/service-accounts/{id}/tokens">
+{scope_inputs}
+<input type="number" name="expires_in_days" min="1" max="3650" placeholder="Expires in days">
+<button type="submit" class="btn">Issue token</button>
+</form>"#,
+            id = account.id
+        )
+    };
+    format!(
+        r#"<article class="service-account-card" data-settings-item>
+<div class="settings-submit-row">
+<strong>{name}</strong><span>{state}</span>
+<button type="button" class="btn" data-service-account-toggle="{base}/service-accounts/{id}" data-disabled="{next}">{toggle}</button>
+</div>
+{mint}{tokens}
+</article>"#,
+        name = html_escape(&account.name),
+        id = account.id,
+        next = !disabled,
+    )
+}
@@ -50,7 +50,7 @@
         if (path === '/admin' || path.startsWith('/admin?')) return prefix + path;
         if (path === '/search' || path.startsWith('/search?')) return prefix + path;
         if (path === '/live' || path.startsWith('/live/')) return prefix + path;
-        if (path === '/resources' || path.startsWith('/resources/')) return prefix + path;
+        if (/^\/(resources|uploads)(\/|$)/.test(path)) return prefix + path;
         return path;
     }
     function setupSpaceRoutes() {
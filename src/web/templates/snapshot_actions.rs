@@ -0,0 +1,35 @@
+//! Restore and compare actions shown on history and snapshot pages
+
+use super::layout::html_escape;
+use super::model::ResourceChrome;
+use crate::web::db::ResourceSnapshot;
+
+/// Older snapshots offer a revert that saves them again as the newest snapshot.
+pub(super) fn restore_button(
+    chrome: &ResourceChrome,
+    snapshot: &ResourceSnapshot,
+    is_admin: bool,
+) -> String {
+    if !is_admin || snapshot.snapshot_number >= chrome.snapshot_number {
+        return String::new();
+    }
+    format!(
+        r#"<button type="button" class="btn" data-snapshot-restore="/api/users/{}/resources/{}/history/{}/restore" data-snapshot-base="{}" data-snapshot-live="{}">Restore this snapshot</button>"#,
+        html_escape(&snapshot.space_slug),
+        html_escape(&chrome.id),
+        snapshot.snapshot_number,
+        chrome.snapshot_number,
+        chrome.current_href,
+    )
+}
+
+/// Link to the compare page when `from` is an older saved snapshot than `to`.
+pub(super) fn compare_link(chrome: &ResourceChrome, from: i32, to: i32, label: &str) -> String {
+    if from < 1 || from >= to {
+        return String::new();
+    }
+    format!(
+        r#"<a href="{}/{from}/diff/{to}" class="btn">{label}</a>"#,
+        chrome.history_href
+    )
+}
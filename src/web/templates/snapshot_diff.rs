@@ -0,0 +1,170 @@
+//! Saved snapshot compare page template
+
+use super::layout::{base, html_escape, shell_page};
+use super::model::ResourceChrome;
+use super::resource_shell::resource_rail;
+use crate::core::text_diff::{split_rows, BodyDiff, DiffLine, LineTag};
+use crate::web::db::ResourceSnapshot;
+use crate::web::site::SiteContext;
+
+pub struct SnapshotDiffView<'a> {
+    pub from: &'a ResourceSnapshot,
+    pub to: &'a ResourceSnapshot,
+    pub diff: &'a BodyDiff,
+    pub split: bool,
+}
+
+pub fn snapshot_diff_page(
+    chrome: &ResourceChrome,
+    view: &SnapshotDiffView<'_>,
+    is_admin: bool,
+    site: &SiteContext,
+) -> String {
+    let (from, to) = (view.from.snapshot_number, view.to.snapshot_number);
+    let href = format!("{}/{from}/diff/{to}", chrome.history_href);
+    let title = format!("Compare snapshots {from} and {to}: {}", chrome.title);
+    let body = if view.diff.hunks.is_empty() {
+        r#"<p class="surface-empty">The Markdown body is identical.</p>"#.to_string()
+    } else {
+        format!(
+            r#"<table class="diff-table">{}</table>"#,
+            view.diff
+                .hunks
+                .iter()
+                .map(|hunk| {
+                    let header = format!(
+                        r#"<tr class="diff-hunk"><td colspan="4">{}</td></tr>"#,
+                        html_escape(&hunk.header)
+                    );
+                    let rows = if view.split {
+                        split_rows(hunk)
+                            .iter()
+                            .map(|row| {
+                                format!(
+                                    "<tr>{}{}</tr>",
+                                    split_cells(row.old.as_ref(), true),
+                                    split_cells(row.new.as_ref(), false)
+                                )
+                            })
+                            .collect::<String>()
+                    } else {
+                        hunk.lines.iter().map(unified_row).collect::<String>()
+                    };
+                    header + &rows
+                })
+                .collect::<String>()
+        )
+    };
+    let content = format!(
+        r#"<header class="page-head">
+<div class="page-title-stack">
+<h1>Compare snapshots {from} and {to}</h1>
+<p class="page-summary diff-stats">+{} / &minus;{} lines</p>
+</div>
+<div class="page-actions">
+<a href="{href}" class="btn{}">Unified</a>
+<a href="{href}?view=split" class="btn{}">Side by side</a>
+<a href="{}" class="btn">Back to history</a>
+</div>
+</header>
+{}<section class="surface resource-surface">{body}</section>"#,
+        view.diff.stats.added,
+        view.diff.stats.removed,
+        if view.split { "" } else { " btn-primary" },
+        if view.split { " btn-primary" } else { "" },
+        chrome.history_href,
+        field_changes(view.from, view.to),
+    );
+    base(
+        &site.page_meta(&title, title.clone(), false, None),
+        &shell_page(
+            if is_admin { "Admin" } else { "Guest" },
+            &resource_rail(chrome, is_admin, &chrome.history_href),
+            &content,
+            "history-page",
+            &site.site_name,
+        ),
+        "",
+        "",
+    )
+}
+
+fn field_changes(from: &ResourceSnapshot, to: &ResourceSnapshot) -> String {
+    let alias = |snapshot: &ResourceSnapshot| snapshot.alias.clone().unwrap_or_default();
+    let changes = [
+        ("Title", from.title.clone(), to.title.clone()),
+        ("Alias", alias(from), alias(to)),
+        (
+            "Visibility",
+            from.visibility.label().to_string(),
+            to.visibility.label().to_string(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(label, old, new)| {
+        format!(
+            "<li>{label}: <code>{}</code> &rarr; <code>{}</code></li>",
+            html_escape(&old),
+            html_escape(&new)
+        )
+    })
+    .collect::<String>();
+    if changes.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<ul class="diff-fields">{changes}</ul>"#)
+    }
+}
+
+fn unified_row(line: &DiffLine) -> String {
+    let (class, sign) = tag_style(line.tag);
+    format!(
+        r#"<tr class="{class}"><td class="diff-num">{}</td><td class="diff-num">{}</td><td colspan="2">{sign}{}</td></tr>"#,
+        line_number(line.old_line),
+        line_number(line.new_line),
+        words(line),
+    )
+}
+
+fn split_cells(line: Option<&DiffLine>, old_side: bool) -> String {
+    let Some(line) = line else {
+        return r#"<td class="diff-num"></td><td></td>"#.to_string();
+    };
+    let number = if old_side {
+        line.old_line
+    } else {
+        line.new_line
+    };
+    format!(
+        r#"<td class="diff-num">{}</td><td class="{}">{}</td>"#,
+        line_number(number),
+        tag_style(line.tag).0,
+        words(line),
+    )
+}
+
+fn tag_style(tag: LineTag) -> (&'static str, &'static str) {
+    match tag {
+        LineTag::Equal => ("diff-equal", "&nbsp;"),
+        LineTag::Insert => ("diff-insert", "+"),
+        LineTag::Delete => ("diff-delete", "&minus;"),
+    }
+}
+
+fn line_number(number: Option<usize>) -> String {
+    number.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn words(line: &DiffLine) -> String {
+    line.words
+        .iter()
+        .map(|span| {
+            if span.changed && line.tag != LineTag::Equal {
+                format!("<mark>{}</mark>", html_escape(&span.text))
+            } else {
+                html_escape(&span.text)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,21 @@
+(function () {
+    var button = document.querySelector('[data-snapshot-restore]');
+    if (!button) return;
+
+    button.addEventListener('click', async function () {
+        if (!window.confirm('Restore this snapshot as the newest saved snapshot?')) return;
+        button.disabled = true;
+        try {
+            var response = await fetch(button.getAttribute('data-snapshot-restore'), {
+                method: 'POST',
+                headers: { 'If-Match': '"' + button.getAttribute('data-snapshot-base') + '"' }
+            });
+            var payload = await response.json().catch(function () { return {}; });
+            if (!response.ok) throw new Error(payload.message || 'Restore failed.');
+            window.location.href = button.getAttribute('data-snapshot-live');
+        } catch (error) {
+            button.disabled = false;
+            window.alert(error.message || 'Restore failed.');
+        }
+    });
+})();
@@ -20,6 +20,8 @@ const EDITOR_EXTRA_CSS: &str = include_str!("editor_extra.css");
 const RESOURCE_FOCUS_CSS: &str = include_str!("resource_focus.css");
 const SETTINGS_FLAT_CSS: &str = include_str!("settings_flat.css");
 const SETTINGS_CSS: &str = include_str!("settings.css");
+const DIFF_CSS: &str = include_str!("diff.css");
+const WIKI_CSS: &str = include_str!("wiki.css");
 
 static STYLESHEET: Lazy<String> = Lazy::new(|| minify_css(raw_css()));
 
@@ -47,6 +49,8 @@ fn raw_css() -> String {
         RESOURCE_FOCUS_CSS,
         SETTINGS_FLAT_CSS,
         SETTINGS_CSS,
+        DIFF_CSS,
+        WIKI_CSS,
     ]
     .join("\n")
 }
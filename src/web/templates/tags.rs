@@ -0,0 +1,70 @@
+//! Space tag index template
+
+use super::index::{admin_create_actions, list_rail};
+use super::layout::{base, html_escape, shell_page};
+use super::search::session_actions;
+use super::sections::{page_header, section};
+use crate::web::db::TagCount;
+use crate::web::feed::alternate_links;
+use crate::web::site::SiteContext;
+use url::form_urlencoded::byte_serialize;
+
+pub fn tags_page(
+    space_slug: &str,
+    tags: &[TagCount],
+    is_admin: bool,
+    guest_login_href: &str,
+    site: &SiteContext,
+) -> String {
+    let slug = html_escape(space_slug);
+    let body = if tags.is_empty() {
+        r#"<p class="surface-empty">No tagged resources yet.</p>"#.to_string()
+    } else {
+        format!(
+            r#"<nav class="tag-list" aria-label="Tags">{}</nav>"#,
+            tags.iter()
+                .map(|tag| tag_link(&slug, tag))
+                .collect::<Vec<_>>()
+                .join("")
+        )
+    };
+    let content = format!(
+        "{}{}",
+        page_header("Tags", None, "tags-head"),
+        section("All tags", &body, "tags-section"),
+    );
+    let admin_actions = is_admin.then(admin_create_actions);
+    base(
+        &site
+            .page_meta(
+                "Tags",
+                format!("Tags used in {}.", site.site_name),
+                false,
+                None,
+            )
+            .with_feed_links(alternate_links(site, space_slug, None)),
+        &shell_page(
+            if is_admin { "Admin" } else { "Guest" },
+            &list_rail(
+                "search",
+                admin_actions.as_deref().unwrap_or(""),
+                &session_actions(is_admin, guest_login_href),
+                is_admin,
+            ),
+            &content,
+            "index-layout",
+            &site.site_name,
+        ),
+        "",
+        "",
+    )
+}
+
+fn tag_link(slug: &str, tag: &TagCount) -> String {
+    let query: String = byte_serialize(tag.tag.as_bytes()).collect();
+    format!(
+        r#"<a class="btn" href="/{slug}/search?tag={query}">#{}<small>{}</small></a>"#,
+        html_escape(&tag.tag),
+        tag.count,
+    )
+}
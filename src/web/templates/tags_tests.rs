@@ -0,0 +1,36 @@
+use super::tags::tags_page;
+use crate::web::db::TagCount;
+use crate::web::site::SiteContext;
+
+fn sample_site() -> SiteContext {
+    SiteContext {
+        site_name: "Launchpad".to_string(),
+        site_description: "Tagged notes.".to_string(),
+        public_base_url: None,
+    }
+}
+
+#[test]
+fn tags_page_links_each_tag_to_filtered_search() {
+    let tags = [
+        TagCount {
+            tag: "road-trip".to_string(),
+            count: 3,
+        },
+        TagCount {
+            tag: "c++".to_string(),
+            count: 1,
+        },
+    ];
+    let html = tags_page("alice", &tags, false, "/login", &sample_site());
+    assert!(html.contains(r#"href="/alice/search?tag=road-trip">#road-trip<small>3</small>"#));
+    assert!(html.contains(r#"href="/alice/search?tag=c%2B%2B""#));
+    assert!(html.contains("Admin sign in"));
+}
+
+#[test]
+fn tags_page_without_tags_shows_empty_state() {
+    let html = tags_page("alice", &[], true, "/login", &sample_site());
+    assert!(html.contains("No tagged resources yet."));
+    assert!(html.contains("Logout"));
+}
@@ -0,0 +1,33 @@
+(function () {
+    var root = document.querySelector('[data-trash]');
+    if (!root) return;
+    var status = root.querySelector('[data-trash-status]');
+
+    root.addEventListener('click', function (event) {
+        var button = event.target.closest('button');
+        if (!button) return;
+        if (button.hasAttribute('data-trash-restore')) {
+            send('POST', button.getAttribute('data-trash-restore'));
+        } else if (button.hasAttribute('data-trash-purge')) {
+            if (!window.confirm('Permanently delete this resource and its snapshots?')) return;
+            send('DELETE', button.getAttribute('data-trash-purge'));
+        }
+    });
+
+    async function send(method, url) {
+        setStatus('', false);
+        try {
+            var response = await fetch(url, { method: method });
+            var payload = response.status === 204 ? {} : await response.json().catch(function () { return {}; });
+            if (!response.ok) throw new Error(payload.message || 'Request failed.');
+            window.location.reload();
+        } catch (error) {
+            setStatus(error.message || 'Request failed.', true);
+        }
+    }
+
+    function setStatus(message, visible) {
+        status.textContent = message;
+        status.hidden = !visible;
+    }
+})();
@@ -0,0 +1,119 @@
+//! Admin trash template
+
+use super::index::{admin_create_actions, list_rail};
+use super::layout::{base, html_escape, render_time, shell_page};
+use super::sections::page_header;
+use crate::web::db::TrashedResource;
+use crate::web::site::SiteContext;
+
+const ACTIONS_JS: &str = include_str!("resource_actions.js");
+const TRASH_JS: &str = include_str!("trash.js");
+
+pub fn trash_page(
+    space_slug: &str,
+    resources: &[TrashedResource],
+    can_purge: bool,
+    retention_days: usize,
+    site: &SiteContext,
+) -> String {
+    let slug = html_escape(space_slug);
+    let rows = if resources.is_empty() {
+        r#"<p class="surface-empty">Trash is empty.</p>"#.to_string()
+    } else {
+        format!(
+            r#"<table class="audit-table trash-table"><thead><tr><th>Deleted</th><th>Title</th><th>Kind</th><th>Visibility</th><th></th></tr></thead><tbody>{}</tbody></table>"#,
+            resources
+                .iter()
+                .map(|resource| trash_row(&slug, resource, can_purge))
+                .collect::<Vec<_>>()
+                .join("")
+        )
+    };
+    let content = format!(
+        r#"{}<section class="section-block trash-section" data-trash data-trash-base="/api/users/{slug}/trash"><p class="surface-empty">{}</p><p class="trash-status" data-trash-status hidden></p>{rows}</section>"#,
+        page_header(
+            "Trash",
+            Some(&format!(
+                r#"<a href="/{slug}/admin/audit" class="btn">Audit log</a>"#
+            )),
+            "trash-head",
+        ),
+        retention_note(retention_days),
+    );
+    base(
+        &site.page_meta(
+            "Trash",
+            format!("Trash for {}.", site.site_name),
+            false,
+            None,
+        ),
+        &shell_page(
+            "Admin",
+            &list_rail(
+                "admin",
+                &admin_create_actions(),
+                r#"<form method="POST" action="/logout"><button type="submit" class="btn">Logout</button></form>"#,
+                true,
+            ),
+            &content,
+            "trash-page",
+            &site.site_name,
+        ),
+        "",
+        &format!(r#"<script>{ACTIONS_JS}</script><script>{TRASH_JS}</script>"#),
+    )
+}
+
+fn retention_note(retention_days: usize) -> String {
+    match retention_days {
+        0 => "Deleted resources stay here until purged.".to_string(),
+        1 => "Deleted resources are purged after 1 day.".to_string(),
+        days => format!("Deleted resources are purged after {days} days."),
+    }
+}
+
+fn trash_row(slug: &str, resource: &TrashedResource, can_purge: bool) -> String {
+    let id = html_escape(&resource.id);
+    let title = match &resource.alias {
+        Some(alias) => format!(
+            "{} <code>{}</code>",
+            html_escape(&resource.title),
+            html_escape(alias)
+        ),
+        None => html_escape(&resource.title),
+    };
+    let purge = if can_purge {
+        format!(
+            r#" <button type="button" class="btn" data-trash-purge="/api/users/{slug}/trash/{id}">Purge</button>"#
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        r#"<tr><td>{}</td><td>{title}</td><td>{}</td><td>{}</td><td><button type="button" class="btn" data-trash-restore="/api/users/{slug}/trash/{id}/restore">Restore</button>{purge}</td></tr>"#,
+        render_time(&resource.deleted_at),
+        resource.kind.as_str(),
+        resource.visibility.label(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::retention_note;
+
+    #[test]
+    fn retention_note_covers_disabled_and_plural_days() {
+        assert_eq!(
+            retention_note(0),
+            "Deleted resources stay here until purged."
+        );
+        assert_eq!(
+            retention_note(1),
+            "Deleted resources are purged after 1 day."
+        );
+        assert_eq!(
+            retention_note(30),
+            "Deleted resources are purged after 30 days."
+        );
+    }
+}
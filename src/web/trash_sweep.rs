@@ -0,0 +1,71 @@
+//! Retention-based trash purge and orphaned object cleanup
+
+use crate::error::AppError;
+use crate::media::transform_prefix;
+use crate::storage::Storage;
+use crate::web::db::{self, DbPool, PurgedResource};
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Start the hourly sweep; a retention of `0` days keeps trash until purged by hand.
+pub fn spawn(pool: DbPool, storage: Storage, retention_days: usize) {
+    let Ok(retention_days) = i32::try_from(retention_days) else {
+        warn!(
+            retention_days,
+            "trash retention too large; automatic purge disabled"
+        );
+        return;
+    };
+    if retention_days == 0 {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut ticker = interval(SWEEP_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match db::purge_expired_trash(&pool, retention_days).await {
+                Ok(purged) if purged.is_empty() => {}
+                Ok(purged) => {
+                    info!(count = purged.len(), "purged expired trash");
+                    for item in &purged {
+                        delete_orphaned_objects(&pool, &storage, item).await;
+                    }
+                }
+                Err(error) => warn!(error = %error, "trash retention sweep failed"),
+            }
+        }
+    });
+}
+
+/// Remove storage objects the purge left unreferenced, with any cached image
+/// transforms of them; failures only log.
+pub async fn delete_orphaned_objects(pool: &DbPool, storage: &Storage, purged: &PurgedResource) {
+    for key in &purged.orphaned_keys {
+        if let Err(error) = delete_if_unreferenced(pool, storage, key, 0).await {
+            warn!(resource = %purged.id, key = %key, error = %error, "orphaned object check failed");
+        }
+    }
+}
+
+/// Drop `released_pins` upload pins from `key`, then delete the object and its
+/// cached transforms if `ref_count` is zero under the blob row lock. An upload
+/// that pinned the key after the purge keeps it.
+pub(crate) async fn delete_if_unreferenced(
+    pool: &DbPool,
+    storage: &Storage,
+    key: &str,
+    released_pins: i32,
+) -> Result<bool, AppError> {
+    db::settle_media_blob(pool, key, released_pins, || async {
+        if let Err(error) = storage.delete_object(key).await {
+            warn!(key = %key, error = %error, "orphaned object delete failed");
+        }
+        if let Err(error) = storage.delete_prefix(&transform_prefix(key)).await {
+            warn!(key = %key, error = %error, "image transform cleanup failed");
+        }
+    })
+    .await
+}
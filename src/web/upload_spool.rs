@@ -0,0 +1,145 @@
+//! Local spool files behind resumable upload sessions
+
+use crate::core::generate_id;
+use crate::error::AppError;
+use crate::web::db::{self, DbPool};
+use axum::body::Body;
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::{NamedTempFile, TempPath};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::time::interval;
+use tracing::{info, warn};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Directory holding one `{session_id}.part` file per open upload session.
+#[derive(Clone)]
+pub struct UploadSpool {
+    dir: Arc<PathBuf>,
+}
+
+impl UploadSpool {
+    pub async fn open(dir: PathBuf) -> Result<Self, AppError> {
+        fs::create_dir_all(&dir).await.map_err(|e| {
+            AppError::StorageError(format!("upload spool {} unusable: {e}", dir.display()))
+        })?;
+        Ok(Self { dir: Arc::new(dir) })
+    }
+
+    fn part_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.part"))
+    }
+
+    /// Write `body` at `offset`, discarding any unacknowledged bytes past it.
+    /// Returns how many bytes landed even when the body fails midway, so a
+    /// dropped connection resumes where it stopped.
+    pub async fn write_chunk(
+        &self,
+        id: &str,
+        offset: u64,
+        max_bytes: u64,
+        body: Body,
+    ) -> (u64, Result<(), AppError>) {
+        let mut file = match OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.part_path(id))
+            .await
+        {
+            Ok(file) => file,
+            Err(error) => return (0, Err(spool_error("open", error))),
+        };
+        if let Err(error) = file.set_len(offset).await {
+            return (0, Err(spool_error("truncate", error)));
+        }
+        if let Err(error) = file.seek(SeekFrom::Start(offset)).await {
+            return (0, Err(spool_error("seek", error)));
+        }
+        let mut written = 0u64;
+        let mut stream = body.into_data_stream();
+        let result = loop {
+            let bytes = match stream.next().await {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(error)) => {
+                    break Err(AppError::InvalidRequest(format!(
+                        "chunk interrupted: {error}"
+                    )))
+                }
+                None => break Ok(()),
+            };
+            if written + bytes.len() as u64 > max_bytes {
+                break Err(AppError::PayloadTooLarge(
+                    "chunk runs past the declared upload size or chunk limit".to_string(),
+                ));
+            }
+            if let Err(error) = file.write_all(&bytes).await {
+                break Err(spool_error("write", error));
+            }
+            written += bytes.len() as u64;
+        };
+        if let Err(error) = file.flush().await {
+            return (written, Err(spool_error("flush", error)));
+        }
+        (written, result)
+    }
+
+    /// Hard-link the finished spool file to a request-owned temp file and hash it.
+    pub async fn checkout(&self, id: &str) -> Result<(NamedTempFile, String), AppError> {
+        let link = self.dir.join(format!("{id}.{}.link", generate_id()));
+        fs::hard_link(self.part_path(id), &link)
+            .await
+            .map_err(|e| spool_error("link", e))?;
+        let temp_path = TempPath::try_from_path(link).map_err(|e| spool_error("link", e))?;
+        tokio::task::spawn_blocking(move || {
+            let mut file = std::fs::File::open(&temp_path).map_err(|e| spool_error("read", e))?;
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher).map_err(|e| spool_error("read", e))?;
+            Ok((
+                NamedTempFile::from_parts(file, temp_path),
+                format!("{:x}", hasher.finalize()),
+            ))
+        })
+        .await
+        .map_err(|e| AppError::StorageError(format!("upload hash task failed: {e}")))?
+    }
+
+    /// Drop spooled bytes; a missing file is already gone.
+    pub async fn remove(&self, id: &str) {
+        match fs::remove_file(self.part_path(id)).await {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => warn!(session = id, error = %error, "upload spool delete failed"),
+        }
+    }
+}
+
+/// Start the hourly removal of expired upload sessions and their spool files.
+pub fn spawn_sweep(pool: DbPool, spool: UploadSpool) {
+    tokio::spawn(async move {
+        let mut ticker = interval(SWEEP_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match db::delete_expired_upload_sessions(&pool).await {
+                Ok(expired) if expired.is_empty() => {}
+                Ok(expired) => {
+                    info!(count = expired.len(), "expired upload sessions removed");
+                    for id in &expired {
+                        spool.remove(id).await;
+                    }
+                }
+                Err(error) => warn!(error = %error, "upload session sweep failed"),
+            }
+        }
+    });
+}
+
+fn spool_error(action: &str, error: std::io::Error) -> AppError {
+    AppError::StorageError(format!("upload spool {action} failed: {error}"))
+}
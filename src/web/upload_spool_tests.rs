@@ -0,0 +1,32 @@
+use super::upload_spool::UploadSpool;
+use axum::body::Body;
+use sha2::{Digest, Sha256};
+
+#[tokio::test]
+async fn chunks_resume_at_offset_and_checkout_hashes_the_whole_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let spool = UploadSpool::open(dir.path().join("spool")).await.unwrap();
+    let id = "aaaaaaaaaaaaaaaaaaaaaaaaaa";
+    let (written, result) = spool.write_chunk(id, 0, 16, Body::from("hello wor")).await;
+    assert_eq!(written, 9);
+    assert!(result.is_ok());
+    let (written, result) = spool.write_chunk(id, 6, 16, Body::from("world")).await;
+    assert_eq!(written, 5);
+    assert!(result.is_ok());
+    let (file, sha256_hex) = spool.checkout(id).await.unwrap();
+    assert_eq!(std::fs::read(file.path()).unwrap(), b"hello world");
+    assert_eq!(sha256_hex, format!("{:x}", Sha256::digest(b"hello world")));
+}
+
+#[tokio::test]
+async fn chunk_past_limit_keeps_only_accepted_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let spool = UploadSpool::open(dir.path().to_path_buf()).await.unwrap();
+    let id = "bbbbbbbbbbbbbbbbbbbbbbbbbb";
+    let (written, result) = spool.write_chunk(id, 0, 4, Body::from("too long")).await;
+    assert_eq!(written, 0);
+    assert!(result.is_err());
+    spool.remove(id).await;
+    spool.remove(id).await;
+    assert!(spool.checkout(id).await.is_err());
+}
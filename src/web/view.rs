@@ -1,14 +1,11 @@
 //! Presentation helpers for HTML templates
 
 use crate::core::derive_title;
-use crate::error::AppError;
 use crate::web::db::{
-    self, DbPool, ListedResource, MediaFamily, PopularWindow, Resource, ResourceSnapshot,
+    self, ListedResource, MediaFamily, PopularWindow, Resource, ResourceSnapshot,
 };
 use crate::web::history_summary::changed_summary;
-use crate::web::templates::{
-    render_time, HistoryLink, IndexItem, IndexMetric, NavLink, ResourceAnalytics, ResourceChrome,
-};
+use crate::web::templates::{render_time, HistoryLink, IndexItem, IndexMetric, ResourceAnalytics};
 use crate::web::view_media::card_file_href;
 
 pub fn index_item(listed: &ListedResource, show_visibility: bool) -> IndexItem {
@@ -41,28 +38,6 @@ pub fn resource_analytics(stats: &db::ResourceViewStats) -> ResourceAnalytics {
     }
 }
 
-pub async fn resource_chrome(
-    pool: &DbPool,
-    resource: &Resource,
-    is_admin: bool,
-) -> Result<ResourceChrome, AppError> {
-    Ok(ResourceChrome {
-        id: resource.id.clone(),
-        kind: resource.kind,
-        alias: resource.alias.clone(),
-        title: title_for(resource),
-        summary: resource.summary.clone(),
-        current_href: resource_href(resource),
-        created_at: render_time(&resource.created_at),
-        updated_at: render_time(&resource.updated_at),
-        is_favorite: resource.is_favorite,
-        visibility: visibility_label(resource.is_private),
-        previous: adjacent_link(pool, &resource.id, is_admin, true).await?,
-        next: adjacent_link(pool, &resource.id, is_admin, false).await?,
-        history_href: history_href(resource),
-    })
-}
-
 pub fn history_links(snapshots: &[ResourceSnapshot], first_page: bool) -> Vec<HistoryLink> {
     snapshots
         .iter()
@@ -79,7 +54,7 @@ pub fn history_links(snapshots: &[ResourceSnapshot], first_page: bool) -> Vec<Hi
                 .map(|older| changed_summary(&older.body, &snapshot.body, &snapshot.summary))
                 .unwrap_or_else(|| snapshot.summary.clone()),
             created_at: render_time(&snapshot.created_at),
-            status: visibility_label(snapshot.is_private),
+            status: snapshot.visibility.label(),
         })
         .collect()
 }
@@ -101,38 +76,16 @@ fn build_index_item(
         media_family: resource.media_family,
         media_href: matches!(
             resource.media_family,
-            Some(MediaFamily::Image | MediaFamily::Video)
+            Some(MediaFamily::Image | MediaFamily::Video | MediaFamily::Audio)
         )
         .then(|| card_file_href(resource)),
         is_favorite: resource.is_favorite,
-        visibility: show_visibility.then_some(visibility_label(resource.is_private)),
+        visibility: show_visibility.then_some(resource.visibility.label()),
         metrics,
     }
 }
 
-async fn adjacent_link(
-    pool: &DbPool,
-    id: &str,
-    include_private: bool,
-    older: bool,
-) -> Result<Option<NavLink>, AppError> {
-    let target = if older {
-        db::get_previous_resource(pool, id, include_private).await?
-    } else {
-        db::get_next_resource(pool, id, include_private).await?
-    };
-    Ok(target.map(|resource| NavLink {
-        href: resource_href(&resource),
-        title: title_for(&resource),
-        summary: resource.summary.clone(),
-        created_at: render_time(&resource.created_at),
-        kind_badge: kind_badge(resource.media_family),
-        is_favorite: resource.is_favorite,
-        visibility: include_private.then_some(visibility_label(resource.is_private)),
-    }))
-}
-
-fn title_for(resource: &Resource) -> String {
+pub fn title_for(resource: &Resource) -> String {
     title_from(&resource.title, &resource.body)
 }
 
@@ -169,18 +122,11 @@ pub fn snapshot_href(snapshot: &ResourceSnapshot) -> String {
     }
 }
 
-pub fn visibility_label(is_private: bool) -> &'static str {
-    if is_private {
-        "Private"
-    } else {
-        "Public"
-    }
-}
-
 pub fn kind_badge(media_family: Option<MediaFamily>) -> &'static str {
     match media_family {
         Some(MediaFamily::Image) => "Image",
         Some(MediaFamily::Video) => "Video",
+        Some(MediaFamily::Audio) => "Audio",
         Some(MediaFamily::File) => "File",
         None => "Note",
     }
@@ -0,0 +1,69 @@
+//! Resource page chrome: neighbours, backlinks, and broken links
+
+use crate::error::AppError;
+use crate::web::db::{self, DbPool, Resource, Visibility};
+use crate::web::templates::{render_time, NavLink, ResourceChrome};
+use crate::web::view::{history_href, kind_badge, resource_href, title_for};
+
+pub async fn resource_chrome(
+    pool: &DbPool,
+    resource: &Resource,
+    max_visibility: Visibility,
+) -> Result<ResourceChrome, AppError> {
+    Ok(ResourceChrome {
+        id: resource.id.clone(),
+        kind: resource.kind,
+        alias: resource.alias.clone(),
+        title: title_for(resource),
+        summary: resource.summary.clone(),
+        current_href: resource_href(resource),
+        created_at: render_time(&resource.created_at),
+        updated_at: render_time(&resource.updated_at),
+        is_favorite: resource.is_favorite,
+        visibility: resource.visibility.label(),
+        previous: adjacent_link(pool, &resource.id, max_visibility, true).await?,
+        next: adjacent_link(pool, &resource.id, max_visibility, false).await?,
+        history_href: history_href(resource),
+        snapshot_number: db::current_snapshot_number(pool, &resource.id).await?,
+        backlinks: db::list_backlinks(pool, resource, max_visibility)
+            .await?
+            .iter()
+            .map(|source| nav_link(source, max_visibility))
+            .collect(),
+        broken_links: db::list_outgoing_links(pool, resource, max_visibility)
+            .await?
+            .into_iter()
+            .filter(|link| link.broken)
+            .map(|link| {
+                let file = if link.is_file { "/file" } else { "" };
+                format!("/{}/{}{file}", link.space_slug, link.reference)
+            })
+            .collect(),
+    })
+}
+
+async fn adjacent_link(
+    pool: &DbPool,
+    id: &str,
+    max_visibility: Visibility,
+    older: bool,
+) -> Result<Option<NavLink>, AppError> {
+    let target = if older {
+        db::get_previous_resource(pool, id, max_visibility).await?
+    } else {
+        db::get_next_resource(pool, id, max_visibility).await?
+    };
+    Ok(target.map(|resource| nav_link(&resource, max_visibility)))
+}
+
+fn nav_link(resource: &Resource, max_visibility: Visibility) -> NavLink {
+    NavLink {
+        href: resource_href(resource),
+        title: title_for(resource),
+        summary: resource.summary.clone(),
+        created_at: render_time(&resource.created_at),
+        kind_badge: kind_badge(resource.media_family),
+        is_favorite: resource.is_favorite,
+        visibility: (max_visibility > Visibility::Public).then_some(resource.visibility.label()),
+    }
+}
@@ -4,6 +4,12 @@ use crate::media::MediaVariants;
 use crate::web::db::{MediaFamily, Resource, ResourceSnapshot};
 use crate::web::view::file_href;
 
+/// One `<source>` of a video player, in the order the browser should try it.
+pub struct VideoSource {
+    pub href: String,
+    pub content_type: Option<String>,
+}
+
 pub fn card_file_href(resource: &Resource) -> String {
     variant_route(&file_href(resource), "card")
 }
@@ -26,7 +32,7 @@ pub fn snapshot_display_file_href(snapshot: &ResourceSnapshot) -> String {
 pub fn social_card_href(resource: &Resource) -> Option<String> {
     match resource.media_family {
         Some(MediaFamily::Image) => current_variant_href(resource, &["display", "card"]),
-        Some(MediaFamily::Video) => current_variant_href(resource, &["card"]),
+        Some(MediaFamily::Video | MediaFamily::Audio) => current_variant_href(resource, &["card"]),
         Some(MediaFamily::File) => None,
         None => None,
     }
@@ -40,6 +46,44 @@ pub fn poster_href(resource: &Resource) -> Option<String> {
     current_variant_href(resource, &["poster"])
 }
 
+pub fn video_sources(resource: &Resource) -> Vec<VideoSource> {
+    video_source_list(&file_href(resource), resource.media_variants.as_ref())
+}
+
+pub fn snapshot_video_sources(snapshot: &ResourceSnapshot) -> Vec<VideoSource> {
+    video_source_list(
+        &format!("/{}/file", snapshot.id),
+        snapshot.media_variants.as_ref(),
+    )
+}
+
+/// HLS first, then the WebM and MP4 renditions, then the original upload
+/// untyped, since browsers often play containers they will not claim.
+fn video_source_list(base_href: &str, variants: Option<&MediaVariants>) -> Vec<VideoSource> {
+    let mut sources = Vec::new();
+    if let Some(variants) = variants {
+        if let Some((_, hls_type)) = variants.object("hls/master.m3u8") {
+            sources.push(VideoSource {
+                href: format!("{base_href}/hls/master.m3u8"),
+                content_type: Some(hls_type.to_string()),
+            });
+        }
+        for name in ["webm", "mp4"] {
+            if let Some(rendition) = variants.rendition(name) {
+                sources.push(VideoSource {
+                    href: variant_route(base_href, name),
+                    content_type: Some(rendition.content_type.clone()),
+                });
+            }
+        }
+    }
+    sources.push(VideoSource {
+        href: base_href.to_string(),
+        content_type: None,
+    });
+    sources
+}
+
 fn current_variant_href(resource: &Resource, variants: &[&str]) -> Option<String> {
     variant_href(
         &file_href(resource),